@@ -2,6 +2,40 @@
 
 set -euo pipefail
 
+# Optional features which are checked one at a time, in addition to the default
+# features (checking them all at once would also build every optional
+# dependency for jobs which can't use them, like MIRI and the MSRV compiler)
+features=(
+    leak-check
+    compact
+    wasm
+    debug-verify
+    source-file
+    codespan-reporting
+    ariadne
+    pyo3
+    shared-memory
+    mprotect
+    failpoints
+    proptest
+    either
+    itertools
+    flatbuffers
+    prost
+    arrow
+    image-pnm
+    yoke
+    sqlx-any
+    dns
+    url
+    tree-sitter-json
+    ropey
+    alloc-budget
+    guard-pages
+    fuzz
+    panic-free
+)
+
 # Prints text formatted as a header (colors and arrows and stuff, very cool)
 print_header() {
     echo -e "\e[1;34m==>\e[0m \e[1m$1\e[0m"
@@ -23,6 +57,11 @@ check_docs() {
 lint() {
     print_header 'Linting with cargo clippy...'
     cargo +stable clippy --no-deps --all-targets -- -D warnings
+
+    for feature in "${features[@]}"; do
+        print_header "Linting with cargo clippy (\`$feature\` feature)..."
+        cargo +stable clippy --no-deps --all-targets --features "$feature" -- -D warnings
+    done
 }
 
 build() {
@@ -40,6 +79,17 @@ run_tests_stable() {
     RUSTFLAGS='-D warnings' cargo +stable test
 }
 
+run_tests_features() {
+    for feature in "${features[@]}"; do
+        print_header "Running tests (\`$feature\` feature)..."
+        RUSTFLAGS='-D warnings' cargo +stable test --features "$feature"
+    done
+
+    # The `panic-free` check happens at link time, and only in optimized builds
+    print_header 'Running tests (`panic-free` feature, release)...'
+    RUSTFLAGS='-D warnings' cargo +stable test --release --features panic-free --test no_panic
+}
+
 run_tests_beta() {
     print_header 'Running tests (beta compiler)...'
     RUSTFLAGS='-D warnings' cargo +beta test
@@ -65,6 +115,11 @@ run_tests_miri() {
     # skipped here.
     print_header 'Running tests with MIRI...'
     RUSTFLAGS='-D warnings' MIRIFLAGS='-Zmiri-strict-provenance' cargo +nightly miri test -- --skip nomiri
+
+    for feature in "${features[@]}"; do
+        print_header "Running tests with MIRI (\`$feature\` feature)..."
+        RUSTFLAGS='-D warnings' MIRIFLAGS='-Zmiri-strict-provenance' cargo +nightly miri test --features "$feature" -- --skip nomiri
+    done
 }
 
 # Run all checks
@@ -75,6 +130,7 @@ all_checks() {
     build_nostd
     lint
     run_tests_stable
+    run_tests_features
     run_tests_beta
     run_tests_msrv
     run_tests_leak_sanitizer
@@ -95,13 +151,14 @@ main() {
         "build")                    build                    ;;
         "build_nostd")              build_nostd              ;;
         "run_tests_stable")         run_tests_stable         ;;
+        "run_tests_features")       run_tests_features       ;;
         "run_tests_beta")           run_tests_beta           ;;
         "run_tests_msrv")           run_tests_msrv           ;;
         "run_tests_leak_sanitizer") run_tests_leak_sanitizer ;;
         "run_tests_miri")           run_tests_miri           ;;
         *)
             echo "Unknown command: $command"
-            echo "Available commands: all (default), check_fmt, check_docs, lint, build, build_nostd, run_tests_stable, run_tests_beta, run_tests_msrv, run_tests_leak_sanitizer, run_tests_miri"
+            echo "Available commands: all (default), check_fmt, check_docs, lint, build, build_nostd, run_tests_stable, run_tests_features, run_tests_beta, run_tests_msrv, run_tests_leak_sanitizer, run_tests_miri"
             exit 1
             ;;
     esac
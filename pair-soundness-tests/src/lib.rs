@@ -0,0 +1,179 @@
+//! A small compile-fail test harness, for checking that unsound uses of a
+//! crate's types are rejected by the compiler.
+//!
+//! This is the harness `pair` uses for its own compile-fail tests, published so
+//! that downstream crates (such as owners with hand-written unsafe code, or
+//! custom covariance markers) can write the same class of negative tests
+//! against their types.
+//!
+//! Each test is a `.rs` file in some directory, alongside an `.expected` file
+//! with the same name. The test passes if the file fails to compile, and the
+//! compiler's error output contains every line of the `.expected` file as a
+//! substring.
+//!
+//! Unlike `trybuild` or `compiletest_rs`, the error output doesn't need to
+//! match exactly - the exact wording and highlighted spans of compiler errors
+//! change in small ways between compiler versions, which shouldn't fail the
+//! tests. All that matters is that the code doesn't compile, for generally the
+//! reason expected.
+//!
+//! ```no_run
+//! // tests/compile_fails.rs
+//! use pair_soundness_tests::CompileFailTests;
+//!
+//! #[test]
+//! fn compile_fail_tests() {
+//!     CompileFailTests::new("tests/compile_fails")
+//!         .extern_crate("my_crate")
+//!         .run();
+//! }
+//! ```
+
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// A set of compile-fail tests, read from `.rs` files in a directory.
+///
+/// See the crate-level documentation for more information.
+#[derive(Debug, Clone)]
+pub struct CompileFailTests {
+    dir: PathBuf,
+    extern_crates: Vec<String>,
+}
+
+impl CompileFailTests {
+    /// Creates a set of compile-fail tests from the `.rs` files in the given
+    /// directory (relative to the current working directory, which is the
+    /// package root when run by `cargo test`).
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            extern_crates: Vec::new(),
+        }
+    }
+
+    /// Makes the library crate with the given name available to the tests, as
+    /// if it were a dependency. This should usually be the crate under test.
+    ///
+    /// The crate must be built by `cargo build` in the current package (that
+    /// is, it's the current package's library, or one of its dependencies).
+    #[must_use]
+    pub fn extern_crate(mut self, name: impl Into<String>) -> Self {
+        self.extern_crates.push(name.into());
+        self
+    }
+
+    /// Runs the tests, checking that each file fails to compile with the
+    /// expected errors.
+    ///
+    /// # Panics
+    /// If the directory or any `.expected` file can't be read, the current
+    /// package fails to build, or any test compiles successfully or without one
+    /// of its expected errors.
+    pub fn run(&self) {
+        let test_file_paths = self.test_file_paths();
+
+        // Ensure the extern crates' build artifacts are available, and get the
+        // target dir
+        let target_dir = ensure_built().expect("failed to build the current package");
+
+        // For each file, ensure it fails to compile with the expected error
+        // message
+        for test_file_path in test_file_paths {
+            let expected_path = test_file_path.with_extension("expected");
+            let expected_substrings: Vec<_> = std::fs::read_to_string(&expected_path)
+                .unwrap_or_else(|_| panic!("failed to read file: {}", expected_path.display()))
+                .lines()
+                .map(str::to_owned)
+                .collect();
+
+            let compiler_output = self.get_compiler_err(&target_dir, &test_file_path);
+
+            for expected_substring in expected_substrings {
+                assert!(
+                    compiler_output.contains(&expected_substring),
+                    "compiler error did not contain expected substring: {expected_substring}"
+                );
+            }
+        }
+    }
+
+    /// Returns the paths of all `.rs` files in the test directory.
+    fn test_file_paths(&self) -> Vec<PathBuf> {
+        // Some majorly sauced up functional magic
+        std::fs::read_dir(&self.dir)
+            .and_then(|dir_iter| {
+                dir_iter
+                    .filter_map(|entry| {
+                        entry
+                            .and_then(|entry| {
+                                Ok((entry.file_type()?.is_file()
+                                    && entry
+                                        .path()
+                                        .extension()
+                                        .is_some_and(|extension| extension == "rs"))
+                                .then_some(entry.path()))
+                            })
+                            .transpose()
+                    })
+                    .collect()
+            })
+            .unwrap_or_else(|_| panic!("failed to read directory: {}", self.dir.display()))
+    }
+
+    /// Returns the stderr from rustc attempting to compile the given file.
+    ///
+    /// Makes quite a few assumptions about the environment, namely that
+    /// `ensure_built` has been called.
+    ///
+    /// # Panics
+    /// In quite a few situations, read the code lol
+    fn get_compiler_err(&self, target_dir: &Path, test_file_path: &Path) -> String {
+        let mut command = Command::new("rustc");
+        command.arg(test_file_path);
+
+        for name in &self.extern_crates {
+            let name = name.replace('-', "_");
+            let mut extern_arg = OsString::from(format!("{name}="));
+            extern_arg.push(target_dir.join(format!("lib{name}.rlib")));
+            command.arg("--extern").arg(extern_arg);
+        }
+
+        let mut dependency_arg = OsString::from("dependency=");
+        dependency_arg.push(target_dir.join("deps"));
+        command.arg("-L").arg(dependency_arg);
+
+        let output = command
+            .output()
+            .expect("failed to get output of rustc command");
+
+        assert!(
+            !output.status.success(),
+            "test compiled, but was expected not to: {}",
+            test_file_path.display()
+        );
+
+        String::from_utf8(output.stderr).expect("rustc output was not UTF-8")
+    }
+}
+
+/// Ensures the current package's build artifacts are available in the target
+/// directory, and returns a path to that target directory (relative to the
+/// current working directory).
+fn ensure_built() -> Option<PathBuf> {
+    Command::new("cargo")
+        .arg("build")
+        .status()
+        .ok()?
+        .success()
+        .then_some(())?;
+
+    Some(
+        std::env::var("CARGO_TARGET_DIR")
+            .map_or_else(|_| PathBuf::from("target"), PathBuf::from)
+            .join("debug"),
+    )
+}
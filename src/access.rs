@@ -0,0 +1,91 @@
+//! Defines [`AccessGuard`], a reusable view of both halves of a
+//! [`Pair`](crate::Pair).
+
+use core::fmt::Debug;
+
+use crate::{Dependent, Owner};
+
+/// Shared access to both the owner and the dependent of a
+/// [`Pair`](crate::Pair), valid for a single borrow of the pair.
+///
+/// Obtained through [`Pair::access`](crate::Pair::access). The references to
+/// the owner and dependent are resolved once when the guard is created, so
+/// code which reads from a pair many times within one borrow region (such as a
+/// hot loop) can do so through the guard without repeating that work for every
+/// access.
+///
+/// Like [`Pair::with_dependent`](crate::Pair::with_dependent), the dependent
+/// can only be accessed within a closure which must work with a [`Dependent`]
+/// of any lifetime - see its documentation for details.
+///
+/// [`Dependent`]: crate::HasDependent::Dependent
+pub struct AccessGuard<'pair, O: Owner + ?Sized> {
+    owner: &'pair O,
+
+    // The real lifetime of the dependent (lasting from the construction of the
+    // pair until drop) is inexpressible - `'pair` is a stand-in which is never
+    // exposed to callers, since the dependent may be invariant over it.
+    dependent: &'pair Dependent<'pair, O>,
+}
+
+impl<'pair, O: Owner + ?Sized> AccessGuard<'pair, O> {
+    /// Creates a new [`AccessGuard`] from references to an owner and its
+    /// dependent.
+    ///
+    /// The dependent must never be handed out with the `'pair` lifetime - it
+    /// may only be exposed through closures generic over its lifetime.
+    pub(crate) fn new(owner: &'pair O, dependent: &'pair Dependent<'pair, O>) -> Self {
+        Self { owner, dependent }
+    }
+
+    /// Returns a reference to the owner.
+    pub fn owner(&self) -> &'pair O {
+        self.owner
+    }
+
+    /// Calls the given closure, providing shared access to the dependent, and
+    /// returns the value computed by the closure.
+    ///
+    /// See the documentation of [`Pair::with_dependent`](crate::Pair::with_dependent)
+    /// for more information.
+    pub fn with_dependent<F, T>(&self, f: F) -> T
+    where
+        F: for<'any> FnOnce(&'pair Dependent<'_, O>) -> T,
+    {
+        f(self.dependent)
+    }
+
+    /// Calls the given closure, providing shared access to both the owner and
+    /// the dependent, and returns the value computed by the closure.
+    ///
+    /// See the documentation of [`Pair::with_both`](crate::Pair::with_both)
+    /// for more information.
+    pub fn with_both<F, T>(&self, f: F) -> T
+    where
+        F: for<'any> FnOnce(&'pair O, &'pair Dependent<'_, O>) -> T,
+    {
+        f(self.owner, self.dependent)
+    }
+}
+
+impl<O: Owner + ?Sized> Clone for AccessGuard<'_, O> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<O: Owner + ?Sized> Copy for AccessGuard<'_, O> {}
+
+impl<O: Owner + Debug + ?Sized> Debug for AccessGuard<'_, O>
+where
+    for<'any> Dependent<'any, O>: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.with_dependent(|dependent| {
+            f.debug_struct("AccessGuard")
+                .field("owner", &self.owner)
+                .field("dependent", dependent)
+                .finish()
+        })
+    }
+}
@@ -0,0 +1,282 @@
+//! Defines [`AlignedVec`], a growable byte buffer with a guaranteed alignment,
+//! for owners of zero-copy dependents.
+
+use core::{
+    alloc::Layout,
+    fmt::Debug,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
+
+use alloc::alloc::{alloc, dealloc, handle_alloc_error, realloc};
+
+/// A growable buffer of bytes, whose data is always aligned to `ALIGN` bytes.
+///
+/// Zero-copy dependents (such as views which reinterpret an owner's bytes as
+/// `&[u32]`, or as an archived type from a serialization framework) often
+/// require their bytes to be aligned - which `Vec<u8>` can't guarantee, since
+/// it only promises the alignment of `u8`. `AlignedVec` can be used as (part
+/// of) the owner of a [`Pair`](crate::Pair) for these dependents, and such
+/// pairs can be constructed from a copy of some unaligned bytes with
+/// [`Pair::new_from_aligned_bytes`](crate::Pair::new_from_aligned_bytes) (and
+/// its fallible and contextual variants).
+///
+/// `ALIGN` must be a power of two.
+///
+/// ```
+/// # use pair::{AlignedVec, Dependent, HasDependent, Owner, Pair};
+/// # use std::convert::Infallible;
+/// struct Samples(AlignedVec<4>);
+///
+/// impl From<AlignedVec<4>> for Samples {
+///     fn from(bytes: AlignedVec<4>) -> Self {
+///         Self(bytes)
+///     }
+/// }
+///
+/// impl<'owner> HasDependent<'owner> for Samples {
+///     type Dependent = &'owner [u32];
+/// }
+///
+/// impl Owner for Samples {
+///     type Context<'a> = ();
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         // SAFETY: any 4 initialized bytes are a valid `u32`
+///         let (prefix, samples, _) = unsafe { self.0.align_to::<u32>() };
+///         // The buffer is aligned, so there is never an unaligned prefix
+///         assert!(prefix.is_empty());
+///         Ok(samples)
+///     }
+/// }
+///
+/// let bytes: Vec<u8> = [1_u32, 2, 3].iter().flat_map(|n| n.to_ne_bytes()).collect();
+/// let pair = Pair::<Samples>::new_from_aligned_bytes(&bytes);
+/// assert_eq!(pair.with_dependent(|samples| *samples), [1, 2, 3]);
+/// ```
+pub struct AlignedVec<const ALIGN: usize> {
+    ptr: NonNull<u8>,
+    len: usize,
+    capacity: usize,
+}
+
+impl<const ALIGN: usize> AlignedVec<ALIGN> {
+    /// Constructs a new, empty [`AlignedVec`]. This does not allocate.
+    pub const fn new() -> Self {
+        const { assert!(ALIGN.is_power_of_two(), "ALIGN must be a power of two") };
+
+        Self {
+            ptr: dangling::<ALIGN>(),
+            len: 0,
+            capacity: 0,
+        }
+    }
+
+    /// Constructs a new, empty [`AlignedVec`] with at least the given capacity.
+    ///
+    /// # Panics
+    /// If the capacity rounded up to `ALIGN` exceeds [`isize::MAX`] bytes.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut vec = Self::new();
+        vec.reserve(capacity);
+        vec
+    }
+
+    /// Returns the number of bytes in the buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the buffer contains no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of bytes the buffer can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the bytes in the buffer. The returned slice is always aligned to
+    /// `ALIGN` bytes.
+    pub fn as_slice(&self) -> &[u8] {
+        // SAFETY: `self.ptr` is either dangling (and suitably aligned) with a
+        // length of 0, or points to an allocation of `self.capacity` bytes,
+        // the first `self.len` of which are initialized. We have a shared
+        // borrow of `self`, so nothing can mutate the bytes.
+        unsafe { core::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Returns the bytes in the buffer mutably. The returned slice is always
+    /// aligned to `ALIGN` bytes.
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        // SAFETY: `self.ptr` is either dangling (and suitably aligned) with a
+        // length of 0, or points to an allocation of `self.capacity` bytes,
+        // the first `self.len` of which are initialized. We have an exclusive
+        // borrow of `self`, so nothing else can access the bytes.
+        unsafe { core::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Reserves capacity for at least `additional` more bytes.
+    ///
+    /// # Panics
+    /// If the new capacity rounded up to `ALIGN` exceeds [`isize::MAX`] bytes.
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self
+            .len
+            .checked_add(additional)
+            .expect("AlignedVec capacity overflow");
+        if required <= self.capacity {
+            return;
+        }
+
+        let new_capacity = required.max(self.capacity.saturating_mul(2));
+        let new_layout = layout::<ALIGN>(new_capacity);
+
+        let ptr = if self.capacity == 0 {
+            // SAFETY: `new_layout` has a non-zero size, since `new_capacity`
+            // is at least `required`, which is greater than 0.
+            unsafe { alloc(new_layout) }
+        } else {
+            // SAFETY: `self.ptr` was allocated with the global allocator, with
+            // the layout for `self.capacity`. `new_capacity` is non-zero, and
+            // `layout` checked that it doesn't overflow `isize` when rounded
+            // up to `ALIGN`.
+            unsafe {
+                realloc(
+                    self.ptr.as_ptr(),
+                    layout::<ALIGN>(self.capacity),
+                    new_capacity,
+                )
+            }
+        };
+
+        self.ptr = NonNull::new(ptr).unwrap_or_else(|| handle_alloc_error(new_layout));
+        self.capacity = new_capacity;
+    }
+
+    /// Appends a byte to the end of the buffer.
+    pub fn push(&mut self, byte: u8) {
+        self.extend_from_slice(&[byte]);
+    }
+
+    /// Appends all bytes of a slice to the end of the buffer.
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        self.reserve(bytes.len());
+
+        // SAFETY: `self.len` is at most `self.capacity`, so the offset pointer
+        // stays within (or one past the end of) our allocation.
+        let end = unsafe { self.ptr.as_ptr().add(self.len) };
+
+        // SAFETY: We just reserved enough capacity for `bytes` after the
+        // initialized bytes, and `bytes` can't overlap our allocation since we
+        // have an exclusive borrow of `self`.
+        unsafe { core::ptr::copy_nonoverlapping(bytes.as_ptr(), end, bytes.len()) };
+        self.len += bytes.len();
+    }
+
+    /// Removes all bytes from the buffer, keeping its capacity.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+/// Returns a dangling pointer aligned to `ALIGN`, for an empty buffer.
+const fn dangling<const ALIGN: usize>() -> NonNull<u8> {
+    // SAFETY: `ALIGN` is a power of two, so it is non-zero.
+    unsafe { NonNull::new_unchecked(core::ptr::without_provenance_mut(ALIGN)) }
+}
+
+/// Returns the layout of an allocation of `capacity` bytes aligned to `ALIGN`.
+fn layout<const ALIGN: usize>(capacity: usize) -> Layout {
+    Layout::from_size_align(capacity, ALIGN).expect("AlignedVec capacity overflow")
+}
+
+impl<const ALIGN: usize> Drop for AlignedVec<ALIGN> {
+    fn drop(&mut self) {
+        if self.capacity > 0 {
+            // SAFETY: `self.ptr` was allocated with the global allocator, with
+            // the layout for `self.capacity`.
+            unsafe { dealloc(self.ptr.as_ptr(), layout::<ALIGN>(self.capacity)) };
+        }
+    }
+}
+
+impl<const ALIGN: usize> Deref for AlignedVec<ALIGN> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<const ALIGN: usize> DerefMut for AlignedVec<ALIGN> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.as_mut_slice()
+    }
+}
+
+impl<const ALIGN: usize> AsRef<[u8]> for AlignedVec<ALIGN> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<const ALIGN: usize> AsMut<[u8]> for AlignedVec<ALIGN> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.as_mut_slice()
+    }
+}
+
+impl<const ALIGN: usize> Default for AlignedVec<ALIGN> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const ALIGN: usize> Clone for AlignedVec<ALIGN> {
+    fn clone(&self) -> Self {
+        Self::from(self.as_slice())
+    }
+}
+
+impl<const ALIGN: usize> From<&[u8]> for AlignedVec<ALIGN> {
+    fn from(bytes: &[u8]) -> Self {
+        let mut vec = Self::with_capacity(bytes.len());
+        vec.extend_from_slice(bytes);
+        vec
+    }
+}
+
+impl<const ALIGN: usize> Extend<u8> for AlignedVec<ALIGN> {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, bytes: I) {
+        let bytes = bytes.into_iter();
+        self.reserve(bytes.size_hint().0);
+        for byte in bytes {
+            self.push(byte);
+        }
+    }
+}
+
+impl<const ALIGN: usize> PartialEq for AlignedVec<ALIGN> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<const ALIGN: usize> Eq for AlignedVec<ALIGN> {}
+
+impl<const ALIGN: usize> Debug for AlignedVec<ALIGN> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.as_slice(), f)
+    }
+}
+
+// SAFETY: An `AlignedVec` uniquely owns its bytes (just like a `Vec<u8>`), so
+// it can be sent to another thread.
+unsafe impl<const ALIGN: usize> Send for AlignedVec<ALIGN> {}
+
+// SAFETY: An `AlignedVec` only exposes its bytes mutably through exclusive
+// references (just like a `Vec<u8>`), so it can be shared across threads.
+unsafe impl<const ALIGN: usize> Sync for AlignedVec<ALIGN> {}
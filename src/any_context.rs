@@ -0,0 +1,33 @@
+//! Defines [`AnyContextError`], the error returned when constructing a
+//! [`Pair`](crate::Pair) from a type-erased `&dyn Any` context.
+
+use core::fmt::Display;
+
+/// The error returned by constructors taking a type-erased context, such as
+/// [`Pair::try_new_with_any_context`](crate::Pair::try_new_with_any_context).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnyContextError<E> {
+    /// The given context was not of the type expected by the owner's
+    /// [`Context`](crate::Owner::Context).
+    WrongType,
+    /// [`make_dependent`](crate::Owner::make_dependent) returned an error.
+    Failed(E),
+}
+
+impl<E> Display for AnyContextError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::WrongType => f.write_str("context was not of the type expected by the owner"),
+            Self::Failed(_) => f.write_str("failed to construct the dependent of a pair"),
+        }
+    }
+}
+
+impl<E: core::error::Error + 'static> core::error::Error for AnyContextError<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::WrongType => None,
+            Self::Failed(err) => Some(err),
+        }
+    }
+}
@@ -0,0 +1,324 @@
+//! Defines [`PairArena`], a collection of owners whose dependents may borrow
+//! from any owner in the arena, and the [`ArenaOwner`] trait for its owners.
+
+use core::{convert::Infallible, fmt::Debug};
+
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{Dependent, HasDependent, Owner, Pair};
+
+/// A type which can act as an owner in a [`PairArena`], producing a dependent
+/// which may borrow from *any* owner in the same arena.
+///
+/// This is the arena equivalent of [`Owner`]. Like `Owner`, the dependent type
+/// is defined by the supertrait [`HasDependent`]. The dependent's lifetime is
+/// the lifetime of the arena's owners, so it may borrow both from `self` and
+/// from the other owners reachable through the given [`ArenaView`].
+#[expect(
+    clippy::missing_errors_doc,
+    reason = "failure modes are specific to the trait's implementation"
+)]
+pub trait ArenaOwner: Sized + for<'any> HasDependent<'any> {
+    /// The error type returned by [`make_dependent`](ArenaOwner::make_dependent)
+    /// in the event of an error.
+    ///
+    /// If `make_dependent` can't fail, this should be set to [`Infallible`].
+    type Error;
+
+    /// Attempts to construct a [`Dependent`](HasDependent::Dependent) from a
+    /// reference to an owner, and a view of every owner in the arena.
+    fn make_dependent<'arena>(
+        &'arena self,
+        arena: ArenaView<'arena, Self>,
+    ) -> Result<Dependent<'arena, Self>, Self::Error>;
+}
+
+/// A stable index identifying an entry in a [`PairArena`].
+///
+/// The ID of an entry is its index in the `Vec` of owners the arena was
+/// constructed from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ArenaId(usize);
+
+impl ArenaId {
+    /// Constructs an [`ArenaId`] from an index.
+    pub fn new(index: usize) -> Self {
+        Self(index)
+    }
+
+    /// Returns the index of this [`ArenaId`].
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// A view of every owner in a [`PairArena`], provided to
+/// [`ArenaOwner::make_dependent`].
+///
+/// All references obtained through an `ArenaView<'arena, O>` have the lifetime
+/// `'arena` - this lifetime acts as a brand, ensuring that dependents may only
+/// borrow from owners in the same arena (which all live exactly as long as each
+/// other).
+pub struct ArenaView<'arena, O> {
+    owners: &'arena [O],
+    id: ArenaId,
+}
+
+impl<'arena, O> ArenaView<'arena, O> {
+    /// Returns the ID of the owner whose dependent is being constructed.
+    pub fn id(&self) -> ArenaId {
+        self.id
+    }
+
+    /// Returns a reference to the owner with the given ID, or [`None`] if there
+    /// is no such owner in the arena.
+    pub fn get(&self, id: ArenaId) -> Option<&'arena O> {
+        self.owners.get(id.0)
+    }
+
+    /// Returns the number of owners in the arena.
+    pub fn len(&self) -> usize {
+        self.owners.len()
+    }
+
+    /// Returns `true` if the arena contains no owners.
+    ///
+    /// Since an `ArenaView` is only provided while constructing the dependent
+    /// of one of the owners in the arena, this always returns `false`.
+    pub fn is_empty(&self) -> bool {
+        self.owners.is_empty()
+    }
+
+    /// Returns an iterator over every owner in the arena, along with their IDs.
+    pub fn iter(&self) -> impl Iterator<Item = (ArenaId, &'arena O)> + 'arena {
+        self.owners
+            .iter()
+            .enumerate()
+            .map(|(index, owner)| (ArenaId(index), owner))
+    }
+}
+
+impl<O> Clone for ArenaView<'_, O> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<O> Copy for ArenaView<'_, O> {}
+
+impl<O> Debug for ArenaView<'_, O> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ArenaView")
+            .field("len", &self.owners.len())
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+/// The owners of a [`PairArena`], which act as the owner of the single [`Pair`]
+/// it stores.
+struct Nodes<O>(Box<[O]>);
+
+impl<'owner, O: ArenaOwner> HasDependent<'owner> for Nodes<O> {
+    type Dependent = Vec<Dependent<'owner, O>>;
+}
+
+impl<O: ArenaOwner> Owner for Nodes<O> {
+    type Context<'a> = ();
+    type Error = (ArenaId, O::Error);
+
+    fn make_dependent(&self, (): Self::Context<'_>) -> Result<Dependent<'_, Self>, Self::Error> {
+        self.0
+            .iter()
+            .enumerate()
+            .map(|(index, owner)| {
+                let arena = ArenaView {
+                    owners: &self.0,
+                    id: ArenaId(index),
+                };
+
+                owner
+                    .make_dependent(arena)
+                    .map_err(|err| (ArenaId(index), err))
+            })
+            .collect()
+    }
+}
+
+/// A collection of owners, each with a dependent which may borrow from any
+/// owner in the collection.
+///
+/// A [`Pair`] allows a dependent to borrow from a single owner. A `PairArena`
+/// extends this to graph-like structures (such as a module graph, where each
+/// module keeps views into several source files): each entry has an owner and
+/// a dependent, and every dependent may borrow from every owner in the arena.
+/// Entries are identified by stable [`ArenaId`]s.
+///
+/// The owners are fixed when the arena is constructed, and every dependent is
+/// then built through [`ArenaOwner::make_dependent`] - this is what allows
+/// dependents to borrow from each other's owners soundly.
+///
+/// ```
+/// # use pair::{ArenaId, ArenaOwner, ArenaView, Dependent, HasDependent, PairArena};
+/// # use std::convert::Infallible;
+/// struct Module {
+///     source: String,
+///     imports: Vec<ArenaId>,
+/// }
+///
+/// impl<'arena> HasDependent<'arena> for Module {
+///     // The first line of every imported module's source
+///     type Dependent = Vec<&'arena str>;
+/// }
+///
+/// impl ArenaOwner for Module {
+///     type Error = Infallible;
+///
+///     fn make_dependent<'arena>(
+///         &'arena self,
+///         arena: ArenaView<'arena, Self>,
+///     ) -> Result<Dependent<'arena, Self>, Self::Error> {
+///         Ok(self
+///             .imports
+///             .iter()
+///             .filter_map(|&id| arena.get(id)?.source.lines().next())
+///             .collect())
+///     }
+/// }
+///
+/// let arena = PairArena::new(vec![
+///     Module { source: String::from("mod a\nfn a() {}"), imports: vec![ArenaId::new(1)] },
+///     Module { source: String::from("mod b\nfn b() {}"), imports: vec![ArenaId::new(0)] },
+/// ]);
+///
+/// assert_eq!(arena.with_dependent(ArenaId::new(0), |imports| imports[0]), Some("mod b"));
+/// assert_eq!(arena.with_dependent(ArenaId::new(1), |imports| imports[0]), Some("mod a"));
+/// ```
+pub struct PairArena<O: ArenaOwner> {
+    pair: Pair<Nodes<O>>,
+}
+
+impl<O: ArenaOwner> PairArena<O> {
+    /// Constructs a new [`PairArena`] with the given owners. The dependent of
+    /// each owner will be computed through [`ArenaOwner::make_dependent`]
+    /// during this construction, in order.
+    ///
+    /// Each owner's [`ArenaId`] is its index in `owners`.
+    ///
+    /// # Errors
+    /// If [`<O as ArenaOwner>::make_dependent`](ArenaOwner::make_dependent)
+    /// returns an error for any owner. The owners are returned alongside the
+    /// ID of the owner which failed, and the error.
+    #[track_caller]
+    pub fn try_new(owners: Vec<O>) -> Result<Self, (Vec<O>, ArenaId, O::Error)> {
+        match Pair::try_new(Nodes(owners.into_boxed_slice())) {
+            Ok(pair) => Ok(Self { pair }),
+            Err((Nodes(owners), (id, err))) => Err((owners.into_vec(), id, err)),
+        }
+    }
+
+    /// Returns the number of entries in the arena.
+    pub fn len(&self) -> usize {
+        self.pair.owner().0.len()
+    }
+
+    /// Returns `true` if the arena contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.pair.owner().0.is_empty()
+    }
+
+    /// Returns an iterator over the IDs of every entry in the arena.
+    pub fn ids(&self) -> impl Iterator<Item = ArenaId> + use<O> {
+        (0..self.len()).map(ArenaId)
+    }
+
+    /// Returns a reference to the owner with the given ID, or [`None`] if there
+    /// is no such entry in the arena.
+    pub fn owner(&self, id: ArenaId) -> Option<&O> {
+        self.pair.owner().0.get(id.0)
+    }
+
+    /// Returns a slice of every owner in the arena, indexed by their IDs.
+    pub fn owners(&self) -> &[O] {
+        &self.pair.owner().0
+    }
+
+    /// Calls the given closure, providing shared access to the dependent with
+    /// the given ID, and returns the value computed by the closure. Returns
+    /// [`None`] (without calling the closure) if there is no such entry in the
+    /// arena.
+    ///
+    /// See the documentation of [`Pair::with_dependent`] for more information
+    /// on the closure.
+    pub fn with_dependent<'self_borrow, F, T>(&'self_borrow self, id: ArenaId, f: F) -> Option<T>
+    where
+        F: for<'any> FnOnce(&'self_borrow Dependent<'_, O>) -> T,
+    {
+        self.pair
+            .with_dependent(|dependents| dependents.get(id.0).map(f))
+    }
+
+    /// Calls the given closure, providing exclusive access to the dependent
+    /// with the given ID, and returns the value computed by the closure.
+    /// Returns [`None`] (without calling the closure) if there is no such entry
+    /// in the arena.
+    ///
+    /// See the documentation of [`Pair::with_dependent_mut`] for more
+    /// information on the closure.
+    pub fn with_dependent_mut<'self_borrow, F, T>(
+        &'self_borrow mut self,
+        id: ArenaId,
+        f: F,
+    ) -> Option<T>
+    where
+        F: for<'any> FnOnce(&'self_borrow mut Dependent<'_, O>) -> T,
+    {
+        self.pair
+            .with_dependent_mut(|dependents| dependents.get_mut(id.0).map(f))
+    }
+
+    /// Consumes the [`PairArena`], dropping every dependent and returning the
+    /// owners, indexed by their IDs.
+    pub fn into_owners(self) -> Vec<O> {
+        self.pair.into_owner().0.into_vec()
+    }
+}
+
+impl<O: ArenaOwner<Error = Infallible>> PairArena<O> {
+    /// Constructs a new [`PairArena`] with the given owners. The dependent of
+    /// each owner will be computed through [`ArenaOwner::make_dependent`]
+    /// during this construction, in order.
+    ///
+    /// Each owner's [`ArenaId`] is its index in `owners`.
+    #[track_caller]
+    pub fn new(owners: Vec<O>) -> Self {
+        let Ok(arena) = Self::try_new(owners);
+        arena
+    }
+}
+
+// SAFETY: A `PairArena` is just a `Pair<Nodes<O>>`, which is `Send` exactly
+// when `Box<[O]>` and `Vec<Dependent<'_, O>>` are - that is, when `O` and
+// `Dependent<'_, O>` are `Send`. See the NOTE on `Pair`'s `Send` impl for why
+// this is written out explicitly.
+unsafe impl<O: ArenaOwner + Send> Send for PairArena<O> where for<'any> Dependent<'any, O>: Send {}
+
+// SAFETY: A `PairArena` is just a `Pair<Nodes<O>>`, which is `Sync` exactly
+// when `Box<[O]>` and `Vec<Dependent<'_, O>>` are - that is, when `O` and
+// `Dependent<'_, O>` are `Sync`. See the NOTE on `Pair`'s `Send` impl for why
+// this is written out explicitly.
+unsafe impl<O: ArenaOwner + Sync> Sync for PairArena<O> where for<'any> Dependent<'any, O>: Sync {}
+
+impl<O: ArenaOwner + Debug> Debug for PairArena<O>
+where
+    for<'any> Dependent<'any, O>: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.pair.with_both(|Nodes(owners), dependents| {
+            f.debug_struct("PairArena")
+                .field("owners", owners)
+                .field("dependents", dependents)
+                .finish()
+        })
+    }
+}
@@ -0,0 +1,204 @@
+//! Defines [`ArgsSnapshot`], an owner of a process's command-line arguments and
+//! environment variables whose dependent is the parsed [`ParsedArgs`]. Only
+//! available with the `std` feature.
+
+use core::convert::Infallible;
+use std::ffi::{OsStr, OsString};
+
+use alloc::vec::Vec;
+
+use crate::{Dependent, HasDependent, Owner, Pair};
+
+/// A [`Pair`] of an [`ArgsSnapshot`] and its [`ParsedArgs`].
+///
+/// Only available with the `std` feature.
+pub type ArgsPair = Pair<ArgsSnapshot>;
+
+/// A snapshot of a process's command-line arguments and environment variables,
+/// whose dependent is the parsed [`ParsedArgs`].
+///
+/// CLI tools often need both the raw arguments (for example, to re-execute
+/// themselves or log their invocation) and a parsed form of them. An
+/// [`ArgsPair`] holds both as a single value, with the parsed form borrowing
+/// from the raw arguments.
+///
+/// ```
+/// # use pair::{ArgsPair, ArgsSnapshot};
+/// let args = ArgsPair::new(ArgsSnapshot::new(
+///     ["tool", "build", "--release", "--jobs=4", "-qv", "--", "--not-an-option"],
+///     [("HOME", "/home/user")],
+/// ));
+///
+/// args.with_dependent(|parsed| {
+///     assert_eq!(parsed.program().unwrap(), "tool");
+///     assert!(parsed.flag("release") && parsed.flag("q") && parsed.flag("v"));
+///     assert_eq!(parsed.value("jobs"), Some("4"));
+///     assert_eq!(parsed.positionals(), ["build", "--not-an-option"]);
+///     assert_eq!(parsed.var("HOME").unwrap(), "/home/user");
+/// });
+/// ```
+///
+/// Only available with the `std` feature.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ArgsSnapshot {
+    args: Vec<OsString>,
+    vars: Vec<(OsString, OsString)>,
+}
+
+impl ArgsSnapshot {
+    /// Constructs a new [`ArgsSnapshot`] from the given arguments (starting
+    /// with the program name, as with [`std::env::args_os`]) and environment
+    /// variables.
+    pub fn new<A, V, K, Val>(args: A, vars: V) -> Self
+    where
+        A: IntoIterator<Item: Into<OsString>>,
+        V: IntoIterator<Item = (K, Val)>,
+        K: Into<OsString>,
+        Val: Into<OsString>,
+    {
+        Self {
+            args: args.into_iter().map(Into::into).collect(),
+            vars: vars
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        }
+    }
+
+    /// Captures a snapshot of the current process's arguments and environment
+    /// variables, with [`std::env::args_os`] and [`std::env::vars_os`].
+    pub fn capture() -> Self {
+        Self {
+            args: std::env::args_os().collect(),
+            vars: std::env::vars_os().collect(),
+        }
+    }
+
+    /// Returns the arguments, starting with the program name.
+    pub fn args(&self) -> &[OsString] {
+        &self.args
+    }
+
+    /// Returns the environment variables, in the order they were captured.
+    pub fn vars(&self) -> &[(OsString, OsString)] {
+        &self.vars
+    }
+}
+
+impl<'owner> HasDependent<'owner> for ArgsSnapshot {
+    type Dependent = ParsedArgs<'owner>;
+}
+
+impl Owner for ArgsSnapshot {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(ParsedArgs::parse(self))
+    }
+}
+
+/// The parsed form of an [`ArgsSnapshot`], borrowing from it.
+///
+/// Arguments are parsed with common conventions:
+/// - `--name` is a long flag, and `--name=value` is a long option with a value
+/// - `-abc` is the short flags `a`, `b`, and `c`
+/// - `--` ends the options - every argument after it is positional
+/// - every other argument (including `-`, and any argument which isn't valid
+///   Unicode) is positional
+///
+/// Options never consume the following argument as their value, since that
+/// would require knowing which options take values.
+///
+/// Only available with the `std` feature.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParsedArgs<'a> {
+    program: Option<&'a OsStr>,
+    options: Vec<(&'a str, Option<&'a str>)>,
+    positionals: Vec<&'a OsStr>,
+    vars: &'a [(OsString, OsString)],
+}
+
+impl<'a> ParsedArgs<'a> {
+    /// Parses the arguments of the given snapshot.
+    pub fn parse(snapshot: &'a ArgsSnapshot) -> Self {
+        let mut args = snapshot.args.iter().map(OsString::as_os_str);
+        let program = args.next();
+
+        let mut options = Vec::new();
+        let mut positionals = Vec::new();
+        while let Some(arg) = args.next() {
+            match arg.to_str() {
+                Some("--") => {
+                    positionals.extend(args.by_ref());
+                }
+                Some(arg) if arg.starts_with("--") => {
+                    let option = &arg[2..];
+                    options.push(match option.split_once('=') {
+                        Some((name, value)) => (name, Some(value)),
+                        None => (option, None),
+                    });
+                }
+                Some(arg) if arg.len() > 1 && arg.starts_with('-') => {
+                    options.extend(
+                        arg[1..]
+                            .char_indices()
+                            .map(|(index, c)| (&arg[1 + index..1 + index + c.len_utf8()], None)),
+                    );
+                }
+                _ => positionals.push(arg),
+            }
+        }
+
+        Self {
+            program,
+            options,
+            positionals,
+            vars: &snapshot.vars,
+        }
+    }
+
+    /// Returns the program name (the first argument), if there is one.
+    pub fn program(&self) -> Option<&'a OsStr> {
+        self.program
+    }
+
+    /// Returns the name and value of every option, in the order they were
+    /// given. Flags (and short options) have no value.
+    pub fn options(&self) -> &[(&'a str, Option<&'a str>)] {
+        &self.options
+    }
+
+    /// Returns whether the option with the given name (without its leading
+    /// dashes) was given, with or without a value.
+    pub fn flag(&self, name: &str) -> bool {
+        self.options.iter().any(|&(option, _)| option == name)
+    }
+
+    /// Returns the value of the last occurrence of the option with the given
+    /// name (without its leading dashes), if it was given with a value.
+    pub fn value(&self, name: &str) -> Option<&'a str> {
+        self.options
+            .iter()
+            .rev()
+            .find(|&&(option, _)| option == name)
+            .and_then(|&(_, value)| value)
+    }
+
+    /// Returns the positional arguments, in the order they were given.
+    pub fn positionals(&self) -> &[&'a OsStr] {
+        &self.positionals
+    }
+
+    /// Returns the value of the environment variable with the given name, if
+    /// it was set.
+    pub fn var(&self, name: impl AsRef<OsStr>) -> Option<&'a OsStr> {
+        let name = name.as_ref();
+
+        self.vars
+            .iter()
+            .rev()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_os_str())
+    }
+}
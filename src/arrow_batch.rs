@@ -0,0 +1,148 @@
+//! Defines [`ArrowBatch`], an owner of an Arrow record batch whose dependent is
+//! a set of typed views of the batch's columns. Only available with the `arrow`
+//! feature.
+
+use core::{any::type_name, fmt::Debug, marker::PhantomData};
+
+use alloc::format;
+
+use arrow_array::{Array, RecordBatch};
+use arrow_schema::ArrowError;
+
+use crate::{Dependent, HasDependent, Owner, Pair};
+
+/// Describes the typed views of a record batch's columns, for use with
+/// [`ArrowBatch`].
+///
+/// Implement this trait for a marker type to define which columns are viewed,
+/// and as which types - usually with [`column_as`].
+///
+/// Only available with the `arrow` feature.
+pub trait BatchView {
+    /// The typed views of the columns, borrowing from a record batch with the
+    /// lifetime `'batch`.
+    type View<'batch>;
+
+    /// Constructs the typed views of the given batch's columns.
+    ///
+    /// # Errors
+    /// If the batch doesn't have the expected columns, or they don't have the
+    /// expected types.
+    fn view(batch: &RecordBatch) -> Result<Self::View<'_>, ArrowError>;
+}
+
+/// An Arrow [`RecordBatch`], which can act as the owner of a [`Pair`] with
+/// typed views of its columns (as described by a [`BatchView`]) as the
+/// dependent.
+///
+/// The columns are looked up and downcast once when the pair is constructed,
+/// so an analytics pipeline can pass around a single value which keeps the
+/// batch's buffers alive alongside their typed accessors.
+///
+/// Only available with the `arrow` feature.
+///
+/// ```
+/// # use pair::{ArrowBatch, ArrowBatchPair, BatchView, Pair, column_as};
+/// # use std::sync::Arc;
+/// # use arrow_array::{Float64Array, RecordBatch, StringArray};
+/// # use arrow_schema::ArrowError;
+/// struct Prices;
+///
+/// impl BatchView for Prices {
+///     type View<'batch> = (&'batch StringArray, &'batch Float64Array);
+///
+///     fn view(batch: &RecordBatch) -> Result<Self::View<'_>, ArrowError> {
+///         Ok((column_as(batch, "item")?, column_as(batch, "price")?))
+///     }
+/// }
+///
+/// let batch = RecordBatch::try_from_iter([
+///     ("item", Arc::new(StringArray::from(vec!["apple", "pear"])) as _),
+///     ("price", Arc::new(Float64Array::from(vec![0.5, 0.75])) as _),
+/// ])
+/// .unwrap();
+///
+/// let pair: ArrowBatchPair<Prices> = Pair::try_new(ArrowBatch::new(batch)).unwrap();
+/// pair.with_dependent(|(items, prices)| {
+///     assert_eq!(items.value(1), "pear");
+///     assert_eq!(prices.value(1), 0.75);
+/// });
+/// ```
+pub struct ArrowBatch<V: BatchView> {
+    batch: RecordBatch,
+    view: PhantomData<fn() -> V>,
+}
+
+/// A [`Pair`] of an [`ArrowBatch`] and its typed column views.
+///
+/// Only available with the `arrow` feature.
+pub type ArrowBatchPair<V> = Pair<ArrowBatch<V>>;
+
+impl<V: BatchView> ArrowBatch<V> {
+    /// Constructs a new [`ArrowBatch`] from a record batch.
+    ///
+    /// The batch's columns are not checked until a [`Pair`] is constructed
+    /// from it.
+    pub fn new(batch: RecordBatch) -> Self {
+        Self {
+            batch,
+            view: PhantomData,
+        }
+    }
+
+    /// Returns the record batch.
+    pub fn batch(&self) -> &RecordBatch {
+        &self.batch
+    }
+
+    /// Consumes the [`ArrowBatch`], returning the record batch.
+    pub fn into_batch(self) -> RecordBatch {
+        self.batch
+    }
+}
+
+impl<V: BatchView> Debug for ArrowBatch<V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ArrowBatch")
+            .field("batch", &self.batch)
+            .finish()
+    }
+}
+
+impl<'owner, V: BatchView> HasDependent<'owner> for ArrowBatch<V> {
+    type Dependent = V::View<'owner>;
+}
+
+impl<V: BatchView> Owner for ArrowBatch<V> {
+    type Context<'a> = ();
+    type Error = ArrowError;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        V::view(&self.batch)
+    }
+}
+
+/// Returns the column of the given batch with the given name, downcast to the
+/// concrete array type `A`.
+///
+/// Only available with the `arrow` feature.
+///
+/// # Errors
+/// [`ArrowError::SchemaError`] if the batch has no column with the given name,
+/// or [`ArrowError::CastError`] if the column isn't an `A`.
+pub fn column_as<'batch, A: Array + 'static>(
+    batch: &'batch RecordBatch,
+    name: &str,
+) -> Result<&'batch A, ArrowError> {
+    let column = batch
+        .column_by_name(name)
+        .ok_or_else(|| ArrowError::SchemaError(format!("no column named \"{name}\"")))?;
+
+    column.as_any().downcast_ref().ok_or_else(|| {
+        ArrowError::CastError(format!(
+            "column \"{name}\" has type {}, not {}",
+            column.data_type(),
+            type_name::<A>(),
+        ))
+    })
+}
@@ -0,0 +1,142 @@
+//! Defines [`AssertSendPair`] and [`AssertSyncPair`], wrappers which assert
+//! that a [`Pair`] is [`Send`] or [`Sync`] when the compiler can't prove it.
+
+use core::{fmt::Debug, ops::Deref};
+
+use crate::{Dependent, Owner, Pair};
+
+/// A [`Pair`] which is asserted to be [`Send`], even if its owner or dependent
+/// is not.
+///
+/// A `Pair<O>` is `Send` only when both `O` and its dependent are. Sometimes,
+/// a dependent is thread-safe in ways the compiler can't see - for example, it
+/// may store raw pointers (which are never `Send`) which only ever point into
+/// the owner. Rather than wrapping the whole pair in your own `unsafe` newtype,
+/// you can wrap it in an `AssertSendPair`, which is always `Send` (and is
+/// [`Sync`] exactly when the pair is).
+///
+/// An `AssertSendPair` dereferences to the inner [`Pair`]. It intentionally
+/// provides no mutable access to the pair, since that would allow replacing it
+/// with a pair the assertion was never made for - to mutate the pair, take it
+/// out with [`into_inner`](AssertSendPair::into_inner) and wrap it again.
+pub struct AssertSendPair<O: Owner + ?Sized> {
+    pair: Pair<O>,
+}
+
+impl<O: Owner + ?Sized> AssertSendPair<O> {
+    /// Wraps the given pair, asserting that it is [`Send`].
+    ///
+    /// # Safety
+    /// It must be sound to move the owner and dependent of `pair` (together)
+    /// to another thread, and to use and drop them there. For example, the
+    /// dependent must not contain any references or pointers to thread-local
+    /// data, or to data outside of the pair which is not itself thread-safe.
+    pub unsafe fn new(pair: Pair<O>) -> Self {
+        Self { pair }
+    }
+
+    /// Returns a reference to the inner [`Pair`].
+    pub fn get(&self) -> &Pair<O> {
+        &self.pair
+    }
+
+    /// Consumes the [`AssertSendPair`], returning the inner [`Pair`].
+    pub fn into_inner(self) -> Pair<O> {
+        self.pair
+    }
+}
+
+impl<O: Owner + ?Sized> Deref for AssertSendPair<O> {
+    type Target = Pair<O>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.pair
+    }
+}
+
+// SAFETY: The caller of `AssertSendPair::new` guaranteed that it's sound to
+// send the pair to another thread.
+unsafe impl<O: Owner + ?Sized> Send for AssertSendPair<O> {}
+
+// SAFETY: An `AssertSendPair` only provides access to the inner pair, so it is
+// `Sync` exactly when the pair is. See the NOTE on `Pair`'s `Send` impl for why
+// this is written out explicitly.
+unsafe impl<O: Owner + Sync + ?Sized> Sync for AssertSendPair<O> where
+    for<'any> Dependent<'any, O>: Sync
+{
+}
+
+impl<O: Owner + Debug + ?Sized> Debug for AssertSendPair<O>
+where
+    for<'any> Dependent<'any, O>: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("AssertSendPair").field(&self.pair).finish()
+    }
+}
+
+/// A [`Pair`] which is asserted to be [`Sync`], even if its owner or dependent
+/// is not.
+///
+/// This is the [`Sync`] counterpart of [`AssertSendPair`] - see its
+/// documentation for more information. An `AssertSyncPair` is always `Sync`,
+/// and is [`Send`] exactly when the pair is.
+///
+/// An `AssertSyncPair` dereferences to the inner [`Pair`], and (like an
+/// [`AssertSendPair`]) provides no mutable access to it.
+pub struct AssertSyncPair<O: Owner + ?Sized> {
+    pair: Pair<O>,
+}
+
+impl<O: Owner + ?Sized> AssertSyncPair<O> {
+    /// Wraps the given pair, asserting that it is [`Sync`].
+    ///
+    /// # Safety
+    /// It must be sound to access the owner and dependent of `pair` through
+    /// shared references from multiple threads at once. For example, the
+    /// dependent must not contain any unsynchronized interior mutability
+    /// (such as a [`Cell`](core::cell::Cell)) which is reachable through a
+    /// shared reference.
+    pub unsafe fn new(pair: Pair<O>) -> Self {
+        Self { pair }
+    }
+
+    /// Returns a reference to the inner [`Pair`].
+    pub fn get(&self) -> &Pair<O> {
+        &self.pair
+    }
+
+    /// Consumes the [`AssertSyncPair`], returning the inner [`Pair`].
+    pub fn into_inner(self) -> Pair<O> {
+        self.pair
+    }
+}
+
+impl<O: Owner + ?Sized> Deref for AssertSyncPair<O> {
+    type Target = Pair<O>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.pair
+    }
+}
+
+// SAFETY: An `AssertSyncPair` only provides access to the inner pair, so it is
+// `Send` exactly when the pair is. See the NOTE on `Pair`'s `Send` impl for why
+// this is written out explicitly.
+unsafe impl<O: Owner + Send + ?Sized> Send for AssertSyncPair<O> where
+    for<'any> Dependent<'any, O>: Send
+{
+}
+
+// SAFETY: The caller of `AssertSyncPair::new` guaranteed that it's sound to
+// share references to the pair across threads.
+unsafe impl<O: Owner + ?Sized> Sync for AssertSyncPair<O> {}
+
+impl<O: Owner + Debug + ?Sized> Debug for AssertSyncPair<O>
+where
+    for<'any> Dependent<'any, O>: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("AssertSyncPair").field(&self.pair).finish()
+    }
+}
@@ -0,0 +1,189 @@
+//! Constructors for building many [`Pair`]s in parallel on scoped threads.
+//! Only available with the `std` feature.
+
+use core::{convert::Infallible, num::NonZeroUsize};
+
+use alloc::vec::Vec;
+
+use crate::{Owner, Pair};
+
+impl<O: Owner + Send> Pair<O> {
+    /// Constructs a new [`Pair`] for each of the given owners, computing the
+    /// dependents in parallel across multiple threads. Each dependent will be
+    /// computed through [`Owner::make_dependent`] with a clone of the given
+    /// context.
+    ///
+    /// The owners are split into contiguous chunks, one for each worker thread
+    /// (up to [`std::thread::available_parallelism`]), which are spawned with
+    /// [`std::thread::scope`]. The results are returned in the same order as
+    /// the owners were given.
+    ///
+    /// Only available with the `std` feature.
+    ///
+    /// # Panics
+    /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) panics, the
+    /// panic is propagated once all worker threads have finished. All other
+    /// owners and pairs are dropped.
+    #[expect(
+        clippy::needless_pass_by_value,
+        reason = "the context is taken by value, consistent with the other constructors"
+    )]
+    pub fn try_new_batch_with_context<'context>(
+        owners: Vec<O>,
+        context: O::Context<'context>,
+    ) -> Vec<Result<Self, (O, O::Error)>>
+    where
+        O::Context<'context>: Clone + Send,
+        O::Error: Send,
+        Self: Send,
+    {
+        let workers = std::thread::available_parallelism()
+            .map_or(1, NonZeroUsize::get)
+            .min(owners.len());
+
+        // There's no need to spawn any threads if there's only a single worker
+        if workers <= 1 {
+            return owners
+                .into_iter()
+                .map(|owner| Self::try_new_with_context(owner, context.clone()))
+                .collect();
+        }
+
+        let chunk_size = owners.len().div_ceil(workers);
+        let mut owners = owners.into_iter();
+        let chunks = core::iter::from_fn(|| {
+            let chunk: Vec<O> = owners.by_ref().take(chunk_size).collect();
+            (!chunk.is_empty()).then_some(chunk)
+        });
+
+        std::thread::scope(|scope| {
+            let workers: Vec<_> = chunks
+                .map(|chunk| {
+                    let context = context.clone();
+                    scope.spawn(move || {
+                        chunk
+                            .into_iter()
+                            .map(|owner| Self::try_new_with_context(owner, context.clone()))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+
+            workers
+                .into_iter()
+                .flat_map(|worker| {
+                    worker
+                        .join()
+                        .unwrap_or_else(|payload| std::panic::resume_unwind(payload))
+                })
+                .collect()
+        })
+    }
+
+    /// Constructs a new [`Pair`] for each of the given owners, computing the
+    /// dependents in parallel across multiple threads, and partitions the
+    /// results instead of failing fast.
+    ///
+    /// The successfully constructed pairs are returned first, and every failed
+    /// owner along with its error second (both in the order the owners were
+    /// given). This allows keeping the good owners and reporting all of the
+    /// bad ones in a single pass. See the documentation of
+    /// [`Pair::try_new_batch_with_context`] for more information.
+    ///
+    /// Only available with the `std` feature.
+    ///
+    /// # Panics
+    /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) panics, the
+    /// panic is propagated once all worker threads have finished. All other
+    /// owners and pairs are dropped.
+    pub fn new_batch_partitioned_with_context<'context>(
+        owners: Vec<O>,
+        context: O::Context<'context>,
+    ) -> (Vec<Self>, Vec<(O, O::Error)>)
+    where
+        O::Context<'context>: Clone + Send,
+        O::Error: Send,
+        Self: Send,
+    {
+        let mut pairs = Vec::new();
+        let mut failures = Vec::new();
+        for result in Self::try_new_batch_with_context(owners, context) {
+            match result {
+                Ok(pair) => pairs.push(pair),
+                Err(failure) => failures.push(failure),
+            }
+        }
+
+        (pairs, failures)
+    }
+}
+
+impl<O: for<'any> Owner<Context<'any> = ()> + Send> Pair<O> {
+    /// Constructs a new [`Pair`] for each of the given owners, computing the
+    /// dependents in parallel across multiple threads.
+    ///
+    /// See the documentation of [`Pair::try_new_batch_with_context`] for more
+    /// information.
+    ///
+    /// Only available with the `std` feature.
+    ///
+    /// # Panics
+    /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) panics, the
+    /// panic is propagated once all worker threads have finished. All other
+    /// owners and pairs are dropped.
+    pub fn try_new_batch(owners: Vec<O>) -> Vec<Result<Self, (O, O::Error)>>
+    where
+        O::Error: Send,
+        Self: Send,
+    {
+        Self::try_new_batch_with_context(owners, ())
+    }
+
+    /// Constructs a new [`Pair`] for each of the given owners, computing the
+    /// dependents in parallel across multiple threads, and partitions the
+    /// results instead of failing fast.
+    ///
+    /// See the documentation of [`Pair::new_batch_partitioned_with_context`]
+    /// for more information.
+    ///
+    /// Only available with the `std` feature.
+    ///
+    /// # Panics
+    /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) panics, the
+    /// panic is propagated once all worker threads have finished. All other
+    /// owners and pairs are dropped.
+    pub fn new_batch_partitioned(owners: Vec<O>) -> (Vec<Self>, Vec<(O, O::Error)>)
+    where
+        O::Error: Send,
+        Self: Send,
+    {
+        Self::new_batch_partitioned_with_context(owners, ())
+    }
+}
+
+impl<O: for<'any> Owner<Context<'any> = (), Error = Infallible> + Send> Pair<O> {
+    /// Constructs a new [`Pair`] for each of the given owners, computing the
+    /// dependents in parallel across multiple threads.
+    ///
+    /// See the documentation of [`Pair::try_new_batch_with_context`] for more
+    /// information.
+    ///
+    /// Only available with the `std` feature.
+    ///
+    /// # Panics
+    /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) panics, the
+    /// panic is propagated once all worker threads have finished. All other
+    /// owners and pairs are dropped.
+    pub fn new_batch(owners: Vec<O>) -> Vec<Self>
+    where
+        Self: Send,
+    {
+        Self::try_new_batch(owners)
+            .into_iter()
+            .map(|result| {
+                let Ok(pair) = result;
+                pair
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,355 @@
+//! Defines [`Blob`], an owner of loaded bytes whose dependent is parsed from
+//! them by a [`BlobParser`], along with [`BlobPool`] for reusing the buffers of
+//! many short-lived blobs.
+
+use core::{
+    fmt::{Debug, Display},
+    marker::PhantomData,
+};
+
+use alloc::vec::Vec;
+
+use crate::{Dependent, HasDependent, Owner, Pair};
+
+/// Parses the bytes of a [`Blob`] into a dependent which may borrow from them.
+///
+/// Implement this trait for a marker type to define how a kind of blob (such
+/// as a git tree object, or a manifest file) is parsed.
+pub trait BlobParser {
+    /// The parsed form of the blob, borrowing from its bytes with the lifetime
+    /// `'blob`.
+    type Parsed<'blob>;
+
+    /// The error returned when the bytes can't be parsed.
+    type Error;
+
+    /// Parses the given bytes.
+    ///
+    /// # Errors
+    /// If the bytes can't be parsed.
+    fn parse(bytes: &[u8]) -> Result<Self::Parsed<'_>, Self::Error>;
+}
+
+/// Some bytes (anything implementing [`AsRef<[u8]>`]), which can act as the
+/// owner of a [`Pair`] with the bytes parsed by the [`BlobParser`] `P` as the
+/// dependent.
+///
+/// This is designed for tooling (such as version control systems) which loads
+/// and parses many blobs from some store: [`Blob::load`] combines the two
+/// steps, [`Blob::load_batch`] parses many blobs in parallel, and [`BlobPool`]
+/// reuses the buffers of blobs which are no longer needed.
+///
+/// ```
+/// # use pair::{Blob, BlobPair, BlobParser};
+/// # use std::str::Utf8Error;
+/// // Parses a blob of "<mode> <name>" lines, like a (simplified) git tree
+/// struct Tree;
+///
+/// impl BlobParser for Tree {
+///     type Parsed<'blob> = Vec<(&'blob str, &'blob str)>;
+///     type Error = Utf8Error;
+///
+///     fn parse(bytes: &[u8]) -> Result<Self::Parsed<'_>, Self::Error> {
+///         Ok(std::str::from_utf8(bytes)?
+///             .lines()
+///             .filter_map(|line| line.split_once(' '))
+///             .collect())
+///     }
+/// }
+///
+/// let store = ["100644 README.md\n040000 src", "100755 build.sh"];
+///
+/// let Ok(tree): Result<BlobPair<&[u8], Tree>, _> =
+///     Blob::load(|| Ok::<_, ()>(store[0].as_bytes()))
+/// else {
+///     panic!("the blob should load and parse");
+/// };
+/// assert_eq!(tree.with_dependent(|entries| entries[1]), ("040000", "src"));
+/// ```
+pub struct Blob<B, P> {
+    bytes: B,
+    parser: PhantomData<fn() -> P>,
+}
+
+/// A [`Pair`] of a [`Blob`] and its parsed form.
+pub type BlobPair<B, P> = Pair<Blob<B, P>>;
+
+impl<B: AsRef<[u8]>, P: BlobParser> Blob<B, P> {
+    /// Constructs a new [`Blob`] from the given bytes.
+    ///
+    /// The bytes are not parsed until a [`Pair`] is constructed from the blob.
+    pub fn new(bytes: B) -> Self {
+        Self {
+            bytes,
+            parser: PhantomData,
+        }
+    }
+
+    /// Returns the bytes of the blob.
+    pub fn bytes(&self) -> &[u8] {
+        self.bytes.as_ref()
+    }
+
+    /// Consumes the [`Blob`], returning its bytes.
+    pub fn into_inner(self) -> B {
+        self.bytes
+    }
+
+    /// Loads a blob's bytes with the given loader, and constructs a [`Pair`]
+    /// with the bytes parsed by `P` as the dependent.
+    ///
+    /// # Errors
+    /// [`BlobError::Load`] if the loader returns an error, or
+    /// [`BlobError::Parse`] if [`P::parse`](BlobParser::parse) returns an
+    /// error.
+    pub fn load<L, E>(loader: L) -> Result<BlobPair<B, P>, BlobError<B, E, P::Error>>
+    where
+        L: FnOnce() -> Result<B, E>,
+    {
+        let bytes = loader().map_err(BlobError::Load)?;
+
+        Pair::try_new(Self::new(bytes))
+            .map_err(|(blob, err)| BlobError::Parse(blob.into_inner(), err))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<B, P> Blob<B, P>
+where
+    B: AsRef<[u8]> + Send,
+    P: BlobParser,
+    P::Error: Send,
+    BlobPair<B, P>: Send,
+{
+    /// Loads the blob with each of the given ids using the given loader, and
+    /// constructs a [`Pair`] for each with its bytes parsed by `P` as the
+    /// dependent.
+    ///
+    /// The blobs are loaded in order on the current thread, and then parsed in
+    /// parallel with [`Pair::try_new_batch`]. The results are returned in the
+    /// same order as the ids were given.
+    ///
+    /// Only available with the `std` feature.
+    ///
+    /// # Panics
+    /// If [`P::parse`](BlobParser::parse) panics, the panic is propagated once
+    /// all blobs have been parsed. All other blobs and pairs are dropped.
+    #[expect(
+        clippy::type_complexity,
+        reason = "the failures mirror the errors of `Blob::load`"
+    )]
+    pub fn load_batch<I, L, E>(
+        ids: I,
+        mut loader: L,
+    ) -> Vec<Result<BlobPair<B, P>, BlobError<B, E, P::Error>>>
+    where
+        I: IntoIterator,
+        L: FnMut(I::Item) -> Result<B, E>,
+    {
+        // Failed loads keep their place in the results, and the rest are
+        // filled in from the parsed pairs (in order) afterward
+        let mut blobs = Vec::new();
+        let slots: Vec<_> = ids
+            .into_iter()
+            .map(|id| {
+                let bytes = loader(id)?;
+                blobs.push(Self::new(bytes));
+                Ok(())
+            })
+            .collect();
+
+        let mut parsed = Pair::try_new_batch(blobs).into_iter();
+        slots
+            .into_iter()
+            .map_while(|slot| match slot {
+                Ok(()) => parsed.next().map(|result| {
+                    result.map_err(|(blob, err)| BlobError::Parse(blob.into_inner(), err))
+                }),
+                Err(err) => Some(Err(BlobError::Load(err))),
+            })
+            .collect()
+    }
+}
+
+impl<B: AsRef<[u8]>, P> Debug for Blob<B, P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Blob")
+            .field("len", &self.bytes.as_ref().len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'owner, B: AsRef<[u8]>, P: BlobParser> HasDependent<'owner> for Blob<B, P> {
+    type Dependent = P::Parsed<'owner>;
+}
+
+impl<B: AsRef<[u8]>, P: BlobParser> Owner for Blob<B, P> {
+    type Context<'a> = ();
+    type Error = P::Error;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        P::parse(self.bytes.as_ref())
+    }
+}
+
+/// A pool of buffers for loading [`Blob`]s into, which can be reused once the
+/// blobs are no longer needed.
+///
+/// When thousands of short-lived blobs are loaded and parsed one after another,
+/// allocating a fresh buffer for each can dominate. A `BlobPool` hands out
+/// idle buffers to its loader, and takes them back with
+/// [`BlobPool::recycle`].
+///
+/// ```
+/// # use pair::{BlobParser, BlobPool};
+/// # use std::convert::Infallible;
+/// struct Lines;
+///
+/// impl BlobParser for Lines {
+///     type Parsed<'blob> = usize;
+///     type Error = Infallible;
+///
+///     fn parse(bytes: &[u8]) -> Result<usize, Infallible> {
+///         Ok(bytes.split(|&byte| byte == b'\n').count())
+///     }
+/// }
+///
+/// let mut pool = BlobPool::<Lines>::new();
+/// for blob in ["a\nb", "c", "d\ne\nf"] {
+///     let Ok(pair) = pool.load(|buffer| {
+///         buffer.extend_from_slice(blob.as_bytes());
+///         Ok::<_, ()>(())
+///     }) else {
+///         panic!("the blob should load and parse");
+///     };
+///     assert!(pair.with_dependent(|&lines| lines > 0));
+///
+///     // The buffer is reused for the next blob
+///     pool.recycle(pair);
+///     assert_eq!(pool.idle(), 1);
+/// }
+/// ```
+pub struct BlobPool<P> {
+    buffers: Vec<Vec<u8>>,
+    parser: PhantomData<fn() -> P>,
+}
+
+impl<P: BlobParser> BlobPool<P> {
+    /// Constructs a new, empty [`BlobPool`].
+    pub fn new() -> Self {
+        Self {
+            buffers: Vec::new(),
+            parser: PhantomData,
+        }
+    }
+
+    /// Returns the number of idle buffers in the pool.
+    pub fn idle(&self) -> usize {
+        self.buffers.len()
+    }
+
+    /// Loads a blob with the given loader into an idle buffer (or a new one, if
+    /// there are none), and constructs a [`Pair`] with the bytes parsed by `P`
+    /// as the dependent.
+    ///
+    /// The buffer given to the loader is always empty. If the loader returns an
+    /// error, the buffer is returned to the pool.
+    ///
+    /// # Errors
+    /// [`BlobError::Load`] if the loader returns an error, or
+    /// [`BlobError::Parse`] if [`P::parse`](BlobParser::parse) returns an
+    /// error.
+    #[expect(
+        clippy::type_complexity,
+        reason = "the failures mirror the errors of `Blob::load`"
+    )]
+    pub fn load<L, E>(
+        &mut self,
+        loader: L,
+    ) -> Result<BlobPair<Vec<u8>, P>, BlobError<Vec<u8>, E, P::Error>>
+    where
+        L: FnOnce(&mut Vec<u8>) -> Result<(), E>,
+    {
+        let mut buffer = self.buffers.pop().unwrap_or_default();
+
+        if let Err(err) = loader(&mut buffer) {
+            self.recycle_buffer(buffer);
+            return Err(BlobError::Load(err));
+        }
+
+        Pair::try_new(Blob::new(buffer))
+            .map_err(|(blob, err)| BlobError::Parse(blob.into_inner(), err))
+    }
+
+    /// Consumes the given pair, returning its buffer to the pool to be reused.
+    pub fn recycle(&mut self, pair: BlobPair<Vec<u8>, P>) {
+        self.recycle_buffer(pair.into_owner().into_inner());
+    }
+
+    /// Returns the given buffer to the pool to be reused, such as the bytes of
+    /// a [`BlobError::Parse`].
+    pub fn recycle_buffer(&mut self, mut buffer: Vec<u8>) {
+        buffer.clear();
+        self.buffers.push(buffer);
+    }
+}
+
+impl<P: BlobParser> Default for BlobPool<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P> Debug for BlobPool<P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BlobPool")
+            .field("idle", &self.buffers.len())
+            .finish()
+    }
+}
+
+/// An error which occurred while loading and parsing a [`Blob`].
+pub enum BlobError<B, E, P> {
+    /// Loading the blob's bytes failed.
+    Load(E),
+
+    /// The bytes were loaded, but [`BlobParser::parse`] returned an error.
+    /// Contains the bytes, along with the error.
+    Parse(B, P),
+}
+
+/// The [`Debug`] implementation for [`BlobError`] does not require (or display)
+/// a `Debug` implementation for the bytes, which may be large.
+impl<B, E: Debug, P: Debug> Debug for BlobError<B, E, P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Load(err) => f.debug_tuple("Load").field(err).finish(),
+            Self::Parse(_, err) => f.debug_tuple("Parse").field(err).finish(),
+        }
+    }
+}
+
+/// The [`Display`] implementation for [`BlobError`] does not include the
+/// underlying error, which is instead available through
+/// [`Error::source`](core::error::Error::source).
+impl<B, E, P> Display for BlobError<B, E, P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Load(_) => f.write_str("failed to load a blob"),
+            Self::Parse(..) => f.write_str("failed to parse a blob"),
+        }
+    }
+}
+
+impl<B, E, P> core::error::Error for BlobError<B, E, P>
+where
+    E: core::error::Error + 'static,
+    P: core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Load(err) => Some(err),
+            Self::Parse(_, err) => Some(err),
+        }
+    }
+}
@@ -0,0 +1,224 @@
+//! Defines [`BudgetAllocator`], a global allocator which enforces per-thread
+//! allocation budgets on the construction of dependents, and the associated
+//! error types. Only available with the `alloc-budget` feature.
+
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    cell::Cell,
+    fmt::Display,
+};
+
+use crate::drop_guard::DropGuard;
+
+/// A global allocator which wraps another allocator `A`, and counts the bytes
+/// allocated on each thread while an allocation budget is active.
+///
+/// Budgets are activated by constructors such as
+/// [`Pair::try_new_with_budget`](crate::Pair::try_new_with_budget), for the
+/// duration of the call to [`make_dependent`](crate::Owner::make_dependent).
+/// This protects services from pathological inputs, whose dependents would
+/// take an unreasonable amount of memory to construct. Outside of an active
+/// budget, this allocator just forwards to `A`.
+///
+/// Budgets can only be enforced when this is installed as the
+/// [`#[global_allocator]`](https://doc.rust-lang.org/std/alloc/index.html#the-global_allocator-attribute)
+/// - otherwise, no allocations are counted, and budgets are never exceeded.
+///
+/// An allocation which exceeds the budget still succeeds (failing it would
+/// abort the process). Instead, the budget is marked as exceeded, which a
+/// long-running `make_dependent` implementation should periodically poll with
+/// [`check_budget`] to stop early. Either way, the constructor then fails with
+/// [`BudgetError::Exceeded`].
+///
+/// Only available with the `alloc-budget` feature.
+///
+/// ```
+/// # use pair::{BudgetAllocator, BudgetError, Dependent, HasDependent, Owner, Pair};
+/// # use std::{alloc::System, convert::Infallible};
+/// #[global_allocator]
+/// static ALLOCATOR: BudgetAllocator = BudgetAllocator::new(System);
+///
+/// #[derive(Debug)]
+/// struct Text(String);
+///
+/// impl<'owner> HasDependent<'owner> for Text {
+///     type Dependent = Vec<&'owner str>;
+/// }
+///
+/// impl Owner for Text {
+///     type Context<'a> = ();
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         Ok(self.0.split_whitespace().collect())
+///     }
+/// }
+///
+/// let text = Text("word ".repeat(1000));
+/// let Err((text, err)) = Pair::try_new_with_budget(text, (), 1024) else {
+///     panic!("budget should have been exceeded");
+/// };
+/// assert_eq!(err, BudgetError::Exceeded);
+///
+/// let pair = Pair::try_new_with_budget(text, (), 1 << 20).unwrap();
+/// assert_eq!(pair.dependent_len(), 1000);
+/// ```
+#[derive(Debug, Default)]
+pub struct BudgetAllocator<A = std::alloc::System> {
+    inner: A,
+}
+
+impl<A> BudgetAllocator<A> {
+    /// Constructs a new [`BudgetAllocator`], wrapping the given allocator.
+    pub const fn new(inner: A) -> Self {
+        Self { inner }
+    }
+}
+
+// SAFETY: every method forwards to the wrapped allocator with the same
+// arguments, so this upholds the contract of `GlobalAlloc` if `A` does.
+// Counting allocations only touches a thread-local `Cell`, which never
+// allocates or unwinds.
+unsafe impl<A: GlobalAlloc> GlobalAlloc for BudgetAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        charge(layout.size());
+        // SAFETY: the caller upholds the requirements of `GlobalAlloc::alloc`
+        unsafe { self.inner.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        charge(layout.size());
+        // SAFETY: the caller upholds the requirements of
+        // `GlobalAlloc::alloc_zeroed`
+        unsafe { self.inner.alloc_zeroed(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: the caller upholds the requirements of `GlobalAlloc::dealloc`
+        unsafe { self.inner.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        charge(new_size.saturating_sub(layout.size()));
+        // SAFETY: the caller upholds the requirements of `GlobalAlloc::realloc`
+        unsafe { self.inner.realloc(ptr, layout, new_size) }
+    }
+}
+
+/// The allocation budget active on a thread.
+#[derive(Clone, Copy)]
+struct Budget {
+    remaining: usize,
+    exceeded: bool,
+}
+
+std::thread_local! {
+    static BUDGET: Cell<Option<Budget>> = const { Cell::new(None) };
+}
+
+/// Charges an allocation of `bytes` to the current thread's budget, if any.
+fn charge(bytes: usize) {
+    // The thread-local may already be destroyed if this thread is exiting, in
+    // which case no budget can be active
+    let _ = BUDGET.try_with(|budget| {
+        if let Some(Budget {
+            remaining,
+            exceeded,
+        }) = budget.get()
+        {
+            budget.set(Some(match remaining.checked_sub(bytes) {
+                Some(remaining) => Budget {
+                    remaining,
+                    exceeded,
+                },
+                None => Budget {
+                    remaining: 0,
+                    exceeded: true,
+                },
+            }));
+        }
+    });
+}
+
+/// Returns [`Err(BudgetExceeded)`](BudgetExceeded) if the allocation budget
+/// active on the current thread has been exceeded, or `Ok(())` otherwise
+/// (including if no budget is active).
+///
+/// A long-running [`make_dependent`](crate::Owner::make_dependent)
+/// implementation can poll this with `?` to stop early once its budget has
+/// been exceeded, rather than continuing to allocate.
+///
+/// Only available with the `alloc-budget` feature.
+///
+/// # Errors
+/// If the active allocation budget has been exceeded.
+pub fn check_budget() -> Result<(), BudgetExceeded> {
+    match BUDGET.with(Cell::get) {
+        Some(Budget { exceeded: true, .. }) => Err(BudgetExceeded),
+        _ => Ok(()),
+    }
+}
+
+/// Calls `f` with an allocation budget of `bytes` active on the current
+/// thread, returning its result and whether the budget was exceeded.
+///
+/// Any budget which was already active is suspended while `f` runs, and
+/// restored afterwards (even if `f` panics).
+pub(crate) fn with_budget<T>(bytes: usize, f: impl FnOnce() -> T) -> (T, bool) {
+    let previous = BUDGET.replace(Some(Budget {
+        remaining: bytes,
+        exceeded: false,
+    }));
+    let restore = DropGuard(|| BUDGET.set(previous));
+
+    let value = f();
+
+    let exceeded = BUDGET.get().is_some_and(|budget| budget.exceeded);
+    drop(restore);
+    (value, exceeded)
+}
+
+/// An error indicating that an allocation budget was exceeded.
+///
+/// Only available with the `alloc-budget` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BudgetExceeded;
+
+impl Display for BudgetExceeded {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("allocation budget was exceeded")
+    }
+}
+
+impl core::error::Error for BudgetExceeded {}
+
+/// The error returned by budgeted constructors, such as
+/// [`Pair::try_new_with_budget`](crate::Pair::try_new_with_budget).
+///
+/// Only available with the `alloc-budget` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BudgetError<E> {
+    /// Constructing the dependent allocated more than the budget allowed.
+    Exceeded,
+    /// [`make_dependent`](crate::Owner::make_dependent) returned an error, and
+    /// the budget was not exceeded.
+    Failed(E),
+}
+
+impl<E> Display for BudgetError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Exceeded => Display::fmt(&BudgetExceeded, f),
+            Self::Failed(_) => f.write_str("failed to construct the dependent of a pair"),
+        }
+    }
+}
+
+impl<E: core::error::Error + 'static> core::error::Error for BudgetError<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Exceeded => None,
+            Self::Failed(err) => Some(err),
+        }
+    }
+}
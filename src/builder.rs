@@ -0,0 +1,178 @@
+//! Defines a type-state builder for [`Pair`], which checks at compile time
+//! that context is provided to owners which require it.
+
+use core::{convert::Infallible, fmt::Debug};
+
+use alloc::boxed::Box;
+
+use crate::{Owner, Pair};
+
+/// A builder for a [`Pair`] whose owner hasn't been given context yet, created
+/// by [`Pair::builder`].
+///
+/// Owners which don't require context (whose [`Context`](Owner::Context) is
+/// `()`) can be built right away. Otherwise, context must first be provided
+/// with [`context`](NeedsContext::context) - calling
+/// [`build`](NeedsContext::build) without it is a compilation error,
+/// explaining that the owner requires context.
+///
+/// ```
+/// # use pair::{Dependent, HasDependent, Owner, Pair};
+/// # use std::num::ParseIntError;
+/// struct Numbers(String);
+///
+/// impl HasDependent<'_> for Numbers {
+///     type Dependent = Vec<u32>;
+/// }
+///
+/// impl Owner for Numbers {
+///     type Context<'a> = &'a str;
+///     type Error = ParseIntError;
+///
+///     fn make_dependent(&self, separator: &str) -> Result<Dependent<'_, Self>, Self::Error> {
+///         self.0.split(separator).map(str::parse).collect()
+///     }
+/// }
+///
+/// let pair = Pair::builder(Numbers(String::from("1, 2, 3")))
+///     .context(", ")
+///     .try_build()
+///     .unwrap_or_else(|(_, err)| panic!("{err}"));
+/// assert_eq!(pair.with_dependent(|numbers| numbers.iter().sum::<u32>()), 6);
+/// ```
+pub struct NeedsContext<O: Owner + ?Sized> {
+    owner: Box<O>,
+}
+
+/// A builder for a [`Pair`] whose owner has been given its context, created by
+/// [`NeedsContext::context`].
+///
+/// See the documentation of [`NeedsContext`] for more information.
+pub struct Ready<'context, O: Owner + ?Sized> {
+    owner: Box<O>,
+    context: O::Context<'context>,
+}
+
+/// Implemented for context types which don't need to be provided to a
+/// [`NeedsContext`] builder - that is, only for `()`.
+///
+/// This trait is sealed, and exists to give a clear error message when
+/// building a [`Pair`] without providing context its owner requires.
+#[diagnostic::on_unimplemented(
+    message = "this owner requires context (of type `{Self}`) to construct a `Pair`",
+    label = "context not provided",
+    note = "provide the context with `.context(..)` before building the pair"
+)]
+pub trait NoContext: sealed::Sealed {
+    /// Returns the context.
+    fn no_context() -> Self;
+}
+
+impl NoContext for () {
+    fn no_context() -> Self {}
+}
+
+impl<O: Owner + ?Sized> Pair<O> {
+    /// Creates a type-state builder for a [`Pair`] with the given owner.
+    ///
+    /// See the documentation of [`NeedsContext`] for more information.
+    pub fn builder(owner: O) -> NeedsContext<O>
+    where
+        O: Sized,
+    {
+        Self::builder_from_box(Box::new(owner))
+    }
+
+    /// Creates a type-state builder for a [`Pair`] with the given boxed owner.
+    ///
+    /// See the documentation of [`NeedsContext`] for more information.
+    pub fn builder_from_box(owner: Box<O>) -> NeedsContext<O> {
+        NeedsContext { owner }
+    }
+}
+
+impl<O: Owner + ?Sized> NeedsContext<O> {
+    /// Provides the context for constructing the dependent.
+    pub fn context(self, context: O::Context<'_>) -> Ready<'_, O> {
+        Ready {
+            owner: self.owner,
+            context,
+        }
+    }
+
+    /// Constructs the [`Pair`], for owners which don't require context.
+    ///
+    /// # Errors
+    /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
+    /// error. The owner is returned alongside the error.
+    #[track_caller]
+    pub fn try_build(self) -> Result<Pair<O>, (Box<O>, O::Error)>
+    where
+        O::Context<'static>: NoContext,
+    {
+        Pair::try_new_from_box_with_context(self.owner, NoContext::no_context())
+    }
+
+    /// Constructs the [`Pair`], for owners which don't require context and
+    /// whose construction can't fail.
+    #[track_caller]
+    pub fn build(self) -> Pair<O>
+    where
+        O: Owner<Error = Infallible>,
+        O::Context<'static>: NoContext,
+    {
+        let Ok(pair) = self.try_build();
+        pair
+    }
+}
+
+impl<O: Owner + ?Sized> Ready<'_, O> {
+    /// Constructs the [`Pair`] with the provided context.
+    ///
+    /// # Errors
+    /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
+    /// error. The owner is returned alongside the error.
+    #[track_caller]
+    pub fn try_build(self) -> Result<Pair<O>, (Box<O>, O::Error)> {
+        Pair::try_new_from_box_with_context(self.owner, self.context)
+    }
+
+    /// Constructs the [`Pair`] with the provided context, for owners whose
+    /// construction can't fail.
+    #[track_caller]
+    pub fn build(self) -> Pair<O>
+    where
+        O: Owner<Error = Infallible>,
+    {
+        let Ok(pair) = self.try_build();
+        pair
+    }
+}
+
+impl<O: Owner + Debug + ?Sized> Debug for NeedsContext<O> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("NeedsContext")
+            .field("owner", &self.owner)
+            .finish()
+    }
+}
+
+impl<'context, O: Owner + Debug + ?Sized> Debug for Ready<'context, O>
+where
+    O::Context<'context>: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Ready")
+            .field("owner", &self.owner)
+            .field("context", &self.context)
+            .finish()
+    }
+}
+
+mod sealed {
+    #![expect(unnameable_types, reason = "...kinda the point")]
+
+    pub trait Sealed {}
+
+    impl Sealed for () {}
+}
@@ -0,0 +1,460 @@
+//! Defines [`CachedPair`], a pair whose dependent can be invalidated and is
+//! rebuilt on demand.
+
+use core::{convert::Infallible, fmt::Debug};
+
+use alloc::boxed::Box;
+
+use crate::{Dependent, Owner, Pair};
+
+/// A pair whose dependent may be missing ("stale"), and is rebuilt on demand.
+///
+/// A `CachedPair` is either fresh (storing a [`Pair`], with a dependent), or
+/// stale (storing only the owner). The dependent is dropped when the pair is
+/// [invalidated](CachedPair::invalidate), which also happens automatically
+/// whenever the owner is mutably borrowed with
+/// [`owner_mut`](CachedPair::owner_mut). Accessors like
+/// [`with_dependent_or_rebuild`](CachedPair::with_dependent_or_rebuild) then
+/// transparently rebuild the dependent before using it, so call sites don't
+/// need to check whether it is stale.
+///
+/// ```
+/// # use pair::{CachedPair, Dependent, HasDependent, Owner};
+/// # use std::convert::Infallible;
+/// struct Text(String);
+///
+/// impl<'owner> HasDependent<'owner> for Text {
+///     type Dependent = Vec<&'owner str>;
+/// }
+///
+/// impl Owner for Text {
+///     type Context<'a> = ();
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         Ok(self.0.split_whitespace().collect())
+///     }
+/// }
+///
+/// let mut pair = CachedPair::new(Text(String::from("hello world")));
+/// assert_eq!(pair.with_dependent_or_rebuild((), |words| words.len()), 2);
+///
+/// // Mutating the owner invalidates the dependent...
+/// pair.owner_mut().0.push_str(" again");
+/// assert!(!pair.is_fresh());
+///
+/// // ...which is rebuilt the next time it is needed
+/// assert_eq!(pair.with_dependent_or_rebuild((), |words| words.len()), 3);
+/// assert!(pair.is_fresh());
+/// ```
+///
+/// # Poisoning
+/// If [`Owner::make_dependent`] panics while rebuilding the dependent, the
+/// owner is dropped (just like when constructing a [`Pair`]), and the
+/// `CachedPair` becomes poisoned. Almost all methods of a poisoned `CachedPair`
+/// panic.
+pub struct CachedPair<O: Owner + ?Sized> {
+    state: State<O>,
+    stats: RebuildStats,
+}
+
+/// Statistics about the rebuilds of the dependent of a [`CachedPair`],
+/// returned by [`CachedPair::stats`].
+///
+/// These are tracked per `CachedPair`, and can be used by caching layers to
+/// tune their invalidation heuristics based on real data (for example, by
+/// avoiding invalidating pairs whose rebuilds are expensive).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RebuildStats {
+    rebuilds: u64,
+    failed_rebuilds: u64,
+    invalidations: u64,
+    #[cfg(feature = "std")]
+    last_rebuild_duration: Option<std::time::Duration>,
+}
+
+impl RebuildStats {
+    /// Returns the number of times the dependent was successfully rebuilt.
+    pub fn rebuilds(&self) -> u64 {
+        self.rebuilds
+    }
+
+    /// Returns the number of times rebuilding the dependent failed (that is,
+    /// [`make_dependent`](Owner::make_dependent) returned an error).
+    pub fn failed_rebuilds(&self) -> u64 {
+        self.failed_rebuilds
+    }
+
+    /// Returns the number of times a fresh dependent was invalidated (and
+    /// dropped).
+    pub fn invalidations(&self) -> u64 {
+        self.invalidations
+    }
+
+    /// Returns how long the most recent attempt to rebuild the dependent took
+    /// (whether or not it succeeded), or [`None`] if it was never rebuilt.
+    ///
+    /// Only available with the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn last_rebuild_duration(&self) -> Option<std::time::Duration> {
+        self.last_rebuild_duration
+    }
+}
+
+/// The state of a [`CachedPair`].
+enum State<O: Owner + ?Sized> {
+    /// The dependent is present.
+    Fresh(Pair<O>),
+    /// The dependent is missing, and must be rebuilt before it can be used.
+    Stale(Box<O>),
+    /// A panic occurred while transitioning between states, and the owner has
+    /// been lost.
+    Poisoned,
+}
+
+/// Panics, reporting that a [`CachedPair`] was poisoned.
+#[cold]
+#[track_caller]
+fn poisoned() -> ! {
+    panic!("CachedPair was poisoned by a panic while changing the state of its dependent");
+}
+
+impl<O: Owner + ?Sized> CachedPair<O> {
+    /// Constructs a new, stale [`CachedPair`] with the given boxed [`Owner`].
+    /// The dependent will not be computed until it is first needed.
+    pub fn new_from_box(owner: Box<O>) -> Self {
+        Self {
+            state: State::Stale(owner),
+            stats: RebuildStats::default(),
+        }
+    }
+
+    /// Constructs a new, fresh [`CachedPair`] from an existing [`Pair`].
+    pub fn from_pair(pair: Pair<O>) -> Self {
+        Self {
+            state: State::Fresh(pair),
+            stats: RebuildStats::default(),
+        }
+    }
+
+    /// Returns statistics about the rebuilds of the dependent of this
+    /// [`CachedPair`]. Unlike most other methods, this never panics.
+    ///
+    /// ```
+    /// # use pair::{CachedPair, Dependent, HasDependent, Owner};
+    /// # use std::convert::Infallible;
+    /// # struct Text(String);
+    /// # impl<'owner> HasDependent<'owner> for Text {
+    /// #     type Dependent = Vec<&'owner str>;
+    /// # }
+    /// # impl Owner for Text {
+    /// #     type Context<'a> = ();
+    /// #     type Error = Infallible;
+    /// #     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+    /// #         Ok(self.0.split_whitespace().collect())
+    /// #     }
+    /// # }
+    /// let mut pair = CachedPair::new(Text(String::from("hello world")));
+    /// pair.refresh(());
+    /// pair.invalidate();
+    /// pair.refresh(());
+    ///
+    /// assert_eq!(pair.stats().rebuilds(), 2);
+    /// assert_eq!(pair.stats().invalidations(), 1);
+    /// ```
+    pub fn stats(&self) -> RebuildStats {
+        self.stats
+    }
+
+    /// Resets the statistics returned by [`stats`](CachedPair::stats).
+    pub fn reset_stats(&mut self) {
+        self.stats = RebuildStats::default();
+    }
+
+    /// Returns `true` if the dependent is present, or `false` if it is stale
+    /// and must be rebuilt before it can be used.
+    ///
+    /// # Panics
+    /// If this [`CachedPair`] is poisoned.
+    pub fn is_fresh(&self) -> bool {
+        match &self.state {
+            State::Fresh(_) => true,
+            State::Stale(_) => false,
+            State::Poisoned => poisoned(),
+        }
+    }
+
+    /// Returns `true` if this [`CachedPair`] is poisoned. Unlike most other
+    /// methods, this never panics.
+    pub fn is_poisoned(&self) -> bool {
+        matches!(self.state, State::Poisoned)
+    }
+
+    /// Returns a reference to the owner.
+    ///
+    /// # Panics
+    /// If this [`CachedPair`] is poisoned.
+    pub fn owner(&self) -> &O {
+        match &self.state {
+            State::Fresh(pair) => pair.owner(),
+            State::Stale(owner) => owner,
+            State::Poisoned => poisoned(),
+        }
+    }
+
+    /// Returns a mutable reference to the owner, invalidating (and dropping)
+    /// the dependent.
+    ///
+    /// # Panics
+    /// If this [`CachedPair`] is poisoned, or if the drop of the dependent
+    /// panics (in which case this [`CachedPair`] becomes poisoned).
+    pub fn owner_mut(&mut self) -> &mut O {
+        self.invalidate();
+
+        match &mut self.state {
+            State::Stale(owner) => owner,
+            State::Fresh(_) | State::Poisoned => unreachable!(),
+        }
+    }
+
+    /// Returns a reference to the inner [`Pair`] if the dependent is fresh, or
+    /// [`None`] if it is stale.
+    ///
+    /// # Panics
+    /// If this [`CachedPair`] is poisoned.
+    pub fn pair(&self) -> Option<&Pair<O>> {
+        match &self.state {
+            State::Fresh(pair) => Some(pair),
+            State::Stale(_) => None,
+            State::Poisoned => poisoned(),
+        }
+    }
+
+    /// Invalidates the dependent, dropping it if it is present. It will be
+    /// rebuilt the next time it is needed.
+    ///
+    /// # Panics
+    /// If this [`CachedPair`] is poisoned, or if the drop of the dependent
+    /// panics (in which case this [`CachedPair`] becomes poisoned).
+    pub fn invalidate(&mut self) {
+        match core::mem::replace(&mut self.state, State::Poisoned) {
+            State::Fresh(pair) => {
+                self.state = State::Stale(pair.into_boxed_owner());
+                self.stats.invalidations += 1;
+            }
+            State::Stale(owner) => self.state = State::Stale(owner),
+            State::Poisoned => poisoned(),
+        }
+    }
+
+    /// Rebuilds the dependent if it is stale, and returns a reference to the
+    /// inner [`Pair`].
+    ///
+    /// # Errors
+    /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
+    /// error. In that case, the dependent remains stale.
+    ///
+    /// # Panics
+    /// If this [`CachedPair`] is poisoned, or if `make_dependent` panics (in
+    /// which case this [`CachedPair`] becomes poisoned).
+    #[track_caller]
+    pub fn try_refresh(&mut self, context: O::Context<'_>) -> Result<&Pair<O>, O::Error> {
+        if let State::Stale(_) = self.state {
+            let State::Stale(owner) = core::mem::replace(&mut self.state, State::Poisoned) else {
+                unreachable!()
+            };
+
+            #[cfg(feature = "std")]
+            let start = std::time::Instant::now();
+
+            let result = Pair::try_new_from_box_with_context(owner, context);
+
+            #[cfg(feature = "std")]
+            {
+                self.stats.last_rebuild_duration = Some(start.elapsed());
+            }
+
+            match result {
+                Ok(pair) => {
+                    self.state = State::Fresh(pair);
+                    self.stats.rebuilds += 1;
+                }
+                Err((owner, err)) => {
+                    self.state = State::Stale(owner);
+                    self.stats.failed_rebuilds += 1;
+                    return Err(err);
+                }
+            }
+        }
+
+        match &self.state {
+            State::Fresh(pair) => Ok(pair),
+            State::Stale(_) => unreachable!(),
+            State::Poisoned => poisoned(),
+        }
+    }
+
+    /// Calls the given closure, providing shared access to the dependent, and
+    /// returns the value computed by the closure. If the dependent is stale, it
+    /// is rebuilt first.
+    ///
+    /// See the documentation of [`Pair::with_dependent`] for more information
+    /// on the closure.
+    ///
+    /// # Errors
+    /// If the dependent is stale, and
+    /// [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an error
+    /// while rebuilding it. In that case, the dependent remains stale and the
+    /// closure is not called.
+    ///
+    /// # Panics
+    /// If this [`CachedPair`] is poisoned, or if `make_dependent` panics (in
+    /// which case this [`CachedPair`] becomes poisoned).
+    #[track_caller]
+    pub fn try_with_dependent_or_rebuild<'self_borrow, F, T>(
+        &'self_borrow mut self,
+        context: O::Context<'_>,
+        f: F,
+    ) -> Result<T, O::Error>
+    where
+        F: for<'any> FnOnce(&'self_borrow Dependent<'_, O>) -> T,
+    {
+        Ok(self.try_refresh(context)?.with_dependent(f))
+    }
+
+    /// Consumes the [`CachedPair`], dropping the dependent (if present) and
+    /// returning the boxed owner.
+    ///
+    /// # Panics
+    /// If this [`CachedPair`] is poisoned.
+    pub fn into_boxed_owner(self) -> Box<O> {
+        match self.state {
+            State::Fresh(pair) => pair.into_boxed_owner(),
+            State::Stale(owner) => owner,
+            State::Poisoned => poisoned(),
+        }
+    }
+
+    /// Consumes the [`CachedPair`] if its dependent is fresh, dropping the
+    /// dependent and returning the boxed owner.
+    ///
+    /// Unlike [`into_boxed_owner`](CachedPair::into_boxed_owner), this allows
+    /// teardown code to distinguish a clean shutdown from one which would lose
+    /// a pending rebuild of the dependent (or a poisoned pair).
+    ///
+    /// # Errors
+    /// If the dependent is stale (a rebuild is pending), or this [`CachedPair`]
+    /// is poisoned. In either case, the [`CachedPair`] is returned unchanged -
+    /// see [`is_fresh`](CachedPair::is_fresh) and
+    /// [`is_poisoned`](CachedPair::is_poisoned).
+    pub fn try_into_boxed_owner(self) -> Result<Box<O>, Self> {
+        match self.state {
+            State::Fresh(pair) => Ok(pair.into_boxed_owner()),
+            state @ (State::Stale(_) | State::Poisoned) => Err(Self {
+                state,
+                stats: self.stats,
+            }),
+        }
+    }
+}
+
+impl<O: Owner<Error = Infallible> + ?Sized> CachedPair<O> {
+    /// Rebuilds the dependent if it is stale, and returns a reference to the
+    /// inner [`Pair`].
+    ///
+    /// # Panics
+    /// If this [`CachedPair`] is poisoned, or if
+    /// [`<O as Owner>::make_dependent`](Owner::make_dependent) panics (in which
+    /// case this [`CachedPair`] becomes poisoned).
+    #[track_caller]
+    pub fn refresh(&mut self, context: O::Context<'_>) -> &Pair<O> {
+        let Ok(pair) = self.try_refresh(context);
+        pair
+    }
+
+    /// Calls the given closure, providing shared access to the dependent, and
+    /// returns the value computed by the closure. If the dependent is stale, it
+    /// is rebuilt first.
+    ///
+    /// See the documentation of [`Pair::with_dependent`] for more information
+    /// on the closure.
+    ///
+    /// # Panics
+    /// If this [`CachedPair`] is poisoned, or if
+    /// [`<O as Owner>::make_dependent`](Owner::make_dependent) panics (in which
+    /// case this [`CachedPair`] becomes poisoned).
+    #[track_caller]
+    pub fn with_dependent_or_rebuild<'self_borrow, F, T>(
+        &'self_borrow mut self,
+        context: O::Context<'_>,
+        f: F,
+    ) -> T
+    where
+        F: for<'any> FnOnce(&'self_borrow Dependent<'_, O>) -> T,
+    {
+        self.refresh(context).with_dependent(f)
+    }
+}
+
+impl<O: Owner> CachedPair<O> {
+    /// Constructs a new, stale [`CachedPair`] with the given [`Owner`]. The
+    /// dependent will not be computed until it is first needed.
+    pub fn new(owner: O) -> Self {
+        Self::new_from_box(Box::new(owner))
+    }
+
+    /// Consumes the [`CachedPair`], dropping the dependent (if present) and
+    /// returning the owner.
+    ///
+    /// # Panics
+    /// If this [`CachedPair`] is poisoned.
+    pub fn into_owner(self) -> O {
+        *self.into_boxed_owner()
+    }
+
+    /// Consumes the [`CachedPair`] if its dependent is fresh, dropping the
+    /// dependent and returning the owner. See
+    /// [`try_into_boxed_owner`](CachedPair::try_into_boxed_owner) for more
+    /// information.
+    ///
+    /// # Errors
+    /// If the dependent is stale (a rebuild is pending), or this [`CachedPair`]
+    /// is poisoned. In either case, the [`CachedPair`] is returned unchanged.
+    pub fn try_into_owner(self) -> Result<O, Self> {
+        self.try_into_boxed_owner().map(|owner| *owner)
+    }
+}
+
+impl<O: Owner + ?Sized> From<Pair<O>> for CachedPair<O> {
+    fn from(pair: Pair<O>) -> Self {
+        Self::from_pair(pair)
+    }
+}
+
+/// The [`Debug`] implementation for [`CachedPair`] does not panic if it is
+/// poisoned.
+#[expect(
+    clippy::missing_fields_in_debug,
+    reason = "the rebuild statistics are bookkeeping, not part of the pair's value"
+)]
+impl<O: Owner + Debug + ?Sized> Debug for CachedPair<O>
+where
+    for<'any> Dependent<'any, O>: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self.state {
+            State::Fresh(pair) => pair.with_dependent(|dependent| {
+                f.debug_struct("CachedPair")
+                    .field("owner", &pair.owner())
+                    .field("dependent", &Some(dependent))
+                    .finish()
+            }),
+            State::Stale(owner) => f
+                .debug_struct("CachedPair")
+                .field("owner", &owner)
+                .field("dependent", &None::<()>)
+                .finish(),
+            State::Poisoned => f.write_str("CachedPair(<poisoned>)"),
+        }
+    }
+}
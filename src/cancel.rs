@@ -0,0 +1,193 @@
+//! Defines [`CancellationToken`], for cooperatively cancelling the construction
+//! of a dependent, and the associated error types.
+
+use core::{
+    fmt::Display,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// A token which can be used to cooperatively cancel the construction of a
+/// dependent.
+///
+/// Cancellation is cooperative - [`cancel`](CancellationToken::cancel) only
+/// sets a flag, which a long-running
+/// [`make_dependent`](crate::Owner::make_dependent) implementation should
+/// periodically poll (typically by receiving a reference to the token as part
+/// of its [`Context`](crate::Owner::Context)) to stop early. Constructors such
+/// as [`Pair::try_new_cancellable`](crate::Pair::try_new_cancellable) check the
+/// token as well, and return [`CancellableError::Cancelled`] (along with the
+/// owner) if it was cancelled.
+///
+/// ```
+/// # use pair::{CancellableError, CancellationToken, Cancelled, Dependent, HasDependent, Owner, Pair};
+/// struct Document(String);
+///
+/// impl<'owner> HasDependent<'owner> for Document {
+///     type Dependent = Vec<&'owner str>;
+/// }
+///
+/// impl Owner for Document {
+///     type Context<'a> = &'a CancellationToken;
+///     type Error = Cancelled;
+///
+///     fn make_dependent(
+///         &self,
+///         token: &CancellationToken,
+///     ) -> Result<Dependent<'_, Self>, Self::Error> {
+///         let mut words = Vec::new();
+///         for word in self.0.split_whitespace() {
+///             token.check()?;
+///             words.push(word);
+///         }
+///
+///         Ok(words)
+///     }
+/// }
+///
+/// let token = CancellationToken::new();
+/// token.cancel();
+///
+/// let result = Pair::try_new_cancellable(
+///     Document(String::from("some very long document")),
+///     &token,
+///     &token,
+/// );
+/// assert!(matches!(result, Err((_, CancellableError::Cancelled))));
+/// ```
+#[derive(Debug, Default)]
+pub struct CancellationToken {
+    cancelled: AtomicBool,
+}
+
+impl CancellationToken {
+    /// Constructs a new [`CancellationToken`] which has not been cancelled.
+    pub const fn new() -> Self {
+        Self {
+            cancelled: AtomicBool::new(false),
+        }
+    }
+
+    /// Cancels this token. This cannot be undone.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    /// Returns `true` if this token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Returns [`Err(Cancelled)`](Cancelled) if this token has been
+    /// cancelled, or `Ok(())` otherwise.
+    ///
+    /// This is convenient for polling the token with `?`.
+    ///
+    /// # Errors
+    /// If this token has been cancelled.
+    pub fn check(&self) -> Result<(), Cancelled> {
+        if self.is_cancelled() {
+            Err(Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// An error indicating that an operation was cancelled through a
+/// [`CancellationToken`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cancelled;
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("operation was cancelled")
+    }
+}
+
+impl core::error::Error for Cancelled {}
+
+/// The error returned by cancellable constructors, such as
+/// [`Pair::try_new_cancellable`](crate::Pair::try_new_cancellable).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CancellableError<E> {
+    /// The [`CancellationToken`] was cancelled before construction completed.
+    Cancelled,
+    /// [`make_dependent`](crate::Owner::make_dependent) returned an error, and
+    /// the token was not cancelled.
+    Failed(E),
+}
+
+impl<E> Display for CancellableError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Cancelled => Display::fmt(&Cancelled, f),
+            Self::Failed(_) => f.write_str("failed to construct the dependent of a pair"),
+        }
+    }
+}
+
+impl<E: core::error::Error + 'static> core::error::Error for CancellableError<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Cancelled => None,
+            Self::Failed(err) => Some(err),
+        }
+    }
+}
+
+/// Calls `f`, cancelling `token` if `f` has not returned within `timeout`.
+///
+/// The deadline is enforced by a scoped watchdog thread, which is woken (and
+/// joined) as soon as `f` returns.
+#[cfg(feature = "std")]
+pub(crate) fn cancel_after<T>(
+    token: &CancellationToken,
+    timeout: std::time::Duration,
+    f: impl FnOnce() -> T,
+) -> T {
+    let deadline = std::time::Instant::now().checked_add(timeout);
+    let done = AtomicBool::new(false);
+
+    std::thread::scope(|scope| {
+        let watchdog = scope.spawn(|| {
+            while !done.load(Ordering::Acquire) {
+                match deadline {
+                    // A deadline too far in the future to represent will never
+                    // be reached, so just wait to be woken
+                    None => std::thread::park(),
+                    Some(deadline) => {
+                        let now = std::time::Instant::now();
+                        if now >= deadline {
+                            token.cancel();
+                            return;
+                        }
+                        std::thread::park_timeout(deadline - now);
+                    }
+                }
+            }
+        });
+
+        // If `f` panics, the watchdog must still be woken so the scope can end
+        let _wake_watchdog = WakeWatchdog {
+            done: &done,
+            watchdog: watchdog.thread(),
+        };
+
+        f()
+    })
+}
+
+/// Stops and wakes the watchdog thread of [`cancel_after`] when dropped.
+#[cfg(feature = "std")]
+struct WakeWatchdog<'a> {
+    done: &'a AtomicBool,
+    watchdog: &'a std::thread::Thread,
+}
+
+#[cfg(feature = "std")]
+impl Drop for WakeWatchdog<'_> {
+    fn drop(&mut self) {
+        self.done.store(true, Ordering::Release);
+        self.watchdog.unpark();
+    }
+}
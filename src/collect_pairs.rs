@@ -0,0 +1,105 @@
+//! Defines the [`PairIteratorExt`] extension trait, for constructing pairs from
+//! iterators of owners. Only available with the `itertools` feature.
+
+use alloc::vec::Vec;
+
+use itertools::Itertools;
+
+use crate::{Owner, Pair};
+
+/// Extension methods for iterators of owners, which construct a [`Pair`] for
+/// each owner and aggregate any failures.
+///
+/// This trait is sealed, and implemented for every [`Iterator`] of sized
+/// owners whose [`Context`](Owner::Context) is `()`.
+///
+/// Only available with the `itertools` feature.
+///
+/// ```
+/// # use pair::{Dependent, HasDependent, Owner, Pair, PairIteratorExt};
+/// # use std::num::ParseIntError;
+/// struct Numbers(String);
+///
+/// impl<'owner> HasDependent<'owner> for Numbers {
+///     type Dependent = Vec<u32>;
+/// }
+///
+/// impl Owner for Numbers {
+///     type Context<'a> = ();
+///     type Error = ParseIntError;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         self.0.split(',').map(str::parse).collect()
+///     }
+/// }
+///
+/// let lines = ["1,2", "x", "3", "4,y"];
+/// let Err(failures) = lines
+///     .into_iter()
+///     .map(|line| Numbers(String::from(line)))
+///     .try_collect_pairs()
+/// else {
+///     panic!("some lines are invalid");
+/// };
+///
+/// // Every failure is reported, not just the first
+/// let invalid: Vec<_> = failures.iter().map(|(owner, _)| owner.0.as_str()).collect();
+/// assert_eq!(invalid, ["x", "4,y"]);
+/// ```
+pub trait PairIteratorExt<O>: Iterator<Item = O> + sealed::Sealed
+where
+    O: for<'any> Owner<Context<'any> = ()>,
+{
+    /// Constructs a [`Pair`] for each owner, returning all of them if every
+    /// construction succeeded.
+    ///
+    /// # Errors
+    /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
+    /// error for any owner. Every failed owner is returned along with its
+    /// error (in iteration order), and the successfully constructed pairs are
+    /// dropped - see [`partition_pairs`](PairIteratorExt::partition_pairs) to
+    /// keep them.
+    #[expect(
+        clippy::type_complexity,
+        reason = "the failures mirror the errors of the `Pair` constructors"
+    )]
+    fn try_collect_pairs(self) -> Result<Vec<Pair<O>>, Vec<(O, O::Error)>>;
+
+    /// Constructs a [`Pair`] for each owner, returning the successfully
+    /// constructed pairs, and every failed owner along with its error (both in
+    /// iteration order).
+    #[expect(
+        clippy::type_complexity,
+        reason = "the failures mirror the errors of the `Pair` constructors"
+    )]
+    fn partition_pairs(self) -> (Vec<Pair<O>>, Vec<(O, O::Error)>);
+}
+
+impl<O, I> PairIteratorExt<O> for I
+where
+    O: for<'any> Owner<Context<'any> = ()>,
+    I: Iterator<Item = O>,
+{
+    fn try_collect_pairs(self) -> Result<Vec<Pair<O>>, Vec<(O, O::Error)>> {
+        let (pairs, failures) = self.partition_pairs();
+
+        if failures.is_empty() {
+            Ok(pairs)
+        } else {
+            Err(failures)
+        }
+    }
+
+    fn partition_pairs(self) -> (Vec<Pair<O>>, Vec<(O, O::Error)>) {
+        self.map(Pair::try_new).partition_result()
+    }
+}
+
+/// Prevents [`PairIteratorExt`] from being implemented outside of this crate.
+mod sealed {
+    #![expect(unnameable_types, reason = "...kinda the point")]
+
+    pub trait Sealed {}
+
+    impl<I: Iterator> Sealed for I {}
+}
@@ -0,0 +1,283 @@
+//! Defines [`CollectionPair`], a wrapper around a [`Pair`] whose dependent is
+//! a collection, and the [`DependentCollection`] trait it relies on.
+
+use core::{
+    fmt::Debug,
+    ops::{Deref, DerefMut},
+};
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    vec::Vec,
+};
+
+use crate::{Dependent, Owner, Pair};
+
+/// A dependent which is a collection of items, supporting common bulk
+/// mutations.
+///
+/// This is used by [`CollectionPair`]. Implementations are provided for
+/// [`Vec`], [`VecDeque`], [`BTreeSet`], and [`BTreeMap`] (and `HashSet` and
+/// `HashMap`, with the `std` feature). For maps, the items are `(key, value)`
+/// tuples.
+pub trait DependentCollection {
+    /// The type of items in the collection.
+    type Item;
+
+    /// Retains only the items for which `f` returns `true`, removing the rest.
+    fn retain<F: FnMut(&Self::Item) -> bool>(&mut self, f: F);
+
+    /// Removes all items from the collection.
+    fn clear(&mut self);
+
+    /// Adds all items of the given iterator to the collection.
+    fn extend_from_iter<I: IntoIterator<Item = Self::Item>>(&mut self, items: I);
+}
+
+impl<T> DependentCollection for Vec<T> {
+    type Item = T;
+
+    fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        Self::retain(self, f);
+    }
+
+    fn clear(&mut self) {
+        Self::clear(self);
+    }
+
+    fn extend_from_iter<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        self.extend(items);
+    }
+}
+
+impl<T> DependentCollection for VecDeque<T> {
+    type Item = T;
+
+    fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        Self::retain(self, f);
+    }
+
+    fn clear(&mut self) {
+        Self::clear(self);
+    }
+
+    fn extend_from_iter<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        self.extend(items);
+    }
+}
+
+impl<T: Ord> DependentCollection for BTreeSet<T> {
+    type Item = T;
+
+    fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        Self::retain(self, f);
+    }
+
+    fn clear(&mut self) {
+        Self::clear(self);
+    }
+
+    fn extend_from_iter<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        self.extend(items);
+    }
+}
+
+impl<K: Ord, V> DependentCollection for BTreeMap<K, V> {
+    type Item = (K, V);
+
+    fn retain<F: FnMut(&(K, V)) -> bool>(&mut self, mut f: F) {
+        // The predicate receives the items by reference, which `BTreeMap`
+        // can't provide in place - so the map is rebuilt
+        *self = core::mem::take(self)
+            .into_iter()
+            .filter(|item| f(item))
+            .collect();
+    }
+
+    fn clear(&mut self) {
+        Self::clear(self);
+    }
+
+    fn extend_from_iter<I: IntoIterator<Item = (K, V)>>(&mut self, items: I) {
+        self.extend(items);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S> DependentCollection for std::collections::HashSet<T, S>
+where
+    T: Eq + core::hash::Hash,
+    S: core::hash::BuildHasher,
+{
+    type Item = T;
+
+    fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        Self::retain(self, f);
+    }
+
+    fn clear(&mut self) {
+        Self::clear(self);
+    }
+
+    fn extend_from_iter<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        self.extend(items);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> DependentCollection for std::collections::HashMap<K, V, S>
+where
+    K: Eq + core::hash::Hash,
+    S: core::hash::BuildHasher,
+{
+    type Item = (K, V);
+
+    fn retain<F: FnMut(&(K, V)) -> bool>(&mut self, mut f: F) {
+        // The predicate receives the items by reference, which `HashMap` can't
+        // provide in place - so the items are drained and reinserted (reusing
+        // the map's allocation)
+        let items: Vec<(K, V)> = self.drain().filter(|item| f(item)).collect();
+        self.extend(items);
+    }
+
+    fn clear(&mut self) {
+        Self::clear(self);
+    }
+
+    fn extend_from_iter<I: IntoIterator<Item = (K, V)>>(&mut self, items: I) {
+        self.extend(items);
+    }
+}
+
+/// A [`Pair`] whose dependent is a collection (see [`DependentCollection`]),
+/// with helpers for common bulk mutations of the dependent.
+///
+/// A `CollectionPair` dereferences (mutably) to the inner [`Pair`], so all of
+/// its other methods are available as usual.
+///
+/// ```
+/// # use pair::{CollectionPair, Dependent, HasDependent, Owner, Pair};
+/// # use std::convert::Infallible;
+/// struct Document(String);
+///
+/// impl<'owner> HasDependent<'owner> for Document {
+///     type Dependent = Vec<&'owner str>;
+/// }
+///
+/// impl Owner for Document {
+///     type Context<'a> = ();
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         Ok(self.0.split_whitespace().collect())
+///     }
+/// }
+///
+/// let mut words = CollectionPair::new(Document(String::from("the quick brown fox")));
+///
+/// words.retain(|word| word.len() > 3);
+/// assert_eq!(words.with_dependent(|words| words.join(" ")), "quick brown");
+///
+/// // Re-borrow from the owner to add items back
+/// words.extend_from_owner(|document| document.0.split_whitespace().take(1).collect());
+/// assert_eq!(words.with_dependent(|words| words.join(" ")), "quick brown the");
+///
+/// words.clear();
+/// assert!(words.dependent_is_empty());
+/// ```
+pub struct CollectionPair<O: Owner + ?Sized> {
+    pair: Pair<O>,
+}
+
+impl<O: Owner + ?Sized> CollectionPair<O>
+where
+    for<'any> Dependent<'any, O>: DependentCollection,
+{
+    /// Wraps the given [`Pair`].
+    pub fn from_pair(pair: Pair<O>) -> Self {
+        Self { pair }
+    }
+
+    /// Consumes the [`CollectionPair`], returning the inner [`Pair`].
+    pub fn into_pair(self) -> Pair<O> {
+        self.pair
+    }
+
+    /// Retains only the items of the dependent for which `f` returns `true`,
+    /// removing the rest.
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: for<'any> FnMut(&<Dependent<'any, O> as DependentCollection>::Item) -> bool,
+    {
+        self.pair
+            .with_dependent_mut(|dependent| DependentCollection::retain(dependent, f));
+    }
+
+    /// Removes all items from the dependent.
+    pub fn clear(&mut self) {
+        self.pair
+            .with_dependent_mut(|dependent| DependentCollection::clear(dependent));
+    }
+
+    /// Calls the given closure with a reference to the owner, and adds the
+    /// items it returns to the dependent.
+    ///
+    /// Unlike [`Pair::with_both_mut`], the returned items may borrow from the
+    /// owner, just like the items created by
+    /// [`make_dependent`](Owner::make_dependent).
+    pub fn extend_from_owner<F>(&mut self, f: F)
+    where
+        F: for<'owner> FnOnce(
+            &'owner O,
+        ) -> Vec<<Dependent<'owner, O> as DependentCollection>::Item>,
+    {
+        self.pair.with_owner_and_dependent_mut(|owner, dependent| {
+            dependent.extend_from_iter(f(owner));
+        });
+    }
+}
+
+impl<O: for<'any> Owner<Context<'any> = (), Error = core::convert::Infallible>> CollectionPair<O>
+where
+    for<'any> Dependent<'any, O>: DependentCollection,
+{
+    /// Constructs a new [`CollectionPair`] with the given [`Owner`]. The
+    /// dependent will be computed through [`Owner::make_dependent`] during this
+    /// construction.
+    #[track_caller]
+    pub fn new(owner: O) -> Self {
+        Self::from_pair(Pair::new(owner))
+    }
+}
+
+impl<O: Owner + ?Sized> Deref for CollectionPair<O> {
+    type Target = Pair<O>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.pair
+    }
+}
+
+impl<O: Owner + ?Sized> DerefMut for CollectionPair<O> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.pair
+    }
+}
+
+impl<O: Owner + ?Sized> From<Pair<O>> for CollectionPair<O>
+where
+    for<'any> Dependent<'any, O>: DependentCollection,
+{
+    fn from(pair: Pair<O>) -> Self {
+        Self::from_pair(pair)
+    }
+}
+
+impl<O: Owner + Debug + ?Sized> Debug for CollectionPair<O>
+where
+    for<'any> Dependent<'any, O>: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("CollectionPair").field(&self.pair).finish()
+    }
+}
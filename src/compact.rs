@@ -0,0 +1,244 @@
+//! A compact binary encoding of pairs, storing the owner along with an
+//! optional sidecar of dependent spans. Only available with the `compact`
+//! feature.
+
+use core::{fmt::Display, ops::Range};
+
+use alloc::vec::Vec;
+
+use crate::{FromSpans, Owner, Pair, ToSpans};
+
+/// The version of the compact encoding, stored as its first byte.
+const FORMAT_VERSION: u8 = 1;
+
+/// An [`Owner`] which can be converted to and from bytes, for use with
+/// [`Pair::encode_compact`] and [`Pair::try_decode_compact`].
+///
+/// Only available with the `compact` feature.
+pub trait CompactOwner: Owner + Sized {
+    /// Returns the bytes representing this owner.
+    fn as_compact_bytes(&self) -> &[u8];
+
+    /// Reconstructs an owner from the bytes returned by
+    /// [`as_compact_bytes`](CompactOwner::as_compact_bytes), or returns
+    /// [`None`] if the bytes are not valid.
+    fn from_compact_bytes(bytes: &[u8]) -> Option<Self>;
+}
+
+/// An error which occurred while decoding a [`Pair`] from the compact encoding.
+///
+/// Only available with the `compact` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompactError<E> {
+    /// The bytes were not a valid compact encoding (for example, they were
+    /// truncated, or encoded with an unsupported version).
+    Malformed,
+    /// [`CompactOwner::from_compact_bytes`] rejected the owner's bytes.
+    InvalidOwner,
+    /// [`make_dependent`](Owner::make_dependent) returned an error.
+    Failed(E),
+}
+
+impl<E> Display for CompactError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::Malformed => "malformed compact pair encoding",
+            Self::InvalidOwner => "invalid owner in compact pair encoding",
+            Self::Failed(_) => "failed to construct the dependent of a pair",
+        })
+    }
+}
+
+impl<E: core::error::Error + 'static> core::error::Error for CompactError<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Malformed | Self::InvalidOwner => None,
+            Self::Failed(err) => Some(err),
+        }
+    }
+}
+
+/// The contents of a compact encoding.
+struct Decoded<O> {
+    owner: O,
+    spans: Option<Vec<Range<usize>>>,
+}
+
+/// Appends `value` to `bytes` as a little-endian `u64`.
+fn write_usize(bytes: &mut Vec<u8>, value: usize) {
+    bytes.extend_from_slice(&(value as u64).to_le_bytes());
+}
+
+/// Encodes an owner, and optionally a sidecar of spans, in the compact
+/// encoding.
+///
+/// The encoding is a version byte, the length and bytes of the owner, then a
+/// byte indicating whether spans follow - if so, their count and each of their
+/// starts and ends. All integers are little-endian `u64`s.
+fn encode<O: CompactOwner>(owner: &O, spans: Option<&[Range<usize>]>) -> Vec<u8> {
+    let owner_bytes = owner.as_compact_bytes();
+
+    let mut bytes = Vec::new();
+    bytes.push(FORMAT_VERSION);
+    write_usize(&mut bytes, owner_bytes.len());
+    bytes.extend_from_slice(owner_bytes);
+    match spans {
+        None => bytes.push(0),
+        Some(spans) => {
+            bytes.push(1);
+            write_usize(&mut bytes, spans.len());
+            for span in spans {
+                write_usize(&mut bytes, span.start);
+                write_usize(&mut bytes, span.end);
+            }
+        }
+    }
+
+    bytes
+}
+
+/// Reads values from the front of a byte slice.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let (bytes, rest) = self.0.split_at_checked(len)?;
+        self.0 = rest;
+
+        Some(bytes)
+    }
+
+    fn byte(&mut self) -> Option<u8> {
+        Some(self.bytes(1)?[0])
+    }
+
+    fn usize(&mut self) -> Option<usize> {
+        let bytes = self.bytes(size_of::<u64>())?.try_into().ok()?;
+
+        usize::try_from(u64::from_le_bytes(bytes)).ok()
+    }
+}
+
+/// Decodes the compact encoding produced by [`encode`].
+fn decode<E, O: CompactOwner>(bytes: &[u8]) -> Result<Decoded<O>, CompactError<E>> {
+    let mut reader = Reader(bytes);
+
+    let mut decode_parts = || {
+        if reader.byte()? != FORMAT_VERSION {
+            return None;
+        }
+
+        let owner_len = reader.usize()?;
+        let owner_bytes = reader.bytes(owner_len)?;
+        let spans = match reader.byte()? {
+            0 => None,
+            1 => {
+                let count = reader.usize()?;
+                // Each span takes 16 bytes, so this can't allocate more than
+                // the size of the input
+                let mut spans = Vec::with_capacity(count.min(reader.0.len() / 16));
+                for _ in 0..count {
+                    spans.push(reader.usize()?..reader.usize()?);
+                }
+                Some(spans)
+            }
+            _ => return None,
+        };
+
+        reader.0.is_empty().then_some((owner_bytes, spans))
+    };
+    let (owner_bytes, spans) = decode_parts().ok_or(CompactError::Malformed)?;
+
+    let owner = O::from_compact_bytes(owner_bytes).ok_or(CompactError::InvalidOwner)?;
+
+    Ok(Decoded { owner, spans })
+}
+
+impl<O: CompactOwner> Pair<O> {
+    /// Encodes the owner of this [`Pair`] in a compact binary encoding, which
+    /// can be decoded with [`Pair::try_decode_compact`].
+    ///
+    /// The dependent is not included, and will be recomputed when decoding.
+    /// See [`Pair::encode_compact_with_spans`] to also include the dependent.
+    ///
+    /// Only available with the `compact` feature.
+    pub fn encode_compact(&self) -> Vec<u8> {
+        encode(self.owner(), None)
+    }
+
+    /// Encodes the owner of this [`Pair`] in a compact binary encoding, along
+    /// with a sidecar of spans describing the dependent (computed with
+    /// [`ToSpans::to_spans`]). This can be decoded with
+    /// [`Pair::try_decode_compact_with_spans`], which reconstructs the
+    /// dependent from the spans rather than recomputing it.
+    ///
+    /// Only available with the `compact` feature.
+    pub fn encode_compact_with_spans(&self) -> Vec<u8>
+    where
+        O: ToSpans<Spans = Vec<Range<usize>>>,
+    {
+        let spans = self.with_both(|owner, dependent| owner.to_spans(dependent));
+
+        encode(self.owner(), Some(&spans))
+    }
+
+    /// Decodes a [`Pair`] from the compact encoding produced by
+    /// [`Pair::encode_compact`] (or [`Pair::encode_compact_with_spans`]). The
+    /// dependent will be computed through [`Owner::make_dependent`], ignoring
+    /// any spans which were encoded.
+    ///
+    /// Only available with the `compact` feature.
+    ///
+    /// # Errors
+    /// [`CompactError::Malformed`] if the bytes are not a valid compact
+    /// encoding, [`CompactError::InvalidOwner`] if
+    /// [`CompactOwner::from_compact_bytes`] rejects the owner's bytes, or
+    /// [`CompactError::Failed`] if `make_dependent` returns an error (in which
+    /// case the owner is dropped).
+    #[track_caller]
+    pub fn try_decode_compact(
+        bytes: &[u8],
+        context: O::Context<'_>,
+    ) -> Result<Self, CompactError<O::Error>> {
+        let Decoded { owner, spans: _ } = decode(bytes)?;
+
+        Self::try_new_with_context(owner, context).map_err(|(_, err)| CompactError::Failed(err))
+    }
+
+    /// Decodes a [`Pair`] from the compact encoding produced by
+    /// [`Pair::encode_compact_with_spans`] (or [`Pair::encode_compact`]).
+    ///
+    /// If spans were encoded, the dependent will be reconstructed from them
+    /// through [`FromSpans::dependent_from_spans`]. If no spans were encoded,
+    /// or they are rejected by `dependent_from_spans`, the dependent will
+    /// instead be computed through [`Owner::make_dependent`].
+    ///
+    /// Only available with the `compact` feature.
+    ///
+    /// # Errors
+    /// [`CompactError::Malformed`] if the bytes are not a valid compact
+    /// encoding, [`CompactError::InvalidOwner`] if
+    /// [`CompactOwner::from_compact_bytes`] rejects the owner's bytes, or
+    /// [`CompactError::Failed`] if `make_dependent` is called and returns an
+    /// error (in which case the owner is dropped).
+    #[track_caller]
+    pub fn try_decode_compact_with_spans(
+        bytes: &[u8],
+        context: O::Context<'_>,
+    ) -> Result<Self, CompactError<O::Error>>
+    where
+        O: FromSpans<Spans = Vec<Range<usize>>>,
+    {
+        let Decoded { owner, spans } = decode(bytes)?;
+
+        let owner = match spans {
+            Some(spans) => match Self::try_new_with_spans(owner, &spans) {
+                Ok(pair) => return Ok(pair),
+                Err(owner) => owner,
+            },
+            None => owner,
+        };
+
+        Self::try_new_with_context(owner, context).map_err(|(_, err)| CompactError::Failed(err))
+    }
+}
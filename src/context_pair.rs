@@ -0,0 +1,221 @@
+//! Defines [`ContextPair`], a [`Pair`] which retains the context it was
+//! constructed with, and the [`StoredContext`] trait it uses to lend that
+//! context to [`Owner::make_dependent`].
+
+use core::{convert::Infallible, fmt::Debug, ops::Deref};
+
+use crate::{Dependent, DependentDiff, Owner, Pair, SuspendedPair};
+
+/// A type which can be stored in a [`ContextPair`], and lent as the
+/// [`Context`](Owner::Context) of the owner `O`.
+///
+/// [`Owner::Context`] is generic over a lifetime, and often borrows its data
+/// (for example, `type Context<'a> = &'a str;`), so it usually can't be stored
+/// directly. This trait defines an owned form of the context, and how to
+/// borrow a context from it.
+///
+/// ```
+/// # use pair::{Dependent, HasDependent, Owner, StoredContext};
+/// # use std::convert::Infallible;
+/// struct Csv(String);
+///
+/// impl<'owner> HasDependent<'owner> for Csv {
+///     type Dependent = Vec<&'owner str>;
+/// }
+///
+/// impl Owner for Csv {
+///     type Context<'a> = &'a str;
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, delimiter: &str) -> Result<Dependent<'_, Self>, Self::Error> {
+///         Ok(self.0.split(delimiter).collect())
+///     }
+/// }
+///
+/// // A `String` can be stored, and lent to `Csv::make_dependent` as a `&str`
+/// impl StoredContext<Csv> for String {
+///     fn context(&self) -> &str {
+///         self
+///     }
+/// }
+/// ```
+pub trait StoredContext<O: Owner + ?Sized> {
+    /// Borrows the context to pass to [`Owner::make_dependent`].
+    fn context(&self) -> O::Context<'_>;
+}
+
+impl<O: for<'any> Owner<Context<'any> = ()> + ?Sized> StoredContext<O> for () {
+    fn context(&self) {}
+}
+
+/// A [`Pair`] which retains the context it was constructed with.
+///
+/// Many contexts (such as delimiters or parsing options) are needed again
+/// whenever the dependent is rebuilt. A `ContextPair` stores the context `C`
+/// alongside the pair, exposes it through [`context`](ContextPair::context),
+/// and uses it automatically in
+/// [`rebuild_dependent`](ContextPair::rebuild_dependent). The context is lent
+/// to [`Owner::make_dependent`] through the [`StoredContext`] trait.
+///
+/// `ContextPair` dereferences to the inner [`Pair`], so all of its accessors
+/// are available directly.
+pub struct ContextPair<O: Owner + ?Sized, C> {
+    pair: Pair<O>,
+    context: C,
+}
+
+impl<O: Owner, C: StoredContext<O>> ContextPair<O, C> {
+    /// Constructs a new [`ContextPair`] with the given [`Owner`] and context.
+    /// The dependent will be computed through [`Owner::make_dependent`] during
+    /// this construction, using the context.
+    ///
+    /// # Errors
+    /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
+    /// error. The owner and context are returned alongside the error.
+    #[track_caller]
+    pub fn try_new(owner: O, context: C) -> Result<Self, (O, C, O::Error)> {
+        match Pair::try_new_with_context(owner, context.context()) {
+            Ok(pair) => Ok(Self { pair, context }),
+            Err((owner, err)) => Err((owner, context, err)),
+        }
+    }
+}
+
+impl<O: Owner<Error = Infallible>, C: StoredContext<O>> ContextPair<O, C> {
+    /// Constructs a new [`ContextPair`] with the given [`Owner`] and context.
+    /// The dependent will be computed through [`Owner::make_dependent`] during
+    /// this construction, using the context.
+    #[track_caller]
+    pub fn new(owner: O, context: C) -> Self {
+        let Ok(pair) = Self::try_new(owner, context);
+        pair
+    }
+}
+
+impl<O: Owner + ?Sized, C: StoredContext<O>> ContextPair<O, C> {
+    /// Recomputes the dependent from the owner with the stored context, with
+    /// [`Owner::update_dependent`].
+    ///
+    /// By default, the new dependent is constructed before the old one is
+    /// replaced (and dropped).
+    ///
+    /// # Errors
+    /// If [`<O as Owner>::update_dependent`](Owner::update_dependent) returns
+    /// an error. In that case, the dependent is left as `update_dependent` left
+    /// it - by default, intact.
+    pub fn try_rebuild_dependent(&mut self) -> Result<(), O::Error> {
+        self.pair.try_update_dependent(self.context.context())
+    }
+
+    /// Recomputes the dependent from the owner with the stored context, with
+    /// [`Owner::update_dependent`].
+    ///
+    /// By default, the new dependent is constructed before the old one is
+    /// replaced (and dropped).
+    pub fn rebuild_dependent(&mut self)
+    where
+        O: Owner<Error = Infallible>,
+    {
+        let Ok(()) = self.try_rebuild_dependent();
+    }
+
+    /// Recomputes the dependent from the owner with the stored context,
+    /// replacing (and dropping) the current dependent, and returns what
+    /// changed as given by [`DependentDiff::diff`].
+    ///
+    /// The diff is computed after the new dependent is constructed, and before
+    /// the old one is dropped.
+    ///
+    /// # Errors
+    /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
+    /// error. In that case, the current dependent is left intact.
+    pub fn try_rebuild_dependent_diff(&mut self) -> Result<O::Diff, O::Error>
+    where
+        O: DependentDiff,
+    {
+        self.pair
+            .try_replace_dependent_with(self.context.context(), O::diff)
+    }
+
+    /// Recomputes the dependent from the owner with the stored context,
+    /// replacing (and dropping) the current dependent, and returns what
+    /// changed as given by [`DependentDiff::diff`].
+    ///
+    /// The diff is computed after the new dependent is constructed, and before
+    /// the old one is dropped.
+    pub fn rebuild_dependent_diff(&mut self) -> O::Diff
+    where
+        O: DependentDiff<Error = Infallible>,
+    {
+        let Ok(diff) = self.try_rebuild_dependent_diff();
+        diff
+    }
+
+    /// Suspends the dependent, returning a [`SuspendedPair`] guard which
+    /// provides exclusive access to the owner, and rebuilds the dependent with
+    /// the stored context when it is dropped.
+    ///
+    /// While suspended, the dependent holds its [`Default`] value. See the
+    /// documentation of [`SuspendedPair`] for more information.
+    pub fn suspend_dependent(&mut self) -> SuspendedPair<'_, O, C>
+    where
+        for<'any> Dependent<'any, O>: Default,
+    {
+        SuspendedPair::new(&mut self.pair, &self.context)
+    }
+}
+
+impl<O: Owner + ?Sized, C> ContextPair<O, C> {
+    /// Returns a reference to the stored context.
+    pub fn context(&self) -> &C {
+        &self.context
+    }
+
+    /// Returns a reference to the inner [`Pair`].
+    pub fn pair(&self) -> &Pair<O> {
+        &self.pair
+    }
+
+    /// Returns a mutable reference to the inner [`Pair`].
+    pub fn pair_mut(&mut self) -> &mut Pair<O> {
+        &mut self.pair
+    }
+
+    /// Consumes the [`ContextPair`], returning the inner [`Pair`] and the
+    /// stored context.
+    pub fn into_parts(self) -> (Pair<O>, C) {
+        (self.pair, self.context)
+    }
+
+    /// Consumes the [`ContextPair`], dropping the dependent and returning the
+    /// owner and the stored context.
+    pub fn into_owner(self) -> (O, C)
+    where
+        O: Sized,
+    {
+        (self.pair.into_owner(), self.context)
+    }
+}
+
+impl<O: Owner + ?Sized, C> Deref for ContextPair<O, C> {
+    type Target = Pair<O>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.pair
+    }
+}
+
+impl<O: Owner + Debug + ?Sized, C: Debug> Debug for ContextPair<O, C>
+where
+    for<'any> Dependent<'any, O>: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.pair.with_dependent(|dependent| {
+            f.debug_struct("ContextPair")
+                .field("owner", &self.pair.owner())
+                .field("dependent", dependent)
+                .field("context", &self.context)
+                .finish()
+        })
+    }
+}
@@ -0,0 +1,134 @@
+//! Defines [`CovariantDependent`], a marker trait for owners whose dependent is
+//! covariant over its lifetime, and the [`covariant!`](crate::covariant!) macro
+//! for safely implementing it.
+
+use crate::Owner;
+
+/// A marker trait for [`Owner`]s whose [`Dependent`](crate::Dependent) is
+/// covariant over the `'owner` lifetime.
+///
+/// The real lifetime of a pair's dependent (lasting from construction until
+/// drop) can't be named, so most accessors (like
+/// [`Pair::with_dependent`](crate::Pair::with_dependent)) only expose the
+/// dependent within a closure which must work with any lifetime. If the
+/// dependent is covariant, though, it's sound to shorten its lifetime to that
+/// of a borrow of the pair, so it can be returned directly - by
+/// [`Pair::dependent`](crate::Pair::dependent) and
+/// [`Pair::dependent_ref`](crate::Pair::dependent_ref).
+///
+/// Exclusive access still requires a closure (see
+/// [`DependentRefMut`](crate::DependentRefMut)), unless the dependent doesn't
+/// borrow from the owner at all. Otherwise, borrows which only live as long as
+/// the borrow of the pair could be stored in the dependent, and would dangle
+/// once it ends.
+///
+/// Most dependents are covariant - references, slices, `Vec`s and `Option`s
+/// of them, and structs containing only covariant fields all are. Dependents
+/// with interior mutability over the lifetime (such as
+/// `Cell<&'owner str>`), or which use it in a function argument, are not.
+///
+/// Rather than implementing this trait manually, prefer the
+/// [`covariant!`](crate::covariant!) macro, which checks covariance at compile
+/// time and doesn't require writing `unsafe`.
+///
+/// # Safety
+/// For every pair of lifetimes `'long: 'short`, `Dependent<'long, Self>` must
+/// be a subtype of `Dependent<'short, Self>`. That is, the following function
+/// must compile for your owner:
+///
+/// ```
+/// # use pair::{Dependent, HasDependent};
+/// # struct MyOwner;
+/// # impl<'owner> HasDependent<'owner> for MyOwner {
+/// #     type Dependent = Vec<&'owner str>;
+/// # }
+/// fn shorten<'long: 'short, 'short>(
+///     dependent: Dependent<'long, MyOwner>,
+/// ) -> Dependent<'short, MyOwner> {
+///     dependent
+/// }
+/// ```
+///
+/// ```
+/// # use pair::{CovariantDependent, Dependent, HasDependent, Owner, Pair};
+/// # use std::convert::Infallible;
+/// struct Words(String);
+///
+/// impl<'owner> HasDependent<'owner> for Words {
+///     type Dependent = Vec<&'owner str>;
+/// }
+///
+/// impl Owner for Words {
+///     type Context<'a> = ();
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         Ok(self.0.split_whitespace().collect())
+///     }
+/// }
+///
+/// // SAFETY: `Vec<&'owner str>` is covariant over `'owner`.
+/// unsafe impl CovariantDependent for Words {}
+///
+/// let pair = Pair::new(Words(String::from("hello world")));
+/// let words: &Vec<&str> = pair.dependent();
+/// assert_eq!(words.iter().map(|word| word.len()).sum::<usize>(), 10);
+/// ```
+pub unsafe trait CovariantDependent: Owner {}
+
+/// Implements [`CovariantDependent`] for the given owner types, after checking
+/// at compile time that their dependents are covariant.
+///
+/// This is a safe alternative to implementing [`CovariantDependent`] manually.
+/// If a dependent isn't covariant, the check fails to compile with a lifetime
+/// error.
+///
+/// ```
+/// # use pair::{Dependent, HasDependent, Owner, Pair, covariant};
+/// # use std::convert::Infallible;
+/// struct Words(String);
+///
+/// impl<'owner> HasDependent<'owner> for Words {
+///     type Dependent = Vec<&'owner str>;
+/// }
+///
+/// impl Owner for Words {
+///     type Context<'a> = ();
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         Ok(self.0.split_whitespace().collect())
+///     }
+/// }
+///
+/// covariant!(Words);
+///
+/// let pair = Pair::new(Words(String::from("no closures here")));
+/// assert_eq!(pair.dependent()[2], "here");
+/// ```
+///
+/// Only owner types without generic parameters are supported. Generic owners
+/// must implement [`CovariantDependent`] manually.
+#[macro_export]
+macro_rules! covariant {
+    ($($owner:ty),+ $(,)?) => {
+        $(
+            const _: () = {
+                // If this fails to compile, the dependent of the owner isn't
+                // covariant, and can't implement `CovariantDependent`.
+                fn assert_covariant<'long: 'short, 'short>(
+                    dependent: $crate::Dependent<'long, $owner>,
+                ) -> $crate::Dependent<'short, $owner> {
+                    dependent
+                }
+
+                let _ = assert_covariant;
+            };
+
+            // SAFETY: the function above only compiles if the dependent is a
+            // subtype of itself with any shorter lifetime - that is, if it's
+            // covariant.
+            unsafe impl $crate::CovariantDependent for $owner {}
+        )+
+    };
+}
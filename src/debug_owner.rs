@@ -0,0 +1,70 @@
+//! Defines [`DebugOwnerOnly`], a [`Debug`] adapter for a [`Pair`] which never
+//! touches the dependent.
+
+use core::fmt::Debug;
+
+use crate::{Owner, Pair};
+
+/// A [`Debug`] adapter for a [`Pair`], which formats only the owner, created by
+/// [`Pair::debug_owner_only`].
+///
+/// The dependent is never accessed - in its place, the output records that it
+/// was skipped. This is useful when the dependent's `Debug` implementation is
+/// expensive, may deadlock (such as by taking a lock the caller already holds),
+/// or doesn't exist at all.
+///
+/// ```
+/// # use pair::{Dependent, HasDependent, Owner, Pair};
+/// # use std::{collections::HashMap, convert::Infallible};
+/// #[derive(Debug)]
+/// struct Config(String);
+///
+/// // An index which doesn't implement `Debug`
+/// struct Index<'a>(HashMap<&'a str, &'a str>);
+///
+/// impl<'owner> HasDependent<'owner> for Config {
+///     type Dependent = Index<'owner>;
+/// }
+///
+/// impl Owner for Config {
+///     type Context<'a> = ();
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         Ok(Index(self.0.lines().filter_map(|line| line.split_once(" = ")).collect()))
+///     }
+/// }
+///
+/// let pair = Pair::new(Config(String::from("a = 1")));
+/// assert_eq!(
+///     format!("{:?}", pair.debug_owner_only()),
+///     r#"Pair { owner: Config("a = 1"), dependent: <skipped> }"#,
+/// );
+/// ```
+pub struct DebugOwnerOnly<'pair, O: Owner + ?Sized> {
+    pair: &'pair Pair<O>,
+}
+
+impl<'pair, O: Owner + ?Sized> DebugOwnerOnly<'pair, O> {
+    /// Creates a new [`DebugOwnerOnly`] for the given [`Pair`].
+    pub(crate) fn new(pair: &'pair Pair<O>) -> Self {
+        Self { pair }
+    }
+}
+
+impl<O: Owner + Debug + ?Sized> Debug for DebugOwnerOnly<'_, O> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Pair")
+            .field("owner", &self.pair.owner())
+            .field("dependent", &format_args!("<skipped>"))
+            .finish()
+    }
+}
+
+impl<O: Owner + ?Sized> Clone for DebugOwnerOnly<'_, O> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<O: Owner + ?Sized> Copy for DebugOwnerOnly<'_, O> {}
@@ -0,0 +1,178 @@
+//! Defines [`DefaultedPair`], a pair which falls back to a default dependent
+//! when constructing the dependent fails.
+
+use core::{fmt::Debug, ops::Deref};
+
+use alloc::boxed::Box;
+
+use crate::{Dependent, Owner, Pair};
+
+/// A [`Pair`] which falls back to a default dependent if
+/// [`make_dependent`](Owner::make_dependent) failed, created by
+/// [`Pair::try_new_or_default_dependent`] (or its variants).
+///
+/// The error (if any) is recorded, and can be inspected later with
+/// [`last_error`](DefaultedPair::last_error). This is useful for resilient
+/// loaders (such as of configuration) which must always produce a usable
+/// object, but still want to report what went wrong.
+///
+/// ```
+/// # use pair::{Dependent, HasDependent, Owner, Pair};
+/// # use std::{collections::HashMap, convert::Infallible};
+/// struct Config(String);
+///
+/// impl<'owner> HasDependent<'owner> for Config {
+///     type Dependent = HashMap<&'owner str, &'owner str>;
+/// }
+///
+/// impl Owner for Config {
+///     type Context<'a> = ();
+///     type Error = String;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         self.0
+///             .lines()
+///             .map(|line| line.split_once('=').ok_or(format!("invalid line: {line}")))
+///             .collect()
+///     }
+/// }
+///
+/// let config = Pair::try_new_or_default_dependent(Config(String::from("a=1\noops")));
+/// assert_eq!(config.last_error().map(String::as_str), Some("invalid line: oops"));
+/// assert!(config.with_dependent(|settings| settings.is_empty()));
+/// ```
+pub struct DefaultedPair<O: Owner + ?Sized> {
+    pair: Pair<O>,
+    last_error: Option<O::Error>,
+}
+
+impl<O: Owner + ?Sized> DefaultedPair<O> {
+    /// Returns a reference to the inner [`Pair`].
+    pub fn pair(&self) -> &Pair<O> {
+        &self.pair
+    }
+
+    /// Returns a mutable reference to the inner [`Pair`].
+    pub fn pair_mut(&mut self) -> &mut Pair<O> {
+        &mut self.pair
+    }
+
+    /// Returns the error from constructing the dependent, if the default
+    /// dependent was used in its place.
+    pub fn last_error(&self) -> Option<&O::Error> {
+        self.last_error.as_ref()
+    }
+
+    /// Returns `true` if constructing the dependent failed, and the default
+    /// dependent was used in its place.
+    pub fn is_defaulted(&self) -> bool {
+        self.last_error.is_some()
+    }
+
+    /// Takes the error from constructing the dependent (if any), leaving
+    /// [`None`] in its place.
+    pub fn take_last_error(&mut self) -> Option<O::Error> {
+        self.last_error.take()
+    }
+
+    /// Consumes the [`DefaultedPair`], returning the inner [`Pair`] and the
+    /// error from constructing the dependent (if any).
+    pub fn into_parts(self) -> (Pair<O>, Option<O::Error>) {
+        (self.pair, self.last_error)
+    }
+}
+
+impl<O: Owner + ?Sized> Deref for DefaultedPair<O> {
+    type Target = Pair<O>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.pair
+    }
+}
+
+impl<O: Owner + Debug + ?Sized> Debug for DefaultedPair<O>
+where
+    O::Error: Debug,
+    for<'any> Dependent<'any, O>: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DefaultedPair")
+            .field("pair", &self.pair)
+            .field("last_error", &self.last_error)
+            .finish()
+    }
+}
+
+impl<O: Owner + ?Sized> Pair<O>
+where
+    for<'any> Dependent<'any, O>: Default,
+{
+    /// Constructs a new [`Pair`] with the given [`Owner`]. The dependent will
+    /// be computed through [`Owner::make_dependent`] during this construction,
+    /// falling back to [`Default::default`] if that fails.
+    ///
+    /// Construction never fails - instead, the error is recorded in the
+    /// returned [`DefaultedPair`]. See its documentation for more information.
+    #[track_caller]
+    pub fn try_new_or_default_dependent_with_context(
+        owner: O,
+        context: O::Context<'_>,
+    ) -> DefaultedPair<O>
+    where
+        O: Sized,
+    {
+        Self::try_new_from_box_or_default_dependent_with_context(Box::new(owner), context)
+    }
+
+    /// Constructs a new [`Pair`] with the given [`Owner`]. The dependent will
+    /// be computed through [`Owner::make_dependent`] during this construction,
+    /// falling back to [`Default::default`] if that fails.
+    ///
+    /// Construction never fails - instead, the error is recorded in the
+    /// returned [`DefaultedPair`]. See its documentation for more information.
+    #[track_caller]
+    pub fn try_new_from_box_or_default_dependent_with_context(
+        owner: Box<O>,
+        context: O::Context<'_>,
+    ) -> DefaultedPair<O> {
+        let mut last_error = None;
+        let pair = Self::new_from_box_with_prebuilt(owner, context, |owner, context| {
+            owner.make_dependent(context).unwrap_or_else(|err| {
+                last_error = Some(err);
+                Default::default()
+            })
+        });
+
+        DefaultedPair { pair, last_error }
+    }
+}
+
+impl<O: for<'any> Owner<Context<'any> = ()> + ?Sized> Pair<O>
+where
+    for<'any> Dependent<'any, O>: Default,
+{
+    /// Constructs a new [`Pair`] with the given [`Owner`]. The dependent will
+    /// be computed through [`Owner::make_dependent`] during this construction,
+    /// falling back to [`Default::default`] if that fails.
+    ///
+    /// Construction never fails - instead, the error is recorded in the
+    /// returned [`DefaultedPair`]. See its documentation for more information.
+    #[track_caller]
+    pub fn try_new_or_default_dependent(owner: O) -> DefaultedPair<O>
+    where
+        O: Sized,
+    {
+        Self::try_new_or_default_dependent_with_context(owner, ())
+    }
+
+    /// Constructs a new [`Pair`] with the given [`Owner`]. The dependent will
+    /// be computed through [`Owner::make_dependent`] during this construction,
+    /// falling back to [`Default::default`] if that fails.
+    ///
+    /// Construction never fails - instead, the error is recorded in the
+    /// returned [`DefaultedPair`]. See its documentation for more information.
+    #[track_caller]
+    pub fn try_new_from_box_or_default_dependent(owner: Box<O>) -> DefaultedPair<O> {
+        Self::try_new_from_box_or_default_dependent_with_context(owner, ())
+    }
+}
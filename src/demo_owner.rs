@@ -0,0 +1,76 @@
+//! Defines the [`demo_owner!`](crate::demo_owner!) macro, for scaffolding a
+//! small but complete [`Owner`](crate::Owner) in doctests and examples.
+
+/// Re-exports used by [`demo_owner!`](crate::demo_owner!), so the generated
+/// code works in `no_std` crates. Not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::{string::String, vec::Vec};
+}
+
+/// Declares a small, fully-working [`Owner`](crate::Owner) with the given name,
+/// for use in doctests and examples.
+///
+/// The generated owner is a tuple struct wrapping a `String`, and its dependent
+/// is a `Vec<&str>` of the whitespace-separated words in it, borrowed from the
+/// owner. It requires no context, and its construction can't fail. Alongside
+/// the [`HasDependent`](crate::HasDependent) and [`Owner`](crate::Owner)
+/// implementations, two constructors are generated: `new(&str) -> Self`, and
+/// `new_pair(&str) -> Pair<Self>`.
+///
+/// ```
+/// # use pair::demo_owner;
+/// demo_owner! {
+///     /// Some words, for demonstration.
+///     pub struct Words;
+/// }
+///
+/// let pair = Words::new_pair("hello there world");
+/// assert_eq!(pair.owner(), &Words::new("hello there world"));
+/// assert_eq!(pair.with_dependent(|words| words[1]), "there");
+/// ```
+///
+/// The expansion is meant as a correct starting point for writing your own
+/// owner - see the macro's source for exactly what is generated.
+#[macro_export]
+macro_rules! demo_owner {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident;
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        $vis struct $name(pub $crate::__private::String);
+
+        impl $name {
+            /// Creates a new owner containing a copy of the given text.
+            #[must_use]
+            $vis fn new(text: &str) -> Self {
+                Self($crate::__private::String::from(text))
+            }
+
+            /// Creates a new `Pair` from an owner containing a copy of the
+            /// given text.
+            #[must_use]
+            $vis fn new_pair(text: &str) -> $crate::Pair<Self> {
+                $crate::Pair::new(Self::new(text))
+            }
+        }
+
+        impl<'owner> $crate::HasDependent<'owner> for $name {
+            type Dependent = $crate::__private::Vec<&'owner str>;
+        }
+
+        impl $crate::Owner for $name {
+            type Context<'a> = ();
+            type Error = ::core::convert::Infallible;
+
+            fn make_dependent(
+                &self,
+                (): Self::Context<'_>,
+            ) -> ::core::result::Result<$crate::Dependent<'_, Self>, Self::Error> {
+                ::core::result::Result::Ok(self.0.split_whitespace().collect())
+            }
+        }
+    };
+}
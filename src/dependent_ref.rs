@@ -0,0 +1,175 @@
+//! Defines [`DependentRef`] and [`DependentRefMut`], guards providing access
+//! to the dependent of a [`Pair`](crate::Pair).
+
+use core::{
+    fmt::Debug,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{CovariantDependent, Dependent, HasDependent, Owner};
+
+/// Shared access to the dependent of a [`Pair`](crate::Pair), valid for a
+/// single borrow of the pair.
+///
+/// Obtained through [`Pair::dependent_ref`](crate::Pair::dependent_ref). Unlike
+/// [`Pair::with_dependent`](crate::Pair::with_dependent), the dependent is
+/// accessed by dereferencing the guard, rather than within a closure - so it
+/// composes with iterator chains and `?`. This is only possible for owners
+/// whose dependent is covariant, which is asserted by implementing
+/// [`CovariantDependent`].
+pub struct DependentRef<'pair, O: CovariantDependent + ?Sized> {
+    // Since the dependent is covariant, the real lifetime of the dependent
+    // (lasting from the construction of the pair until drop) can be safely
+    // shortened to `'pair`.
+    dependent: &'pair Dependent<'pair, O>,
+}
+
+impl<'pair, O: CovariantDependent + ?Sized> DependentRef<'pair, O> {
+    /// Creates a new [`DependentRef`] from a reference to a dependent.
+    pub(crate) fn new(dependent: &'pair Dependent<'pair, O>) -> Self {
+        Self { dependent }
+    }
+
+    /// Converts the guard into a plain reference to the dependent, valid for
+    /// the same borrow of the pair.
+    ///
+    /// This is an associated function (rather than a method) so it doesn't
+    /// conflict with methods of the dependent.
+    pub fn into_ref(this: Self) -> &'pair Dependent<'pair, O> {
+        this.dependent
+    }
+}
+
+impl<'pair, O: CovariantDependent + ?Sized> Deref for DependentRef<'pair, O> {
+    type Target = Dependent<'pair, O>;
+
+    fn deref(&self) -> &Self::Target {
+        self.dependent
+    }
+}
+
+impl<O: CovariantDependent + ?Sized> Clone for DependentRef<'_, O> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<O: CovariantDependent + ?Sized> Copy for DependentRef<'_, O> {}
+
+impl<'pair, O: CovariantDependent + ?Sized> Debug for DependentRef<'pair, O>
+where
+    Dependent<'pair, O>: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.dependent, f)
+    }
+}
+
+/// Exclusive access to the dependent of a [`Pair`](crate::Pair), valid for a
+/// single exclusive borrow of the pair.
+///
+/// Obtained through [`Pair::dependent_mut`](crate::Pair::dependent_mut). This
+/// allows a long-lived mutable session with the dependent to be split across
+/// many statements (and functions), rather than pushed into a single
+/// [`Pair::with_dependent_mut`](crate::Pair::with_dependent_mut) closure.
+///
+/// How the dependent can be accessed depends on the owner:
+/// - For any owner, [`with_mut`](DependentRefMut::with_mut) provides exclusive
+///   access within a closure which must work with any lifetime - just like
+///   [`Pair::with_dependent_mut`](crate::Pair::with_dependent_mut).
+/// - For owners implementing [`CovariantDependent`], the guard dereferences to
+///   the dependent for shared access.
+/// - For owners whose dependent doesn't borrow from the owner at all (the same
+///   type `D` for every lifetime), the guard also mutably dereferences to it.
+///
+/// A dependent which does borrow from the owner can never be mutably
+/// dereferenced, even if it's covariant - that would allow storing borrows
+/// which only live as long as the guard in it, which would dangle once the
+/// guard is dropped.
+///
+/// ```
+/// # use pair::{CovariantDependent, Dependent, HasDependent, Owner, Pair};
+/// # use std::convert::Infallible;
+/// struct Text(String);
+///
+/// impl<'owner> HasDependent<'owner> for Text {
+///     type Dependent = Vec<&'owner str>;
+/// }
+///
+/// impl Owner for Text {
+///     type Context<'a> = ();
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         Ok(self.0.split_whitespace().collect())
+///     }
+/// }
+///
+/// // SAFETY: `Vec<&'owner str>` is covariant over `'owner`.
+/// unsafe impl CovariantDependent for Text {}
+///
+/// let mut pair = Pair::new(Text(String::from("b a c a")));
+/// let mut words = pair.dependent_mut();
+///
+/// words.with_mut(|words| words.sort_unstable());
+/// assert_eq!(words.first(), Some(&"a"));
+///
+/// words.with_mut(|words| words.dedup());
+/// assert_eq!(words.len(), 3);
+/// ```
+pub struct DependentRefMut<'pair, O: Owner + ?Sized> {
+    // The real lifetime of the dependent (lasting from the construction of the
+    // pair until drop) is inexpressible - `'pair` is a stand-in which is never
+    // exposed mutably to callers, since that would allow storing borrows which
+    // don't live as long as the owner.
+    dependent: &'pair mut Dependent<'pair, O>,
+}
+
+impl<'pair, O: Owner + ?Sized> DependentRefMut<'pair, O> {
+    /// Creates a new [`DependentRefMut`] from a reference to a dependent.
+    ///
+    /// The dependent must never be handed out mutably with the `'pair`
+    /// lifetime (unless it doesn't depend on that lifetime).
+    pub(crate) fn new(dependent: &'pair mut Dependent<'pair, O>) -> Self {
+        Self { dependent }
+    }
+
+    /// Calls the given closure, providing exclusive access to the dependent,
+    /// and returns the value computed by the closure.
+    ///
+    /// See the documentation of
+    /// [`Pair::with_dependent_mut`](crate::Pair::with_dependent_mut) for more
+    /// information.
+    pub fn with_mut<'guard, F, T>(&'guard mut self, f: F) -> T
+    where
+        F: for<'any> FnOnce(&'guard mut Dependent<'_, O>) -> T,
+    {
+        f(self.dependent)
+    }
+}
+
+impl<'pair, O: CovariantDependent + ?Sized> Deref for DependentRefMut<'pair, O> {
+    type Target = Dependent<'pair, O>;
+
+    fn deref(&self) -> &Self::Target {
+        self.dependent
+    }
+}
+
+impl<O, D> DerefMut for DependentRefMut<'_, O>
+where
+    O: CovariantDependent + for<'any> HasDependent<'any, Dependent = D> + ?Sized,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.dependent
+    }
+}
+
+impl<'pair, O: CovariantDependent + ?Sized> Debug for DependentRefMut<'pair, O>
+where
+    Dependent<'pair, O>: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&*self.dependent, f)
+    }
+}
@@ -0,0 +1,289 @@
+//! Defines the [`DependentSlice`] trait, for dependents which are contiguous
+//! sequences of items, and helpers for borrowing (and processing in parallel)
+//! disjoint parts of them.
+
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{Dependent, Owner, Pair};
+
+/// A dependent which is a contiguous sequence of items, and so can be viewed
+/// as a slice.
+///
+/// This is used by [`Pair::with_dependent_split_mut`] and (with the `std`
+/// feature) `Pair::par_with_dependent_chunks`. Implementations are
+/// provided for [`Vec`], boxed slices, and arrays.
+pub trait DependentSlice {
+    /// The type of items in the slice.
+    type Item;
+
+    /// Returns the items as a slice.
+    fn as_slice(&self) -> &[Self::Item];
+
+    /// Returns the items as a mutable slice.
+    fn as_mut_slice(&mut self) -> &mut [Self::Item];
+}
+
+impl<T> DependentSlice for Vec<T> {
+    type Item = T;
+
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T> DependentSlice for Box<[T]> {
+    type Item = T;
+
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T, const N: usize> DependentSlice for [T; N] {
+    type Item = T;
+
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<O: Owner + ?Sized> Pair<O>
+where
+    for<'any> Dependent<'any, O>: DependentSlice,
+{
+    /// Splits the dependent into disjoint mutable parts, and calls the given
+    /// closure with them, returning the value computed by the closure.
+    ///
+    /// The `split` closure is given the dependent's items, and returns the
+    /// (ascending) indices to split them at. For example, split points of
+    /// `[2, 5]` for 8 items produce the parts `[0..2]`, `[2..5]`, and `[5..8]`.
+    /// The parts are disjoint, so they may be mutated independently - for
+    /// example, in parallel on threads spawned with [`std::thread::scope`].
+    ///
+    /// Like [`Pair::with_dependent_mut`], the closures must be able to work
+    /// with items of a [`Dependent`] with any arbitrary lifetime.
+    ///
+    /// ```
+    /// # use pair::{Dependent, HasDependent, Owner, Pair};
+    /// # use std::convert::Infallible;
+    /// struct Log(String);
+    ///
+    /// impl<'owner> HasDependent<'owner> for Log {
+    ///     type Dependent = Vec<&'owner str>;
+    /// }
+    ///
+    /// impl Owner for Log {
+    ///     type Context<'a> = ();
+    ///     type Error = Infallible;
+    ///
+    ///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+    ///         Ok(self.0.lines().collect())
+    ///     }
+    /// }
+    ///
+    /// let mut pair = Pair::new(Log(String::from("b\na\n--\nd\nc")));
+    ///
+    /// // Sort each section of the log independently, on its own thread
+    /// pair.with_dependent_split_mut(
+    ///     |lines| (0..lines.len()).filter(|&i| lines[i] == "--").collect(),
+    ///     |sections| {
+    ///         std::thread::scope(|scope| {
+    ///             for section in sections {
+    ///                 scope.spawn(move || section.sort_unstable());
+    ///             }
+    ///         });
+    ///     },
+    /// );
+    ///
+    /// assert_eq!(pair.with_dependent(|lines| lines.join(" ")), "a b -- c d");
+    /// ```
+    ///
+    /// # Panics
+    /// If the split points are not in ascending order, or any is greater than
+    /// the number of items.
+    ///
+    /// [`Dependent`]: crate::HasDependent::Dependent
+    pub fn with_dependent_split_mut<'self_borrow, S, F, T>(
+        &'self_borrow mut self,
+        split: S,
+        f: F,
+    ) -> T
+    where
+        S: for<'any> FnOnce(&[<Dependent<'any, O> as DependentSlice>::Item]) -> Vec<usize>,
+        F: for<'any> FnOnce(
+            Vec<&'self_borrow mut [<Dependent<'any, O> as DependentSlice>::Item]>,
+        ) -> T,
+    {
+        self.with_dependent_mut(|dependent| {
+            let split_points = split(dependent.as_slice());
+
+            let mut parts = Vec::with_capacity(split_points.len() + 1);
+            let mut rest = dependent.as_mut_slice();
+            let mut offset = 0;
+            for split_point in split_points {
+                assert!(
+                    split_point >= offset && split_point - offset <= rest.len(),
+                    "split points must be ascending and in bounds",
+                );
+
+                let (part, remaining) = rest.split_at_mut(split_point - offset);
+                parts.push(part);
+                rest = remaining;
+                offset = split_point;
+            }
+            parts.push(rest);
+
+            f(parts)
+        })
+    }
+
+    /// Splits the dependent into (at most) `chunks` contiguous chunks of
+    /// roughly equal size, and calls the given closure with each of them in
+    /// parallel on scoped threads. The values computed by the closure are
+    /// returned in the same order as the chunks.
+    ///
+    /// One thread is spawned for each chunk (with [`std::thread::scope`]),
+    /// except when there is only a single chunk, which is processed on the
+    /// current thread.
+    ///
+    /// Like [`Pair::with_dependent`], the closure must be able to work with
+    /// items of a [`Dependent`] with any arbitrary lifetime.
+    ///
+    /// Only available with the `std` feature.
+    ///
+    /// ```
+    /// # use pair::{Dependent, HasDependent, Owner, Pair};
+    /// # use std::convert::Infallible;
+    /// struct Csv(String);
+    ///
+    /// impl<'owner> HasDependent<'owner> for Csv {
+    ///     type Dependent = Vec<&'owner str>;
+    /// }
+    ///
+    /// impl Owner for Csv {
+    ///     type Context<'a> = ();
+    ///     type Error = Infallible;
+    ///
+    ///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+    ///         Ok(self.0.split(',').collect())
+    ///     }
+    /// }
+    ///
+    /// let pair = Pair::new(Csv(String::from("1,2,3,4,5,6,7")));
+    /// let sums = pair.par_with_dependent_chunks(3, |fields| {
+    ///     fields.iter().map(|field| field.parse::<u32>().unwrap()).sum::<u32>()
+    /// });
+    /// assert_eq!(sums, [6, 15, 7]);
+    /// ```
+    ///
+    /// # Panics
+    /// If `chunks` is 0. If the closure panics, the panic is propagated once
+    /// all threads have finished.
+    ///
+    /// [`Dependent`]: crate::HasDependent::Dependent
+    #[cfg(feature = "std")]
+    pub fn par_with_dependent_chunks<'self_borrow, F, R>(
+        &'self_borrow self,
+        chunks: usize,
+        f: F,
+    ) -> Vec<R>
+    where
+        F: for<'any> Fn(&'self_borrow [<Dependent<'any, O> as DependentSlice>::Item]) -> R + Sync,
+        R: Send,
+        for<'any> <Dependent<'any, O> as DependentSlice>::Item: Sync,
+    {
+        assert!(chunks > 0, "the number of chunks must be non-zero");
+
+        self.with_dependent(|dependent| {
+            let items = dependent.as_slice();
+            let chunk_size = items.len().div_ceil(chunks).max(1);
+
+            // There's no need to spawn any threads if there's only one chunk
+            if items.len() <= chunk_size {
+                return items.chunks(chunk_size).map(&f).collect();
+            }
+
+            std::thread::scope(|scope| {
+                let workers: Vec<_> = items
+                    .chunks(chunk_size)
+                    .map(|chunk| scope.spawn(|| f(chunk)))
+                    .collect();
+
+                workers
+                    .into_iter()
+                    .map(|worker| {
+                        worker
+                            .join()
+                            .unwrap_or_else(|payload| std::panic::resume_unwind(payload))
+                    })
+                    .collect()
+            })
+        })
+    }
+
+    /// Splits the dependent into (at most) `chunks` contiguous chunks of
+    /// roughly equal size, and calls the given closure with exclusive access to
+    /// each of them in parallel on scoped threads. The values computed by the
+    /// closure are returned in the same order as the chunks.
+    ///
+    /// See the documentation of [`Pair::par_with_dependent_chunks`] for more
+    /// information.
+    ///
+    /// Only available with the `std` feature.
+    ///
+    /// # Panics
+    /// If `chunks` is 0. If the closure panics, the panic is propagated once
+    /// all threads have finished.
+    #[cfg(feature = "std")]
+    pub fn par_with_dependent_chunks_mut<'self_borrow, F, R>(
+        &'self_borrow mut self,
+        chunks: usize,
+        f: F,
+    ) -> Vec<R>
+    where
+        F: for<'any> Fn(&'self_borrow mut [<Dependent<'any, O> as DependentSlice>::Item]) -> R
+            + Sync,
+        R: Send,
+        for<'any> <Dependent<'any, O> as DependentSlice>::Item: Send,
+    {
+        assert!(chunks > 0, "the number of chunks must be non-zero");
+
+        self.with_dependent_mut(|dependent| {
+            let items = dependent.as_mut_slice();
+            let chunk_size = items.len().div_ceil(chunks).max(1);
+
+            // There's no need to spawn any threads if there's only one chunk
+            if items.len() <= chunk_size {
+                return items.chunks_mut(chunk_size).map(&f).collect();
+            }
+
+            std::thread::scope(|scope| {
+                let workers: Vec<_> = items
+                    .chunks_mut(chunk_size)
+                    .map(|chunk| scope.spawn(|| f(chunk)))
+                    .collect();
+
+                workers
+                    .into_iter()
+                    .map(|worker| {
+                        worker
+                            .join()
+                            .unwrap_or_else(|payload| std::panic::resume_unwind(payload))
+                    })
+                    .collect()
+            })
+        })
+    }
+}
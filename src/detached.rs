@@ -0,0 +1,49 @@
+//! Defines the [`CloneDetached`] trait, for cloning a dependent into a form
+//! which no longer borrows from its owner.
+
+use alloc::{borrow::ToOwned, vec::Vec};
+
+/// A dependent which can be cloned into a "detached" form, which no longer
+/// borrows from its owner.
+///
+/// This is used by [`Pair::dependent_cloned`](crate::Pair::dependent_cloned)
+/// to extract owned copies of a dependent. The [`Detached`] type must be the
+/// same for every lifetime of the dependent - typically, it's an "owned
+/// mirror" of the dependent (for example, `Vec<String>` for a `Vec<&str>`).
+///
+/// Implementations are provided for references to any [`ToOwned`] type (such
+/// as `&str` and `&[T]`), as well as [`Vec`]s and [`Option`]s of
+/// [`CloneDetached`] types.
+///
+/// [`Detached`]: CloneDetached::Detached
+pub trait CloneDetached {
+    /// The detached form of this type, which does not borrow from the owner.
+    type Detached;
+
+    /// Clones `self` into its detached form.
+    fn clone_detached(&self) -> Self::Detached;
+}
+
+impl<T: ToOwned + ?Sized> CloneDetached for &T {
+    type Detached = T::Owned;
+
+    fn clone_detached(&self) -> Self::Detached {
+        (**self).to_owned()
+    }
+}
+
+impl<T: CloneDetached> CloneDetached for Vec<T> {
+    type Detached = Vec<T::Detached>;
+
+    fn clone_detached(&self) -> Self::Detached {
+        self.iter().map(CloneDetached::clone_detached).collect()
+    }
+}
+
+impl<T: CloneDetached> CloneDetached for Option<T> {
+    type Detached = Option<T::Detached>;
+
+    fn clone_detached(&self) -> Self::Detached {
+        self.as_ref().map(CloneDetached::clone_detached)
+    }
+}
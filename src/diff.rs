@@ -0,0 +1,58 @@
+//! Defines the [`DependentDiff`] trait, for reporting what changed when a
+//! dependent is rebuilt.
+
+use crate::{Dependent, Owner};
+
+/// An [`Owner`] whose dependents can be compared, to report what changed when
+/// the dependent is rebuilt.
+///
+/// When a pair's dependent is rebuilt (for example, with
+/// [`ContextPair::rebuild_dependent_diff`](crate::ContextPair::rebuild_dependent_diff)),
+/// [`diff`](DependentDiff::diff) is called with the old and new dependents
+/// before the old one is dropped. The resulting [`Diff`](DependentDiff::Diff)
+/// is returned to the caller, so downstream state (such as a UI or a search
+/// index) can be updated incrementally rather than recomputed from scratch.
+///
+/// ```
+/// # use pair::{ContextPair, Dependent, DependentDiff, HasDependent, Owner, StoredContext};
+/// # use std::{cell::RefCell, convert::Infallible};
+/// struct Settings(RefCell<String>);
+///
+/// impl<'owner> HasDependent<'owner> for Settings {
+///     type Dependent = Vec<String>;
+/// }
+///
+/// impl Owner for Settings {
+///     type Context<'a> = ();
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         Ok(self.0.borrow().lines().map(String::from).collect())
+///     }
+/// }
+///
+/// impl DependentDiff for Settings {
+///     // The lines which were added
+///     type Diff = Vec<String>;
+///
+///     fn diff(old: &Vec<String>, new: &Vec<String>) -> Vec<String> {
+///         new.iter().filter(|line| !old.contains(line)).cloned().collect()
+///     }
+/// }
+///
+/// let mut pair = ContextPair::new(Settings(RefCell::new(String::from("a=1"))), ());
+///
+/// pair.owner().0.borrow_mut().push_str("\nb=2");
+/// assert_eq!(pair.rebuild_dependent_diff(), ["b=2"]);
+/// ```
+pub trait DependentDiff: Owner {
+    /// A description of what changed between two dependents.
+    ///
+    /// This can't borrow from either dependent, since the old dependent is
+    /// dropped before the diff is returned.
+    type Diff;
+
+    /// Compares the old and new dependents of a pair which is being rebuilt,
+    /// returning a description of what changed.
+    fn diff<'owner>(old: &Dependent<'owner, Self>, new: &Dependent<'owner, Self>) -> Self::Diff;
+}
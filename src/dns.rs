@@ -0,0 +1,87 @@
+//! Defines [`DnsPacket`], an owner of a raw DNS packet whose dependent is the
+//! parsed packet. Only available with the `dns` feature.
+
+use core::fmt::Debug;
+
+use alloc::boxed::Box;
+
+use simple_dns::{Packet, SimpleDnsError};
+
+use crate::{Dependent, HasDependent, Owner, Pair};
+
+/// The bytes of a raw DNS packet, which can act as the owner of a [`Pair`] with
+/// the parsed [`Packet`] as the dependent.
+///
+/// `simple_dns` parses packets without copying - names, character strings, and
+/// record data all borrow from the packet's buffer. This makes the parsed
+/// packet hard to store or pass between tasks (for example, from a socket
+/// reader to a resolver) on its own. A `DnsPacket` pair keeps the buffer and
+/// the parsed packet together as a single value.
+///
+/// Only available with the `dns` feature.
+///
+/// ```
+/// # use pair::{DnsPacket, DnsPacketPair, Pair};
+/// # use simple_dns::{CLASS, Name, Packet, QCLASS, QTYPE, Question, TYPE};
+/// let mut query = Packet::new_query(42);
+/// query.questions.push(Question::new(
+///     Name::new("example.com").unwrap(),
+///     QTYPE::TYPE(TYPE::A),
+///     QCLASS::CLASS(CLASS::IN),
+///     false,
+/// ));
+/// let bytes = query.build_bytes_vec().unwrap();
+///
+/// let pair: DnsPacketPair = Pair::try_new(DnsPacket::new(bytes)).unwrap();
+/// pair.with_dependent(|packet| {
+///     assert_eq!(packet.id(), 42);
+///     assert_eq!(packet.questions[0].qname.to_string(), "example.com");
+/// });
+/// ```
+pub struct DnsPacket(Box<[u8]>);
+
+/// A [`Pair`] of a [`DnsPacket`] and the parsed [`Packet`].
+///
+/// Only available with the `dns` feature.
+pub type DnsPacketPair = Pair<DnsPacket>;
+
+impl DnsPacket {
+    /// Constructs a new [`DnsPacket`] from the bytes of a raw packet.
+    ///
+    /// The bytes are not parsed until a [`Pair`] is constructed from the
+    /// packet.
+    pub fn new(bytes: impl Into<Box<[u8]>>) -> Self {
+        Self(bytes.into())
+    }
+
+    /// Returns the bytes of the raw packet.
+    pub fn bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Consumes the [`DnsPacket`], returning the bytes of the raw packet.
+    pub fn into_bytes(self) -> Box<[u8]> {
+        self.0
+    }
+}
+
+impl Debug for DnsPacket {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DnsPacket")
+            .field("len", &self.0.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'owner> HasDependent<'owner> for DnsPacket {
+    type Dependent = Packet<'owner>;
+}
+
+impl Owner for DnsPacket {
+    type Context<'a> = ();
+    type Error = SimpleDnsError;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Packet::parse(&self.0)
+    }
+}
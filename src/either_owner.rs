@@ -0,0 +1,81 @@
+//! Implements [`Owner`] for [`Either`], for owners which may be one of two
+//! types. Only available with the `either` feature.
+
+use either::Either;
+
+use crate::{Dependent, HasDependent, Owner};
+
+impl<'owner, L: Owner, R: Owner> HasDependent<'owner> for Either<L, R> {
+    type Dependent = Either<Dependent<'owner, L>, Dependent<'owner, R>>;
+}
+
+/// An [`Either`] of two owners with the same context and error types is itself
+/// an owner, whose dependent is an `Either` of their dependents.
+///
+/// This allows a single [`Pair`](crate::Pair) type to hold either of two kinds
+/// of owner (for example, a document which was either loaded from disk or
+/// generated in memory), without defining a wrapper enum and owner by hand.
+///
+/// Only available with the `either` feature.
+///
+/// ```
+/// # use pair::{Dependent, HasDependent, Owner, Pair};
+/// # use std::convert::Infallible;
+/// # use either::Either;
+/// struct Loaded(Vec<u8>);
+/// struct Generated(String);
+///
+/// impl<'owner> HasDependent<'owner> for Loaded {
+///     type Dependent = &'owner [u8];
+/// }
+///
+/// impl Owner for Loaded {
+///     type Context<'a> = ();
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         Ok(&self.0)
+///     }
+/// }
+///
+/// impl<'owner> HasDependent<'owner> for Generated {
+///     type Dependent = &'owner str;
+/// }
+///
+/// impl Owner for Generated {
+///     type Context<'a> = ();
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         Ok(&self.0)
+///     }
+/// }
+///
+/// let pairs: [Pair<Either<Loaded, Generated>>; 2] = [
+///     Pair::new(Either::Left(Loaded(vec![1, 2, 3]))),
+///     Pair::new(Either::Right(Generated(String::from("hi")))),
+/// ];
+///
+/// let lens = pairs.map(|pair| {
+///     pair.with_dependent(|dependent| dependent.as_ref().either(|bytes| bytes.len(), |text| text.len()))
+/// });
+/// assert_eq!(lens, [3, 2]);
+/// ```
+impl<L, R> Owner for Either<L, R>
+where
+    L: Owner,
+    R: for<'any> Owner<Context<'any> = L::Context<'any>, Error = L::Error>,
+{
+    type Context<'a> = L::Context<'a>;
+    type Error = L::Error;
+
+    fn make_dependent(
+        &self,
+        context: Self::Context<'_>,
+    ) -> Result<Dependent<'_, Self>, Self::Error> {
+        match self {
+            Self::Left(owner) => owner.make_dependent(context).map(Either::Left),
+            Self::Right(owner) => owner.make_dependent(context).map(Either::Right),
+        }
+    }
+}
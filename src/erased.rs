@@ -0,0 +1,187 @@
+//! Defines [`ErasedSendPair`], a type-erased [`Pair`] which is
+//! `Send + Sync + 'static`, for embedding pairs in error types.
+
+use core::{any::Any, fmt::Debug};
+
+use alloc::boxed::Box;
+
+use crate::{Dependent, Owner, Pair};
+
+/// A type-erased [`Pair`], which is always `Send + Sync + 'static`.
+///
+/// Error types are commonly required to be `Send + Sync + 'static` (for
+/// example, to be converted into a `Box<dyn Error + Send + Sync>`), and usually
+/// can't be generic over every owner they might carry. A [`Pair`] is
+/// `'static` whenever its owner is, and `Send`/`Sync` whenever both its owner
+/// and dependent are - so any such pair (for example, a source file along with
+/// its parsed tokens) can be wrapped in an `ErasedSendPair` and embedded in an
+/// error, then recovered with [`downcast_ref`](ErasedSendPair::downcast_ref)
+/// or [`downcast`](ErasedSendPair::downcast) when reporting it.
+///
+/// Neither the owner nor the dependent need implement [`Debug`] - the `Debug`
+/// implementation only prints the names of their types (see
+/// [`owner_type_name`](ErasedSendPair::owner_type_name) and
+/// [`dependent_type_name`](ErasedSendPair::dependent_type_name)).
+///
+/// ```
+/// # use pair::{Dependent, ErasedSendPair, HasDependent, Owner, Pair};
+/// # use std::{convert::Infallible, fmt};
+/// struct Source(String);
+///
+/// impl<'owner> HasDependent<'owner> for Source {
+///     type Dependent = Vec<&'owner str>;
+/// }
+///
+/// impl Owner for Source {
+///     type Context<'a> = ();
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         Ok(self.0.lines().collect())
+///     }
+/// }
+///
+/// #[derive(Debug)]
+/// struct SyntaxError {
+///     line: usize,
+///     source: ErasedSendPair,
+/// }
+///
+/// impl fmt::Display for SyntaxError {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         let line = self
+///             .source
+///             .downcast_ref::<Source>()
+///             .and_then(|source| source.with_dependent(|lines| lines.get(self.line).copied()))
+///             .unwrap_or("<unknown>");
+///
+///         write!(f, "syntax error on line {}: {line}", self.line + 1)
+///     }
+/// }
+///
+/// impl std::error::Error for SyntaxError {}
+///
+/// let source = Pair::new(Source(String::from("fn main() {\n    let = 5;\n}")));
+/// let err: Box<dyn std::error::Error + Send + Sync> = Box::new(SyntaxError {
+///     line: 1,
+///     source: ErasedSendPair::new(source),
+/// });
+/// assert_eq!(err.to_string(), "syntax error on line 2:     let = 5;");
+/// ```
+pub struct ErasedSendPair {
+    pair: Box<dyn ErasedPair>,
+}
+
+impl ErasedSendPair {
+    /// Erases the type of the given pair.
+    pub fn new<O: Owner + ?Sized + 'static>(pair: Pair<O>) -> Self
+    where
+        Pair<O>: Send + Sync,
+    {
+        Self {
+            pair: Box::new(pair),
+        }
+    }
+
+    /// Returns `true` if the erased pair is a `Pair<O>`.
+    pub fn is<O: Owner + ?Sized + 'static>(&self) -> bool {
+        self.pair.as_any().is::<Pair<O>>()
+    }
+
+    /// Returns a reference to the erased pair if it is a `Pair<O>`, or
+    /// [`None`] if it isn't.
+    pub fn downcast_ref<O: Owner + ?Sized + 'static>(&self) -> Option<&Pair<O>> {
+        self.pair.as_any().downcast_ref()
+    }
+
+    /// Returns a mutable reference to the erased pair if it is a `Pair<O>`, or
+    /// [`None`] if it isn't.
+    pub fn downcast_mut<O: Owner + ?Sized + 'static>(&mut self) -> Option<&mut Pair<O>> {
+        self.pair.as_any_mut().downcast_mut()
+    }
+
+    /// Recovers the erased pair if it is a `Pair<O>`.
+    ///
+    /// # Errors
+    /// If the erased pair is not a `Pair<O>`, in which case `self` is returned
+    /// unchanged.
+    pub fn downcast<O: Owner + ?Sized + 'static>(self) -> Result<Pair<O>, Self> {
+        if !self.is::<O>() {
+            return Err(self);
+        }
+
+        match self.pair.into_any().downcast() {
+            Ok(pair) => Ok(*pair),
+            // We just checked the type of the erased pair
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// Returns the name of the erased pair's owner type, for diagnostic
+    /// purposes. See [`core::any::type_name`] for caveats.
+    pub fn owner_type_name(&self) -> &'static str {
+        self.pair.owner_type_name()
+    }
+
+    /// Returns the name of the erased pair's dependent type, for diagnostic
+    /// purposes. Lifetimes are not included in the name. See
+    /// [`core::any::type_name`] for other caveats.
+    ///
+    /// Together with [`owner_type_name`](ErasedSendPair::owner_type_name), this
+    /// is useful for logging heterogeneous collections of erased pairs.
+    pub fn dependent_type_name(&self) -> &'static str {
+        self.pair.dependent_type_name()
+    }
+}
+
+impl<O: Owner + ?Sized + 'static> From<Pair<O>> for ErasedSendPair
+where
+    Pair<O>: Send + Sync,
+{
+    fn from(pair: Pair<O>) -> Self {
+        Self::new(pair)
+    }
+}
+
+impl Debug for ErasedSendPair {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ErasedSendPair")
+            .field("owner_type", &self.owner_type_name())
+            .field("dependent_type", &self.dependent_type_name())
+            .finish()
+    }
+}
+
+/// The object-safe interface of a type-erased `Pair<O>`.
+trait ErasedPair: Any + Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+    fn owner_type_name(&self) -> &'static str;
+    fn dependent_type_name(&self) -> &'static str;
+}
+
+impl<O: Owner + ?Sized + 'static> ErasedPair for Pair<O>
+where
+    Self: Send + Sync,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    fn owner_type_name(&self) -> &'static str {
+        core::any::type_name::<O>()
+    }
+
+    fn dependent_type_name(&self) -> &'static str {
+        core::any::type_name::<Dependent<'static, O>>()
+    }
+}
@@ -0,0 +1,145 @@
+//! Defines [`ConstructionError`], a structured error for failures when
+//! constructing a [`Pair`](crate::Pair).
+
+use core::{
+    fmt::{Debug, Display},
+    panic::Location,
+};
+
+/// An error which occurred while constructing a [`Pair`](crate::Pair).
+///
+/// Contains both the error returned by
+/// [`make_dependent`](crate::Owner::make_dependent) and the owner it was
+/// attempting to borrow from.
+///
+/// The fallible constructors of `Pair` return a tuple of the owner and the
+/// error on failure, which can be converted into a `ConstructionError` with
+/// [`From`]. Unlike the tuple, `ConstructionError` implements [`Display`], and
+/// implements [`Error`](core::error::Error) whenever the underlying error does
+/// (with [`source`](core::error::Error::source) returning the underlying
+/// error). This allows construction failures to integrate with standard error
+/// reporting - for example, propagating them with `?` into a
+/// `Box<dyn Error>`.
+///
+/// ```
+/// # use pair::{ConstructionError, Dependent, HasDependent, Owner, Pair};
+/// # use std::num::ParseIntError;
+/// # struct Number(String);
+/// # impl HasDependent<'_> for Number {
+/// #     type Dependent = u32;
+/// # }
+/// # impl Owner for Number {
+/// #     type Context<'a> = ();
+/// #     type Error = ParseIntError;
+/// #     fn make_dependent(&self, (): ()) -> Result<u32, ParseIntError> {
+/// #         self.0.parse()
+/// #     }
+/// # }
+/// fn parse(text: String) -> Result<Pair<Number>, ConstructionError<Number, ParseIntError>> {
+///     Ok(Pair::try_new(Number(text))?)
+/// }
+///
+/// fn run() -> Result<(), Box<dyn std::error::Error>> {
+///     let pair = parse(String::from("123"))?;
+///     assert_eq!(pair.with_dependent(|n| *n), 123);
+///
+///     parse(String::from("abc"))?;
+///     unreachable!()
+/// }
+///
+/// let err = run().unwrap_err();
+/// assert!(err.source().unwrap().is::<ParseIntError>());
+/// ```
+pub struct ConstructionError<O, E> {
+    owner: O,
+    error: E,
+    location: &'static Location<'static>,
+}
+
+impl<O, E> ConstructionError<O, E> {
+    /// Constructs a new [`ConstructionError`] from an owner and the error
+    /// returned while constructing its dependent.
+    ///
+    /// The location of the caller is captured, and available through
+    /// [`location`](ConstructionError::location).
+    #[track_caller]
+    pub fn new(owner: O, error: E) -> Self {
+        Self {
+            owner,
+            error,
+            location: Location::caller(),
+        }
+    }
+
+    /// Returns a reference to the owner.
+    pub fn owner(&self) -> &O {
+        &self.owner
+    }
+
+    /// Returns a reference to the error.
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// Returns the location in the source code where this
+    /// [`ConstructionError`] was created.
+    ///
+    /// When created with [`From`] through the `?` operator, this is the
+    /// location of the `?`. Note that converting with a function that is not
+    /// [`#[track_caller]`](https://doc.rust-lang.org/reference/attributes/codegen.html#the-track_caller-attribute)
+    /// (such as passing `ConstructionError::from` to [`Result::map_err`])
+    /// will capture a location inside that function instead.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// Consumes the [`ConstructionError`], returning the owner.
+    pub fn into_owner(self) -> O {
+        self.owner
+    }
+
+    /// Consumes the [`ConstructionError`], returning the error.
+    pub fn into_error(self) -> E {
+        self.error
+    }
+
+    /// Consumes the [`ConstructionError`], returning both the owner and the
+    /// error.
+    pub fn into_parts(self) -> (O, E) {
+        (self.owner, self.error)
+    }
+}
+
+impl<O, E> From<(O, E)> for ConstructionError<O, E> {
+    #[track_caller]
+    fn from((owner, error): (O, E)) -> Self {
+        Self::new(owner, error)
+    }
+}
+
+/// The [`Debug`] implementation for [`ConstructionError`] does not require (or
+/// display) a `Debug` implementation for the owner, which may be large.
+impl<O, E: Debug> Debug for ConstructionError<O, E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ConstructionError")
+            .field("error", &self.error)
+            .field("location", &self.location)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The [`Display`] implementation for [`ConstructionError`] does not include
+/// the underlying error, which is instead available through
+/// [`Error::source`](core::error::Error::source) (or
+/// [`error`](ConstructionError::error)).
+impl<O, E> Display for ConstructionError<O, E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("failed to construct the dependent of a pair")
+    }
+}
+
+impl<O, E: core::error::Error + 'static> core::error::Error for ConstructionError<O, E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
@@ -0,0 +1,226 @@
+//! Defines [`FailPoints`], for injecting failures into pair construction and
+//! destruction in tests. Only available with the `failpoints` feature.
+
+use core::{cell::RefCell, marker::PhantomData};
+
+use alloc::vec::Vec;
+
+use crate::Owner;
+
+/// A point at which [`FailPoints`] can inject a failure.
+///
+/// Only available with the `failpoints` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FailPoint {
+    /// A call to [`Owner::make_dependent`] of an owner which checks for
+    /// injected failures with [`FailPointOwner::check_fail_point`].
+    ///
+    /// When triggered, `check_fail_point` returns the error from
+    /// [`FailPointOwner::injected_error`], which `make_dependent` should
+    /// return (with `?`) instead of constructing a dependent.
+    MakeDependent,
+
+    /// The allocation of a new pair's dependent on the heap.
+    ///
+    /// When triggered, this simulates the allocation failing by panicking
+    /// (like [`alloc::alloc::handle_alloc_error`] would with `-Zoom=panic`),
+    /// after dropping the dependent. The owner is dropped during unwinding.
+    Allocation,
+
+    /// Dropping a pair's dependent, when the pair is dropped (or consumed, such
+    /// as by [`Pair::into_owner`](crate::Pair::into_owner)).
+    ///
+    /// When triggered, this simulates the dependent's drop panicking by
+    /// panicking right after dropping it. The owner is dropped during
+    /// unwinding.
+    DropDependent,
+}
+
+impl FailPoint {
+    fn index(self) -> usize {
+        match self {
+            Self::MakeDependent => 0,
+            Self::Allocation => 1,
+            Self::DropDependent => 2,
+        }
+    }
+}
+
+/// An [`Owner`] which supports injecting [`make_dependent`](Owner::make_dependent)
+/// errors with [`FailPoints`].
+///
+/// Construction code is generic over every owner, so it has no way to make up
+/// an owner's error on its own. Instead, an owner opts in by implementing this
+/// trait, and calling [`check_fail_point`](FailPointOwner::check_fail_point)
+/// at the start of its `make_dependent` - see the documentation of
+/// [`FailPoints`] for an example.
+///
+/// Only available with the `failpoints` feature.
+pub trait FailPointOwner: Owner {
+    /// Returns the error to return from
+    /// [`make_dependent`](Owner::make_dependent) when a failure is injected at
+    /// [`FailPoint::MakeDependent`].
+    fn injected_error(&self) -> Self::Error;
+
+    /// Records a call to [`make_dependent`](Owner::make_dependent), returning
+    /// the [`injected_error`](FailPointOwner::injected_error) if a failure
+    /// should be injected.
+    ///
+    /// # Errors
+    /// If a failure is scheduled for this call at
+    /// [`FailPoint::MakeDependent`].
+    fn check_fail_point(&self) -> Result<(), Self::Error> {
+        if hit(FailPoint::MakeDependent) {
+            return Err(self.injected_error());
+        }
+
+        Ok(())
+    }
+}
+
+/// Deterministically injects failures into pairs at specified [`FailPoint`]s,
+/// for exercising error handling and panic safety in tests.
+///
+/// Creating a `FailPoints` starts counting how many times each fail point is
+/// reached on the current thread. Failures can then be scheduled with
+/// [`fail_at`](FailPoints::fail_at), for a specific call count of a specific
+/// fail point. Dropping the `FailPoints` stops counting, and cancels any
+/// failures which weren't triggered.
+///
+/// Fail points are tracked per thread, so tests running in parallel don't
+/// interfere with each other - but pairs constructed or dropped on other
+/// threads (such as by [`Pair::try_new_batch`](crate::Pair::try_new_batch))
+/// are not affected.
+///
+/// Only available with the `failpoints` feature.
+///
+/// ```
+/// # use pair::{Dependent, FailPoint, FailPointOwner, FailPoints, HasDependent, Owner, Pair};
+/// # use std::num::ParseIntError;
+/// struct Numbers(String);
+///
+/// impl<'owner> HasDependent<'owner> for Numbers {
+///     type Dependent = Vec<u32>;
+/// }
+///
+/// impl Owner for Numbers {
+///     type Context<'a> = ();
+///     type Error = ParseIntError;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         self.check_fail_point()?;
+///         self.0.split(',').map(str::parse).collect()
+///     }
+/// }
+///
+/// impl FailPointOwner for Numbers {
+///     fn injected_error(&self) -> Self::Error {
+///         "not a number".parse::<u32>().unwrap_err()
+///     }
+/// }
+///
+/// let failpoints = FailPoints::new();
+/// failpoints.fail_at(FailPoint::MakeDependent, 2);
+///
+/// assert!(Pair::try_new(Numbers(String::from("1,2"))).is_ok());
+/// assert!(Pair::try_new(Numbers(String::from("1,2"))).is_err());
+/// assert!(Pair::try_new(Numbers(String::from("1,2"))).is_ok());
+/// assert_eq!(failpoints.calls(FailPoint::MakeDependent), 3);
+/// ```
+#[derive(Debug)]
+pub struct FailPoints {
+    // The fail points are tracked per thread, so a `FailPoints` must not be
+    // sent to (and dropped on) another thread
+    _not_send: PhantomData<*const ()>,
+}
+
+impl FailPoints {
+    /// Starts counting how many times each [`FailPoint`] is reached on the
+    /// current thread, from zero.
+    ///
+    /// If another `FailPoints` already exists on this thread, its counts and
+    /// scheduled failures are reset.
+    pub fn new() -> Self {
+        STATE.with_borrow_mut(|state| *state = Some(State::default()));
+
+        Self {
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Schedules a failure at the given (one-based) call count of the given
+    /// [`FailPoint`] on the current thread.
+    ///
+    /// For example, `fail_at(FailPoint::MakeDependent, 2)` makes the second
+    /// call to `make_dependent` (counting from the creation of this
+    /// `FailPoints`) fail. Calls which have already happened can't fail.
+    pub fn fail_at(&self, point: FailPoint, call: usize) -> &Self {
+        STATE.with_borrow_mut(|state| {
+            if let Some(state) = state {
+                state.scheduled.push((point, call));
+            }
+        });
+
+        self
+    }
+
+    /// Returns how many times the given [`FailPoint`] has been reached on the
+    /// current thread since this `FailPoints` was created.
+    pub fn calls(&self, point: FailPoint) -> usize {
+        STATE.with_borrow(|state| state.as_ref().map_or(0, |state| state.calls[point.index()]))
+    }
+
+    /// Returns the number of scheduled failures which haven't been triggered
+    /// yet.
+    pub fn pending(&self) -> usize {
+        STATE.with_borrow(|state| state.as_ref().map_or(0, |state| state.scheduled.len()))
+    }
+}
+
+impl Default for FailPoints {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for FailPoints {
+    fn drop(&mut self) {
+        STATE.with_borrow_mut(|state| *state = None);
+    }
+}
+
+#[derive(Default)]
+struct State {
+    calls: [usize; 3],
+    scheduled: Vec<(FailPoint, usize)>,
+}
+
+std::thread_local! {
+    static STATE: RefCell<Option<State>> = const { RefCell::new(None) };
+}
+
+/// Records that the given fail point was reached, returning `true` if a failure
+/// should be injected.
+pub(crate) fn hit(point: FailPoint) -> bool {
+    // `try_with` rather than `with`, since pairs may be dropped while thread
+    // locals are being destroyed
+    STATE
+        .try_with(|state| {
+            let mut state = state.borrow_mut();
+            let Some(state) = state.as_mut() else {
+                return false;
+            };
+
+            state.calls[point.index()] += 1;
+            let call = state.calls[point.index()];
+
+            let scheduled = state
+                .scheduled
+                .iter()
+                .position(|&scheduled| scheduled == (point, call));
+            scheduled
+                .map(|index| state.scheduled.swap_remove(index))
+                .is_some()
+        })
+        .unwrap_or(false)
+}
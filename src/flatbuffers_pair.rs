@@ -0,0 +1,123 @@
+//! Defines [`FlatbufferMessage`], an owner of the bytes of a flatbuffers
+//! message whose dependent is the message's verified root. Only available with
+//! the `flatbuffers` feature.
+
+use core::{fmt::Debug, marker::PhantomData};
+
+use alloc::boxed::Box;
+
+use flatbuffers::{Follow, InvalidFlatbuffer, Verifiable};
+
+use crate::{Dependent, HasDependent, Owner, Pair};
+
+/// Names the root type of a flatbuffers message, for use with
+/// [`FlatbufferMessage`].
+///
+/// The root types generated by `flatc` borrow the message's buffer (such as
+/// `Monster<'buf>`), so they can't be named without a lifetime. Implement this
+/// trait for a marker type to name the root type for any lifetime of the
+/// buffer.
+///
+/// Only available with the `flatbuffers` feature.
+///
+/// ```ignore
+/// struct MonsterRoot;
+///
+/// impl FlatbufferRoot for MonsterRoot {
+///     type Root<'buf> = Monster<'buf>;
+/// }
+/// ```
+pub trait FlatbufferRoot {
+    /// The root type of the message, borrowing from a buffer with the lifetime
+    /// `'buf`.
+    type Root<'buf>: Follow<'buf> + Verifiable + 'buf;
+}
+
+/// The bytes of a flatbuffers message, which can act as the owner of a
+/// [`Pair`] with the message's verified root (such as a table generated by
+/// `flatc`) as the dependent.
+///
+/// The root is verified once when the pair is constructed, and borrows the
+/// buffer thereafter - so the message can be passed around (for example,
+/// between the layers of an RPC service) as a single value, without
+/// re-verifying it or keeping its buffer alive separately.
+///
+/// Only available with the `flatbuffers` feature.
+///
+/// ```
+/// # use pair::{FlatbufferMessage, FlatbufferPair, FlatbufferRoot, Pair};
+/// # use flatbuffers::FlatBufferBuilder;
+/// // A message whose root is a string (usually, this would be a table)
+/// struct Greeting;
+///
+/// impl FlatbufferRoot for Greeting {
+///     type Root<'buf> = &'buf str;
+/// }
+///
+/// let mut builder = FlatBufferBuilder::new();
+/// let root = builder.create_string("hello");
+/// builder.finish_minimal(root);
+///
+/// let message = FlatbufferMessage::<Greeting>::new(builder.finished_data());
+/// let Ok(pair): Result<FlatbufferPair<Greeting>, _> = Pair::try_new(message) else {
+///     panic!("the message should be valid");
+/// };
+/// assert_eq!(pair.with_dependent(|greeting| *greeting), "hello");
+///
+/// // Invalid messages are rejected during construction
+/// let message = FlatbufferMessage::<Greeting>::new([0xFF; 3].as_slice());
+/// assert!(Pair::try_new(message).is_err());
+/// ```
+pub struct FlatbufferMessage<R: FlatbufferRoot> {
+    bytes: Box<[u8]>,
+    root: PhantomData<fn() -> R>,
+}
+
+/// A [`Pair`] of a [`FlatbufferMessage`] and its verified root.
+///
+/// Only available with the `flatbuffers` feature.
+pub type FlatbufferPair<R> = Pair<FlatbufferMessage<R>>;
+
+impl<R: FlatbufferRoot> FlatbufferMessage<R> {
+    /// Constructs a new [`FlatbufferMessage`] from the bytes of a message.
+    ///
+    /// The bytes are not verified until a [`Pair`] is constructed from the
+    /// message.
+    pub fn new(bytes: impl Into<Box<[u8]>>) -> Self {
+        Self {
+            bytes: bytes.into(),
+            root: PhantomData,
+        }
+    }
+
+    /// Returns the bytes of the message.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Consumes the [`FlatbufferMessage`], returning the bytes of the message.
+    pub fn into_bytes(self) -> Box<[u8]> {
+        self.bytes
+    }
+}
+
+impl<R: FlatbufferRoot> Debug for FlatbufferMessage<R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FlatbufferMessage")
+            .field("len", &self.bytes.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'owner, R: FlatbufferRoot> HasDependent<'owner> for FlatbufferMessage<R> {
+    type Dependent = <R::Root<'owner> as Follow<'owner>>::Inner;
+}
+
+impl<R: FlatbufferRoot> Owner for FlatbufferMessage<R> {
+    type Context<'a> = ();
+    type Error = InvalidFlatbuffer;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        flatbuffers::root::<R::Root<'_>>(&self.bytes)
+    }
+}
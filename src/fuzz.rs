@@ -0,0 +1,164 @@
+//! Fuzzing helpers for checking [`Owner`] implementations against arbitrary
+//! input. Only available with the `fuzz` feature.
+//!
+//! Each helper takes the raw bytes provided by a fuzzer, builds an owner from
+//! them, constructs a [`Pair`], exercises its accessors, and drops it. They're
+//! intended to be called directly from a `cargo-fuzz` (`libfuzzer-sys`) fuzz
+//! target:
+//!
+//! ```ignore
+//! #![no_main]
+//!
+//! libfuzzer_sys::fuzz_target!(|data: &[u8]| {
+//!     pair::fuzz::fuzz_owner::<my_crate::MyOwner>(data);
+//! });
+//! ```
+//!
+//! Every helper panics if the owner is found misbehaving (so the fuzzer
+//! reports the input as a crash) - see [`fuzz_owner_with_context`] for what is
+//! checked. Any panic in [`make_dependent`](Owner::make_dependent), or in the
+//! owner's or dependent's [`Debug`] or [`Drop`] implementations, is reported
+//! the same way.
+
+use core::fmt::Debug;
+use std::io::Write;
+
+use arbitrary::{Arbitrary, Unstructured};
+
+use crate::{Dependent, Owner, Pair};
+
+/// Fuzzes an [`Owner`] implementation (with a `()` context), using its
+/// [`Arbitrary`] implementation to build owners from the fuzzer's bytes.
+///
+/// See [`fuzz_owner_with_context`] for what is checked.
+///
+/// Only available with the `fuzz` feature.
+///
+/// ```
+/// # use pair::{Dependent, HasDependent, Owner, fuzz::fuzz_owner};
+/// # use arbitrary::{Arbitrary, Unstructured};
+/// # use std::num::ParseIntError;
+/// #[derive(Debug)]
+/// struct Numbers(String);
+///
+/// impl<'a> Arbitrary<'a> for Numbers {
+///     fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+///         u.arbitrary().map(Self)
+///     }
+/// }
+///
+/// impl<'owner> HasDependent<'owner> for Numbers {
+///     type Dependent = Vec<(&'owner str, u8)>;
+/// }
+///
+/// impl Owner for Numbers {
+///     type Context<'a> = ();
+///     type Error = ParseIntError;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         self.0
+///             .split(',')
+///             .map(|field| Ok((field, field.parse()?)))
+///             .collect()
+///     }
+/// }
+///
+/// // In a fuzz target, these bytes would come from the fuzzer
+/// fuzz_owner::<Numbers>(b"\x051,2,3");
+/// fuzz_owner::<Numbers>(b"\xff\x00\x12");
+/// ```
+///
+/// # Panics
+/// If the owner is found misbehaving, or if any of its (or its dependent's)
+/// trait implementations panic.
+#[track_caller]
+pub fn fuzz_owner<O>(data: &[u8])
+where
+    O: for<'a> Owner<Context<'a> = ()> + for<'a> Arbitrary<'a> + Debug,
+    for<'any> Dependent<'any, O>: Debug,
+{
+    fuzz_owner_with(data, |data| {
+        O::arbitrary_take_rest(Unstructured::new(data)).ok()
+    });
+}
+
+/// Fuzzes an [`Owner`] implementation (with a `()` context), using
+/// `make_owner` to build owners from the fuzzer's bytes.
+///
+/// Inputs for which `make_owner` returns [`None`] are skipped. See
+/// [`fuzz_owner_with_context`] for what is checked.
+///
+/// Only available with the `fuzz` feature.
+///
+/// # Panics
+/// If the owner is found misbehaving, or if any of its (or its dependent's)
+/// trait implementations panic.
+#[track_caller]
+pub fn fuzz_owner_with<O, F>(data: &[u8], make_owner: F)
+where
+    O: for<'a> Owner<Context<'a> = ()> + Debug,
+    for<'any> Dependent<'any, O>: Debug,
+    F: FnOnce(&[u8]) -> Option<O>,
+{
+    fuzz_owner_with_context(data, make_owner, || ());
+}
+
+/// Fuzzes an [`Owner`] implementation, using `make_owner` to build owners from
+/// the fuzzer's bytes.
+///
+/// Inputs for which `make_owner` returns [`None`] are skipped. Each time a
+/// context is needed, it's produced by calling `context`.
+///
+/// For each owner, this:
+/// - Constructs a [`Pair`] from it (if construction fails, the returned owner
+///   is formatted with [`Debug`] and dropped)
+/// - Formats the owner and dependent with `Debug`
+/// - Rebuilds the dependent, checking that
+///   [`make_dependent`](Owner::make_dependent) succeeds again (since it
+///   should be deterministic)
+/// - Converts the pair back into its owner, and drops it
+///
+/// Only available with the `fuzz` feature.
+///
+/// # Panics
+/// If `make_dependent` fails when rebuilding a dependent which was previously
+/// constructed successfully, or if any of the owner's (or its dependent's)
+/// trait implementations panic.
+#[track_caller]
+pub fn fuzz_owner_with_context<O, F, C>(data: &[u8], make_owner: F, context: C)
+where
+    O: Owner + Debug,
+    for<'any> Dependent<'any, O>: Debug,
+    F: FnOnce(&[u8]) -> Option<O>,
+    C: Fn() -> O::Context<'static>,
+{
+    let Some(owner) = make_owner(data) else {
+        return;
+    };
+
+    let mut pair = match Pair::try_new_with_context(owner, context()) {
+        Ok(pair) => pair,
+        Err((owner, _)) => {
+            exercise_debug(&owner);
+            return;
+        }
+    };
+
+    exercise_debug(pair.owner());
+    pair.with_dependent(|dependent| exercise_debug(dependent));
+
+    assert!(
+        pair.try_replace_dependent(context()).is_ok(),
+        "make_dependent returned an error when rebuilding a dependent which was previously constructed successfully",
+    );
+    pair.with_dependent(|dependent| exercise_debug(dependent));
+
+    drop(pair.into_boxed_owner());
+}
+
+/// Formats the given value with [`Debug`], discarding the output.
+fn exercise_debug<T: Debug + ?Sized>(value: &T) {
+    // Writing to a sink never fails (unless `Debug` itself returns an error,
+    // which is allowed)
+    let _ = write!(std::io::sink(), "{value:#?}");
+}
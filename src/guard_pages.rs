@@ -0,0 +1,215 @@
+//! Defines [`GuardedBytes`], a read-only byte buffer surrounded by guard pages.
+//! Only available with the `guard-pages` feature, on unix.
+
+use core::{fmt::Debug, ops::Deref, ptr::NonNull};
+
+/// A read-only buffer of bytes, allocated in its own memory mapping with an
+/// inaccessible guard page on either side.
+///
+/// `GuardedBytes` is intended to be held by the owner of a [`Pair`](crate::Pair)
+/// whose dependent is produced by an untrusted or hard-to-audit parser. The
+/// bytes are placed so that they end exactly at the trailing guard page, so an
+/// out-of-bounds read past the end (the most common parser bug) faults
+/// immediately, rather than silently reading adjacent heap data. Any slack
+/// before the bytes is zeroed and followed by the leading guard page, so reads
+/// before the start can never observe other data either. The bytes are also
+/// protected as read-only, so stray writes fault as well.
+///
+/// Each buffer takes at least two extra pages of address space, and a system
+/// call to map and unmap, so this is only suitable for fairly large or
+/// security-sensitive buffers.
+///
+/// Guard pages only catch accesses outside of the buffer - they don't make the
+/// buffer's address any less predictable. The mapping is placed wherever the
+/// operating system chooses (randomized, if it applies address space layout
+/// randomization to `mmap`), and `pair` does nothing to change that.
+///
+/// Only available with the `guard-pages` feature, on unix.
+///
+/// ```
+/// # use pair::{Dependent, GuardedBytes, HasDependent, Owner, Pair};
+/// # use std::convert::Infallible;
+/// struct Packet(GuardedBytes);
+///
+/// impl<'owner> HasDependent<'owner> for Packet {
+///     type Dependent = Vec<&'owner [u8]>;
+/// }
+///
+/// impl Owner for Packet {
+///     type Context<'a> = ();
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         Ok(self.0.split(|&byte| byte == 0).collect())
+///     }
+/// }
+///
+/// # // Miri can't map guard pages with `mmap` and `mprotect`
+/// # #[cfg(miri)] fn main() {}
+/// # #[cfg(not(miri))] fn main() {
+/// let bytes = GuardedBytes::new(b"header\0body").unwrap();
+/// let pair = Pair::new(Packet(bytes));
+/// assert_eq!(pair.with_dependent(|fields| fields[1]), b"body");
+/// # }
+/// ```
+pub struct GuardedBytes {
+    // The start of the mapping, including the guard pages
+    map: NonNull<u8>,
+    map_len: usize,
+
+    // The start of the bytes, within the mapping
+    data: NonNull<u8>,
+    len: usize,
+}
+
+// SAFETY: `GuardedBytes` exclusively owns its mapping, which is never mutated
+// after construction, so it is safe to send and share between threads like a
+// `Box<[u8]>`.
+unsafe impl Send for GuardedBytes {}
+// SAFETY: see above.
+unsafe impl Sync for GuardedBytes {}
+
+impl GuardedBytes {
+    /// Copies the given bytes into a new mapping, between two guard pages.
+    ///
+    /// # Errors
+    /// If mapping the memory, or changing its protection, fails.
+    pub fn new(bytes: &[u8]) -> std::io::Result<Self> {
+        // SAFETY: `sysconf` has no preconditions.
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+        let page_size = usize::try_from(page_size).map_err(|_| std::io::Error::last_os_error())?;
+
+        let too_large = || {
+            std::io::Error::new(
+                std::io::ErrorKind::OutOfMemory,
+                "the bytes are too large to map",
+            )
+        };
+        let data_pages_len = bytes
+            .len()
+            .checked_next_multiple_of(page_size)
+            .ok_or_else(too_large)?;
+        let map_len = data_pages_len
+            .checked_add(2 * page_size)
+            .ok_or_else(too_large)?;
+
+        // SAFETY: this creates a new private, anonymous mapping, which can't
+        // alias any existing memory.
+        let map = unsafe {
+            libc::mmap(
+                core::ptr::null_mut(),
+                map_len,
+                libc::PROT_NONE,
+                libc::MAP_PRIVATE | libc::MAP_ANON,
+                -1,
+                0,
+            )
+        };
+        if map == libc::MAP_FAILED {
+            return Err(std::io::Error::last_os_error());
+        }
+        let map = NonNull::new(map.cast::<u8>()).ok_or_else(std::io::Error::last_os_error)?;
+
+        // From here on, dropping `guarded` unmaps the mapping
+        let mut guarded = Self {
+            map,
+            map_len,
+            data: map,
+            len: 0,
+        };
+        if data_pages_len == 0 {
+            return Ok(guarded);
+        }
+
+        // SAFETY: the mapping is `data_pages_len + 2 * page_size` bytes long,
+        // so the pages between the guard pages are in bounds.
+        let data_pages = unsafe { map.add(page_size) };
+        // SAFETY: these pages are part of our new mapping, which nothing else
+        // has access to.
+        unsafe {
+            mprotect(
+                data_pages,
+                data_pages_len,
+                libc::PROT_READ | libc::PROT_WRITE,
+            )?;
+        }
+
+        // SAFETY: `bytes.len() <= data_pages_len`, so this is within the data
+        // pages.
+        let data = unsafe { data_pages.add(data_pages_len - bytes.len()) };
+        // SAFETY: `data` is valid for writes of `bytes.len()` bytes, and can't
+        // overlap `bytes`, since the mapping is new.
+        unsafe { core::ptr::copy_nonoverlapping(bytes.as_ptr(), data.as_ptr(), bytes.len()) };
+        guarded.data = data;
+        guarded.len = bytes.len();
+
+        // SAFETY: the bytes are only ever read after this point.
+        unsafe { mprotect(data_pages, data_pages_len, libc::PROT_READ)? };
+
+        Ok(guarded)
+    }
+
+    /// Returns the number of bytes in the buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the buffer contains no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the bytes in the buffer.
+    pub fn as_slice(&self) -> &[u8] {
+        // SAFETY: `data` points to `len` initialized, readable bytes within our
+        // mapping, which live until we are dropped and are never mutated.
+        unsafe { core::slice::from_raw_parts(self.data.as_ptr(), self.len) }
+    }
+}
+
+/// Sets the protection of the `len` bytes (a whole number of pages) starting at
+/// the page boundary `start`.
+///
+/// # Safety
+/// The pages must not be accessed in ways the new protection disallows.
+unsafe fn mprotect(start: NonNull<u8>, len: usize, protection: libc::c_int) -> std::io::Result<()> {
+    // SAFETY: the caller guaranteed that the protection of these pages may be
+    // changed.
+    let result = unsafe { libc::mprotect(start.as_ptr().cast(), len, protection) };
+
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+impl Drop for GuardedBytes {
+    fn drop(&mut self) {
+        // SAFETY: the mapping was created by `mmap` with this address and
+        // length, and no references to it outlive `self`.
+        unsafe { libc::munmap(self.map.as_ptr().cast(), self.map_len) };
+    }
+}
+
+impl Deref for GuardedBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl AsRef<[u8]> for GuardedBytes {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl Debug for GuardedBytes {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("GuardedBytes")
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
+}
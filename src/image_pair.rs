@@ -0,0 +1,181 @@
+//! Defines [`EncodedImage`], an owner of encoded image bytes whose dependent is
+//! the decoded image. Only available with the `image` feature.
+
+use core::fmt::Debug;
+
+use alloc::{boxed::Box, vec};
+use std::io::Cursor;
+
+use image::{
+    ColorType, ImageDecoder, ImageError, ImageFormat, ImageReader,
+    error::{LimitError, LimitErrorKind},
+};
+
+use crate::{Dependent, HasDependent, Owner, Pair};
+
+/// The bytes of an encoded image, which can act as the owner of a [`Pair`]
+/// with the [`DecodedImage`] as the dependent.
+///
+/// This is useful for asset pipelines which need both the original encoded
+/// bytes (for example, to write them back out or hash them) and the decoded
+/// pixels, without keeping track of them separately.
+///
+/// The image's format is guessed from its contents. Decoders are only
+/// available for the formats enabled on the `image` crate itself (through its
+/// own features).
+///
+/// Only available with the `image` feature.
+///
+/// ```
+/// # use pair::{EncodedImage, EncodedImagePair, Pair};
+/// # use image::{ColorType, ImageFormat, RgbImage};
+/// # use std::io::Cursor;
+/// # // PNM images can only be encoded and decoded with the `image-pnm` feature
+/// # #[cfg(not(feature = "image-pnm"))] fn main() {}
+/// # #[cfg(feature = "image-pnm")] fn main() {
+/// let mut encoded = Vec::new();
+/// RgbImage::from_fn(4, 2, |x, _| [x as u8; 3].into())
+///     .write_to(&mut Cursor::new(&mut encoded), ImageFormat::Pnm)
+///     .unwrap();
+///
+/// let pair: EncodedImagePair = Pair::try_new(EncodedImage::new(encoded.clone())).unwrap();
+/// pair.with_dependent(|image| {
+///     assert_eq!(image.format(), ImageFormat::Pnm);
+///     assert_eq!(image.dimensions(), (4, 2));
+///     assert_eq!(image.color_type(), ColorType::Rgb8);
+///     assert_eq!(image.row(1), Some([0, 0, 0, 1, 1, 1, 2, 2, 2, 3, 3, 3].as_slice()));
+///     assert_eq!(image.encoded(), encoded);
+/// });
+/// # }
+/// ```
+pub struct EncodedImage(Box<[u8]>);
+
+/// A [`Pair`] of an [`EncodedImage`] and its [`DecodedImage`].
+///
+/// Only available with the `image` feature.
+pub type EncodedImagePair = Pair<EncodedImage>;
+
+impl EncodedImage {
+    /// Constructs a new [`EncodedImage`] from the bytes of an encoded image.
+    ///
+    /// The bytes are not decoded until a [`Pair`] is constructed from the
+    /// image.
+    pub fn new(encoded: impl Into<Box<[u8]>>) -> Self {
+        Self(encoded.into())
+    }
+
+    /// Returns the bytes of the encoded image.
+    pub fn bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Consumes the [`EncodedImage`], returning the bytes of the encoded image.
+    pub fn into_bytes(self) -> Box<[u8]> {
+        self.0
+    }
+}
+
+impl Debug for EncodedImage {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("EncodedImage")
+            .field("len", &self.0.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'owner> HasDependent<'owner> for EncodedImage {
+    type Dependent = DecodedImage<'owner>;
+}
+
+impl Owner for EncodedImage {
+    type Context<'a> = ();
+    type Error = ImageError;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        let format = image::guess_format(&self.0)?;
+        let decoder = ImageReader::with_format(Cursor::new(&self.0), format).into_decoder()?;
+
+        let (width, height) = decoder.dimensions();
+        let color_type = decoder.color_type();
+        let len = usize::try_from(decoder.total_bytes()).map_err(|_| {
+            ImageError::Limits(LimitError::from_kind(LimitErrorKind::InsufficientMemory))
+        })?;
+
+        // Decode directly into the final buffer, rather than through a
+        // `DynamicImage`
+        let mut pixels = vec![0; len].into_boxed_slice();
+        decoder.read_image(&mut pixels)?;
+
+        Ok(DecodedImage {
+            encoded: &self.0,
+            format,
+            width,
+            height,
+            color_type,
+            pixels,
+        })
+    }
+}
+
+/// A decoded image, along with its metadata and the encoded bytes it was
+/// decoded from.
+///
+/// Only available with the `image` feature.
+pub struct DecodedImage<'a> {
+    encoded: &'a [u8],
+    format: ImageFormat,
+    width: u32,
+    height: u32,
+    color_type: ColorType,
+    pixels: Box<[u8]>,
+}
+
+impl<'a> DecodedImage<'a> {
+    /// Returns the encoded bytes the image was decoded from.
+    pub fn encoded(&self) -> &'a [u8] {
+        self.encoded
+    }
+
+    /// Returns the format the image was encoded in.
+    pub fn format(&self) -> ImageFormat {
+        self.format
+    }
+
+    /// Returns the width and height of the image, in pixels.
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Returns the color type of the decoded pixels.
+    pub fn color_type(&self) -> ColorType {
+        self.color_type
+    }
+
+    /// Returns the decoded pixels, row by row, in native endianness.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Returns the decoded pixels of the row at the given index, or `None` if
+    /// the index is out of bounds.
+    pub fn row(&self, y: u32) -> Option<&[u8]> {
+        self.rows().nth(y as usize)
+    }
+
+    /// Returns an iterator over the decoded pixels of each row.
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[u8]> {
+        let row_len = self.width as usize * usize::from(self.color_type.bytes_per_pixel());
+        (0..self.height as usize).map(move |y| &self.pixels[y * row_len..(y + 1) * row_len])
+    }
+}
+
+impl Debug for DecodedImage<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DecodedImage")
+            .field("format", &self.format)
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("color_type", &self.color_type)
+            .finish_non_exhaustive()
+    }
+}
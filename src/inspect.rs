@@ -0,0 +1,190 @@
+//! Defines the [`DependentInspect`] trait, for answering cheap questions about
+//! a dependent without a closure.
+
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    string::String,
+    vec::Vec,
+};
+
+/// A dependent which can answer cheap questions about itself, such as its
+/// length or validity.
+///
+/// This is used by [`Pair::dependent_len`](crate::Pair::dependent_len),
+/// [`Pair::dependent_is_empty`](crate::Pair::dependent_is_empty), and
+/// [`Pair::dependent_is_valid`](crate::Pair::dependent_is_valid), so that
+/// call sites don't need to write a
+/// [`with_dependent`](crate::Pair::with_dependent) closure just to ask these
+/// questions.
+///
+/// Implementations are provided for slices, [`str`], and the collections in
+/// [`alloc`] (and [`std`], with the `std` feature), as well as references and
+/// [`Box`]es of [`DependentInspect`] types.
+///
+/// ```
+/// # use pair::{Dependent, DependentInspect, HasDependent, Owner, Pair};
+/// # use std::convert::Infallible;
+/// struct Config(String);
+///
+/// struct Entries<'a> {
+///     entries: Vec<(&'a str, &'a str)>,
+///     malformed_lines: usize,
+/// }
+///
+/// impl DependentInspect for Entries<'_> {
+///     fn len(&self) -> usize {
+///         self.entries.len()
+///     }
+///
+///     fn is_valid(&self) -> bool {
+///         self.malformed_lines == 0
+///     }
+/// }
+///
+/// impl<'owner> HasDependent<'owner> for Config {
+///     type Dependent = Entries<'owner>;
+/// }
+///
+/// impl Owner for Config {
+///     type Context<'a> = ();
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         let mut entries = Vec::new();
+///         let mut malformed_lines = 0;
+///         for line in self.0.lines() {
+///             match line.split_once('=') {
+///                 Some(entry) => entries.push(entry),
+///                 None => malformed_lines += 1,
+///             }
+///         }
+///
+///         Ok(Entries { entries, malformed_lines })
+///     }
+/// }
+///
+/// let pair = Pair::new(Config(String::from("a=1\nb=2\noops")));
+/// assert_eq!(pair.dependent_len(), 2);
+/// assert!(!pair.dependent_is_empty());
+/// assert!(!pair.dependent_is_valid());
+/// ```
+pub trait DependentInspect {
+    /// Returns the number of elements in this dependent.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if this dependent contains no elements.
+    ///
+    /// The default implementation checks whether [`len`](DependentInspect::len)
+    /// returns zero.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if this dependent is valid, according to some
+    /// implementation-defined notion of validity (for example, whether a parse
+    /// completed without recoverable errors).
+    ///
+    /// The default implementation always returns `true`.
+    fn is_valid(&self) -> bool {
+        true
+    }
+}
+
+impl<T: DependentInspect + ?Sized> DependentInspect for &T {
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+
+    fn is_empty(&self) -> bool {
+        (**self).is_empty()
+    }
+
+    fn is_valid(&self) -> bool {
+        (**self).is_valid()
+    }
+}
+
+impl<T: DependentInspect + ?Sized> DependentInspect for &mut T {
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+
+    fn is_empty(&self) -> bool {
+        (**self).is_empty()
+    }
+
+    fn is_valid(&self) -> bool {
+        (**self).is_valid()
+    }
+}
+
+impl<T: DependentInspect + ?Sized> DependentInspect for Box<T> {
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+
+    fn is_empty(&self) -> bool {
+        (**self).is_empty()
+    }
+
+    fn is_valid(&self) -> bool {
+        (**self).is_valid()
+    }
+}
+
+impl<T> DependentInspect for [T] {
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl DependentInspect for str {
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl DependentInspect for String {
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> DependentInspect for Vec<T> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> DependentInspect for VecDeque<T> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V> DependentInspect for BTreeMap<K, V> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> DependentInspect for BTreeSet<T> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> DependentInspect for std::collections::HashMap<K, V, S> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S> DependentInspect for std::collections::HashSet<T, S> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
@@ -0,0 +1,245 @@
+//! Defines [`LayeredPair`] and the [`Overlay`] trait, for pairs whose dependent
+//! borrows from both their own owner and the owner of a shared base pair.
+
+use core::{convert::Infallible, fmt::Debug};
+
+use alloc::sync::Arc;
+
+use crate::{Dependent, HasDependent, Owner, Pair};
+
+/// A type which can act as the owner of a [`LayeredPair`], producing a
+/// dependent which borrows from both itself and the owner of a base pair.
+///
+/// This is like [`Owner`], except that
+/// [`make_dependent`](Overlay::make_dependent) is also given a reference to the
+/// base pair's owner, which lives at least as long as the overlay's own borrow.
+/// The dependent type is defined by [`HasDependent`], just as for `Owner`.
+#[expect(
+    clippy::missing_errors_doc,
+    reason = "failure modes are specific to the trait's implementation"
+)]
+pub trait Overlay<B: Owner + ?Sized>: for<'any> HasDependent<'any> {
+    /// Additional context provided to [`make_dependent`](Overlay::make_dependent)
+    /// as an argument. See [`Owner::Context`] for more information.
+    type Context<'a>;
+
+    /// The error type returned by [`make_dependent`](Overlay::make_dependent)
+    /// in the event of an error. See [`Owner::Error`] for more information.
+    type Error;
+
+    /// Attempts to construct a [`Dependent`](HasDependent::Dependent) from a
+    /// reference to an overlay, a reference to the owner of the base pair, and
+    /// some context.
+    fn make_dependent<'owner>(
+        &'owner self,
+        base: &'owner B,
+        context: Self::Context<'_>,
+    ) -> Result<Dependent<'owner, Self>, Self::Error>;
+}
+
+/// An [`Overlay`] along with its dependent, which may borrow from both the
+/// overlay and the owner of a shared, [`Arc`]-backed base [`Pair`].
+///
+/// The layered pair holds a clone of the base pair's `Arc`, which keeps the
+/// base owner alive (and in place) for as long as the dependent borrows from
+/// it. This enables layered documents, such as a base file and overlays
+/// referencing it, without merging the owners into one struct.
+///
+/// ```
+/// # use pair::{Dependent, HasDependent, LayeredPair, Overlay, Owner, Pair};
+/// # use std::{convert::Infallible, sync::Arc};
+/// struct Document(String);
+///
+/// impl<'owner> HasDependent<'owner> for Document {
+///     type Dependent = Vec<&'owner str>;
+/// }
+///
+/// impl Owner for Document {
+///     type Context<'a> = ();
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         Ok(self.0.lines().collect())
+///     }
+/// }
+///
+/// // Replacements for some lines of a base document
+/// struct Patch(Vec<(usize, String)>);
+///
+/// impl<'owner> HasDependent<'owner> for Patch {
+///     type Dependent = Vec<&'owner str>;
+/// }
+///
+/// impl Overlay<Document> for Patch {
+///     type Context<'a> = ();
+///     type Error = Infallible;
+///
+///     fn make_dependent<'owner>(
+///         &'owner self,
+///         base: &'owner Document,
+///         (): (),
+///     ) -> Result<Dependent<'owner, Self>, Self::Error> {
+///         let mut lines: Vec<&str> = base.0.lines().collect();
+///         for (index, replacement) in &self.0 {
+///             lines[*index] = replacement;
+///         }
+///         Ok(lines)
+///     }
+/// }
+///
+/// let base = Arc::new(Pair::new(Document(String::from("a\nb\nc"))));
+/// let patched = LayeredPair::new_with_context(
+///     Arc::clone(&base),
+///     Patch(vec![(1, String::from("B"))]),
+///     (),
+/// );
+///
+/// assert_eq!(patched.with_dependent(|lines| lines.join(" ")), "a B c");
+/// assert_eq!(base.with_dependent(|lines| lines.join(" ")), "a b c");
+/// ```
+pub struct LayeredPair<B: Owner + ?Sized, O: Overlay<B>> {
+    pair: Pair<Layer<B, O>>,
+}
+
+impl<B: Owner + ?Sized, O: Overlay<B>> LayeredPair<B, O> {
+    /// Constructs a new [`LayeredPair`] with the given base pair and
+    /// [`Overlay`]. The dependent will be computed through
+    /// [`Overlay::make_dependent`] during this construction.
+    ///
+    /// # Errors
+    /// If [`<O as Overlay>::make_dependent`](Overlay::make_dependent) returns
+    /// an error. The base pair and overlay are returned along with the error.
+    #[track_caller]
+    #[expect(
+        clippy::type_complexity,
+        reason = "the error returns both parts, like the constructors of `Pair`"
+    )]
+    pub fn try_new_with_context(
+        base: Arc<Pair<B>>,
+        overlay: O,
+        context: O::Context<'_>,
+    ) -> Result<Self, (Arc<Pair<B>>, O, O::Error)> {
+        match Pair::try_new_with_context(Layer { base, overlay }, context) {
+            Ok(pair) => Ok(Self { pair }),
+            Err((Layer { base, overlay }, err)) => Err((base, overlay, err)),
+        }
+    }
+
+    /// Returns a reference to the base pair.
+    pub fn base(&self) -> &Arc<Pair<B>> {
+        &self.pair.owner().base
+    }
+
+    /// Returns a reference to the overlay.
+    pub fn overlay(&self) -> &O {
+        &self.pair.owner().overlay
+    }
+
+    /// Calls the given closure, providing shared access to the dependent, and
+    /// returns the value computed by the closure.
+    ///
+    /// The closure must be able to work with a [`Dependent`] with any
+    /// arbitrary lifetime - see the documentation of [`Pair::with_dependent`]
+    /// for more information.
+    ///
+    /// [`Dependent`]: crate::HasDependent::Dependent
+    pub fn with_dependent<'self_borrow, F, T>(&'self_borrow self, f: F) -> T
+    where
+        F: for<'any> FnOnce(&'self_borrow Dependent<'_, O>) -> T,
+    {
+        self.pair.with_dependent(|dependent| f(dependent))
+    }
+
+    /// Calls the given closure, providing exclusive access to the dependent,
+    /// and returns the value computed by the closure.
+    ///
+    /// The closure must be able to work with a [`Dependent`] with any
+    /// arbitrary lifetime - see the documentation of
+    /// [`Pair::with_dependent_mut`] for more information.
+    ///
+    /// [`Dependent`]: crate::HasDependent::Dependent
+    pub fn with_dependent_mut<'self_borrow, F, T>(&'self_borrow mut self, f: F) -> T
+    where
+        F: for<'any> FnOnce(&'self_borrow mut Dependent<'_, O>) -> T,
+    {
+        self.pair.with_dependent_mut(|dependent| f(dependent))
+    }
+
+    /// Consumes the [`LayeredPair`], dropping the dependent and returning the
+    /// base pair and overlay.
+    pub fn into_parts(self) -> (Arc<Pair<B>>, O) {
+        let Layer { base, overlay } = self.pair.into_owner();
+        (base, overlay)
+    }
+}
+
+impl<B: Owner + ?Sized, O: Overlay<B, Error = Infallible>> LayeredPair<B, O> {
+    /// Constructs a new [`LayeredPair`] with the given base pair and
+    /// [`Overlay`]. The dependent will be computed through
+    /// [`Overlay::make_dependent`] during this construction.
+    #[track_caller]
+    pub fn new_with_context(base: Arc<Pair<B>>, overlay: O, context: O::Context<'_>) -> Self {
+        let Ok(pair) = Self::try_new_with_context(base, overlay, context);
+        pair
+    }
+}
+
+// SAFETY: A `LayeredPair` is just a `Pair` of its base pair's `Arc`, overlay,
+// and dependent, so it is `Send` exactly when those are. See the NOTE on
+// `Pair`'s `Send` impl for why this is written out explicitly.
+unsafe impl<B: Owner + ?Sized, O: Overlay<B> + Send> Send for LayeredPair<B, O>
+where
+    Arc<Pair<B>>: Send,
+    for<'any> Dependent<'any, O>: Send,
+{
+}
+
+// SAFETY: A `LayeredPair` is just a `Pair` of its base pair's `Arc`, overlay,
+// and dependent, so it is `Sync` exactly when those are. See the NOTE on
+// `Pair`'s `Send` impl for why this is written out explicitly.
+unsafe impl<B: Owner + ?Sized, O: Overlay<B> + Sync> Sync for LayeredPair<B, O>
+where
+    Arc<Pair<B>>: Sync,
+    for<'any> Dependent<'any, O>: Sync,
+{
+}
+
+impl<B: Owner + ?Sized, O: Overlay<B> + Debug> Debug for LayeredPair<B, O>
+where
+    Pair<B>: Debug,
+    for<'any> Dependent<'any, O>: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.with_dependent(|dependent| {
+            f.debug_struct("LayeredPair")
+                .field("base", self.base())
+                .field("overlay", self.overlay())
+                .field("dependent", dependent)
+                .finish()
+        })
+    }
+}
+
+/// The owner of a [`LayeredPair`], which acts as the owner of the single
+/// [`Pair`] it stores.
+struct Layer<B: Owner + ?Sized, O> {
+    // Keeps the base owner alive and in place while the dependent borrows it
+    base: Arc<Pair<B>>,
+    overlay: O,
+}
+
+impl<'owner, B: Owner + ?Sized, O: Overlay<B>> HasDependent<'owner> for Layer<B, O> {
+    type Dependent = Dependent<'owner, O>;
+}
+
+impl<B: Owner + ?Sized, O: Overlay<B>> Owner for Layer<B, O> {
+    type Context<'a> = O::Context<'a>;
+    type Error = O::Error;
+
+    fn make_dependent(
+        &self,
+        context: Self::Context<'_>,
+    ) -> Result<Dependent<'_, Self>, Self::Error> {
+        self.overlay.make_dependent(self.base.owner(), context)
+    }
+}
@@ -0,0 +1,87 @@
+//! A global registry of live [`Pair`](crate::Pair)s, for diagnosing leaks.
+//! Only available with the `leak-check` feature.
+
+use core::{
+    panic::Location,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use std::sync::{Mutex, PoisonError};
+
+/// Information about a [`Pair`](crate::Pair) which has been constructed, but
+/// not yet dropped (or otherwise deconstructed). Returned by
+/// [`dump_live_pairs`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LivePair {
+    owner_type_name: &'static str,
+    location: &'static Location<'static>,
+}
+
+impl LivePair {
+    /// Returns the name of the owner type of the pair, as given by
+    /// [`core::any::type_name`].
+    ///
+    /// Like `type_name`, the returned string is intended for diagnostics, and
+    /// its exact contents are not guaranteed.
+    pub fn owner_type_name(&self) -> &'static str {
+        self.owner_type_name
+    }
+
+    /// Returns the location in the source code where the pair was constructed.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+}
+
+/// The ID to assign to the next registered pair.
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// All currently live pairs, keyed by their ID.
+static LIVE_PAIRS: Mutex<BTreeMap<u64, LivePair>> = Mutex::new(BTreeMap::new());
+
+/// Registers a newly constructed pair with an owner of type `O`, returning the
+/// ID it was registered with.
+pub(crate) fn register<O: ?Sized>(location: &'static Location<'static>) -> u64 {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let live_pair = LivePair {
+        owner_type_name: core::any::type_name::<O>(),
+        location,
+    };
+
+    // A panic while holding this lock can't leave the map in an inconsistent
+    // state, so poisoning can safely be ignored
+    LIVE_PAIRS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(id, live_pair);
+
+    id
+}
+
+/// Unregisters the pair with the given ID, which is being deconstructed.
+pub(crate) fn unregister(id: u64) {
+    LIVE_PAIRS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .remove(&id);
+}
+
+/// Returns information about every [`Pair`](crate::Pair) which is currently
+/// live (constructed, but not yet dropped or otherwise deconstructed), in the
+/// order they were constructed.
+///
+/// Pairs which were intentionally leaked (for example, with
+/// [`core::mem::forget`]) will continue to be reported as live.
+///
+/// Only available with the `leak-check` feature. With it enabled, every pair
+/// construction and deconstruction briefly takes a global lock, so this feature
+/// is intended for debugging, not for production builds.
+pub fn dump_live_pairs() -> Vec<LivePair> {
+    LIVE_PAIRS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .values()
+        .copied()
+        .collect()
+}
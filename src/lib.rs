@@ -6,10 +6,183 @@
 #![no_std]
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
+mod access;
+mod aligned_vec;
+mod any_context;
+mod arena;
+#[cfg(feature = "std")]
+mod args;
+#[cfg(feature = "arrow")]
+mod arrow_batch;
+mod assert_thread_safe;
+#[cfg(feature = "std")]
+mod batch;
+mod blob;
+#[cfg(feature = "alloc-budget")]
+mod budget;
+mod builder;
+mod cached_pair;
+mod cancel;
+#[cfg(feature = "itertools")]
+mod collect_pairs;
+mod collection_pair;
+#[cfg(feature = "compact")]
+mod compact;
+mod context_pair;
+mod covariant;
+mod debug_owner;
+mod defaulted;
+mod demo_owner;
+mod dependent_ref;
+mod dependent_slice;
+mod detached;
+mod diff;
+#[cfg(feature = "dns")]
+mod dns;
 mod drop_guard;
+#[cfg(feature = "either")]
+mod either_owner;
+mod erased;
+mod error;
+#[cfg(feature = "failpoints")]
+mod failpoints;
+#[cfg(feature = "flatbuffers")]
+mod flatbuffers_pair;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+#[cfg(all(feature = "guard-pages", unix))]
+mod guard_pages;
+#[cfg(feature = "image")]
+mod image_pair;
+mod inspect;
+mod layered_pair;
+#[cfg(feature = "leak-check")]
+mod leak_check;
+mod memo;
+mod no_panic;
+mod on_drop;
+mod option_pair;
 mod owner;
 mod pair;
+mod progress;
+mod project;
+#[cfg(all(feature = "mprotect", unix))]
+mod protect;
+#[cfg(feature = "prost")]
+mod protobuf;
+#[cfg(feature = "pyo3")]
+mod pyo3_pair;
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(feature = "ropey")]
+mod rope;
+#[cfg(all(feature = "shared-memory", unix))]
+mod shared_memory;
+mod size_guard;
+#[cfg(feature = "wasm")]
+mod slab;
+#[cfg(feature = "source-file")]
+mod source_file;
+mod spans;
+#[cfg(feature = "sqlx")]
+mod sqlx_row;
+#[cfg(feature = "std")]
+mod static_pair;
+mod suspend;
+#[cfg(feature = "proptest")]
+pub mod testing;
+#[cfg(feature = "tree-sitter")]
+mod tree_sitter_pair;
+#[cfg(feature = "url")]
+mod url;
+mod validate;
+mod views_pair;
+#[cfg(feature = "yoke")]
+mod yoke_cart;
 
+pub use access::AccessGuard;
+pub use aligned_vec::AlignedVec;
+pub use any_context::AnyContextError;
+pub use arena::{ArenaId, ArenaOwner, ArenaView, PairArena};
+#[cfg(feature = "std")]
+pub use args::{ArgsPair, ArgsSnapshot, ParsedArgs};
+#[cfg(feature = "arrow")]
+pub use arrow_batch::{ArrowBatch, ArrowBatchPair, BatchView, column_as};
+pub use assert_thread_safe::{AssertSendPair, AssertSyncPair};
+pub use blob::{Blob, BlobError, BlobPair, BlobParser, BlobPool};
+#[cfg(feature = "alloc-budget")]
+pub use budget::{BudgetAllocator, BudgetError, BudgetExceeded, check_budget};
+pub use builder::{NeedsContext, NoContext, Ready};
+pub use cached_pair::{CachedPair, RebuildStats};
+pub use cancel::{CancellableError, CancellationToken, Cancelled};
+#[cfg(feature = "itertools")]
+pub use collect_pairs::PairIteratorExt;
+pub use collection_pair::{CollectionPair, DependentCollection};
+#[cfg(feature = "compact")]
+pub use compact::{CompactError, CompactOwner};
+pub use context_pair::{ContextPair, StoredContext};
+pub use covariant::CovariantDependent;
+pub use debug_owner::DebugOwnerOnly;
+pub use defaulted::DefaultedPair;
+#[doc(hidden)]
+pub use demo_owner::__private;
+pub use dependent_ref::{DependentRef, DependentRefMut};
+pub use dependent_slice::DependentSlice;
+pub use detached::CloneDetached;
+pub use diff::DependentDiff;
+#[cfg(feature = "dns")]
+pub use dns::{DnsPacket, DnsPacketPair};
+pub use erased::ErasedSendPair;
+pub use error::ConstructionError;
+#[cfg(feature = "failpoints")]
+pub use failpoints::{FailPoint, FailPointOwner, FailPoints};
+#[cfg(feature = "flatbuffers")]
+pub use flatbuffers_pair::{FlatbufferMessage, FlatbufferPair, FlatbufferRoot};
+#[cfg(all(feature = "guard-pages", unix))]
+pub use guard_pages::GuardedBytes;
+#[cfg(feature = "image")]
+pub use image_pair::{DecodedImage, EncodedImage, EncodedImagePair};
+pub use inspect::DependentInspect;
+pub use layered_pair::{LayeredPair, Overlay};
+#[cfg(feature = "leak-check")]
+pub use leak_check::{LivePair, dump_live_pairs};
+pub use memo::MemoPair;
+pub use on_drop::OnDropPair;
+pub use option_pair::OptionPairExt;
 pub use owner::{Dependent, HasDependent, Owner};
 pub use pair::Pair;
+pub use progress::Progress;
+pub use project::{Project, Projected};
+#[cfg(all(feature = "mprotect", unix))]
+pub use protect::ReadOnlyPair;
+#[cfg(feature = "prost")]
+pub use protobuf::{FieldValue, ProtobufFields, ProtobufMessage, ProtobufPair};
+#[cfg(feature = "pyo3")]
+pub use pyo3_pair::PyPair;
+#[cfg(feature = "std")]
+pub use reader::ReadError;
+#[cfg(feature = "ropey")]
+pub use rope::{RopeBuffer, RopePair, RopeSnapshot, RopeView};
+#[cfg(all(feature = "shared-memory", unix))]
+pub use shared_memory::{SegmentError, SegmentLayout, SharedSegment, SharedSegmentPair};
+#[cfg(feature = "wasm")]
+pub use slab::{PairHandle, PairSlab};
+#[cfg(feature = "source-file")]
+pub use source_file::{LineCol, LineIndex, SourceFile, SourceFilePair};
+pub use spans::{FromSpans, SpanPair, ToSpans, span_of};
+#[cfg(feature = "sqlx")]
+pub use sqlx_row::{DatabaseRow, RowDecoder, RowPair};
+#[cfg(feature = "std")]
+pub use static_pair::StaticPair;
+pub use suspend::SuspendedPair;
+#[cfg(feature = "tree-sitter")]
+pub use tree_sitter_pair::{SyntaxTree, SyntaxTreePair, SyntaxView};
+#[cfg(feature = "url")]
+pub use url::{UrlComponents, UrlError, UrlPair, UrlText};
+pub use validate::ValidationError;
+pub use views_pair::ViewsPair;
+#[cfg(feature = "yoke")]
+pub use yoke_cart::PairCart;
@@ -0,0 +1,181 @@
+//! Defines [`MemoPair`], a shared [`Pair`] which can be stored as a memoized
+//! value in incremental-computation frameworks.
+
+use core::{
+    convert::Infallible,
+    fmt::Debug,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+use alloc::sync::Arc;
+
+use crate::{Owner, Pair};
+
+/// A cheaply-cloneable, shared [`Pair`], whose equality and hash are those of
+/// its owner.
+///
+/// Incremental-computation frameworks (such as `salsa`) memoize the results of
+/// queries, and need those results to be cheap to clone and comparable, so that
+/// a recomputed result equal to the old one doesn't invalidate the queries
+/// depending on it. A `Pair` is neither - but since its dependent is derived
+/// from its owner, two pairs with equal owners (and the same context) are
+/// interchangeable. A `MemoPair` shares a pair through an [`Arc`], and compares
+/// and hashes only the owner, so it can be returned directly from a query.
+///
+/// When recomputing a query, [`try_reuse_or_new`](MemoPair::try_reuse_or_new)
+/// avoids rebuilding the dependent at all if the owner hasn't changed.
+///
+/// ```
+/// # use pair::{Dependent, HasDependent, MemoPair, Owner, Pair};
+/// # use std::convert::Infallible;
+/// #[derive(PartialEq, Eq, Hash, Debug)]
+/// struct Source(String);
+///
+/// impl<'owner> HasDependent<'owner> for Source {
+///     type Dependent = Vec<&'owner str>;
+/// }
+///
+/// impl Owner for Source {
+///     type Context<'a> = ();
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         Ok(self.0.split_whitespace().collect())
+///     }
+/// }
+///
+/// // A query, which tokenizes its input
+/// fn tokens(previous: Option<&MemoPair<Source>>, input: &str) -> MemoPair<Source> {
+///     MemoPair::reuse_or_new(previous, Source(String::from(input)))
+/// }
+///
+/// let first = tokens(None, "a b c");
+/// // Recomputing the query with the same input reuses the pair
+/// let second = tokens(Some(&first), "a b c");
+/// assert!(MemoPair::ptr_eq(&first, &second));
+///
+/// // Changing the input builds a new pair, which compares unequal
+/// let third = tokens(Some(&second), "a b");
+/// assert_ne!(third, second);
+/// assert_eq!(third.dependent_len(), 2);
+/// ```
+pub struct MemoPair<O: Owner + ?Sized>(Arc<Pair<O>>);
+
+impl<O: Owner + ?Sized> MemoPair<O> {
+    /// Constructs a new [`MemoPair`], sharing the given [`Pair`].
+    pub fn new(pair: Pair<O>) -> Self {
+        Self(Arc::new(pair))
+    }
+
+    /// Returns a reference to the shared [`Pair`].
+    pub fn pair(&self) -> &Pair<O> {
+        &self.0
+    }
+
+    /// Returns `true` if both [`MemoPair`]s share the same [`Pair`].
+    ///
+    /// This is a cheaper check than equality, which compares the owners.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.0, &other.0)
+    }
+
+    /// Consumes the [`MemoPair`], returning the [`Pair`] if it isn't shared
+    /// with any other `MemoPair`s. Otherwise, the `MemoPair` is returned
+    /// unchanged.
+    ///
+    /// # Errors
+    /// If the [`Pair`] is shared with another `MemoPair`.
+    pub fn try_unwrap(this: Self) -> Result<Pair<O>, Self> {
+        Arc::try_unwrap(this.0).map_err(Self)
+    }
+}
+
+impl<O: Owner + PartialEq> MemoPair<O> {
+    /// Returns a clone of `previous` if its owner is equal to `owner`, without
+    /// rebuilding the dependent. Otherwise, constructs a new [`MemoPair`] from
+    /// `owner` and `context`.
+    ///
+    /// The context is assumed to be the same as the one `previous` was
+    /// constructed with - if it may have changed, pass [`None`] as `previous`.
+    ///
+    /// # Errors
+    /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
+    /// error. The owner is returned alongside the error.
+    #[track_caller]
+    pub fn try_reuse_or_new_with_context(
+        previous: Option<&Self>,
+        owner: O,
+        context: O::Context<'_>,
+    ) -> Result<Self, (O, O::Error)> {
+        match previous {
+            Some(previous) if *previous.0.owner() == owner => Ok(previous.clone()),
+            _ => Pair::try_new_with_context(owner, context).map(Self::new),
+        }
+    }
+}
+
+impl<O: for<'any> Owner<Context<'any> = ()> + PartialEq> MemoPair<O> {
+    /// Returns a clone of `previous` if its owner is equal to `owner`, without
+    /// rebuilding the dependent. Otherwise, constructs a new [`MemoPair`] from
+    /// `owner`.
+    ///
+    /// # Errors
+    /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
+    /// error. The owner is returned alongside the error.
+    #[track_caller]
+    pub fn try_reuse_or_new(previous: Option<&Self>, owner: O) -> Result<Self, (O, O::Error)> {
+        Self::try_reuse_or_new_with_context(previous, owner, ())
+    }
+}
+
+impl<O: for<'any> Owner<Context<'any> = (), Error = Infallible> + PartialEq> MemoPair<O> {
+    /// Returns a clone of `previous` if its owner is equal to `owner`, without
+    /// rebuilding the dependent. Otherwise, constructs a new [`MemoPair`] from
+    /// `owner`.
+    #[track_caller]
+    pub fn reuse_or_new(previous: Option<&Self>, owner: O) -> Self {
+        let Ok(pair) = Self::try_reuse_or_new(previous, owner);
+        pair
+    }
+}
+
+impl<O: Owner + ?Sized> Clone for MemoPair<O> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<O: Owner + ?Sized> Deref for MemoPair<O> {
+    type Target = Pair<O>;
+
+    fn deref(&self) -> &Pair<O> {
+        &self.0
+    }
+}
+
+impl<O: Owner + ?Sized> From<Pair<O>> for MemoPair<O> {
+    fn from(pair: Pair<O>) -> Self {
+        Self::new(pair)
+    }
+}
+
+impl<O: Owner + PartialEq + ?Sized> PartialEq for MemoPair<O> {
+    fn eq(&self, other: &Self) -> bool {
+        Self::ptr_eq(self, other) || self.0.owner() == other.0.owner()
+    }
+}
+
+impl<O: Owner + Eq + ?Sized> Eq for MemoPair<O> {}
+
+impl<O: Owner + Hash + ?Sized> Hash for MemoPair<O> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.owner().hash(state);
+    }
+}
+
+impl<O: Owner + Debug + ?Sized> Debug for MemoPair<O> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("MemoPair").field(&self.0.owner()).finish()
+    }
+}
@@ -0,0 +1,52 @@
+//! Link-time verification that the core accessor paths can't panic, with the
+//! `panic-free` feature.
+//!
+//! This uses the same technique as the `no-panic` crate: a guard is created
+//! before the guarded code runs, and forgotten after it returns. The guard's
+//! drop (which would only run if the guarded code unwound) calls a function
+//! which doesn't exist. If the optimizer can prove the guarded code never
+//! panics, the drop is removed as dead code - otherwise, linking fails with an
+//! error explaining which guarantee was broken.
+//!
+//! This only works in optimized builds, since the drop is never removed without
+//! optimizations. The guard is therefore only enabled with the `panic-free`
+//! feature and without `debug_assertions`, such as in `cargo build --release
+//! --features panic-free`.
+
+/// Calls `f`, which must provably never panic.
+///
+/// With the `panic-free` feature (and without `debug_assertions`), linking fails
+/// if the optimizer can't prove `f` never panics.
+#[inline]
+pub(crate) fn no_panic<T, F: FnOnce() -> T>(f: F) -> T {
+    #[cfg(all(feature = "panic-free", not(debug_assertions)))]
+    {
+        let guard = Guard;
+        let value = f();
+        core::mem::forget(guard);
+        value
+    }
+
+    #[cfg(not(all(feature = "panic-free", not(debug_assertions))))]
+    {
+        f()
+    }
+}
+
+/// A guard which fails to link unless its drop is proven unreachable.
+#[cfg(all(feature = "panic-free", not(debug_assertions)))]
+struct Guard;
+
+#[cfg(all(feature = "panic-free", not(debug_assertions)))]
+impl Drop for Guard {
+    fn drop(&mut self) {
+        unsafe extern "C" {
+            #[link_name = "\n\nERROR[pair]: a `pair` accessor verified by the `panic-free` feature may panic\n\n"]
+            fn pair_accessor_may_panic() -> !;
+        }
+
+        // SAFETY: this function doesn't exist - calling it is only possible if
+        // this drop is reachable, in which case linking fails.
+        unsafe { pair_accessor_may_panic() }
+    }
+}
@@ -0,0 +1,129 @@
+//! Defines [`OnDropPair`], a pair which hands its owner to a callback when
+//! dropped, rather than dropping it.
+
+use core::{
+    fmt::Debug,
+    mem::ManuallyDrop,
+    ops::{Deref, DerefMut},
+};
+
+use alloc::boxed::Box;
+
+use crate::{Dependent, Owner, Pair};
+
+impl<O: Owner + ?Sized> Pair<O> {
+    /// Registers a callback to be called with the owner when the pair is
+    /// dropped, after its dependent has been dropped.
+    ///
+    /// The callback takes ownership of the owner instead of it being dropped
+    /// in place, so owners needing deferred or asynchronous cleanup can be
+    /// handed off to a cleanup queue or async runtime, rather than blocking in
+    /// their [`Drop`] implementation.
+    ///
+    /// ```
+    /// # use pair::{Dependent, HasDependent, Owner, Pair};
+    /// # use std::{convert::Infallible, sync::mpsc};
+    /// struct Connection(String);
+    ///
+    /// impl<'owner> HasDependent<'owner> for Connection {
+    ///     type Dependent = &'owner str;
+    /// }
+    ///
+    /// impl Owner for Connection {
+    ///     type Context<'a> = ();
+    ///     type Error = Infallible;
+    ///
+    ///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+    ///         Ok(self.0.split(':').next().unwrap_or_default())
+    ///     }
+    /// }
+    ///
+    /// let (cleanup_queue, cleanup_worker) = mpsc::channel();
+    ///
+    /// let pair = Pair::new(Connection(String::from("db:5432")))
+    ///     .on_drop(move |connection| cleanup_queue.send(connection).unwrap());
+    /// assert_eq!(pair.with_dependent(|host| *host), "db");
+    /// drop(pair);
+    ///
+    /// // The connection was handed off to the cleanup worker
+    /// assert_eq!(cleanup_worker.recv().unwrap().0, "db:5432");
+    /// ```
+    pub fn on_drop<F: FnOnce(Box<O>)>(self, on_drop: F) -> OnDropPair<O, F> {
+        OnDropPair {
+            pair: ManuallyDrop::new(self),
+            on_drop: ManuallyDrop::new(on_drop),
+        }
+    }
+}
+
+/// A [`Pair`] which, when dropped, drops its dependent and then calls a
+/// callback with its owner (rather than dropping the owner).
+///
+/// This is constructed by [`Pair::on_drop`], and dereferences to the inner
+/// `Pair`. If the dependent's drop panics, the owner is dropped during
+/// unwinding as usual, and the callback is not called.
+pub struct OnDropPair<O: Owner + ?Sized, F: FnOnce(Box<O>)> {
+    pair: ManuallyDrop<Pair<O>>,
+    on_drop: ManuallyDrop<F>,
+}
+
+impl<O: Owner + ?Sized, F: FnOnce(Box<O>)> OnDropPair<O, F> {
+    /// Returns the inner [`Pair`], dropping the callback without calling it.
+    pub fn into_pair(self) -> Pair<O> {
+        let mut this = ManuallyDrop::new(self);
+
+        // SAFETY: `this` is never used (or dropped) again, so taking the pair
+        // out of it can't cause a double drop.
+        let pair = unsafe { ManuallyDrop::take(&mut this.pair) };
+
+        // SAFETY: `this` is never used (or dropped) again, so taking the
+        // callback out of it can't cause a double drop.
+        let on_drop = unsafe { ManuallyDrop::take(&mut this.on_drop) };
+
+        // If the callback's drop panics, the pair is dropped during unwinding
+        drop(on_drop);
+
+        pair
+    }
+}
+
+impl<O: Owner + ?Sized, F: FnOnce(Box<O>)> Deref for OnDropPair<O, F> {
+    type Target = Pair<O>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.pair
+    }
+}
+
+impl<O: Owner + ?Sized, F: FnOnce(Box<O>)> DerefMut for OnDropPair<O, F> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.pair
+    }
+}
+
+impl<O: Owner + ?Sized, F: FnOnce(Box<O>)> Drop for OnDropPair<O, F> {
+    fn drop(&mut self) {
+        // SAFETY: we are in drop, so the pair is never used again, and it's
+        // wrapped in `ManuallyDrop` so it won't be dropped again either.
+        let pair = unsafe { ManuallyDrop::take(&mut self.pair) };
+
+        // SAFETY: we are in drop, so the callback is never used again, and it's
+        // wrapped in `ManuallyDrop` so it won't be dropped again either.
+        let on_drop = unsafe { ManuallyDrop::take(&mut self.on_drop) };
+
+        // If the dependent's drop panics, the owner (and callback) are dropped
+        // during unwinding
+        on_drop(pair.into_boxed_owner());
+    }
+}
+
+impl<O: Owner + Debug + ?Sized, F: FnOnce(Box<O>)> Debug for OnDropPair<O, F>
+where
+    for<'any> Dependent<'any, O>: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("OnDropPair")
+            .field("pair", &*self.pair)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,111 @@
+//! Defines the [`OptionPairExt`] extension trait, for maintaining optional,
+//! lazily-created pairs.
+
+use core::convert::Infallible;
+
+use crate::{Owner, Pair};
+
+/// Extension methods for an [`Option<Pair<O>>`], such as a lazily-created pair
+/// stored in a struct.
+///
+/// This trait is sealed, and only implemented for `Option<Pair<O>>`.
+///
+/// ```
+/// # use pair::{Dependent, HasDependent, OptionPairExt, Owner, Pair};
+/// # use std::convert::Infallible;
+/// struct Index(String);
+///
+/// impl<'owner> HasDependent<'owner> for Index {
+///     type Dependent = Vec<&'owner str>;
+/// }
+///
+/// impl Owner for Index {
+///     type Context<'a> = ();
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         Ok(self.0.split_whitespace().collect())
+///     }
+/// }
+///
+/// struct Editor {
+///     index: Option<Pair<Index>>,
+/// }
+///
+/// let mut editor = Editor { index: None };
+///
+/// // The index is only created when it's first needed
+/// let index = editor.index.get_or_create(|| Index(String::from("b a")));
+/// index.with_dependent_mut(|words| words.sort_unstable());
+/// assert_eq!(index.with_dependent(|words| words.join(" ")), "a b");
+///
+/// // Throw away the sorting once more than one word is indexed
+/// assert!(editor.index.rebuild_if(|index| index.with_dependent(|words| words.len()) > 1));
+/// assert_eq!(editor.index.as_ref().unwrap().with_dependent(|words| words.join(" ")), "b a");
+///
+/// assert_eq!(editor.index.take_owner().unwrap().0, "b a");
+/// assert!(editor.index.is_none());
+/// ```
+pub trait OptionPairExt<O: Owner + ?Sized>: sealed::Sealed {
+    /// Returns the pair, first creating it with the owner returned by `owner`
+    /// if it is [`None`].
+    fn get_or_create<F: FnOnce() -> O>(&mut self, owner: F) -> &mut Pair<O>
+    where
+        O: for<'any> Owner<Context<'any> = (), Error = Infallible> + Sized;
+
+    /// Recomputes the dependent from the owner (with
+    /// [`Owner::update_dependent`]) if there is a pair and `pred` returns
+    /// `true` for it. Returns whether the dependent was rebuilt.
+    ///
+    /// By default, the new dependent is constructed before the old one is
+    /// replaced (and dropped).
+    fn rebuild_if<P: FnOnce(&Pair<O>) -> bool>(&mut self, pred: P) -> bool
+    where
+        O: for<'any> Owner<Context<'any> = (), Error = Infallible>;
+
+    /// Takes the pair out of the option (leaving [`None`] in its place), drops
+    /// its dependent, and returns its owner.
+    fn take_owner(&mut self) -> Option<O>
+    where
+        O: Sized;
+}
+
+impl<O: Owner + ?Sized> OptionPairExt<O> for Option<Pair<O>> {
+    fn get_or_create<F: FnOnce() -> O>(&mut self, owner: F) -> &mut Pair<O>
+    where
+        O: for<'any> Owner<Context<'any> = (), Error = Infallible> + Sized,
+    {
+        self.get_or_insert_with(|| Pair::new(owner()))
+    }
+
+    fn rebuild_if<P: FnOnce(&Pair<O>) -> bool>(&mut self, pred: P) -> bool
+    where
+        O: for<'any> Owner<Context<'any> = (), Error = Infallible>,
+    {
+        match self {
+            Some(pair) if pred(pair) => {
+                let Ok(()) = pair.try_update_dependent(());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn take_owner(&mut self) -> Option<O>
+    where
+        O: Sized,
+    {
+        self.take().map(Pair::into_owner)
+    }
+}
+
+/// Prevents [`OptionPairExt`] from being implemented outside of this crate.
+mod sealed {
+    #![expect(unnameable_types, reason = "...kinda the point")]
+
+    use crate::{Owner, Pair};
+
+    pub trait Sealed {}
+
+    impl<O: Owner + ?Sized> Sealed for Option<Pair<O>> {}
+}
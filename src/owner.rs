@@ -1,6 +1,8 @@
 //! Defines the [`Owner`] and [`HasDependent`] traits, the common interface for
 //! types stored in a [`Pair`](crate::Pair).
 
+use crate::Progress;
+
 /// Defines the dependent type for the [`Owner`] trait.
 ///
 /// Semantically, you can think of this like a lifetime Generic Associated Type
@@ -35,12 +37,28 @@ pub type Dependent<'owner, O> = <O as HasDependent<'owner>>::Dependent;
 /// of generic associated type - see its documentation for more information. The
 /// [`make_dependent`](Owner::make_dependent) function defines how to create a
 /// dependent from a reference to an owner.
+///
+/// The owner doesn't need to store its data inline - it may also be a handle to
+/// data stored elsewhere, such as a shared-memory segment or a memory-mapped
+/// file, as long as the data it exposes through `&self` remains valid and
+/// unchanged while the handle is borrowed. In that case, `make_dependent` is
+/// the place to validate the handle (for example, checking that the segment
+/// was mapped successfully and has a well-formed header) before parsing in
+/// place, reporting failures through [`Error`](Owner::Error). The fallible
+/// constructors of [`Pair`](crate::Pair) then return those errors along with
+/// the handle. `SharedSegment` (with the `shared-memory` feature) is such an
+/// owner, for read-only shared-memory segments.
 pub trait Owner: for<'any> HasDependent<'any> {
     /// Additional context provided to [`make_dependent`](Owner::make_dependent)
     /// as an argument.
     ///
     /// If additional context is not necessary, this should be set to
     /// [`()`](prim@unit).
+    ///
+    /// Since the context is generic over a lifetime, borrows of unsized types
+    /// can be used as context directly, without wrapping them in a sized type.
+    /// For example, `type Context<'a> = &'a str;`, `&'a [u8]`, and
+    /// `&'a dyn Fn(&str) -> bool` are all valid contexts.
     //
     // TODO(ichen): default this to () when associated type defaults are
     // stabilized (https://github.com/rust-lang/rust/issues/29661)
@@ -63,6 +81,71 @@ pub trait Owner: for<'any> HasDependent<'any> {
         &'owner self,
         context: Self::Context<'_>,
     ) -> Result<Dependent<'owner, Self>, Self::Error>;
+
+    /// Attempts to construct a [`Dependent`](HasDependent::Dependent) from a
+    /// reference to an owner and some context, reporting progress along the
+    /// way by calling `progress`.
+    ///
+    /// This is used by constructors such as
+    /// [`Pair::try_new_with_progress`](crate::Pair::try_new_with_progress),
+    /// and is useful for owners whose dependent is slow to construct (for
+    /// example, indexing a large file), so that progress can be displayed to
+    /// users. It should construct the same dependent as
+    /// [`make_dependent`](Owner::make_dependent).
+    ///
+    /// The default implementation simply calls `make_dependent`, without
+    /// reporting any progress.
+    fn make_dependent_with_progress<'owner>(
+        &'owner self,
+        context: Self::Context<'_>,
+        progress: &mut dyn FnMut(Progress),
+    ) -> Result<Dependent<'owner, Self>, Self::Error> {
+        let _ = progress;
+
+        self.make_dependent(context)
+    }
+
+    /// Attempts to update an existing [`Dependent`](HasDependent::Dependent)
+    /// in place, so that it is consistent with `self` and some context.
+    ///
+    /// This is used by APIs which rebuild the dependent of an existing pair
+    /// (such as [`ContextPair::rebuild_dependent`](crate::ContextPair::rebuild_dependent)),
+    /// and is useful for owners whose dependent can be updated incrementally
+    /// rather than reconstructed from scratch (for example, a parser which
+    /// supports incremental reparsing, or an index whose options are given as
+    /// context). It should result in the same dependent as
+    /// [`make_dependent`](Owner::make_dependent).
+    ///
+    /// If this returns an error (or panics), the pair keeps `dependent` in
+    /// whatever state it was left in, so it should be left usable.
+    ///
+    /// The default implementation constructs a new dependent with
+    /// `make_dependent`, and only replaces `dependent` with it if that
+    /// succeeds.
+    fn update_dependent<'owner>(
+        &'owner self,
+        dependent: &mut Dependent<'owner, Self>,
+        context: Self::Context<'_>,
+    ) -> Result<(), Self::Error> {
+        *dependent = self.make_dependent(context)?;
+
+        Ok(())
+    }
+
+    /// Returns `true` if `dependent` is consistent with `self`.
+    ///
+    /// This is used by [`Pair::debug_assert_verified`](crate::Pair::debug_assert_verified)
+    /// to enforce invariants between an owner and its dependent (for example,
+    /// that an index covers every line of the document it was built from).
+    /// With the `debug-verify` feature, it is also checked automatically (in
+    /// debug builds) whenever a pair's dependent is constructed or rebuilt.
+    ///
+    /// The default implementation always returns `true`.
+    fn verify_dependent(&self, dependent: &Dependent<'_, Self>) -> bool {
+        let _ = dependent;
+
+        true
+    }
 }
 
 /// Used to prevent implementors of [`HasDependent`] from overriding the
@@ -1,10 +1,20 @@
 //! Defines [`Pair`], the primary abstraction provided by this crate.
 
-use core::{convert::Infallible, fmt::Debug, marker::PhantomData, mem::ManuallyDrop, ptr::NonNull};
+use core::{
+    any::Any, convert::Infallible, fmt::Debug, marker::PhantomData, mem::ManuallyDrop, ptr::NonNull,
+};
 
-use alloc::boxed::Box;
+use alloc::{boxed::Box, string::String, vec::Vec};
 
-use crate::{Dependent, Owner, drop_guard::DropGuard};
+use crate::{
+    AccessGuard, AlignedVec, AnyContextError, CancellableError, CancellationToken, Cancelled,
+    CloneDetached, CovariantDependent, DebugOwnerOnly, Dependent, DependentInspect, DependentRef,
+    DependentRefMut, FromSpans, HasDependent, Owner, Progress, SuspendedPair, ToSpans,
+    ValidationError, drop_guard::DropGuard, no_panic::no_panic,
+};
+
+#[cfg(feature = "alloc-budget")]
+use crate::BudgetError;
 
 /// A self-referential pair containing both some [`Owner`] and its [`Dependent`].
 ///
@@ -40,12 +50,37 @@ use crate::{Dependent, Owner, drop_guard::DropGuard};
 /// `*_with_context` constructors.
 ///
 /// If your owner is already stored in a `Box`, you should use one of the
-/// `*_from_box` constructors.
+/// `*_from_box` constructors. Unsized owners (such as a wrapper around `str` or
+/// `[u8]`) can also be constructed from a [`String`] or [`Vec`] with the
+/// `*_from_string` and `*_from_vec` constructors, which discard any excess
+/// capacity. Owners holding an [`AlignedVec`] can be constructed from a copy of
+/// some bytes with the `*_from_aligned_bytes` constructors, and (with the
+/// `std` feature) unsized owners of bytes can be read from a
+/// [`Read`](std::io::Read)er with the `from_reader*` constructors.
 ///
 /// Every combination of these is supported, up to the most powerful (and least
 /// ergonomic) [`Pair::try_new_from_box_with_context`]. You should use the
 /// simplest constructor you can for your implementation of `Owner`.
 ///
+/// If you don't need the owner back when construction fails, the
+/// `*_discard_owner` constructors drop it and return only the error.
+///
+/// Additionally, the `*_with_progress` constructors use
+/// [`Owner::make_dependent_with_progress`] to report the progress of
+/// constructing the dependent, and the `*_cancellable` constructors allow
+/// construction to be cooperatively cancelled with a [`CancellationToken`]. The
+/// `*_validated` constructors check the dependent with a closure before
+/// accepting it.
+/// Owners whose context is a reference to some `'static` type can also be
+/// constructed from a type-erased `&dyn Any` context with the
+/// `*_with_any_context` constructors.
+///
+/// All constructors are [`#[track_caller]`](https://doc.rust-lang.org/reference/attributes/codegen.html#the-track_caller-attribute),
+/// so any location they capture (such as for the `leak-check` feature) is that
+/// of your call site. Failures can be converted into a structured
+/// [`ConstructionError`](crate::ConstructionError), which records the location
+/// of the conversion.
+///
 /// [`Dependent`]: crate::HasDependent::Dependent
 pub struct Pair<O: Owner + ?Sized> {
     // Derived from a Box<O>
@@ -66,6 +101,10 @@ pub struct Pair<O: Owner + ?Sized> {
     // have different impls of the same trait, but also have a subtype/supertype
     // relationship (namely, `fn(&'static T)` and `for<'a> fn(&'a T)` )
     prevent_covariance: PhantomData<*mut O>,
+
+    // The ID this pair was registered with in the global leak-check registry
+    #[cfg(feature = "leak-check")]
+    leak_check_id: u64,
 }
 
 /// Creates a [`NonNull<T>`] from [`Box<T>`]. The returned `NonNull` is the same
@@ -91,6 +130,7 @@ impl<O: Owner + ?Sized> Pair<O> {
     /// # Errors
     /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
     /// error.
+    #[track_caller]
     pub fn try_new_with_context(owner: O, context: O::Context<'_>) -> Result<Self, (O, O::Error)>
     where
         O: Sized,
@@ -108,10 +148,649 @@ impl<O: Owner + ?Sized> Pair<O> {
     /// # Errors
     /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
     /// error.
+    #[track_caller]
     pub fn try_new_from_box_with_context(
         owner: Box<O>,
         context: O::Context<'_>,
     ) -> Result<Self, (Box<O>, O::Error)> {
+        Self::try_new_from_box_with(owner, |owner| owner.make_dependent(context))
+    }
+
+    /// Constructs a new [`Pair`] with the given [`Owner`]. The dependent will
+    /// be computed through [`Owner::make_dependent`] during this construction.
+    ///
+    /// Unlike [`Pair::try_new_with_context`], the owner is dropped if
+    /// construction fails, and only the error is returned. Since the error
+    /// doesn't carry the owner, it is `Send + 'static` whenever
+    /// [`Owner::Error`] is, even if the owner is not - so it can be boxed into
+    /// a `Box<dyn Error + Send + Sync>`, for example.
+    ///
+    /// See the "Constructors" section in the documentation of [`Pair`] for
+    /// information on the differences between constructors.
+    ///
+    /// # Errors
+    /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
+    /// error.
+    #[track_caller]
+    pub fn try_new_discard_owner_with_context(
+        owner: O,
+        context: O::Context<'_>,
+    ) -> Result<Self, O::Error>
+    where
+        O: Sized,
+    {
+        Self::try_new_with_context(owner, context).map_err(|(_, err)| err)
+    }
+
+    /// Constructs a new [`Pair`] with an unsized [`Owner`] converted from a
+    /// [`Vec`]. The dependent will be computed through
+    /// [`Owner::make_dependent`] during this construction.
+    ///
+    /// The vector is first converted into a boxed slice (see
+    /// [`Vec::into_boxed_slice`]), which discards any excess capacity with at
+    /// most one reallocation, and then into a `Box<O>`. This reduces the memory
+    /// footprint of long-lived pairs whose owner never grows again. See
+    /// [`Pair::try_new_from_string_with_context`] for an example.
+    ///
+    /// See the "Constructors" section in the documentation of [`Pair`] for
+    /// information on the differences between constructors.
+    ///
+    /// # Errors
+    /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
+    /// error.
+    #[track_caller]
+    pub fn try_new_from_vec_with_context<T>(
+        owner: Vec<T>,
+        context: O::Context<'_>,
+    ) -> Result<Self, (Box<O>, O::Error)>
+    where
+        Box<[T]>: Into<Box<O>>,
+    {
+        Self::try_new_from_box_with_context(owner.into_boxed_slice().into(), context)
+    }
+
+    /// Constructs a new [`Pair`] with an unsized [`Owner`] converted from a
+    /// [`String`]. The dependent will be computed through
+    /// [`Owner::make_dependent`] during this construction.
+    ///
+    /// The string is first converted into a boxed `str` (see
+    /// [`String::into_boxed_str`]), which discards any excess capacity with at
+    /// most one reallocation, and then into a `Box<O>`. This reduces the memory
+    /// footprint of long-lived pairs whose owner never grows again.
+    ///
+    /// ```
+    /// # use pair::{Dependent, HasDependent, Owner, Pair};
+    /// # use std::num::ParseIntError;
+    /// #[repr(transparent)]
+    /// struct Numbers(str);
+    ///
+    /// impl From<Box<str>> for Box<Numbers> {
+    ///     fn from(text: Box<str>) -> Self {
+    ///         // SAFETY: `Numbers` is a `#[repr(transparent)]` wrapper of `str`
+    ///         unsafe { Box::from_raw(Box::into_raw(text) as *mut Numbers) }
+    ///     }
+    /// }
+    ///
+    /// impl HasDependent<'_> for Numbers {
+    ///     type Dependent = Vec<u32>;
+    /// }
+    ///
+    /// impl Owner for Numbers {
+    ///     type Context<'a> = char;
+    ///     type Error = ParseIntError;
+    ///
+    ///     fn make_dependent(&self, separator: char) -> Result<Dependent<'_, Self>, Self::Error> {
+    ///         self.0.split(separator).map(str::parse).collect()
+    ///     }
+    /// }
+    ///
+    /// let mut text = String::with_capacity(1024);
+    /// text.push_str("1;2;3");
+    ///
+    /// let pair = Pair::<Numbers>::try_new_from_string_with_context(text, ';');
+    /// assert!(matches!(pair, Ok(pair) if pair.owner().0.len() == 5));
+    /// ```
+    ///
+    /// See the "Constructors" section in the documentation of [`Pair`] for
+    /// information on the differences between constructors.
+    ///
+    /// # Errors
+    /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
+    /// error.
+    #[track_caller]
+    pub fn try_new_from_string_with_context(
+        owner: String,
+        context: O::Context<'_>,
+    ) -> Result<Self, (Box<O>, O::Error)>
+    where
+        Box<str>: Into<Box<O>>,
+    {
+        Self::try_new_from_box_with_context(owner.into_boxed_str().into(), context)
+    }
+
+    /// Constructs a new [`Pair`] with an [`Owner`] converted from an
+    /// [`AlignedVec`] holding a copy of the given bytes. The dependent will be
+    /// computed through [`Owner::make_dependent`] during this construction.
+    ///
+    /// This is useful for zero-copy dependents which require their bytes to be
+    /// aligned, when the bytes come from an unaligned source (such as a
+    /// `Vec<u8>` or a network buffer). See [`AlignedVec`] for an example.
+    ///
+    /// See the "Constructors" section in the documentation of [`Pair`] for
+    /// information on the differences between constructors.
+    ///
+    /// # Errors
+    /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
+    /// error.
+    #[track_caller]
+    pub fn try_new_from_aligned_bytes_with_context<const ALIGN: usize>(
+        bytes: &[u8],
+        context: O::Context<'_>,
+    ) -> Result<Self, (O, O::Error)>
+    where
+        O: Sized,
+        AlignedVec<ALIGN>: Into<O>,
+    {
+        Self::try_new_with_context(AlignedVec::from(bytes).into(), context)
+    }
+
+    /// Constructs a new [`Pair`] with the given [`Owner`]. The dependent will
+    /// be computed through [`Owner::make_dependent_with_progress`] during this
+    /// construction, which reports its progress by calling `progress`.
+    ///
+    /// See the "Constructors" section in the documentation of [`Pair`] for
+    /// information on the differences between constructors.
+    ///
+    /// # Errors
+    /// If [`<O as Owner>::make_dependent_with_progress`](Owner::make_dependent_with_progress)
+    /// returns an error.
+    #[track_caller]
+    pub fn try_new_with_progress<F: FnMut(Progress)>(
+        owner: O,
+        context: O::Context<'_>,
+        progress: F,
+    ) -> Result<Self, (O, O::Error)>
+    where
+        O: Sized,
+    {
+        Self::try_new_from_box_with_progress(Box::new(owner), context, progress)
+            .map_err(|(owner, err)| (*owner, err))
+    }
+
+    /// Constructs a new [`Pair`] with the given [`Owner`]. The dependent will
+    /// be computed through [`Owner::make_dependent_with_progress`] during this
+    /// construction, which reports its progress by calling `progress`.
+    ///
+    /// See the "Constructors" section in the documentation of [`Pair`] for
+    /// information on the differences between constructors.
+    ///
+    /// # Errors
+    /// If [`<O as Owner>::make_dependent_with_progress`](Owner::make_dependent_with_progress)
+    /// returns an error.
+    #[track_caller]
+    pub fn try_new_from_box_with_progress<F: FnMut(Progress)>(
+        owner: Box<O>,
+        context: O::Context<'_>,
+        mut progress: F,
+    ) -> Result<Self, (Box<O>, O::Error)> {
+        Self::try_new_from_box_with(owner, |owner| {
+            owner.make_dependent_with_progress(context, &mut progress)
+        })
+    }
+
+    /// Constructs a new [`Pair`] with the given [`Owner`]. The dependent will
+    /// be computed through [`Owner::make_dependent`] during this construction,
+    /// unless it is cancelled through the given [`CancellationToken`].
+    ///
+    /// Cancellation is cooperative: the token is checked before and after
+    /// calling `make_dependent`, which should itself poll the token (typically
+    /// received through its context) to stop early. See the documentation of
+    /// [`CancellationToken`] for more information.
+    ///
+    /// See the "Constructors" section in the documentation of [`Pair`] for
+    /// information on the differences between constructors.
+    ///
+    /// # Errors
+    /// [`CancellableError::Cancelled`] if the token was cancelled before
+    /// construction completed (in which case any dependent which was
+    /// constructed is dropped), or [`CancellableError::Failed`] if
+    /// [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an error
+    /// without the token being cancelled.
+    #[track_caller]
+    pub fn try_new_cancellable(
+        owner: O,
+        context: O::Context<'_>,
+        token: &CancellationToken,
+    ) -> Result<Self, (O, CancellableError<O::Error>)>
+    where
+        O: Sized,
+    {
+        Self::try_new_from_box_cancellable(Box::new(owner), context, token)
+            .map_err(|(owner, err)| (*owner, err))
+    }
+
+    /// Constructs a new [`Pair`] with the given [`Owner`]. The dependent will
+    /// be computed through [`Owner::make_dependent`] during this construction,
+    /// unless it is cancelled through the given [`CancellationToken`].
+    ///
+    /// See the documentation of [`Pair::try_new_cancellable`] for more
+    /// information.
+    ///
+    /// # Errors
+    /// [`CancellableError::Cancelled`] if the token was cancelled before
+    /// construction completed (in which case any dependent which was
+    /// constructed is dropped), or [`CancellableError::Failed`] if
+    /// [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an error
+    /// without the token being cancelled.
+    #[track_caller]
+    pub fn try_new_from_box_cancellable(
+        owner: Box<O>,
+        context: O::Context<'_>,
+        token: &CancellationToken,
+    ) -> Result<Self, (Box<O>, CancellableError<O::Error>)> {
+        Self::try_new_from_box_with(owner, |owner| {
+            make_dependent_cancellable(owner, context, token)
+        })
+    }
+
+    /// Constructs a new [`Pair`] with the given [`Owner`]. The dependent will
+    /// be computed through [`Owner::make_dependent`] during this construction,
+    /// and then checked by calling `validate` with the owner and dependent.
+    ///
+    /// If `validate` returns an error, the dependent is dropped and the owner
+    /// is returned, just as if `make_dependent` had failed. This separates
+    /// "constructing the dependent succeeded" from "the dependent is
+    /// acceptable" (for example, a config file which parses successfully, but
+    /// is missing a required key), without needing to fold validation
+    /// failures into [`Owner::Error`].
+    ///
+    /// ```
+    /// # use pair::{Dependent, HasDependent, Owner, Pair, ValidationError};
+    /// # use std::convert::Infallible;
+    /// struct Config(String);
+    ///
+    /// impl<'owner> HasDependent<'owner> for Config {
+    ///     type Dependent = Vec<(&'owner str, &'owner str)>;
+    /// }
+    ///
+    /// impl Owner for Config {
+    ///     type Context<'a> = ();
+    ///     type Error = Infallible;
+    ///
+    ///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+    ///         Ok(self.0.lines().filter_map(|line| line.split_once('=')).collect())
+    ///     }
+    /// }
+    ///
+    /// let result = Pair::try_new_validated(Config(String::from("port=80")), (), |_, entries| {
+    ///     if entries.iter().any(|&(key, _)| key == "host") {
+    ///         Ok(())
+    ///     } else {
+    ///         Err("missing `host`")
+    ///     }
+    /// });
+    ///
+    /// let Err((config, ValidationError::Rejected(err))) = result else {
+    ///     panic!("config should be rejected");
+    /// };
+    /// assert_eq!(config.0, "port=80");
+    /// assert_eq!(err, "missing `host`");
+    /// ```
+    ///
+    /// See the "Constructors" section in the documentation of [`Pair`] for
+    /// information on the differences between constructors.
+    ///
+    /// # Errors
+    /// [`ValidationError::Failed`] if
+    /// [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an error
+    /// (in which case `validate` is not called), or
+    /// [`ValidationError::Rejected`] if `validate` returns an error.
+    #[track_caller]
+    pub fn try_new_validated<F, V>(
+        owner: O,
+        context: O::Context<'_>,
+        validate: F,
+    ) -> Result<Self, (O, ValidationError<O::Error, V>)>
+    where
+        O: Sized,
+        F: for<'any> FnOnce(&O, &Dependent<'any, O>) -> Result<(), V>,
+    {
+        Self::try_new_from_box_validated(Box::new(owner), context, validate)
+            .map_err(|(owner, err)| (*owner, err))
+    }
+
+    /// Constructs a new [`Pair`] with the given [`Owner`]. The dependent will
+    /// be computed through [`Owner::make_dependent`] during this construction,
+    /// and then checked by calling `validate` with the owner and dependent.
+    ///
+    /// See the documentation of [`Pair::try_new_validated`] for more
+    /// information.
+    ///
+    /// # Errors
+    /// [`ValidationError::Failed`] if
+    /// [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an error
+    /// (in which case `validate` is not called), or
+    /// [`ValidationError::Rejected`] if `validate` returns an error.
+    #[track_caller]
+    #[expect(
+        clippy::type_complexity,
+        reason = "the return type mirrors the other `*_from_box` constructors"
+    )]
+    pub fn try_new_from_box_validated<F, V>(
+        owner: Box<O>,
+        context: O::Context<'_>,
+        validate: F,
+    ) -> Result<Self, (Box<O>, ValidationError<O::Error, V>)>
+    where
+        F: for<'any> FnOnce(&O, &Dependent<'any, O>) -> Result<(), V>,
+    {
+        Self::try_new_from_box_with(owner, |owner| {
+            let dependent = owner
+                .make_dependent(context)
+                .map_err(ValidationError::Failed)?;
+
+            // If validation fails, the rejected dependent is dropped here
+            validate(owner, &dependent).map_err(ValidationError::Rejected)?;
+
+            Ok(dependent)
+        })
+    }
+
+    /// Constructs a new [`Pair`] with the given [`Owner`]. The dependent will
+    /// be computed through [`Owner::make_dependent`] during this construction,
+    /// unless it takes longer than `timeout`.
+    ///
+    /// The given [`CancellationToken`] is cancelled once `timeout` has elapsed
+    /// (by a watchdog on a scoped thread), and construction then behaves like
+    /// [`Pair::try_new_cancellable`]. Like cancellation, timeouts are
+    /// cooperative: `make_dependent` should poll the token (typically received
+    /// through its context) to stop early, otherwise this constructor will
+    /// still wait for it to complete before returning the timeout error. The
+    /// token may also be cancelled manually, as with `try_new_cancellable`.
+    ///
+    /// Only available with the `std` feature.
+    ///
+    /// # Errors
+    /// [`CancellableError::Cancelled`] if the token was cancelled (including by
+    /// the timeout elapsing) before construction completed, or
+    /// [`CancellableError::Failed`] if
+    /// [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an error
+    /// without the token being cancelled.
+    #[cfg(feature = "std")]
+    #[track_caller]
+    pub fn try_new_timeout(
+        owner: O,
+        context: O::Context<'_>,
+        token: &CancellationToken,
+        timeout: std::time::Duration,
+    ) -> Result<Self, (O, CancellableError<O::Error>)>
+    where
+        O: Sized,
+    {
+        Self::try_new_from_box_timeout(Box::new(owner), context, token, timeout)
+            .map_err(|(owner, err)| (*owner, err))
+    }
+
+    /// Constructs a new [`Pair`] with the given [`Owner`]. The dependent will
+    /// be computed through [`Owner::make_dependent`] during this construction,
+    /// unless it takes longer than `timeout`.
+    ///
+    /// See the documentation of [`Pair::try_new_timeout`] for more
+    /// information.
+    ///
+    /// Only available with the `std` feature.
+    ///
+    /// # Errors
+    /// [`CancellableError::Cancelled`] if the token was cancelled (including by
+    /// the timeout elapsing) before construction completed, or
+    /// [`CancellableError::Failed`] if
+    /// [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an error
+    /// without the token being cancelled.
+    #[cfg(feature = "std")]
+    #[track_caller]
+    pub fn try_new_from_box_timeout(
+        owner: Box<O>,
+        context: O::Context<'_>,
+        token: &CancellationToken,
+        timeout: std::time::Duration,
+    ) -> Result<Self, (Box<O>, CancellableError<O::Error>)> {
+        Self::try_new_from_box_with(owner, |owner| {
+            crate::cancel::cancel_after(token, timeout, || {
+                make_dependent_cancellable(owner, context, token)
+            })
+        })
+    }
+
+    /// Constructs a new [`Pair`] with the given [`Owner`]. The dependent will
+    /// be computed through [`Owner::make_dependent`] during this construction,
+    /// unless doing so allocates more than `bytes` bytes.
+    ///
+    /// Allocations are only counted if a [`BudgetAllocator`] is installed as
+    /// the global allocator, and only those made on the current thread. An
+    /// allocation which exceeds the budget still succeeds, but `make_dependent`
+    /// can poll [`check_budget`](crate::check_budget) to stop early. See the
+    /// documentation of [`BudgetAllocator`] for more information.
+    ///
+    /// Only available with the `alloc-budget` feature.
+    ///
+    /// # Errors
+    /// [`BudgetError::Exceeded`] if constructing the dependent exceeded the
+    /// budget (in which case any dependent which was constructed is dropped),
+    /// or [`BudgetError::Failed`] if
+    /// [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an error
+    /// without exceeding the budget.
+    ///
+    /// [`BudgetAllocator`]: crate::BudgetAllocator
+    #[cfg(feature = "alloc-budget")]
+    #[track_caller]
+    pub fn try_new_with_budget(
+        owner: O,
+        context: O::Context<'_>,
+        bytes: usize,
+    ) -> Result<Self, (O, BudgetError<O::Error>)>
+    where
+        O: Sized,
+    {
+        Self::try_new_from_box_with_budget(Box::new(owner), context, bytes)
+            .map_err(|(owner, err)| (*owner, err))
+    }
+
+    /// Constructs a new [`Pair`] with the given [`Owner`]. The dependent will
+    /// be computed through [`Owner::make_dependent`] during this construction,
+    /// unless doing so allocates more than `bytes` bytes.
+    ///
+    /// See the documentation of [`Pair::try_new_with_budget`] for more
+    /// information.
+    ///
+    /// Only available with the `alloc-budget` feature.
+    ///
+    /// # Errors
+    /// [`BudgetError::Exceeded`] if constructing the dependent exceeded the
+    /// budget (in which case any dependent which was constructed is dropped),
+    /// or [`BudgetError::Failed`] if
+    /// [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an error
+    /// without exceeding the budget.
+    #[cfg(feature = "alloc-budget")]
+    #[track_caller]
+    pub fn try_new_from_box_with_budget(
+        owner: Box<O>,
+        context: O::Context<'_>,
+        bytes: usize,
+    ) -> Result<Self, (Box<O>, BudgetError<O::Error>)> {
+        Self::try_new_from_box_with(owner, |owner| {
+            match crate::budget::with_budget(bytes, || owner.make_dependent(context)) {
+                // Regardless of whether `make_dependent` succeeded, if the
+                // budget was exceeded then construction failed
+                (_, true) => Err(BudgetError::Exceeded),
+                (maybe_dependent, false) => maybe_dependent.map_err(BudgetError::Failed),
+            }
+        })
+    }
+
+    /// Constructs a new [`Pair`] with the given [`Owner`]. The dependent will
+    /// be reconstructed from the given spans through
+    /// [`FromSpans::dependent_from_spans`], rather than computed through
+    /// [`Owner::make_dependent`].
+    ///
+    /// This allows a dependent which was previously converted into spans (for
+    /// example, with [`Pair::into_owner_with_spans`]) and persisted to be
+    /// restored without recomputing it.
+    ///
+    /// # Errors
+    /// If [`<O as FromSpans>::dependent_from_spans`](FromSpans::dependent_from_spans)
+    /// returns [`None`]. The owner is returned.
+    #[track_caller]
+    pub fn try_new_with_spans(owner: O, spans: &O::Spans) -> Result<Self, O>
+    where
+        O: FromSpans + Sized,
+    {
+        Self::try_new_from_box_with_spans(Box::new(owner), spans).map_err(|owner| *owner)
+    }
+
+    /// Constructs a new [`Pair`] with the given [`Owner`]. The dependent will
+    /// be reconstructed from the given spans through
+    /// [`FromSpans::dependent_from_spans`], rather than computed through
+    /// [`Owner::make_dependent`].
+    ///
+    /// See the documentation of [`Pair::try_new_with_spans`] for more
+    /// information.
+    ///
+    /// # Errors
+    /// If [`<O as FromSpans>::dependent_from_spans`](FromSpans::dependent_from_spans)
+    /// returns [`None`]. The owner is returned.
+    #[track_caller]
+    pub fn try_new_from_box_with_spans(owner: Box<O>, spans: &O::Spans) -> Result<Self, Box<O>>
+    where
+        O: FromSpans,
+    {
+        Self::try_new_from_box_with(owner, |owner| owner.dependent_from_spans(spans).ok_or(()))
+            .map_err(|(owner, ())| owner)
+    }
+
+    /// Constructs a new [`Pair`] with the given [`Owner`], adopting some
+    /// previously computed `prebuilt` value as the dependent, rather than
+    /// computing it through [`Owner::make_dependent`].
+    ///
+    /// A dependent which already borrows from the owner can't be moved into a
+    /// pair, since the owner moves when it's boxed (invalidating the borrows).
+    /// Instead, the expensive work can be done up front into a value which
+    /// doesn't borrow from the owner (such as byte offsets), and `adopt` is
+    /// called with the boxed owner to cheaply anchor it into the dependent.
+    ///
+    /// ```
+    /// # use pair::{Dependent, HasDependent, Owner, Pair};
+    /// # use std::{convert::Infallible, ops::Range};
+    /// struct Document(String);
+    ///
+    /// impl<'owner> HasDependent<'owner> for Document {
+    ///     type Dependent = Vec<&'owner str>;
+    /// }
+    ///
+    /// impl Owner for Document {
+    ///     type Context<'a> = ();
+    ///     type Error = Infallible;
+    ///
+    ///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+    ///         Ok(self.0.split(',').collect())
+    ///     }
+    /// }
+    ///
+    /// // Validating the document already found the ranges of every field
+    /// let document = Document(String::from("a,bc,d"));
+    /// let ranges: Vec<Range<usize>> = vec![0..1, 2..4, 5..6];
+    ///
+    /// let pair = Pair::try_new_with_prebuilt(document, ranges, |document, ranges| {
+    ///     ranges
+    ///         .into_iter()
+    ///         .map(|range| document.0.get(range).ok_or("invalid range"))
+    ///         .collect()
+    /// })
+    /// .unwrap_or_else(|(_, err)| panic!("{err}"));
+    /// assert_eq!(pair.with_dependent(|fields| fields[1]), "bc");
+    /// ```
+    ///
+    /// # Errors
+    /// If `adopt` returns an error. The owner is returned alongside the error.
+    #[track_caller]
+    pub fn try_new_with_prebuilt<P, F, E>(owner: O, prebuilt: P, adopt: F) -> Result<Self, (O, E)>
+    where
+        O: Sized,
+        F: for<'owner> FnOnce(&'owner O, P) -> Result<Dependent<'owner, O>, E>,
+    {
+        Self::try_new_from_box_with_prebuilt(Box::new(owner), prebuilt, adopt)
+            .map_err(|(owner, err)| (*owner, err))
+    }
+
+    /// Constructs a new [`Pair`] with the given [`Owner`], adopting some
+    /// previously computed `prebuilt` value as the dependent, rather than
+    /// computing it through [`Owner::make_dependent`].
+    ///
+    /// See the documentation of [`Pair::try_new_with_prebuilt`] for more
+    /// information.
+    ///
+    /// # Errors
+    /// If `adopt` returns an error. The owner is returned alongside the error.
+    #[track_caller]
+    pub fn try_new_from_box_with_prebuilt<P, F, E>(
+        owner: Box<O>,
+        prebuilt: P,
+        adopt: F,
+    ) -> Result<Self, (Box<O>, E)>
+    where
+        F: for<'owner> FnOnce(&'owner O, P) -> Result<Dependent<'owner, O>, E>,
+    {
+        Self::try_new_from_box_with(owner, |owner| adopt(owner, prebuilt))
+    }
+
+    /// Constructs a new [`Pair`] with the given [`Owner`], adopting some
+    /// previously computed `prebuilt` value as the dependent, rather than
+    /// computing it through [`Owner::make_dependent`].
+    ///
+    /// See the documentation of [`Pair::try_new_with_prebuilt`] for more
+    /// information.
+    #[track_caller]
+    pub fn new_with_prebuilt<P, F>(owner: O, prebuilt: P, adopt: F) -> Self
+    where
+        O: Sized,
+        F: for<'owner> FnOnce(&'owner O, P) -> Dependent<'owner, O>,
+    {
+        Self::new_from_box_with_prebuilt(Box::new(owner), prebuilt, adopt)
+    }
+
+    /// Constructs a new [`Pair`] with the given [`Owner`], adopting some
+    /// previously computed `prebuilt` value as the dependent, rather than
+    /// computing it through [`Owner::make_dependent`].
+    ///
+    /// See the documentation of [`Pair::try_new_with_prebuilt`] for more
+    /// information.
+    #[track_caller]
+    pub fn new_from_box_with_prebuilt<P, F>(owner: Box<O>, prebuilt: P, adopt: F) -> Self
+    where
+        F: for<'owner> FnOnce(&'owner O, P) -> Dependent<'owner, O>,
+    {
+        let Ok(pair) = Self::try_new_from_box_with_prebuilt(owner, prebuilt, |owner, prebuilt| {
+            Ok::<_, Infallible>(adopt(owner, prebuilt))
+        });
+        pair
+    }
+
+    /// Constructs a new [`Pair`] with the given [`Owner`]. The dependent will
+    /// be computed by the given closure during this construction.
+    ///
+    /// This is the core constructor which all others are implemented with. The
+    /// closure is typically a call to [`Owner::make_dependent`], possibly with
+    /// some additional bookkeeping (such as mapping the error).
+    //
+    // NOTE(ichen): Allowing an arbitrary closure here (rather than only calling
+    // `make_dependent`) does not weaken any guarantees. The closure must work
+    // for *any* `'owner` lifetime, so just like an implementation of
+    // `make_dependent`, the only borrows it can place in the dependent are of
+    // the owner itself, or things which outlive the owner type `O`.
+    #[track_caller]
+    fn try_new_from_box_with<F, E>(owner: Box<O>, make_dependent: F) -> Result<Self, (Box<O>, E)>
+    where
+        F: for<'owner> FnOnce(&'owner O) -> Result<Dependent<'owner, O>, E>,
+    {
         // Convert owner into a NonNull, so we are no longer restricted by the
         // aliasing requirements of Box
         let owner = non_null_from_box(owner);
@@ -130,7 +809,7 @@ impl<O: Owner + ?Sized> Pair<O> {
             // boxed owner.
 
             // SAFETY: `owner` was just created from a Box earlier in
-            // `try_new_from_box_with_context`, and not invalidated since then.
+            // `try_new_from_box_with`, and not invalidated since then.
             // Because we haven't given away access to a `Self`, and the one
             // borrow we took of the owner to pass to `make_dependent` has
             // expired (since it panicked), we know there are no outstanding
@@ -153,7 +832,7 @@ impl<O: Owner + ?Sized> Pair<O> {
             // marks the beginning of a shared borrow which will last until the
             // returned `Pair` is dropped (or ends immediately if make_dependent
             // panics or returns an error).
-            unsafe { owner.as_ref() }.make_dependent(context)
+            make_dependent(unsafe { owner.as_ref() })
         };
 
         // The call to `make_dependent` didn't panic - disarm our drop guard
@@ -184,7 +863,7 @@ impl<O: Owner + ?Sized> Pair<O> {
             // boxed owner.
 
             // SAFETY: `owner` was just created from a Box earlier in
-            // `try_new_from_box_with_context`, and not invalidated since then.
+            // `try_new_from_box_with`, and not invalidated since then.
             // Because we haven't given away access to a `Self`, and the one
             // borrow of the owner stored in the dependent has expired (since we
             // gave ownership of the dependent to the `Box::new(..)` call that
@@ -200,6 +879,14 @@ impl<O: Owner + ?Sized> Pair<O> {
             drop(owner);
         });
 
+        #[cfg(feature = "failpoints")]
+        if crate::failpoints::hit(crate::FailPoint::Allocation) {
+            // The dependent must be dropped before the owner (by the drop
+            // guard), since it may borrow from it
+            drop(dependent);
+            panic!("injected an allocation failure");
+        }
+
         // Move `dependent` to the heap, so we can store it as a type-erased
         // pointer.
         let dependent = Box::new(dependent);
@@ -213,23 +900,49 @@ impl<O: Owner + ?Sized> Pair<O> {
         let dependent: NonNull<Dependent<'_, O>> = non_null_from_box(dependent);
         let dependent: NonNull<()> = dependent.cast();
 
-        Ok(Self {
+        let pair = Self {
             owner,
             dependent,
             prevent_covariance: PhantomData,
-        })
+            #[cfg(feature = "leak-check")]
+            leak_check_id: crate::leak_check::register::<O>(core::panic::Location::caller()),
+        };
+
+        #[cfg(feature = "debug-verify")]
+        pair.debug_assert_verified();
+
+        Ok(pair)
     }
 
     /// Returns a reference to the owner.
+    ///
+    /// This never panics - with the `panic-free` feature, optimized builds fail
+    /// to link if that can't be proven.
+    #[inline]
     pub fn owner(&self) -> &O {
-        // SAFETY: `self.owner` was originally converted from a valid Box, and
-        // inherited the alignment and validity guarantees of Box - and neither
-        // our code nor any of our exposed APIs could have invalidated those
-        // since construction. Additionally, the value behind the pointer is
-        // currently in a shared borrow state (no exclusive borrows, no other
-        // code assuming unique ownership), and will be until the Pair is
-        // dropped. Here, we only add another shared borrow.
-        unsafe { self.owner.as_ref() }
+        no_panic(|| {
+            // SAFETY: `self.owner` was originally converted from a valid Box,
+            // and inherited the alignment and validity guarantees of Box - and
+            // neither our code nor any of our exposed APIs could have
+            // invalidated those since construction. Additionally, the value
+            // behind the pointer is currently in a shared borrow state (no
+            // exclusive borrows, no other code assuming unique ownership), and
+            // will be until the Pair is dropped. Here, we only add another
+            // shared borrow.
+            unsafe { self.owner.as_ref() }
+        })
+    }
+
+    /// Calls the given closure, providing shared access to the owner, and
+    /// returns the value computed by the closure.
+    ///
+    /// This is equivalent to calling the closure with [`Pair::owner`], and
+    /// exists for symmetry with [`Pair::with_dependent`].
+    pub fn with_owner<'self_borrow, F, T>(&'self_borrow self, f: F) -> T
+    where
+        F: FnOnce(&'self_borrow O) -> T,
+    {
+        f(self.owner())
     }
 
     /// Calls the given closure, providing shared access to the dependent, and
@@ -245,25 +958,133 @@ impl<O: Owner + ?Sized> Pair<O> {
     /// reference.
     ///
     /// [`Dependent`]: crate::HasDependent::Dependent
+    #[inline]
     pub fn with_dependent<'self_borrow, F, T>(&'self_borrow self, f: F) -> T
     where
         F: for<'any> FnOnce(&'self_borrow Dependent<'_, O>) -> T,
     {
-        // SAFETY: `self.dependent` was originally converted from a valid
-        // Box<Dependent<'_, O>>, and type-erased to a NonNull<()>. As such, it
-        // inherited the alignment and validity guarantees of Box (for a
-        // Dependent<'_, O>) - and neither our code nor any of our exposed APIs
-        // could have invalidated those since construction. Additionally,
-        // because we have a shared reference to self, we know that the value
-        // behind the pointer is currently either not borrowed at all, or in a
-        // shared borrow state (no exclusive borrows, no other code assuming
-        // unique ownership). Here, we only either create the first shared
-        // borrow, or add another.
-        let dependent = unsafe { self.dependent.cast::<Dependent<'_, O>>().as_ref() };
+        let dependent = no_panic(|| {
+            // SAFETY: `self.dependent` was originally converted from a valid
+            // Box<Dependent<'_, O>>, and type-erased to a NonNull<()>. As such,
+            // it inherited the alignment and validity guarantees of Box (for a
+            // Dependent<'_, O>) - and neither our code nor any of our exposed
+            // APIs could have invalidated those since construction.
+            // Additionally, because we have a shared reference to self, we know
+            // that the value behind the pointer is currently either not
+            // borrowed at all, or in a shared borrow state (no exclusive
+            // borrows, no other code assuming unique ownership). Here, we only
+            // either create the first shared borrow, or add another.
+            unsafe { self.dependent.cast::<Dependent<'_, O>>().as_ref() }
+        });
 
         f(dependent)
     }
 
+    /// Returns a clone of the dependent in its detached form, which no longer
+    /// borrows from the owner.
+    ///
+    /// This is a convenience for extracting owned copies of a dependent without
+    /// a closure. See the documentation of [`CloneDetached`] for more
+    /// information.
+    pub fn dependent_cloned<D>(&self) -> D
+    where
+        for<'any> Dependent<'any, O>: CloneDetached<Detached = D>,
+    {
+        self.with_dependent(|dependent| dependent.clone_detached())
+    }
+
+    /// Converts a reference to the dependent into a type which no longer
+    /// borrows from the owner, using that type's [`From`] implementation.
+    ///
+    /// This is useful for getting data out of a pair with a hand-written "owned
+    /// mirror" of the dependent (for example, a struct of `String`s mirroring a
+    /// struct of `&str`s). Unlike [`dependent_cloned`](Pair::dependent_cloned),
+    /// the target type is chosen by the caller, so a dependent can have several
+    /// detached forms.
+    ///
+    /// ```
+    /// # use pair::{Dependent, HasDependent, Owner, Pair};
+    /// # use std::convert::Infallible;
+    /// struct Header(String);
+    ///
+    /// struct Fields<'a> {
+    ///     name: &'a str,
+    ///     value: &'a str,
+    /// }
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct OwnedFields {
+    ///     name: String,
+    ///     value: String,
+    /// }
+    ///
+    /// impl From<&Fields<'_>> for OwnedFields {
+    ///     fn from(fields: &Fields<'_>) -> Self {
+    ///         Self {
+    ///             name: fields.name.to_owned(),
+    ///             value: fields.value.to_owned(),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// impl<'owner> HasDependent<'owner> for Header {
+    ///     type Dependent = Fields<'owner>;
+    /// }
+    ///
+    /// impl Owner for Header {
+    ///     type Context<'a> = ();
+    ///     type Error = Infallible;
+    ///
+    ///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+    ///         let (name, value) = self.0.split_once(": ").unwrap_or((&self.0, ""));
+    ///         Ok(Fields { name, value })
+    ///     }
+    /// }
+    ///
+    /// let pair = Pair::new(Header(String::from("Host: example.com")));
+    /// let fields: OwnedFields = pair.to_detached();
+    /// assert_eq!(
+    ///     fields,
+    ///     OwnedFields {
+    ///         name: String::from("Host"),
+    ///         value: String::from("example.com"),
+    ///     }
+    /// );
+    /// ```
+    pub fn to_detached<T>(&self) -> T
+    where
+        T: for<'borrow, 'any> From<&'borrow Dependent<'any, O>>,
+    {
+        self.with_dependent(|dependent| T::from(dependent))
+    }
+
+    /// Returns the number of elements in the dependent, as given by
+    /// [`DependentInspect::len`].
+    pub fn dependent_len(&self) -> usize
+    where
+        for<'any> Dependent<'any, O>: DependentInspect,
+    {
+        self.with_dependent(|dependent| dependent.len())
+    }
+
+    /// Returns `true` if the dependent contains no elements, as given by
+    /// [`DependentInspect::is_empty`].
+    pub fn dependent_is_empty(&self) -> bool
+    where
+        for<'any> Dependent<'any, O>: DependentInspect,
+    {
+        self.with_dependent(|dependent| dependent.is_empty())
+    }
+
+    /// Returns `true` if the dependent is valid, as given by
+    /// [`DependentInspect::is_valid`].
+    pub fn dependent_is_valid(&self) -> bool
+    where
+        for<'any> Dependent<'any, O>: DependentInspect,
+    {
+        self.with_dependent(|dependent| dependent.is_valid())
+    }
+
     /// Calls the given closure, providing exclusive access to the dependent,
     /// and returns the value computed by the closure.
     ///
@@ -274,6 +1095,7 @@ impl<O: Owner + ?Sized> Pair<O> {
     /// lasting from the construction of `self` until drop) is inexpressible.
     ///
     /// [`Dependent`]: crate::HasDependent::Dependent
+    #[inline]
     pub fn with_dependent_mut<'self_borrow, F, T>(&'self_borrow mut self, f: F) -> T
     where
         F: for<'any> FnOnce(&'self_borrow mut Dependent<'_, O>) -> T,
@@ -284,47 +1106,764 @@ impl<O: Owner + ?Sized> Pair<O> {
     /// Calls the given closure, providing shared access to both the owner and
     /// the dependent, and returns the value computed by the closure.
     ///
-    /// The closure must be able to work with a [`Dependent`] with any arbitrary
-    /// lifetime that lives at least as long as the borrow of `self`. See the
-    /// documentation of [`with_dependent`](Pair::with_dependent) for more
-    /// information on this.
+    /// The closure must be able to work with a [`Dependent`] with any arbitrary
+    /// lifetime that lives at least as long as the borrow of `self`. See the
+    /// documentation of [`with_dependent`](Pair::with_dependent) for more
+    /// information on this.
+    ///
+    /// [`Dependent`]: crate::HasDependent::Dependent
+    pub fn with_both<'self_borrow, F, T>(&'self_borrow self, f: F) -> T
+    where
+        F: for<'any> FnOnce(&'self_borrow O, &'self_borrow Dependent<'_, O>) -> T,
+    {
+        self.with_dependent(|dependent| f(self.owner(), dependent))
+    }
+
+    /// Calls the given closure, providing shared access to the owner and
+    /// exclusive access to the dependent, and returns the value computed by the
+    /// closure.
+    ///
+    /// The closure must be able to work with a [`Dependent`] with any arbitrary
+    /// lifetime that lives at least as long as the borrow of `self`. See the
+    /// documentation of [`with_dependent_mut`](Pair::with_dependent_mut) for
+    /// more information on this.
+    ///
+    /// [`Dependent`]: crate::HasDependent::Dependent
+    #[inline]
+    pub fn with_both_mut<'self_borrow, F, T>(&'self_borrow mut self, f: F) -> T
+    where
+        F: for<'any> FnOnce(&'self_borrow O, &'self_borrow mut Dependent<'_, O>) -> T,
+    {
+        let owner: &O = self.owner();
+
+        let dependent = no_panic(|| {
+            // SAFETY: `self.dependent` was originally converted from a valid
+            // Box<Dependent<'_, O>>, and type-erased to a NonNull<()>. As such,
+            // it inherited the alignment and validity guarantees of Box (for a
+            // Dependent<'_, O>) - and neither our code nor any of our exposed
+            // APIs could have invalidated those since construction.
+            // Additionally, because we have an exclusive reference to self (and
+            // Pair::owner(..) doesn't borrow the dependent), we know that the
+            // value behind the pointer is currently not borrowed at all, and
+            // can't be until our exclusive borrow of `self` expires.
+            unsafe { self.dependent.cast::<Dependent<'_, O>>().as_mut() }
+        });
+
+        f(owner, dependent)
+    }
+
+    /// Calls the given fallible closure, providing shared access to the
+    /// dependent, and returns the result computed by the closure.
+    ///
+    /// This is equivalent to [`with_dependent`](Pair::with_dependent), but
+    /// since the closure is known to return a [`Result`], its error type can
+    /// usually be inferred from the surrounding code - so `?` can be used both
+    /// inside the closure and on the returned result without annotations.
+    ///
+    /// ```
+    /// # use pair::{Dependent, HasDependent, Owner, Pair};
+    /// # use std::{convert::Infallible, num::ParseIntError};
+    /// struct Csv(String);
+    ///
+    /// impl<'owner> HasDependent<'owner> for Csv {
+    ///     type Dependent = Vec<&'owner str>;
+    /// }
+    ///
+    /// impl Owner for Csv {
+    ///     type Context<'a> = ();
+    ///     type Error = Infallible;
+    ///
+    ///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+    ///         Ok(self.0.split(',').collect())
+    ///     }
+    /// }
+    ///
+    /// fn sum(pair: &Pair<Csv>) -> Result<u32, ParseIntError> {
+    ///     pair.try_with_dependent(|fields| {
+    ///         let mut sum = 0;
+    ///         for field in fields {
+    ///             sum += field.parse::<u32>()?;
+    ///         }
+    ///         Ok(sum)
+    ///     })
+    /// }
+    ///
+    /// assert_eq!(sum(&Pair::new(Csv(String::from("1,2,3")))), Ok(6));
+    /// assert!(sum(&Pair::new(Csv(String::from("1,two,3")))).is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// If the closure returns an error.
+    pub fn try_with_dependent<'self_borrow, F, T, E>(&'self_borrow self, f: F) -> Result<T, E>
+    where
+        F: for<'any> FnOnce(&'self_borrow Dependent<'_, O>) -> Result<T, E>,
+    {
+        self.with_dependent(f)
+    }
+
+    /// Calls the given fallible closure, providing shared access to both the
+    /// owner and the dependent, and returns the result computed by the
+    /// closure.
+    ///
+    /// See the documentation of [`try_with_dependent`](Pair::try_with_dependent)
+    /// for more information.
+    ///
+    /// # Errors
+    /// If the closure returns an error.
+    pub fn try_with_both<'self_borrow, F, T, E>(&'self_borrow self, f: F) -> Result<T, E>
+    where
+        F: for<'any> FnOnce(&'self_borrow O, &'self_borrow Dependent<'_, O>) -> Result<T, E>,
+    {
+        self.with_both(f)
+    }
+
+    /// Calls the given fallible closure, providing exclusive access to the
+    /// dependent, and returns the result computed by the closure.
+    ///
+    /// Any changes the closure made to the dependent before returning an error
+    /// are kept. See the documentation of
+    /// [`try_with_dependent`](Pair::try_with_dependent) for more information.
+    ///
+    /// # Errors
+    /// If the closure returns an error.
+    pub fn try_with_dependent_mut<'self_borrow, F, T, E>(
+        &'self_borrow mut self,
+        f: F,
+    ) -> Result<T, E>
+    where
+        F: for<'any> FnOnce(&'self_borrow mut Dependent<'_, O>) -> Result<T, E>,
+    {
+        self.with_dependent_mut(f)
+    }
+
+    /// Calls the given fallible closure, providing shared access to the owner
+    /// and exclusive access to the dependent, and returns the result computed
+    /// by the closure.
+    ///
+    /// Any changes the closure made to the dependent before returning an error
+    /// are kept. See the documentation of
+    /// [`try_with_dependent`](Pair::try_with_dependent) for more information.
+    ///
+    /// # Errors
+    /// If the closure returns an error.
+    pub fn try_with_both_mut<'self_borrow, F, T, E>(&'self_borrow mut self, f: F) -> Result<T, E>
+    where
+        F: for<'any> FnOnce(&'self_borrow O, &'self_borrow mut Dependent<'_, O>) -> Result<T, E>,
+    {
+        self.with_both_mut(f)
+    }
+
+    /// Calls the given closure with shared references to the owner and the
+    /// dependent, and returns its result - typically, whether the pair
+    /// satisfies some consistency invariant.
+    ///
+    /// This is a convenience for checking invariants in tests and assertions.
+    /// To define an invariant once for every pair with a given owner, see
+    /// [`Owner::verify_dependent`] and
+    /// [`debug_assert_verified`](Pair::debug_assert_verified).
+    ///
+    /// ```
+    /// # use pair::Pair;
+    /// # use std::convert::Infallible;
+    /// # #[derive(Debug)]
+    /// # struct Document(String);
+    /// # impl<'owner> pair::HasDependent<'owner> for Document {
+    /// #     type Dependent = Vec<&'owner str>;
+    /// # }
+    /// # impl pair::Owner for Document {
+    /// #     type Context<'a> = ();
+    /// #     type Error = Infallible;
+    /// #     fn make_dependent(&self, (): ()) -> Result<pair::Dependent<'_, Self>, Infallible> {
+    /// #         Ok(self.0.lines().collect())
+    /// #     }
+    /// # }
+    /// let pair = Pair::new(Document(String::from("a\nb\nc")));
+    ///
+    /// assert!(pair.verify(|document, lines| lines.len() == document.0.lines().count()));
+    /// ```
+    pub fn verify<F>(&self, check: F) -> bool
+    where
+        F: for<'any> FnOnce(&O, &Dependent<'any, O>) -> bool,
+    {
+        self.with_both(|owner, dependent| check(owner, dependent))
+    }
+
+    /// Asserts (in debug builds only) that the dependent is consistent with
+    /// the owner, according to [`Owner::verify_dependent`].
+    ///
+    /// With the `debug-verify` feature, this is called automatically after
+    /// every construction of a pair, and every rebuild of its dependent.
+    ///
+    /// # Panics
+    /// In debug builds, if [`verify_dependent`](Owner::verify_dependent)
+    /// returns `false`.
+    #[track_caller]
+    pub fn debug_assert_verified(&self) {
+        debug_assert!(
+            self.with_both(|owner, dependent| owner.verify_dependent(dependent)),
+            "the dependent of a pair is inconsistent with its owner"
+        );
+    }
+
+    /// Calls the given closure, providing shared access to the owner and
+    /// exclusive access to the dependent, and returns the value computed by the
+    /// closure.
+    ///
+    /// Unlike [`with_both_mut`](Pair::with_both_mut), the owner is borrowed for
+    /// the same lifetime as the dependent's borrow of it, so the closure may
+    /// store new borrows of the owner in the dependent.
+    pub(crate) fn with_owner_and_dependent_mut<F, T>(&mut self, f: F) -> T
+    where
+        F: for<'owner> FnOnce(&'owner O, &mut Dependent<'owner, O>) -> T,
+    {
+        // SAFETY: `self.owner` was originally converted from a valid Box, and
+        // inherited the alignment and validity guarantees of Box - and neither
+        // our code nor any of our exposed APIs could have invalidated those
+        // since construction. Additionally, the value behind the pointer is
+        // currently in a shared borrow state (by the dependent), and will be
+        // until the Pair is dropped. Here, we only add another shared borrow,
+        // which the closure may give to the dependent - just like the borrow
+        // given to `make_dependent` during construction.
+        let owner: &O = unsafe { self.owner.as_ref() };
+
+        // SAFETY: `self.dependent` was originally converted from a valid
+        // Box<Dependent<'_, O>>, and type-erased to a NonNull<()>. As such, it
+        // inherited the alignment and validity guarantees of Box (for a
+        // Dependent<'_, O>) - and neither our code nor any of our exposed APIs
+        // could have invalidated those since construction. Additionally,
+        // because we have an exclusive reference to self (and the owner
+        // reference above doesn't borrow the dependent), we know that the value
+        // behind the pointer is currently not borrowed at all, and can't be
+        // until our exclusive borrow of `self` expires.
+        let dependent = unsafe { self.dependent.cast::<Dependent<'_, O>>().as_mut() };
+
+        f(owner, dependent)
+    }
+
+    /// Returns an exclusive reference to the owner.
+    ///
+    /// # Safety
+    /// The dependent must not hold any borrows of the owner - for example,
+    /// because it was replaced with a value constructed without access to the
+    /// owner. It must not be given any new borrows of the owner until the
+    /// returned reference has expired.
+    pub(crate) unsafe fn owner_mut_unchecked(&mut self) -> &mut O {
+        // SAFETY: `self.owner` was originally converted from a valid Box, and
+        // inherited the alignment and validity guarantees of Box - and neither
+        // our code nor any of our exposed APIs could have invalidated those
+        // since construction. The caller guarantees that the dependent doesn't
+        // borrow the owner, and because we have an exclusive reference to
+        // self, nothing else can borrow it until our borrow of `self` expires.
+        unsafe { self.owner.as_mut() }
+    }
+
+    /// Suspends the dependent, returning a [`SuspendedPair`] guard which
+    /// provides exclusive access to the owner, and rebuilds the dependent when
+    /// it is dropped.
+    ///
+    /// While suspended, the dependent holds its [`Default`] value. See the
+    /// documentation of [`SuspendedPair`] for more information.
+    pub fn suspend_dependent(&mut self) -> SuspendedPair<'_, O>
+    where
+        O: for<'any> Owner<Context<'any> = ()>,
+        for<'any> Dependent<'any, O>: Default,
+    {
+        SuspendedPair::new(self, &())
+    }
+
+    /// Consumes the [`Pair`], calls the given closure with exclusive access to
+    /// the owner, and then rebuilds the dependent from the (possibly mutated)
+    /// owner. Returns the rebuilt pair, along with the value computed by the
+    /// closure.
+    ///
+    /// The dependent is dropped before the closure is called. If the closure
+    /// panics, the owner is dropped during unwinding.
+    ///
+    /// # Errors
+    /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
+    /// error. In that case, the (mutated) owner is returned alongside the
+    /// error.
+    pub fn try_with_owner_mut<F, T>(self, f: F) -> Result<(Self, T), (Box<O>, O::Error)>
+    where
+        O: for<'any> Owner<Context<'any> = ()>,
+        F: FnOnce(&mut O) -> T,
+    {
+        let mut owner = self.into_boxed_owner();
+        let output = f(&mut owner);
+        let pair = Self::try_new_from_box_with_context(owner, ())?;
+
+        Ok((pair, output))
+    }
+
+    /// Calls the given closure, providing exclusive access to the owner, and
+    /// then rebuilds the dependent from the (possibly mutated) owner. Returns
+    /// the value computed by the closure.
+    ///
+    /// The dependent is dropped in place before the closure is called, and
+    /// rebuilt afterwards - even if the closure panics.
+    ///
+    /// # Panics
+    /// If [`make_dependent`](Owner::make_dependent) panics while rebuilding the
+    /// dependent, the process is aborted, since the pair would otherwise be
+    /// left without a dependent.
+    pub fn with_owner_mut<F, T>(&mut self, f: F) -> T
+    where
+        O: for<'any> Owner<Context<'any> = (), Error = Infallible>,
+        F: FnOnce(&mut O) -> T,
+    {
+        let owner = self.owner;
+        let dependent = self.dependent.cast::<Dependent<'_, O>>();
+
+        // Once the old dependent is dropped, the pair must get a new one before
+        // anything else can observe it - so this guard rebuilds the dependent
+        // when it's dropped, whether or not we're unwinding.
+        let rebuild_guard = DropGuard(move || {
+            // If `make_dependent(..)` panics, there is no dependent to leave in
+            // the pair. This guard's panic will then be a double-panic, which
+            // will cause an abort.
+            let abort_guard = DropGuard(|| panic!("`make_dependent` panicked in `with_owner_mut`"));
+
+            // SAFETY: `owner` was originally converted from a valid Box, and
+            // inherited the alignment and validity guarantees of Box - and
+            // neither our code nor any of our exposed APIs could have
+            // invalidated those since construction. The exclusive borrow given
+            // to the closure has expired (since the closure returned or
+            // panicked), so here we only add a shared borrow, which is given
+            // to the new dependent.
+            let Ok(new_dependent) = unsafe { owner.as_ref() }.make_dependent(());
+
+            // SAFETY: `dependent` points to the pair's dependent allocation,
+            // whose value was dropped in place below, so it is valid for writes
+            // of a Dependent<'_, O> and overwriting it doesn't leak anything.
+            unsafe { dependent.as_ptr().write(new_dependent) };
+
+            core::mem::forget(abort_guard);
+        });
+
+        // SAFETY: `dependent` was originally converted from a valid
+        // Box<Dependent<'_, O>>, and inherited the alignment and validity
+        // guarantees of Box - and neither our code nor any of our exposed APIs
+        // could have invalidated those since construction. Because we have an
+        // exclusive reference to self, the dependent is not borrowed at all.
+        // It's rebuilt by the guard above before the pair can be observed
+        // again, so it's never dropped twice (even if its drop panics).
+        unsafe { core::ptr::drop_in_place(dependent.as_ptr()) };
+
+        // SAFETY: `self.owner` was originally converted from a valid Box, and
+        // inherited the alignment and validity guarantees of Box - and neither
+        // our code nor any of our exposed APIs could have invalidated those
+        // since construction. The dependent (which held the only borrows of
+        // the owner) was just dropped, and because we have an exclusive
+        // reference to self, nothing else can borrow the owner until the
+        // closure returns.
+        let output = f(unsafe { self.owner.as_mut() });
+
+        drop(rebuild_guard);
+
+        #[cfg(feature = "debug-verify")]
+        self.debug_assert_verified();
+
+        output
+    }
+
+    /// Returns a [`Debug`] adapter which formats only the owner, never
+    /// touching the dependent.
+    ///
+    /// See the documentation of [`DebugOwnerOnly`] for more information.
+    pub fn debug_owner_only(&self) -> DebugOwnerOnly<'_, O> {
+        DebugOwnerOnly::new(self)
+    }
+
+    /// Returns an [`AccessGuard`], providing shared access to both the owner
+    /// and the dependent for the duration of the borrow of `self`.
+    ///
+    /// This is useful when reading from the pair many times within a single
+    /// borrow region, such as in a hot loop. See the documentation of
+    /// [`AccessGuard`] for more information.
+    pub fn access(&self) -> AccessGuard<'_, O> {
+        // SAFETY: `self.dependent` was originally converted from a valid
+        // Box<Dependent<'_, O>>, and type-erased to a NonNull<()>. As such, it
+        // inherited the alignment and validity guarantees of Box (for a
+        // Dependent<'_, O>) - and neither our code nor any of our exposed APIs
+        // could have invalidated those since construction. Additionally,
+        // because we have a shared reference to self, we know that the value
+        // behind the pointer is currently either not borrowed at all, or in a
+        // shared borrow state (no exclusive borrows, no other code assuming
+        // unique ownership). Here, we only either create the first shared
+        // borrow, or add another. The `AccessGuard` only exposes this borrow
+        // through closures generic over the dependent's lifetime.
+        let dependent = unsafe { self.dependent.cast::<Dependent<'_, O>>().as_ref() };
+
+        AccessGuard::new(self.owner(), dependent)
+    }
+
+    /// Returns a [`DependentRef`], providing shared access to the dependent
+    /// (through [`Deref`](core::ops::Deref)) for the duration of the borrow of
+    /// `self`.
+    ///
+    /// This is only available for owners whose dependent is covariant - see
+    /// the documentation of [`CovariantDependent`] for more information.
+    pub fn dependent_ref(&self) -> DependentRef<'_, O>
+    where
+        O: CovariantDependent,
+    {
+        // SAFETY: `self.dependent` was originally converted from a valid
+        // Box<Dependent<'_, O>>, and type-erased to a NonNull<()>. As such, it
+        // inherited the alignment and validity guarantees of Box (for a
+        // Dependent<'_, O>) - and neither our code nor any of our exposed APIs
+        // could have invalidated those since construction. Additionally,
+        // because we have a shared reference to self, we know that the value
+        // behind the pointer is currently either not borrowed at all, or in a
+        // shared borrow state. Here, we only either create the first shared
+        // borrow, or add another. Finally, `O: CovariantDependent` guarantees
+        // the dependent is covariant, so its real lifetime (which outlives the
+        // borrow of `self`) may be shortened to that of the borrow of `self`.
+        let dependent = unsafe { self.dependent.cast::<Dependent<'_, O>>().as_ref() };
+
+        DependentRef::new(dependent)
+    }
+
+    /// Returns a reference to the dependent, valid for the duration of the
+    /// borrow of `self`.
+    ///
+    /// This is only available for owners whose dependent is covariant - see
+    /// the documentation of [`CovariantDependent`] for more information. For
+    /// exclusive access, see [`dependent_mut`](Pair::dependent_mut).
+    ///
+    /// ```
+    /// # use pair::{CovariantDependent, Dependent, HasDependent, Owner, Pair};
+    /// # use std::convert::Infallible;
+    /// struct Csv(String);
+    ///
+    /// impl<'owner> HasDependent<'owner> for Csv {
+    ///     type Dependent = Vec<&'owner str>;
+    /// }
+    ///
+    /// impl Owner for Csv {
+    ///     type Context<'a> = ();
+    ///     type Error = Infallible;
+    ///
+    ///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+    ///         Ok(self.0.split(',').collect())
+    ///     }
+    /// }
+    ///
+    /// // SAFETY: `Vec<&'owner str>` is covariant over `'owner`.
+    /// unsafe impl CovariantDependent for Csv {}
+    ///
+    /// let pair = Pair::new(Csv(String::from("a,b,c")));
+    /// let fields: &[&str] = pair.dependent();
+    /// assert_eq!(fields, ["a", "b", "c"]);
+    /// ```
+    pub fn dependent(&self) -> &Dependent<'_, O>
+    where
+        O: CovariantDependent,
+    {
+        DependentRef::into_ref(self.dependent_ref())
+    }
+
+    /// Returns a [`DependentRefMut`], providing exclusive access to the
+    /// dependent for the duration of the exclusive borrow of `self`.
+    ///
+    /// This allows a long-lived mutable session with the dependent to be split
+    /// across many statements, rather than pushed into a single
+    /// [`with_dependent_mut`](Pair::with_dependent_mut) closure. See the
+    /// documentation of [`DependentRefMut`] for more information.
+    pub fn dependent_mut(&mut self) -> DependentRefMut<'_, O> {
+        // SAFETY: `self.dependent` was originally converted from a valid
+        // Box<Dependent<'_, O>>, and type-erased to a NonNull<()>. As such, it
+        // inherited the alignment and validity guarantees of Box (for a
+        // Dependent<'_, O>) - and neither our code nor any of our exposed APIs
+        // could have invalidated those since construction. Additionally,
+        // because we have an exclusive reference to self, we know that the
+        // value behind the pointer is currently not borrowed at all, and can't
+        // be until our exclusive borrow of `self` expires. The
+        // `DependentRefMut` only exposes this borrow mutably through closures
+        // generic over the dependent's lifetime (or when the dependent doesn't
+        // depend on its lifetime at all).
+        let dependent = unsafe { self.dependent.cast::<Dependent<'_, O>>().as_mut() };
+
+        DependentRefMut::new(dependent)
+    }
+
+    /// Returns a raw pointer to the dependent.
+    ///
+    /// This is intended for engines which index dependents by address (such as
+    /// in intrusive or custom hash tables). The pointer may be compared and
+    /// hashed freely. It is valid to read through as long as:
+    /// - the pair has not been dropped or consumed, and
+    /// - the pair has not been exclusively borrowed since this call, since any
+    ///   method taking `&mut self` may replace the dependent, and
+    /// - the dependent is not exclusively borrowed during the read.
+    ///
+    /// Since the dependent is heap-allocated, moving the pair does not
+    /// invalidate the pointer. The returned pointer's type borrows the pair
+    /// for `'pair`, though - to keep an address across moves, erase it first
+    /// (for example, by casting it to `*const ()`), and cast it back
+    /// to a `*const Dependent<'_, O>` with a fresh borrow of the pair before
+    /// reading through it.
+    ///
+    /// The `'pair` lifetime is not the real lifetime of the dependent (which
+    /// is inexpressible). Unless the owner implements [`CovariantDependent`],
+    /// a reference created from this pointer must not be used to store
+    /// borrows in the dependent.
+    ///
+    /// ```
+    /// # use pair::{Dependent, HasDependent, Owner, Pair};
+    /// # use std::convert::Infallible;
+    /// struct Text(String);
+    ///
+    /// impl<'owner> HasDependent<'owner> for Text {
+    ///     type Dependent = Vec<&'owner str>;
+    /// }
+    ///
+    /// impl Owner for Text {
+    ///     type Context<'a> = ();
+    ///     type Error = Infallible;
+    ///
+    ///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+    ///         Ok(self.0.split_whitespace().collect())
+    ///     }
+    /// }
+    ///
+    /// let pair = Pair::new(Text(String::from("raw access")));
+    /// let address = pair.dependent_raw_for().cast::<()>();
+    ///
+    /// // Moving the pair doesn't move the dependent
+    /// let pairs = vec![pair];
+    /// let dependent = address.cast::<Dependent<'_, Text>>();
+    /// assert_eq!(dependent, pairs[0].dependent_raw_for());
+    ///
+    /// // SAFETY: the pair is alive, and hasn't been exclusively borrowed since
+    /// // the pointer was created.
+    /// let words = unsafe { &*dependent };
+    /// assert_eq!(words[1], "access");
+    /// ```
+    pub fn dependent_raw_for<'pair>(&'pair self) -> *const Dependent<'pair, O> {
+        self.dependent
+            .cast::<Dependent<'pair, O>>()
+            .as_ptr()
+            .cast_const()
+    }
+
+    /// Returns a reference to the owner along with an [`AccessGuard`], both
+    /// valid for the duration of the borrow of `self`.
+    ///
+    /// This is a closure-free alternative to [`Pair::with_both`], for passing
+    /// the owner and a view of the dependent into separate function arguments.
+    ///
+    /// ```
+    /// # use pair::{AccessGuard, Dependent, HasDependent, Owner, Pair};
+    /// # use std::convert::Infallible;
+    /// struct Csv(String);
+    ///
+    /// impl<'owner> HasDependent<'owner> for Csv {
+    ///     type Dependent = Vec<&'owner str>;
+    /// }
+    ///
+    /// impl Owner for Csv {
+    ///     type Context<'a> = ();
+    ///     type Error = Infallible;
+    ///
+    ///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+    ///         Ok(self.0.split(',').collect())
+    ///     }
+    /// }
+    ///
+    /// fn summarize(csv: &Csv, fields: AccessGuard<'_, Csv>) -> String {
+    ///     let count = fields.with_dependent(|fields| fields.len());
+    ///     format!("{count} fields in {} bytes", csv.0.len())
+    /// }
+    ///
+    /// let pair = Pair::new(Csv(String::from("a,b,c")));
+    /// let (csv, fields) = pair.as_parts_ref();
+    /// assert_eq!(summarize(csv, fields), "3 fields in 5 bytes");
+    /// ```
+    pub fn as_parts_ref(&self) -> (&O, AccessGuard<'_, O>) {
+        (self.owner(), self.access())
+    }
+
+    /// Recomputes the dependent from the owner with the given context,
+    /// replacing (and dropping) the current dependent.
+    ///
+    /// The new dependent is fully constructed before the old one is dropped,
+    /// so if [`make_dependent`](Owner::make_dependent) returns an error (or
+    /// panics), the pair is left untouched with its old dependent. This makes
+    /// it safe to retry with a different context.
+    ///
+    /// See the documentation of [`Pair::remake_dependent`] for more
+    /// information.
+    ///
+    /// ```
+    /// # use pair::{Dependent, HasDependent, Owner, Pair};
+    /// #[derive(Debug)]
+    /// struct Numbers(String);
+    ///
+    /// impl HasDependent<'_> for Numbers {
+    ///     type Dependent = Vec<u32>;
+    /// }
+    ///
+    /// impl Owner for Numbers {
+    ///     type Context<'a> = u32;
+    ///     type Error = std::num::ParseIntError;
+    ///
+    ///     fn make_dependent(&self, radix: u32) -> Result<Dependent<'_, Self>, Self::Error> {
+    ///         self.0
+    ///             .split(',')
+    ///             .map(|number| u32::from_str_radix(number, radix))
+    ///             .collect()
+    ///     }
+    /// }
+    ///
+    /// let mut pair = Pair::try_new_with_context(Numbers(String::from("a,ff")), 16).unwrap();
+    /// assert_eq!(pair.with_dependent(|numbers| numbers.clone()), [10, 255]);
+    ///
+    /// // The numbers aren't decimal, so the old dependent is kept
+    /// assert!(pair.try_remake_dependent(10).is_err());
+    /// assert_eq!(pair.with_dependent(|numbers| numbers.clone()), [10, 255]);
+    ///
+    /// pair.try_remake_dependent(36).unwrap();
+    /// assert_eq!(pair.with_dependent(|numbers| numbers.clone()), [10, 555]);
+    /// ```
+    ///
+    /// # Errors
+    /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
+    /// error. In that case, the current dependent is left intact.
+    pub fn try_remake_dependent(&mut self, context: O::Context<'_>) -> Result<(), O::Error> {
+        self.try_replace_dependent(context)
+    }
+
+    /// Recomputes the dependent from the owner with the given context,
+    /// replacing (and dropping) the current dependent.
+    ///
+    /// This is useful when the context a dependent was constructed with has
+    /// changed, since the owner doesn't need to be moved or re-boxed. For
+    /// owners whose `make_dependent` can fail, see
+    /// [`Pair::try_remake_dependent`]. To store
+    /// the context alongside the pair instead, see
+    /// [`ContextPair`](crate::ContextPair).
+    ///
+    /// ```
+    /// # use pair::{Dependent, HasDependent, Owner, Pair};
+    /// # use std::convert::Infallible;
+    /// struct Record(String);
+    ///
+    /// impl<'owner> HasDependent<'owner> for Record {
+    ///     type Dependent = Vec<&'owner str>;
+    /// }
+    ///
+    /// impl Owner for Record {
+    ///     type Context<'a> = char;
+    ///     type Error = Infallible;
+    ///
+    ///     fn make_dependent(&self, delimiter: char) -> Result<Dependent<'_, Self>, Self::Error> {
+    ///         Ok(self.0.split(delimiter).collect())
+    ///     }
+    /// }
     ///
-    /// [`Dependent`]: crate::HasDependent::Dependent
-    pub fn with_both<'self_borrow, F, T>(&'self_borrow self, f: F) -> T
+    /// let mut pair = Pair::new_with_context(Record(String::from("a,b;c,d")), ',');
+    /// assert_eq!(pair.dependent_len(), 3);
+    ///
+    /// pair.remake_dependent(';');
+    /// assert_eq!(pair.with_dependent(|fields| fields.clone()), ["a,b", "c,d"]);
+    /// ```
+    pub fn remake_dependent(&mut self, context: O::Context<'_>)
     where
-        F: for<'any> FnOnce(&'self_borrow O, &'self_borrow Dependent<'_, O>) -> T,
+        O: Owner<Error = Infallible>,
     {
-        self.with_dependent(|dependent| f(self.owner(), dependent))
+        let Ok(()) = self.try_remake_dependent(context);
     }
 
-    /// Calls the given closure, providing shared access to the owner and
-    /// exclusive access to the dependent, and returns the value computed by the
-    /// closure.
+    /// Computes a new dependent from the owner and the given context, and
+    /// replaces the current dependent with it (dropping the old dependent).
     ///
-    /// The closure must be able to work with a [`Dependent`] with any arbitrary
-    /// lifetime that lives at least as long as the borrow of `self`. See the
-    /// documentation of [`with_dependent_mut`](Pair::with_dependent_mut) for
-    /// more information on this.
+    /// The new dependent is fully constructed before the old one is dropped,
+    /// so if [`make_dependent`](Owner::make_dependent) returns an error (or
+    /// panics), the pair is left untouched with its old dependent.
+    pub(crate) fn try_replace_dependent(
+        &mut self,
+        context: O::Context<'_>,
+    ) -> Result<(), O::Error> {
+        self.try_replace_dependent_with(context, |_, _| ())
+    }
+
+    /// Like [`try_replace_dependent`](Pair::try_replace_dependent), but calls
+    /// the given closure with the old and new dependents before the old one is
+    /// dropped, and returns the value computed by the closure.
     ///
-    /// [`Dependent`]: crate::HasDependent::Dependent
-    pub fn with_both_mut<'self_borrow, F, T>(&'self_borrow mut self, f: F) -> T
+    /// If the closure panics, the new dependent is dropped, and the pair is
+    /// left untouched with its old dependent.
+    pub(crate) fn try_replace_dependent_with<F, T>(
+        &mut self,
+        context: O::Context<'_>,
+        f: F,
+    ) -> Result<T, O::Error>
     where
-        F: for<'any> FnOnce(&'self_borrow O, &'self_borrow mut Dependent<'_, O>) -> T,
+        F: for<'owner> FnOnce(&Dependent<'owner, O>, &Dependent<'owner, O>) -> T,
     {
-        let owner: &O = self.owner();
+        let new_dependent = {
+            // SAFETY: `self.owner` was originally converted from a valid Box,
+            // and inherited the alignment and validity guarantees of Box - and
+            // neither our code nor any of our exposed APIs could have
+            // invalidated those since construction. Additionally, the value
+            // behind the pointer is currently in a shared borrow state (by the
+            // old dependent), and will be until the Pair is dropped. Here, we
+            // only add another shared borrow, which is given to the new
+            // dependent.
+            let owner: &O = unsafe { self.owner.as_ref() };
+
+            owner.make_dependent(context)?
+        };
 
         // SAFETY: `self.dependent` was originally converted from a valid
         // Box<Dependent<'_, O>>, and type-erased to a NonNull<()>. As such, it
         // inherited the alignment and validity guarantees of Box (for a
         // Dependent<'_, O>) - and neither our code nor any of our exposed APIs
         // could have invalidated those since construction. Additionally,
-        // because we have an exclusive reference to self (and Pair::owner(..)
-        // doesn't borrow the dependent), we know that the value behind the
-        // pointer is currently not borrowed at all, and can't be until our
-        // exclusive borrow of `self` expires.
-        let dependent = unsafe { self.dependent.cast::<Dependent<'_, O>>().as_mut() };
+        // because we have an exclusive reference to self, we know that the
+        // value behind the pointer is currently not borrowed at all. Here, we
+        // only add a shared borrow, which expires before the old dependent is
+        // dropped.
+        let old_dependent: &Dependent<'_, O> = unsafe { self.dependent.cast().as_ref() };
+        let output = f(old_dependent, &new_dependent);
 
-        f(owner, dependent)
+        // Move the new dependent to the heap, and type-erase it. If
+        // `Box::new(..)` panics, the new dependent is simply dropped during
+        // unwinding, and the pair still has its old dependent.
+        let new_dependent: NonNull<Dependent<'_, O>> = non_null_from_box(Box::new(new_dependent));
+        let old_dependent = core::mem::replace(&mut self.dependent, new_dependent.cast());
+
+        // SAFETY: `old_dependent` was originally created from a Box, and never
+        // invalidated since then. Because we have an exclusive reference to
+        // self, and we just replaced `self.dependent` (so nothing else can
+        // observe the old dependent), we know there are no outstanding borrows
+        // to the old dependent. Therefore, reconstructing the original
+        // Box<Dependent<'_, O>> is okay.
+        let old_dependent: Box<Dependent<'_, O>> =
+            unsafe { Box::from_raw(old_dependent.cast::<Dependent<'_, O>>().as_ptr()) };
+
+        // If the old dependent's drop panics, that's okay - the pair already
+        // holds its new dependent, and the Box's memory will still be freed
+        // during unwinding.
+        drop(old_dependent);
+
+        #[cfg(feature = "debug-verify")]
+        self.debug_assert_verified();
+
+        Ok(output)
+    }
+
+    /// Updates the dependent in place with [`Owner::update_dependent`], using
+    /// the given context.
+    pub(crate) fn try_update_dependent(&mut self, context: O::Context<'_>) -> Result<(), O::Error> {
+        self.with_owner_and_dependent_mut(|owner, dependent| {
+            owner.update_dependent(dependent, context)
+        })?;
+
+        #[cfg(feature = "debug-verify")]
+        self.debug_assert_verified();
+
+        Ok(())
     }
 
     /// Consumes the [`Pair`], dropping the dependent and returning the owner.
@@ -332,6 +1871,10 @@ impl<O: Owner + ?Sized> Pair<O> {
     /// If you don't need the returned owner in a [`Box`], consider the
     /// convenience method [`Pair::into_owner`], which moves the owner out of
     /// the box for you.
+    ///
+    /// # Panics
+    /// If the dependent's drop panics. With the `failpoints` feature, this may
+    /// also inject a panic at [`FailPoint::DropDependent`](crate::FailPoint::DropDependent).
     pub fn into_boxed_owner(self) -> Box<O> {
         // Prevent dropping `self` at the end of this scope - otherwise, the
         // Pair drop implementation would attempt to drop the owner and
@@ -342,6 +1885,9 @@ impl<O: Owner + ?Sized> Pair<O> {
         // we attempt to drop the dependent again when dropping `self`.
         let this = ManuallyDrop::new(self);
 
+        #[cfg(feature = "leak-check")]
+        crate::leak_check::unregister(this.leak_check_id);
+
         // SAFETY: `this.dependent` was originally created from a Box, and never
         // invalidated since then. Because we took ownership of `self`, we know
         // there are no outstanding borrows to the dependent. Therefore,
@@ -376,6 +1922,12 @@ impl<O: Owner + ?Sized> Pair<O> {
         // Drop the dependent
         drop(dependent);
 
+        #[cfg(feature = "failpoints")]
+        assert!(
+            !crate::failpoints::hit(crate::FailPoint::DropDependent),
+            "injected a panic while dropping a dependent"
+        );
+
         // The dependent's drop didn't panic - disarm our drop guard
         core::mem::forget(panic_drop_guard);
 
@@ -396,6 +1948,398 @@ impl<O: Owner + ?Sized> Pair<O> {
     {
         *self.into_boxed_owner()
     }
+
+    /// Consumes the [`Pair`], returning both the owner and the dependent, for
+    /// owners whose dependent doesn't actually borrow from them.
+    ///
+    /// This requires the dependent to be the same type `D` for every lifetime
+    /// of the owner - meaning it can't borrow from the owner, and is safe to
+    /// move out of the pair. This is useful for owners whose dependent is an
+    /// owned summary (such as a hash, or counts), which would be wasteful to
+    /// drop with [`Pair::into_boxed_owner`].
+    ///
+    /// If you don't need the returned owner in a [`Box`], consider the
+    /// convenience method [`Pair::into_parts`], which moves the owner out of
+    /// the box for you.
+    pub fn into_boxed_parts<D>(self) -> (Box<O>, D)
+    where
+        O: for<'any> HasDependent<'any, Dependent = D>,
+    {
+        // Prevent dropping `self` at the end of this scope - otherwise, the
+        // Pair drop implementation would attempt to drop the owner and
+        // dependent again, which would be... not good (unsound).
+        let this = ManuallyDrop::new(self);
+
+        #[cfg(feature = "leak-check")]
+        crate::leak_check::unregister(this.leak_check_id);
+
+        // SAFETY: `this.dependent` was originally created from a Box, and never
+        // invalidated since then. Because we took ownership of `self`, we know
+        // there are no outstanding borrows to the dependent. The dependent is a
+        // `D` for every lifetime of the owner, so it doesn't borrow from the
+        // owner, and can safely outlive it.
+        let dependent: Box<D> = unsafe { Box::from_raw(this.dependent.cast::<D>().as_ptr()) };
+
+        // SAFETY: `this.owner` was originally created from a Box, and never
+        // invalidated since then. Because we took ownership of `self`, and the
+        // dependent doesn't borrow from the owner, we know there are no
+        // outstanding borrows to owner. Therefore, reconstructing the original
+        // Box<O> is okay.
+        let owner: Box<O> = unsafe { Box::from_raw(this.owner.as_ptr()) };
+
+        (owner, *dependent)
+    }
+
+    /// Consumes the [`Pair`], returning both the owner and the dependent, for
+    /// owners whose dependent doesn't actually borrow from them.
+    ///
+    /// See the documentation of [`Pair::into_boxed_parts`] for more
+    /// information.
+    ///
+    /// ```
+    /// # use pair::{Dependent, HasDependent, Owner, Pair};
+    /// # use std::convert::Infallible;
+    /// struct Document(String);
+    ///
+    /// // Counts of the words and lines, which don't borrow from the document
+    /// impl HasDependent<'_> for Document {
+    ///     type Dependent = (usize, usize);
+    /// }
+    ///
+    /// impl Owner for Document {
+    ///     type Context<'a> = ();
+    ///     type Error = Infallible;
+    ///
+    ///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+    ///         Ok((self.0.split_whitespace().count(), self.0.lines().count()))
+    ///     }
+    /// }
+    ///
+    /// let pair = Pair::new(Document(String::from("one two\nthree")));
+    /// let (document, counts) = pair.into_parts();
+    /// assert_eq!(document.0, "one two\nthree");
+    /// assert_eq!(counts, (3, 2));
+    /// ```
+    pub fn into_parts<D>(self) -> (O, D)
+    where
+        O: for<'any> HasDependent<'any, Dependent = D> + Sized,
+    {
+        let (owner, dependent) = self.into_boxed_parts();
+        (*owner, dependent)
+    }
+
+    /// Consumes the [`Pair`], dropping the owner and returning the dependent,
+    /// for owners whose dependent doesn't actually borrow from them.
+    ///
+    /// Like [`Pair::into_parts`], this requires the dependent to be the same
+    /// type `D` for every lifetime of the owner (such as a `'static` type). This
+    /// is useful when the owner is only a source for computing the dependent,
+    /// and is no longer needed once the dependent exists.
+    ///
+    /// ```
+    /// # use pair::{Dependent, HasDependent, Owner, Pair};
+    /// # use std::convert::Infallible;
+    /// struct Source(Vec<u8>);
+    ///
+    /// // A checksum of the source, which doesn't borrow from it
+    /// impl HasDependent<'_> for Source {
+    ///     type Dependent = u32;
+    /// }
+    ///
+    /// impl Owner for Source {
+    ///     type Context<'a> = ();
+    ///     type Error = Infallible;
+    ///
+    ///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+    ///         Ok(self.0.iter().map(|&byte| u32::from(byte)).sum())
+    ///     }
+    /// }
+    ///
+    /// let pair = Pair::new(Source(vec![1, 2, 3]));
+    /// assert_eq!(pair.into_dependent(), 6);
+    /// ```
+    pub fn into_dependent<D>(self) -> D
+    where
+        O: for<'any> HasDependent<'any, Dependent = D>,
+    {
+        let (_, dependent) = self.into_boxed_parts();
+        dependent
+    }
+
+    /// Consumes the [`Pair`], transforming its owner with `f` into a new owner
+    /// (possibly of a different type), and constructs a new `Pair` from it
+    /// with the given context.
+    ///
+    /// The dependent is dropped before `f` is called, and the new pair's
+    /// dependent is computed through [`Owner::make_dependent`]. This is useful
+    /// for pipelines where one owner (such as a raw buffer) is normalized into
+    /// another.
+    ///
+    /// # Errors
+    /// If [`<O2 as Owner>::make_dependent`](Owner::make_dependent) returns an
+    /// error. The new owner is returned alongside the error.
+    #[track_caller]
+    pub fn try_map_owner_with_context<O2: Owner, F: FnOnce(O) -> O2>(
+        self,
+        context: O2::Context<'_>,
+        f: F,
+    ) -> Result<Pair<O2>, (O2, O2::Error)>
+    where
+        O: Sized,
+    {
+        Pair::try_new_with_context(f(self.into_owner()), context)
+    }
+
+    /// Consumes the [`Pair`], transforming its owner with `f` into a new owner
+    /// (possibly of a different type), and constructs a new `Pair` from it.
+    ///
+    /// See the documentation of [`Pair::try_map_owner_with_context`] for more
+    /// information.
+    ///
+    /// # Errors
+    /// If [`<O2 as Owner>::make_dependent`](Owner::make_dependent) returns an
+    /// error. The new owner is returned alongside the error.
+    #[track_caller]
+    pub fn try_map_owner<O2, F>(self, f: F) -> Result<Pair<O2>, (O2, O2::Error)>
+    where
+        O: Sized,
+        O2: for<'any> Owner<Context<'any> = ()>,
+        F: FnOnce(O) -> O2,
+    {
+        self.try_map_owner_with_context((), f)
+    }
+
+    /// Consumes the [`Pair`], transforming its owner with `f` into a new owner
+    /// (possibly of a different type), and constructs a new `Pair` from it.
+    ///
+    /// See the documentation of [`Pair::try_map_owner_with_context`] for more
+    /// information.
+    ///
+    /// ```
+    /// # use pair::{Dependent, HasDependent, Owner, Pair};
+    /// # use std::convert::Infallible;
+    /// // A raw buffer, split into lines
+    /// struct Raw(Vec<u8>);
+    ///
+    /// impl<'owner> HasDependent<'owner> for Raw {
+    ///     type Dependent = Vec<&'owner [u8]>;
+    /// }
+    ///
+    /// impl Owner for Raw {
+    ///     type Context<'a> = ();
+    ///     type Error = Infallible;
+    ///
+    ///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+    ///         Ok(self.0.split(|&byte| byte == b'\n').collect())
+    ///     }
+    /// }
+    ///
+    /// // Normalized text, split into words
+    /// struct Text(String);
+    ///
+    /// impl<'owner> HasDependent<'owner> for Text {
+    ///     type Dependent = Vec<&'owner str>;
+    /// }
+    ///
+    /// impl Owner for Text {
+    ///     type Context<'a> = ();
+    ///     type Error = Infallible;
+    ///
+    ///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+    ///         Ok(self.0.split_whitespace().collect())
+    ///     }
+    /// }
+    ///
+    /// let raw = Pair::new(Raw(b"Hello,\nworld!".to_vec()));
+    /// assert_eq!(raw.dependent_len(), 2);
+    ///
+    /// let text = raw.map_owner(|Raw(bytes)| Text(String::from_utf8_lossy(&bytes).to_lowercase()));
+    /// assert_eq!(text.with_dependent(|words| words.join(" ")), "hello, world!");
+    /// ```
+    #[track_caller]
+    pub fn map_owner<O2, F>(self, f: F) -> Pair<O2>
+    where
+        O: Sized,
+        O2: for<'any> Owner<Context<'any> = (), Error = Infallible>,
+        F: FnOnce(O) -> O2,
+    {
+        let Ok(pair) = self.try_map_owner(f);
+        pair
+    }
+
+    /// Consumes the [`Pair`] without dropping the owner or dependent,
+    /// intentionally leaking both of their allocations, and returns a pointer
+    /// to the owner.
+    ///
+    /// This is useful for programs which construct pairs that live until
+    /// exit, where running their drops at shutdown is undesirable, and for
+    /// handing an owner off across an FFI boundary.
+    ///
+    /// The returned pointer is valid for reads forever. Since the leaked
+    /// dependent may still borrow from the owner, the owner must never be
+    /// mutated (except through interior mutability), moved out of, or
+    /// deallocated - but it is sound to create shared references to it with
+    /// any lifetime, including `'static`.
+    ///
+    /// ```
+    /// # use pair::{Dependent, HasDependent, Owner, Pair};
+    /// # use std::convert::Infallible;
+    /// struct Config(String);
+    ///
+    /// impl<'owner> HasDependent<'owner> for Config {
+    ///     type Dependent = Vec<&'owner str>;
+    /// }
+    ///
+    /// impl Owner for Config {
+    ///     type Context<'a> = ();
+    ///     type Error = Infallible;
+    ///
+    ///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+    ///         Ok(self.0.lines().collect())
+    ///     }
+    /// }
+    ///
+    /// # // Miri reports the intentional leak
+    /// # #[cfg(miri)] fn main() {}
+    /// # #[cfg(not(miri))] fn main() {
+    /// let owner = Pair::new(Config(String::from("verbose=true"))).forget();
+    ///
+    /// // SAFETY: the owner is leaked, so it's valid (and never mutated) forever
+    /// let config: &'static Config = unsafe { owner.as_ref() };
+    /// assert_eq!(config.0, "verbose=true");
+    /// # }
+    /// ```
+    pub fn forget(self) -> NonNull<O> {
+        let this = ManuallyDrop::new(self);
+
+        #[cfg(feature = "leak-check")]
+        crate::leak_check::unregister(this.leak_check_id);
+
+        this.owner
+    }
+
+    /// Consumes and leaks the [`Pair`], returning `'static` references to both
+    /// the owner and the dependent.
+    ///
+    /// This is useful for long-lived, process-lifetime data (such as
+    /// configuration parsed at startup). Since the owner is never dropped, the
+    /// dependent's borrows of it are valid forever - so unlike with
+    /// [`Box::leak`]ing the whole pair, the dependent's lifetime can be named
+    /// as `'static`. Neither half is ever dropped.
+    ///
+    /// ```
+    /// # use pair::{Dependent, HasDependent, Owner, Pair};
+    /// # use std::{collections::HashMap, convert::Infallible};
+    /// struct Config(String);
+    ///
+    /// impl<'owner> HasDependent<'owner> for Config {
+    ///     type Dependent = HashMap<&'owner str, &'owner str>;
+    /// }
+    ///
+    /// impl Owner for Config {
+    ///     type Context<'a> = ();
+    ///     type Error = Infallible;
+    ///
+    ///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+    ///         Ok(self.0.lines().filter_map(|line| line.split_once('=')).collect())
+    ///     }
+    /// }
+    ///
+    /// # // Miri reports the intentional leak
+    /// # #[cfg(miri)] fn main() {}
+    /// # #[cfg(not(miri))] fn main() {
+    /// let (config, settings) = Pair::new(Config(String::from("level=debug"))).leak();
+    ///
+    /// let level: &'static str = settings["level"];
+    /// assert_eq!(level, "debug");
+    /// assert_eq!(config.0, "level=debug");
+    /// # }
+    /// ```
+    pub fn leak(self) -> (&'static O, &'static Dependent<'static, O>)
+    where
+        O: 'static,
+    {
+        let this = ManuallyDrop::new(self);
+
+        #[cfg(feature = "leak-check")]
+        crate::leak_check::unregister(this.leak_check_id);
+
+        // SAFETY: `this.owner` was originally converted from a valid Box, and
+        // inherited the alignment and validity guarantees of Box - and neither
+        // our code nor any of our exposed APIs could have invalidated those
+        // since construction. Since we took ownership of `self` and will never
+        // drop it, the owner is never dropped, deallocated, or exclusively
+        // borrowed again - so it's sound to share it forever.
+        let owner: &'static O = unsafe { this.owner.as_ref() };
+
+        // SAFETY: `this.dependent` was originally converted from a valid
+        // Box<Dependent<'_, O>>, and type-erased to a NonNull<()>. As such, it
+        // inherited the alignment and validity guarantees of Box (for a
+        // Dependent<'_, O>) - and neither our code nor any of our exposed APIs
+        // could have invalidated those since construction. Since we took
+        // ownership of `self` and will never drop it, the dependent is never
+        // dropped or exclusively borrowed again. Additionally, the owner it
+        // borrows from is now valid forever (see above), so `'static` is the
+        // dependent's real lifetime.
+        let dependent: &'static Dependent<'static, O> =
+            unsafe { this.dependent.cast::<Dependent<'static, O>>().as_ref() };
+
+        (owner, dependent)
+    }
+
+    /// Consumes the [`Pair`], converting the dependent into spans with
+    /// [`ToSpans::to_spans`], dropping the dependent, and returning the owner
+    /// along with those spans.
+    ///
+    /// If you don't need the returned owner in a [`Box`], consider the
+    /// convenience method [`Pair::into_owner_with_spans`], which moves the
+    /// owner out of the box for you.
+    pub fn into_boxed_owner_with_spans(self) -> (Box<O>, O::Spans)
+    where
+        O: ToSpans,
+    {
+        let spans = self.with_both(|owner, dependent| owner.to_spans(dependent));
+
+        (self.into_boxed_owner(), spans)
+    }
+
+    /// Consumes the [`Pair`], converting the dependent into spans with
+    /// [`ToSpans::to_spans`], dropping the dependent, and returning the owner
+    /// along with those spans.
+    ///
+    /// This allows the information in a borrowing dependent to survive the
+    /// owner being returned by value. See the documentation of [`ToSpans`]
+    /// for more information.
+    pub fn into_owner_with_spans(self) -> (O, O::Spans)
+    where
+        O: ToSpans + Sized,
+    {
+        let (owner, spans) = self.into_boxed_owner_with_spans();
+
+        (*owner, spans)
+    }
+}
+
+/// Calls [`Owner::make_dependent`], unless the given [`CancellationToken`] is
+/// cancelled before it starts or by the time it finishes.
+fn make_dependent_cancellable<'owner, O: Owner + ?Sized>(
+    owner: &'owner O,
+    context: O::Context<'_>,
+    token: &CancellationToken,
+) -> Result<Dependent<'owner, O>, CancellableError<O::Error>> {
+    token
+        .check()
+        .map_err(|Cancelled| CancellableError::Cancelled)?;
+
+    let maybe_dependent = owner.make_dependent(context);
+
+    // Regardless of whether `make_dependent` succeeded, if the token was
+    // cancelled in the meantime then construction was cancelled
+    token
+        .check()
+        .map_err(|Cancelled| CancellableError::Cancelled)?;
+
+    maybe_dependent.map_err(CancellableError::Failed)
 }
 
 impl<O: for<'any> Owner<Context<'any> = (), Error = Infallible> + ?Sized> Pair<O> {
@@ -404,6 +2348,7 @@ impl<O: for<'any> Owner<Context<'any> = (), Error = Infallible> + ?Sized> Pair<O
     ///
     /// See the "Constructors" section in the documentation of [`Pair`] for
     /// information on the differences between constructors.
+    #[track_caller]
     pub fn new(owner: O) -> Self
     where
         O: Sized,
@@ -416,9 +2361,53 @@ impl<O: for<'any> Owner<Context<'any> = (), Error = Infallible> + ?Sized> Pair<O
     ///
     /// See the "Constructors" section in the documentation of [`Pair`] for
     /// information on the differences between constructors.
+    #[track_caller]
     pub fn new_from_box(owner: Box<O>) -> Self {
         Self::new_from_box_with_context(owner, ())
     }
+
+    /// Constructs a new [`Pair`] with an unsized [`Owner`] converted from a
+    /// [`Vec`]. The dependent will be computed through
+    /// [`Owner::make_dependent`] during this construction.
+    ///
+    /// See the documentation of [`Pair::try_new_from_vec_with_context`] for
+    /// more information.
+    #[track_caller]
+    pub fn new_from_vec<T>(owner: Vec<T>) -> Self
+    where
+        Box<[T]>: Into<Box<O>>,
+    {
+        Self::new_from_box(owner.into_boxed_slice().into())
+    }
+
+    /// Constructs a new [`Pair`] with an unsized [`Owner`] converted from a
+    /// [`String`]. The dependent will be computed through
+    /// [`Owner::make_dependent`] during this construction.
+    ///
+    /// See the documentation of [`Pair::try_new_from_string_with_context`] for
+    /// more information.
+    #[track_caller]
+    pub fn new_from_string(owner: String) -> Self
+    where
+        Box<str>: Into<Box<O>>,
+    {
+        Self::new_from_box(owner.into_boxed_str().into())
+    }
+
+    /// Constructs a new [`Pair`] with an [`Owner`] converted from an
+    /// [`AlignedVec`] holding a copy of the given bytes. The dependent will be
+    /// computed through [`Owner::make_dependent`] during this construction.
+    ///
+    /// See the documentation of
+    /// [`Pair::try_new_from_aligned_bytes_with_context`] for more information.
+    #[track_caller]
+    pub fn new_from_aligned_bytes<const ALIGN: usize>(bytes: &[u8]) -> Self
+    where
+        O: Sized,
+        AlignedVec<ALIGN>: Into<O>,
+    {
+        Self::new(AlignedVec::from(bytes).into())
+    }
 }
 
 impl<O: for<'any> Owner<Context<'any> = ()> + ?Sized> Pair<O> {
@@ -431,6 +2420,7 @@ impl<O: for<'any> Owner<Context<'any> = ()> + ?Sized> Pair<O> {
     /// # Errors
     /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
     /// error.
+    #[track_caller]
     pub fn try_new(owner: O) -> Result<Self, (O, O::Error)>
     where
         O: Sized,
@@ -447,9 +2437,151 @@ impl<O: for<'any> Owner<Context<'any> = ()> + ?Sized> Pair<O> {
     /// # Errors
     /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
     /// error.
+    #[track_caller]
     pub fn try_new_from_box(owner: Box<O>) -> Result<Self, (Box<O>, O::Error)> {
         Self::try_new_from_box_with_context(owner, ())
     }
+
+    /// Constructs a new [`Pair`] with the given [`Owner`]. The dependent will
+    /// be computed through [`Owner::make_dependent`] during this construction.
+    ///
+    /// The owner is dropped if construction fails, and only the error is
+    /// returned. See [`Pair::try_new_discard_owner_with_context`] for more
+    /// information.
+    ///
+    /// ```
+    /// # use pair::{Dependent, HasDependent, Owner, Pair};
+    /// # use std::{num::ParseIntError, rc::Rc};
+    /// // Not `Send`, but its error is
+    /// struct Numbers(Rc<str>);
+    ///
+    /// impl HasDependent<'_> for Numbers {
+    ///     type Dependent = Vec<u32>;
+    /// }
+    ///
+    /// impl Owner for Numbers {
+    ///     type Context<'a> = ();
+    ///     type Error = ParseIntError;
+    ///
+    ///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+    ///         self.0.split(',').map(str::parse).collect()
+    ///     }
+    /// }
+    ///
+    /// fn parse(text: &str) -> Result<Pair<Numbers>, Box<dyn std::error::Error + Send + Sync>> {
+    ///     Ok(Pair::try_new_discard_owner(Numbers(Rc::from(text)))?)
+    /// }
+    ///
+    /// assert!(parse("1,2,3").is_ok());
+    /// assert!(parse("1,two,3").is_err());
+    /// ```
+    ///
+    /// See the "Constructors" section in the documentation of [`Pair`] for
+    /// information on the differences between constructors.
+    ///
+    /// # Errors
+    /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
+    /// error.
+    #[track_caller]
+    pub fn try_new_discard_owner(owner: O) -> Result<Self, O::Error>
+    where
+        O: Sized,
+    {
+        Self::try_new_discard_owner_with_context(owner, ())
+    }
+}
+
+impl<C: Any, O: for<'any> Owner<Context<'any> = &'any C> + ?Sized> Pair<O> {
+    /// Constructs a new [`Pair`] with the given [`Owner`], downcasting the
+    /// given type-erased context to the owner's context type. The dependent
+    /// will be computed through [`Owner::make_dependent`] during this
+    /// construction.
+    ///
+    /// This is useful for plugin systems, where a host drives the construction
+    /// of pairs for many different owners, and can't name each owner's context
+    /// type at compile time. Owners with a context of `&'a C` (for any
+    /// `C: Any`) can be constructed from a `&dyn Any`.
+    ///
+    /// ```
+    /// # use pair::{AnyContextError, Dependent, HasDependent, Owner, Pair};
+    /// # use std::{any::Any, convert::Infallible};
+    /// struct Config {
+    ///     separator: char,
+    /// }
+    ///
+    /// struct Document(String);
+    ///
+    /// impl<'owner> HasDependent<'owner> for Document {
+    ///     type Dependent = Vec<&'owner str>;
+    /// }
+    ///
+    /// impl Owner for Document {
+    ///     type Context<'a> = &'a Config;
+    ///     type Error = Infallible;
+    ///
+    ///     fn make_dependent(&self, config: &Config) -> Result<Dependent<'_, Self>, Self::Error> {
+    ///         Ok(self.0.split(config.separator).collect())
+    ///     }
+    /// }
+    ///
+    /// // The host only knows the context as a `&dyn Any`
+    /// let context: &dyn Any = &Config { separator: ',' };
+    ///
+    /// let pair = Pair::try_new_with_any_context(Document(String::from("a,b,c")), context);
+    /// assert!(matches!(pair, Ok(pair) if pair.with_dependent(|parts| parts.len()) == 3));
+    ///
+    /// let pair = Pair::try_new_with_any_context(Document(String::from("a,b,c")), &42_u32);
+    /// assert!(matches!(pair, Err((_, AnyContextError::WrongType))));
+    /// ```
+    ///
+    /// See the "Constructors" section in the documentation of [`Pair`] for
+    /// information on the differences between constructors.
+    ///
+    /// # Errors
+    /// [`AnyContextError::WrongType`] if the context is not a `C` (in which
+    /// case `make_dependent` is not called), or [`AnyContextError::Failed`] if
+    /// [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
+    /// error.
+    #[track_caller]
+    pub fn try_new_with_any_context(
+        owner: O,
+        context: &dyn Any,
+    ) -> Result<Self, (O, AnyContextError<O::Error>)>
+    where
+        O: Sized,
+    {
+        Self::try_new_from_box_with_any_context(Box::new(owner), context)
+            .map_err(|(owner, err)| (*owner, err))
+    }
+
+    /// Constructs a new [`Pair`] with the given [`Owner`], downcasting the
+    /// given type-erased context to the owner's context type. The dependent
+    /// will be computed through [`Owner::make_dependent`] during this
+    /// construction.
+    ///
+    /// See the documentation of [`Pair::try_new_with_any_context`] for more
+    /// information.
+    ///
+    /// # Errors
+    /// [`AnyContextError::WrongType`] if the context is not a `C` (in which
+    /// case `make_dependent` is not called), or [`AnyContextError::Failed`] if
+    /// [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
+    /// error.
+    #[track_caller]
+    pub fn try_new_from_box_with_any_context(
+        owner: Box<O>,
+        context: &dyn Any,
+    ) -> Result<Self, (Box<O>, AnyContextError<O::Error>)> {
+        Self::try_new_from_box_with(owner, |owner| {
+            let context = context
+                .downcast_ref::<C>()
+                .ok_or(AnyContextError::WrongType)?;
+
+            owner
+                .make_dependent(context)
+                .map_err(AnyContextError::Failed)
+        })
+    }
 }
 
 impl<O: Owner<Error = Infallible> + ?Sized> Pair<O> {
@@ -458,6 +2590,7 @@ impl<O: Owner<Error = Infallible> + ?Sized> Pair<O> {
     ///
     /// See the "Constructors" section in the documentation of [`Pair`] for
     /// information on the differences between constructors.
+    #[track_caller]
     pub fn new_with_context(owner: O, context: O::Context<'_>) -> Self
     where
         O: Sized,
@@ -471,10 +2604,56 @@ impl<O: Owner<Error = Infallible> + ?Sized> Pair<O> {
     ///
     /// See the "Constructors" section in the documentation of [`Pair`] for
     /// information on the differences between constructors.
+    #[track_caller]
     pub fn new_from_box_with_context(owner: Box<O>, context: O::Context<'_>) -> Self {
         let Ok(pair) = Self::try_new_from_box_with_context(owner, context);
         pair
     }
+
+    /// Constructs a new [`Pair`] with the given [`Owner`]. The dependent will
+    /// be computed through [`Owner::make_dependent_with_progress`] during this
+    /// construction, which reports its progress by calling `progress`.
+    ///
+    /// See the "Constructors" section in the documentation of [`Pair`] for
+    /// information on the differences between constructors.
+    #[track_caller]
+    pub fn new_with_progress<F: FnMut(Progress)>(
+        owner: O,
+        context: O::Context<'_>,
+        progress: F,
+    ) -> Self
+    where
+        O: Sized,
+    {
+        let Ok(pair) = Self::try_new_with_progress(owner, context, progress);
+        pair
+    }
+
+    /// Constructs a new [`Pair`] with the given [`Owner`]. The dependent will
+    /// be computed through [`Owner::make_dependent`] during this construction,
+    /// and then checked by calling `validate` with the owner and dependent.
+    ///
+    /// See the documentation of [`Pair::try_new_validated`] for more
+    /// information.
+    ///
+    /// # Errors
+    /// If `validate` returns an error, in which case the dependent is dropped
+    /// and the owner is returned.
+    #[track_caller]
+    pub fn new_validated<F, V>(
+        owner: O,
+        context: O::Context<'_>,
+        validate: F,
+    ) -> Result<Self, (O, V)>
+    where
+        O: Sized,
+        F: for<'any> FnOnce(&O, &Dependent<'any, O>) -> Result<(), V>,
+    {
+        Self::try_new_validated(owner, context, validate).map_err(|(owner, err)| match err {
+            ValidationError::Failed(never) => match never {},
+            ValidationError::Rejected(err) => (owner, err),
+        })
+    }
 }
 
 /// The [`Drop`] implementation for [`Pair`] will drop both the dependent and
@@ -499,6 +2678,9 @@ impl<O: Owner<Error = Infallible> + ?Sized> Pair<O> {
 // for the reasons described above.
 impl<O: Owner + ?Sized> Drop for Pair<O> {
     fn drop(&mut self) {
+        #[cfg(feature = "leak-check")]
+        crate::leak_check::unregister(self.leak_check_id);
+
         // Drop the dependent `Box<Dependent<'_, O>>`
 
         // SAFETY: `self.dependent` was originally created from a Box, and never
@@ -535,6 +2717,12 @@ impl<O: Owner + ?Sized> Drop for Pair<O> {
         // Drop the dependent
         drop(dependent);
 
+        #[cfg(feature = "failpoints")]
+        assert!(
+            !crate::failpoints::hit(crate::FailPoint::DropDependent),
+            "injected a panic while dropping a dependent"
+        );
+
         // The dependent's drop didn't panic - disarm our drop guard
         core::mem::forget(panic_drop_guard);
 
@@ -550,6 +2738,14 @@ impl<O: Owner + ?Sized> Drop for Pair<O> {
     }
 }
 
+// NOTE(ichen): Types which wrap a `Pair` (such as `PairArena`, `ViewsPair`,
+// `LayeredPair`, and `AssertSendPair`/`AssertSyncPair`) write out their `Send`
+// and `Sync` impls explicitly, with the same bounds these impls would give
+// them, rather than leaving them to auto trait inference. That's only because
+// rustdoc currently crashes while inferring auto traits through the
+// higher-ranked `for<'any>` bounds below. They're tested to match `Pair`'s in
+// tests/send_sync.rs, and can be removed once rustdoc handles them.
+//
 // SAFETY: `Pair` has no special thread-related invariants or requirements, so
 // sending a `Pair` to another thread could only cause problems if sending
 // either the owner or the dependent to another thread could cause problems
@@ -0,0 +1,45 @@
+//! Defines [`Progress`], reported by
+//! [`Owner::make_dependent_with_progress`](crate::Owner::make_dependent_with_progress).
+
+/// The progress of constructing a dependent, as reported by
+/// [`Owner::make_dependent_with_progress`](crate::Owner::make_dependent_with_progress).
+///
+/// Progress is measured in arbitrary "units" of work, defined by the
+/// [`Owner`](crate::Owner) implementation - for example, bytes or lines of a
+/// file which have been indexed. The total amount of work may not be known in
+/// advance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Progress {
+    completed: u64,
+    total: Option<u64>,
+}
+
+impl Progress {
+    /// Constructs a new [`Progress`], with the given number of completed units
+    /// of work out of the given total (if known).
+    pub fn new(completed: u64, total: Option<u64>) -> Self {
+        Self { completed, total }
+    }
+
+    /// Returns the number of units of work which have been completed.
+    pub fn completed(&self) -> u64 {
+        self.completed
+    }
+
+    /// Returns the total number of units of work, if known.
+    pub fn total(&self) -> Option<u64> {
+        self.total
+    }
+
+    /// Returns the fraction of work which has been completed (typically
+    /// between `0.0` and `1.0`), if the total is known and nonzero.
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "a fraction is inherently approximate"
+    )]
+    pub fn fraction(&self) -> Option<f64> {
+        self.total
+            .filter(|&total| total != 0)
+            .map(|total| self.completed as f64 / total as f64)
+    }
+}
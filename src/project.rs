@@ -0,0 +1,113 @@
+//! Defines the [`Project`] trait, and [`Projected`], an [`Owner`] for any type
+//! implementing it.
+
+use core::{convert::Infallible, fmt::Debug, marker::PhantomData};
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use crate::{CovariantDependent, Dependent, HasDependent, Owner};
+
+/// A type which can be projected to a reference to some `Target` it contains.
+///
+/// This is a simpler alternative to implementing [`Owner`] and
+/// [`HasDependent`] directly, for owners whose dependent is just a reference
+/// into themselves - there are no lifetimes to write. Wrap the type in a
+/// [`Projected`] to use it as an owner.
+///
+/// ```
+/// # use pair::{Pair, Project, Projected};
+/// struct Config {
+///     name: String,
+///     raw: String,
+/// }
+///
+/// impl Project<str> for Config {
+///     fn project(&self) -> &str {
+///         &self.name
+///     }
+/// }
+///
+/// let config = Config {
+///     name: String::from("server"),
+///     raw: String::from("name = server"),
+/// };
+///
+/// let pair = Pair::new(Projected::<_, str>::new(config));
+/// assert_eq!(&**pair.dependent_ref(), "server");
+/// assert_eq!(pair.owner().get().raw, "name = server");
+/// ```
+pub trait Project<Target: ?Sized> {
+    /// Returns a reference to the target.
+    fn project(&self) -> &Target;
+}
+
+impl Project<str> for String {
+    fn project(&self) -> &str {
+        self
+    }
+}
+
+impl<T> Project<[T]> for Vec<T> {
+    fn project(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T: ?Sized> Project<T> for Box<T> {
+    fn project(&self) -> &T {
+        self
+    }
+}
+
+/// An [`Owner`] wrapping a type implementing [`Project<Target>`](Project),
+/// whose dependent is a reference to the projected target.
+///
+/// The dependent is `&'owner Target`, created by calling
+/// [`Project::project`]. See the documentation of [`Project`] for more
+/// information.
+pub struct Projected<O: Project<Target>, Target: ?Sized> {
+    owner: O,
+    target: PhantomData<fn() -> Box<Target>>,
+}
+
+impl<O: Project<Target>, Target: ?Sized> Projected<O, Target> {
+    /// Wraps the given owner.
+    pub fn new(owner: O) -> Self {
+        Self {
+            owner,
+            target: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the wrapped owner.
+    pub fn get(&self) -> &O {
+        &self.owner
+    }
+
+    /// Consumes the [`Projected`], returning the wrapped owner.
+    pub fn into_inner(self) -> O {
+        self.owner
+    }
+}
+
+impl<'owner, O: Project<Target>, Target: ?Sized> HasDependent<'owner> for Projected<O, Target> {
+    type Dependent = &'owner Target;
+}
+
+impl<O: Project<Target>, Target: ?Sized> Owner for Projected<O, Target> {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): Self::Context<'_>) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.owner.project())
+    }
+}
+
+// SAFETY: `&'owner Target` is covariant over `'owner`.
+unsafe impl<O: Project<Target>, Target: ?Sized> CovariantDependent for Projected<O, Target> {}
+
+impl<O: Project<Target> + Debug, Target: ?Sized> Debug for Projected<O, Target> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Projected").field(&self.owner).finish()
+    }
+}
@@ -0,0 +1,184 @@
+//! Defines [`ReadOnlyPair`], a [`Pair`] whose owner's pages are protected as
+//! read-only. Only available with the `mprotect` feature, on unix.
+
+use core::{fmt::Debug, mem::ManuallyDrop, ops::Deref};
+
+use crate::{Dependent, Owner, Pair};
+
+impl<O: Owner + AsRef<[u8]> + ?Sized> Pair<O> {
+    /// Protects the memory pages holding the owner's bytes as read-only,
+    /// returning a [`ReadOnlyPair`] which restores them to read-write when it
+    /// is dropped or converted back into a [`Pair`].
+    ///
+    /// The owner's bytes (as returned by [`AsRef<[u8]>`](AsRef)) must start on
+    /// a page boundary - such as the bytes of an
+    /// [`AlignedVec<4096>`](crate::AlignedVec), on platforms with 4 KiB pages.
+    /// Only the whole pages they cover are protected; a trailing partial page
+    /// remains writable, since other data may share it.
+    ///
+    /// A [`ReadOnlyPair`] only gives out shared access to the pair (it
+    /// implements [`Deref`] to `&Pair`, but not `DerefMut`), and the safety
+    /// contract below rules out any other writers, so the owner's bytes can't
+    /// be mutated while they're protected. Any write to them is a bug (likely
+    /// in some `unsafe` code), and immediately faults rather than silently
+    /// corrupting the pair. This is intended as a testing aid.
+    ///
+    /// Only available with the `mprotect` feature, on unix.
+    ///
+    /// # Safety
+    /// The owner's bytes must be exclusively owned by the owner, and
+    /// [`as_ref`](AsRef::as_ref) must return the same bytes every time it is
+    /// called. In particular, they must not be shared with (or deallocated
+    /// through) anything outside of this pair, such as another clone of an
+    /// `Arc<[u8]>`.
+    ///
+    /// # Errors
+    /// If the owner's bytes don't start on a page boundary, or if the call to
+    /// `mprotect` fails. In either case, the pair is returned unchanged along
+    /// with the error.
+    pub unsafe fn protect_owner_readonly(self) -> Result<ReadOnlyPair<O>, (Self, std::io::Error)> {
+        let protected_len = match protectable_len(self.owner().as_ref()) {
+            Ok(protected_len) => protected_len,
+            Err(err) => return Err((self, err)),
+        };
+
+        // SAFETY: the caller guaranteed the pages are exclusively owned by the
+        // owner, which is only ever read while the pages are protected. The
+        // protection is restored before the owner can be mutated or dropped.
+        let result = unsafe { mprotect(self.owner().as_ref(), protected_len, libc::PROT_READ) };
+        if let Err(err) = result {
+            return Err((self, err));
+        }
+
+        Ok(ReadOnlyPair {
+            pair: self,
+            protected_len,
+        })
+    }
+}
+
+/// A [`Pair`] whose owner's memory pages are protected as read-only, created by
+/// [`Pair::protect_owner_readonly`].
+///
+/// The pages are restored to read-write when the `ReadOnlyPair` is dropped or
+/// converted back into a [`Pair`] with
+/// [`into_pair`](ReadOnlyPair::into_pair).
+///
+/// A `ReadOnlyPair` dereferences to the inner [`Pair`].
+///
+/// Only available with the `mprotect` feature, on unix.
+pub struct ReadOnlyPair<O: Owner + AsRef<[u8]> + ?Sized> {
+    pair: Pair<O>,
+    // The number of protected bytes at the start of the owner's bytes
+    protected_len: usize,
+}
+
+impl<O: Owner + AsRef<[u8]> + ?Sized> ReadOnlyPair<O> {
+    /// Returns a reference to the inner [`Pair`].
+    pub fn get(&self) -> &Pair<O> {
+        &self.pair
+    }
+
+    /// Returns the number of bytes which are protected. This is the length of
+    /// the owner's bytes, rounded down to a whole number of pages.
+    pub fn protected_len(&self) -> usize {
+        self.protected_len
+    }
+
+    /// Restores the owner's pages to read-write, returning the inner [`Pair`].
+    ///
+    /// # Panics
+    /// If the call to `mprotect` fails - since the owner could then never
+    /// safely be mutated or dropped.
+    pub fn into_pair(self) -> Pair<O> {
+        let this = ManuallyDrop::new(self);
+        this.unprotect();
+
+        // SAFETY: `this` is never used (or dropped) again, so reading the pair
+        // out of it can't cause a double drop. `protected_len` is a `usize`,
+        // which has no drop glue, so not dropping it leaks nothing.
+        unsafe { core::ptr::read(&raw const this.pair) }
+    }
+
+    fn unprotect(&self) {
+        // SAFETY: the pages were protected by `Pair::protect_owner_readonly`,
+        // and are exclusively owned by the owner.
+        let result = unsafe {
+            mprotect(
+                self.pair.owner().as_ref(),
+                self.protected_len,
+                libc::PROT_READ | libc::PROT_WRITE,
+            )
+        };
+
+        if let Err(err) = result {
+            panic!("failed to restore the protection of a pair's owner: {err}");
+        }
+    }
+}
+
+/// Returns the length of the whole pages covered by the given bytes, which must
+/// start on a page boundary.
+fn protectable_len(bytes: &[u8]) -> std::io::Result<usize> {
+    // SAFETY: `sysconf` has no preconditions.
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    let page_size = usize::try_from(page_size).map_err(|_| std::io::Error::last_os_error())?;
+
+    if bytes.as_ptr().addr() % page_size != 0 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "the bytes of the owner don't start on a page boundary",
+        ));
+    }
+
+    Ok(bytes.len() / page_size * page_size)
+}
+
+/// Sets the protection of the first `len` bytes of `bytes`, which must be a
+/// whole number of pages (as returned by `protectable_len`).
+///
+/// # Safety
+/// The pages must not be accessed in ways the new protection disallows, and
+/// must not be deallocated while protected.
+unsafe fn mprotect(bytes: &[u8], len: usize, protection: libc::c_int) -> std::io::Result<()> {
+    if len == 0 {
+        return Ok(());
+    }
+
+    // SAFETY: the caller guaranteed that the protection of these pages may be
+    // changed. `bytes` starts on a page boundary, and `len` covers only whole
+    // pages within it.
+    let result = unsafe { libc::mprotect(bytes.as_ptr().cast_mut().cast(), len, protection) };
+
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+impl<O: Owner + AsRef<[u8]> + ?Sized> Deref for ReadOnlyPair<O> {
+    type Target = Pair<O>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.pair
+    }
+}
+
+impl<O: Owner + AsRef<[u8]> + ?Sized> Drop for ReadOnlyPair<O> {
+    fn drop(&mut self) {
+        self.unprotect();
+    }
+}
+
+impl<O: Owner + AsRef<[u8]> + Debug + ?Sized> Debug for ReadOnlyPair<O>
+where
+    for<'any> Dependent<'any, O>: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ReadOnlyPair")
+            .field("pair", &self.pair)
+            .field("protected_len", &self.protected_len())
+            .finish()
+    }
+}
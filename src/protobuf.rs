@@ -0,0 +1,261 @@
+//! Defines [`ProtobufMessage`], an owner of an encoded protobuf message whose
+//! dependent is an index of the message's fields, which borrow from the
+//! encoded bytes and are decoded lazily. Only available with the `prost`
+//! feature.
+
+use core::{fmt::Debug, str::Utf8Error};
+
+use alloc::{boxed::Box, vec::Vec};
+
+use prost::{
+    DecodeError, Message,
+    bytes::Buf,
+    encoding::{DecodeContext, WireType, decode_key, decode_varint, skip_field},
+};
+
+use crate::{Dependent, HasDependent, Owner, Pair};
+
+/// The bytes of an encoded protobuf message, which can act as the owner of a
+/// [`Pair`] with the message's [`ProtobufFields`] as the dependent.
+///
+/// Fully decoding a message with [`prost`] allocates for every string, bytes,
+/// and repeated field. For read-mostly uses (such as a proxy which inspects a
+/// few fields of each message before forwarding it), a `ProtobufMessage` pair
+/// instead validates the message's framing once during construction, and
+/// decodes individual fields on access - string and bytes fields borrow
+/// directly from the encoded message, without copying.
+///
+/// Only available with the `prost` feature.
+///
+/// ```
+/// # use pair::{Pair, ProtobufMessage, ProtobufPair};
+/// # use prost::encoding::{string, uint32};
+/// // message Request { string path = 1; uint32 retries = 2; }
+/// let mut encoded = Vec::new();
+/// string::encode(1, &String::from("/index.html"), &mut encoded);
+/// uint32::encode(2, &3, &mut encoded);
+///
+/// let Ok(pair): Result<ProtobufPair, _> = Pair::try_new(ProtobufMessage::new(encoded)) else {
+///     panic!("the message should be valid");
+/// };
+///
+/// pair.with_dependent(|fields| {
+///     assert_eq!(fields.string(1), Some(Ok("/index.html")));
+///     assert_eq!(fields.varint(2), Some(3));
+///     assert_eq!(fields.varint(3), None);
+/// });
+/// ```
+pub struct ProtobufMessage(Box<[u8]>);
+
+/// A [`Pair`] of a [`ProtobufMessage`] and its [`ProtobufFields`].
+///
+/// Only available with the `prost` feature.
+pub type ProtobufPair = Pair<ProtobufMessage>;
+
+impl ProtobufMessage {
+    /// Constructs a new [`ProtobufMessage`] from the bytes of an encoded
+    /// message.
+    ///
+    /// The bytes are not validated until a [`Pair`] is constructed from the
+    /// message.
+    pub fn new(bytes: impl Into<Box<[u8]>>) -> Self {
+        Self(bytes.into())
+    }
+
+    /// Returns the bytes of the encoded message.
+    pub fn bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Fully decodes the message as the given [`Message`] type.
+    ///
+    /// # Errors
+    /// If the bytes aren't a valid encoding of `M`.
+    pub fn decode<M: Message + Default>(&self) -> Result<M, DecodeError> {
+        M::decode(self.bytes())
+    }
+
+    /// Consumes the [`ProtobufMessage`], returning the bytes of the encoded
+    /// message.
+    pub fn into_bytes(self) -> Box<[u8]> {
+        self.0
+    }
+}
+
+impl Debug for ProtobufMessage {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ProtobufMessage")
+            .field("len", &self.0.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'owner> HasDependent<'owner> for ProtobufMessage {
+    type Dependent = ProtobufFields<'owner>;
+}
+
+impl Owner for ProtobufMessage {
+    type Context<'a> = ();
+    type Error = DecodeError;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        ProtobufFields::parse(&self.0)
+    }
+}
+
+/// The encoded value of a single protobuf field, borrowing from the encoded
+/// message.
+///
+/// Which variant a field is encoded as depends on its type in the message's
+/// schema - see the [protobuf encoding documentation] for details.
+///
+/// Only available with the `prost` feature.
+///
+/// [protobuf encoding documentation]: https://protobuf.dev/programming-guides/encoding/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldValue<'a> {
+    /// A varint, used for `int32`, `int64`, `uint32`, `uint64`, `sint32`,
+    /// `sint64`, `bool`, and `enum` fields.
+    Varint(u64),
+
+    /// A 64-bit value, used for `fixed64`, `sfixed64`, and `double` fields.
+    Fixed64(u64),
+
+    /// A length-delimited value, used for `string`, `bytes`, embedded message,
+    /// and packed repeated fields.
+    LengthDelimited(&'a [u8]),
+
+    /// The encoded fields of a (deprecated) group.
+    Group(&'a [u8]),
+
+    /// A 32-bit value, used for `fixed32`, `sfixed32`, and `float` fields.
+    Fixed32(u32),
+}
+
+/// An index of the fields of an encoded protobuf message, which borrow from
+/// the encoded bytes.
+///
+/// Constructing a `ProtobufFields` validates the framing of every field (their
+/// keys, and that each field's value fits within the message), but not their
+/// contents - those are decoded lazily, when accessed. Embedded messages are
+/// not parsed until accessed with [`ProtobufFields::message`].
+///
+/// As in protobuf, if a non-repeated field occurs multiple times, the last
+/// occurrence takes precedence.
+///
+/// Only available with the `prost` feature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProtobufFields<'a> {
+    fields: Vec<(u32, FieldValue<'a>)>,
+}
+
+impl<'a> ProtobufFields<'a> {
+    /// Parses the fields of an encoded protobuf message.
+    ///
+    /// # Errors
+    /// If the framing of any field is invalid.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, DecodeError> {
+        let mut fields = Vec::new();
+        let mut buf = bytes;
+
+        while !buf.is_empty() {
+            let (tag, wire_type) = decode_key(&mut buf)?;
+
+            let value_start = bytes.len() - buf.len();
+            let value_end = if wire_type == WireType::StartGroup {
+                loop {
+                    let inner_start = bytes.len() - buf.len();
+                    let (inner_tag, inner_wire_type) = decode_key(&mut buf)?;
+                    if inner_wire_type == WireType::EndGroup && inner_tag == tag {
+                        break inner_start;
+                    }
+                    skip_field(
+                        inner_wire_type,
+                        inner_tag,
+                        &mut buf,
+                        DecodeContext::default(),
+                    )?;
+                }
+            } else {
+                // The end of a group is only valid while skipping over its
+                // start, so skipping a lone end returns the appropriate error
+                skip_field(wire_type, tag, &mut buf, DecodeContext::default())?;
+                bytes.len() - buf.len()
+            };
+
+            let mut raw = &bytes[value_start..value_end];
+            let value = match wire_type {
+                WireType::Varint => FieldValue::Varint(decode_varint(&mut raw)?),
+                WireType::SixtyFourBit => FieldValue::Fixed64(raw.get_u64_le()),
+                WireType::LengthDelimited => {
+                    decode_varint(&mut raw)?;
+                    FieldValue::LengthDelimited(raw)
+                }
+                WireType::StartGroup | WireType::EndGroup => FieldValue::Group(raw),
+                WireType::ThirtyTwoBit => FieldValue::Fixed32(raw.get_u32_le()),
+            };
+
+            fields.push((tag, value));
+        }
+
+        Ok(Self { fields })
+    }
+
+    /// Returns an iterator over the tag and value of every field, in the
+    /// order they occur in the message.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, FieldValue<'a>)> + '_ {
+        self.fields.iter().copied()
+    }
+
+    /// Returns the value of the last occurrence of the field with the given
+    /// tag, or `None` if the field isn't present.
+    pub fn get(&self, tag: u32) -> Option<FieldValue<'a>> {
+        self.fields
+            .iter()
+            .rev()
+            .find(|&&(field_tag, _)| field_tag == tag)
+            .map(|&(_, value)| value)
+    }
+
+    /// Returns an iterator over the values of every occurrence of the field
+    /// with the given tag, such as the elements of an unpacked repeated field.
+    pub fn get_all(&self, tag: u32) -> impl Iterator<Item = FieldValue<'a>> + '_ {
+        self.fields
+            .iter()
+            .filter(move |&&(field_tag, _)| field_tag == tag)
+            .map(|&(_, value)| value)
+    }
+
+    /// Returns the value of the varint field with the given tag, or `None` if
+    /// the field isn't present or isn't a varint.
+    pub fn varint(&self, tag: u32) -> Option<u64> {
+        match self.get(tag)? {
+            FieldValue::Varint(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the bytes of the length-delimited field with the given tag, or
+    /// `None` if the field isn't present or isn't length-delimited.
+    pub fn bytes(&self, tag: u32) -> Option<&'a [u8]> {
+        match self.get(tag)? {
+            FieldValue::LengthDelimited(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Returns the string field with the given tag, or `None` if the field
+    /// isn't present or isn't length-delimited. Returns `Some(Err(_))` if the
+    /// field's bytes aren't valid UTF-8.
+    pub fn string(&self, tag: u32) -> Option<Result<&'a str, Utf8Error>> {
+        self.bytes(tag).map(core::str::from_utf8)
+    }
+
+    /// Parses the fields of the embedded message with the given tag, or
+    /// returns `None` if the field isn't present or isn't length-delimited.
+    /// Returns `Some(Err(_))` if the framing of the embedded message is
+    /// invalid.
+    pub fn message(&self, tag: u32) -> Option<Result<Self, DecodeError>> {
+        self.bytes(tag).map(Self::parse)
+    }
+}
@@ -0,0 +1,174 @@
+//! Defines [`PyPair`], a shell around a [`Pair`] for embedding in a
+//! `#[pyclass]`, with GIL-aware accessors. Only available with the `pyo3`
+//! feature.
+
+use core::{fmt::Debug, ops::Deref};
+
+use pyo3::{Bound, PyAny, PyErr, PyResult, Python};
+
+use crate::{Dependent, Owner, Pair};
+
+/// A [`Pair`] which can be stored in a `#[pyclass]`, for exposing "document
+/// and view" objects (such as a parsed source file) from a Rust core built on
+/// `pair` to Python.
+///
+/// A `#[pyclass]` can't be generic, so each class is declared in the
+/// downstream crate with a `PyPair` of a concrete owner as a field. The class
+/// must be `Send` and `Sync` (unless it's declared `unsendable`), which a
+/// `PyPair` is exactly when its pair is.
+///
+/// Python objects can't borrow from the pair, so
+/// [`dependent_to_py`](PyPair::dependent_to_py) converts the dependent (or
+/// part of it) into Python objects while it's borrowed. Parsing and other long
+/// computations over the dependent can release the GIL (letting other Python
+/// threads run), with [`PyPair::try_new`] and
+/// [`with_dependent_detached`](PyPair::with_dependent_detached).
+///
+/// A `PyPair` dereferences to the inner [`Pair`].
+///
+/// Only available with the `pyo3` feature.
+///
+/// ```
+/// # use pair::{Dependent, HasDependent, Owner, PyPair};
+/// # use pyo3::{IntoPyObjectExt, exceptions::PyValueError, prelude::*};
+/// struct Csv(String);
+///
+/// impl<'owner> HasDependent<'owner> for Csv {
+///     type Dependent = Vec<Vec<&'owner str>>;
+/// }
+///
+/// impl Owner for Csv {
+///     type Context<'a> = ();
+///     type Error = PyErr;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         let rows: Vec<Vec<&str>> = self.0.lines().map(|line| line.split(',').collect()).collect();
+///         if rows.windows(2).any(|rows| rows[0].len() != rows[1].len()) {
+///             return Err(PyValueError::new_err("rows have different lengths"));
+///         }
+///         Ok(rows)
+///     }
+/// }
+///
+/// #[pyclass(frozen)]
+/// struct Table {
+///     csv: PyPair<Csv>,
+/// }
+///
+/// #[pymethods]
+/// impl Table {
+///     #[new]
+///     fn new(py: Python<'_>, text: String) -> PyResult<Self> {
+///         // Parses with the GIL released
+///         Ok(Self { csv: PyPair::try_new(py, Csv(text))? })
+///     }
+///
+///     fn row<'py>(&self, py: Python<'py>, index: usize) -> PyResult<Bound<'py, PyAny>> {
+///         self.csv.dependent_to_py(py, |py, rows| rows.get(index).into_bound_py_any(py))
+///     }
+/// }
+///
+/// # // Miri can't run the Python interpreter
+/// # #[cfg(miri)] fn main() {}
+/// # #[cfg(not(miri))] fn main() {
+/// Python::initialize();
+/// Python::attach(|py| {
+///     let table = Bound::new(py, Table::new(py, String::from("a,b\nc,d")).unwrap()).unwrap();
+///     let row: Vec<String> = table.call_method1("row", (1,)).unwrap().extract().unwrap();
+///     assert_eq!(row, ["c", "d"]);
+///
+///     let err = Table::new(py, String::from("a,b\nc")).err().unwrap();
+///     assert!(err.is_instance_of::<PyValueError>(py));
+/// });
+/// # }
+/// ```
+pub struct PyPair<O: Owner + ?Sized> {
+    pair: Pair<O>,
+}
+
+impl<O: for<'any> Owner<Context<'any> = ()> + Send> PyPair<O>
+where
+    Pair<O>: Send,
+    O::Error: Into<PyErr> + Send,
+{
+    /// Constructs a new [`PyPair`] with the given [`Owner`], releasing the GIL
+    /// while the dependent is computed through [`Owner::make_dependent`].
+    ///
+    /// # Errors
+    /// If [`make_dependent`](Owner::make_dependent) returns an error, which is
+    /// converted into a Python exception. The owner is dropped.
+    pub fn try_new(py: Python<'_>, owner: O) -> PyResult<Self> {
+        py.detach(|| Pair::try_new(owner))
+            .map(Self::from)
+            .map_err(|(_, err)| err.into())
+    }
+}
+
+impl<O: Owner + ?Sized> PyPair<O> {
+    /// Returns a reference to the inner [`Pair`].
+    pub fn get(&self) -> &Pair<O> {
+        &self.pair
+    }
+
+    /// Returns a mutable reference to the inner [`Pair`].
+    pub fn get_mut(&mut self) -> &mut Pair<O> {
+        &mut self.pair
+    }
+
+    /// Consumes the [`PyPair`], returning the inner [`Pair`].
+    pub fn into_pair(self) -> Pair<O> {
+        self.pair
+    }
+
+    /// Calls the given closure with the GIL token and the dependent, returning
+    /// the Python object it converts the dependent (or part of it) into.
+    ///
+    /// Python objects can't hold Rust borrows, so this is where borrowed views
+    /// are copied into Python values (such as `&str`s into `str`s) - usually
+    /// with [`into_bound_py_any`](pyo3::IntoPyObjectExt::into_bound_py_any).
+    ///
+    /// # Errors
+    /// If the closure returns an error.
+    pub fn dependent_to_py<'py, F>(&self, py: Python<'py>, f: F) -> PyResult<Bound<'py, PyAny>>
+    where
+        F: for<'any> FnOnce(Python<'py>, &Dependent<'any, O>) -> PyResult<Bound<'py, PyAny>>,
+    {
+        self.pair.with_dependent(|dependent| f(py, dependent))
+    }
+
+    /// Calls the given closure with the dependent, releasing the GIL while it
+    /// runs so that other Python threads can make progress.
+    ///
+    /// The closure can't use the GIL (or any Python objects) itself.
+    pub fn with_dependent_detached<F, T>(&self, py: Python<'_>, f: F) -> T
+    where
+        Pair<O>: Sync,
+        F: for<'any> FnOnce(&Dependent<'any, O>) -> T + Send,
+        T: Send,
+    {
+        py.detach(|| self.pair.with_dependent(|dependent| f(dependent)))
+    }
+}
+
+impl<O: Owner + ?Sized> From<Pair<O>> for PyPair<O> {
+    fn from(pair: Pair<O>) -> Self {
+        Self { pair }
+    }
+}
+
+impl<O: Owner + ?Sized> Deref for PyPair<O> {
+    type Target = Pair<O>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.pair
+    }
+}
+
+impl<O: Owner + Debug + ?Sized> Debug for PyPair<O>
+where
+    for<'any> Dependent<'any, O>: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("PyPair").field(&self.pair).finish()
+    }
+}
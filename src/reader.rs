@@ -0,0 +1,150 @@
+//! Defines constructors of [`Pair`] which read the owner's bytes from a
+//! [`Read`]er, and [`ReadError`], the error they return. Only available with
+//! the `std` feature.
+
+use core::fmt::{Debug, Display};
+
+use alloc::{boxed::Box, vec::Vec};
+use std::io::Read;
+
+use crate::{Owner, Pair};
+
+impl<O: Owner + ?Sized> Pair<O> {
+    /// Constructs a new [`Pair`] with an unsized [`Owner`] holding all of the
+    /// bytes read from the given reader. The dependent will be computed through
+    /// [`Owner::make_dependent`] during this construction, and may borrow
+    /// directly from the bytes without copying them.
+    ///
+    /// The bytes are read into a growable buffer, which is then converted into
+    /// a boxed slice (discarding any excess capacity) and then into a
+    /// `Box<O>` - see [`Pair::try_new_from_vec_with_context`].
+    ///
+    /// ```
+    /// # use pair::{Dependent, HasDependent, Owner, Pair, ReadError};
+    /// # use std::convert::Infallible;
+    /// #[repr(transparent)]
+    /// struct Records([u8]);
+    ///
+    /// impl From<Box<[u8]>> for Box<Records> {
+    ///     fn from(bytes: Box<[u8]>) -> Self {
+    ///         // SAFETY: `Records` is a `#[repr(transparent)]` wrapper of `[u8]`
+    ///         unsafe { Box::from_raw(Box::into_raw(bytes) as *mut Records) }
+    ///     }
+    /// }
+    ///
+    /// impl<'owner> HasDependent<'owner> for Records {
+    ///     type Dependent = Vec<&'owner [u8]>;
+    /// }
+    ///
+    /// impl Owner for Records {
+    ///     type Context<'a> = u8;
+    ///     type Error = Infallible;
+    ///
+    ///     fn make_dependent(&self, separator: u8) -> Result<Dependent<'_, Self>, Self::Error> {
+    ///         Ok(self.0.split(|&byte| byte == separator).collect())
+    ///     }
+    /// }
+    ///
+    /// let input: &[u8] = b"ab;cd;ef";
+    /// let Ok(pair) = Pair::<Records>::from_reader_with_context(input, b';') else {
+    ///     panic!("reading from a slice can't fail");
+    /// };
+    /// assert_eq!(pair.with_dependent(|records| records[1]), b"cd");
+    /// ```
+    ///
+    /// Only available with the `std` feature.
+    ///
+    /// See the "Constructors" section in the documentation of [`Pair`] for
+    /// information on the differences between constructors.
+    ///
+    /// # Errors
+    /// [`ReadError::Io`] if reading from the reader fails, or
+    /// [`ReadError::Dependent`] if
+    /// [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
+    /// error.
+    #[track_caller]
+    pub fn from_reader_with_context<R: Read>(
+        mut reader: R,
+        context: O::Context<'_>,
+    ) -> Result<Self, ReadError<O, O::Error>>
+    where
+        Box<[u8]>: Into<Box<O>>,
+    {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).map_err(ReadError::Io)?;
+
+        Self::try_new_from_vec_with_context(bytes, context)
+            .map_err(|(owner, err)| ReadError::Dependent(owner, err))
+    }
+}
+
+impl<O: for<'any> Owner<Context<'any> = ()> + ?Sized> Pair<O> {
+    /// Constructs a new [`Pair`] with an unsized [`Owner`] holding all of the
+    /// bytes read from the given reader. The dependent will be computed through
+    /// [`Owner::make_dependent`] during this construction, and may borrow
+    /// directly from the bytes without copying them.
+    ///
+    /// See the documentation of [`Pair::from_reader_with_context`] for more
+    /// information.
+    ///
+    /// Only available with the `std` feature.
+    ///
+    /// # Errors
+    /// [`ReadError::Io`] if reading from the reader fails, or
+    /// [`ReadError::Dependent`] if
+    /// [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
+    /// error.
+    #[track_caller]
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, ReadError<O, O::Error>>
+    where
+        Box<[u8]>: Into<Box<O>>,
+    {
+        Self::from_reader_with_context(reader, ())
+    }
+}
+
+/// An error which occurred while constructing a [`Pair`] from a
+/// [`Read`]er, with [`Pair::from_reader`] or
+/// [`Pair::from_reader_with_context`].
+///
+/// Only available with the `std` feature.
+pub enum ReadError<O: ?Sized, E> {
+    /// Reading the owner's bytes failed.
+    Io(std::io::Error),
+
+    /// The bytes were read, but [`make_dependent`](Owner::make_dependent)
+    /// returned an error. Contains the owner, along with the error.
+    Dependent(Box<O>, E),
+}
+
+/// The [`Debug`] implementation for [`ReadError`] does not require (or display)
+/// a `Debug` implementation for the owner, which may be large.
+impl<O: ?Sized, E: Debug> Debug for ReadError<O, E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Io(err) => f.debug_tuple("Io").field(err).finish(),
+            Self::Dependent(_, err) => f.debug_tuple("Dependent").field(err).finish(),
+        }
+    }
+}
+
+/// The [`Display`] implementation for [`ReadError`] does not include the
+/// underlying error, which is instead available through
+/// [`Error::source`](core::error::Error::source).
+impl<O: ?Sized, E> Display for ReadError<O, E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Io(_) => f.write_str("failed to read the owner of a pair"),
+            Self::Dependent(..) => f.write_str("failed to construct the dependent of a pair"),
+        }
+    }
+}
+
+impl<O: ?Sized, E: core::error::Error + 'static> core::error::Error for ReadError<O, E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Dependent(_, err) => Some(err),
+        }
+    }
+}
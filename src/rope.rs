@@ -0,0 +1,288 @@
+//! Defines [`RopeSnapshot`], an owner of a rope whose dependent is a view of a
+//! range of it, and [`RopeBuffer`], an editable rope which keeps such a view up
+//! to date. Only available with the `ropey` feature.
+
+use core::{convert::Infallible, fmt::Debug, marker::PhantomData, ops::Range};
+
+use ropey::{Rope, RopeSlice};
+
+use crate::{Dependent, HasDependent, Owner, Pair};
+
+/// Defines a view of a range of a rope, for use as the dependent of a
+/// [`RopePair`].
+///
+/// Implement this trait for a marker type (or the view's "static" form) to
+/// define what the view contains. Slices, chunks, and lines of the rope can be
+/// borrowed directly, without copying.
+///
+/// Only available with the `ropey` feature.
+pub trait RopeView {
+    /// The view, borrowing from a rope with the lifetime `'rope`.
+    type View<'rope>;
+
+    /// Creates a view from a slice of the covered range of a rope.
+    fn view(slice: RopeSlice<'_>) -> Self::View<'_>;
+}
+
+/// A [`Rope`] and a range of it (in chars) covered by a view, which can act as
+/// the owner of a [`Pair`] with the view (defined by the [`RopeView`] `V`) as
+/// the dependent.
+///
+/// Cloning a rope is cheap (its chunks are shared), so a snapshot can be taken
+/// of an editor buffer without copying its text. To keep a view up to date as
+/// a rope is edited, see [`RopeBuffer`].
+///
+/// Only available with the `ropey` feature.
+pub struct RopeSnapshot<V> {
+    rope: Rope,
+    range: Range<usize>,
+    view: PhantomData<fn() -> V>,
+}
+
+/// A [`Pair`] of a [`RopeSnapshot`] and a view of its covered range.
+///
+/// Only available with the `ropey` feature.
+pub type RopePair<V> = Pair<RopeSnapshot<V>>;
+
+impl<V: RopeView> RopeSnapshot<V> {
+    /// Constructs a new [`RopeSnapshot`] of the given rope, covering the given
+    /// range of chars.
+    ///
+    /// The view is not created until a [`Pair`] is constructed from the
+    /// snapshot.
+    ///
+    /// # Panics
+    /// If the range is decreasing, or extends past the end of the rope.
+    pub fn new(rope: Rope, range: Range<usize>) -> Self {
+        assert_covers(&rope, &range);
+
+        Self {
+            rope,
+            range,
+            view: PhantomData,
+        }
+    }
+
+    /// Returns the rope.
+    pub fn rope(&self) -> &Rope {
+        &self.rope
+    }
+
+    /// Returns the range of chars covered by the view.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Consumes the [`RopeSnapshot`], returning the rope.
+    pub fn into_rope(self) -> Rope {
+        self.rope
+    }
+}
+
+/// Asserts that `range` is a valid range of chars in `rope`.
+#[track_caller]
+fn assert_covers(rope: &Rope, range: &Range<usize>) {
+    assert!(
+        range.start <= range.end && range.end <= rope.len_chars(),
+        "range {range:?} is out of bounds for a rope of {} chars",
+        rope.len_chars(),
+    );
+}
+
+impl<V> Debug for RopeSnapshot<V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RopeSnapshot")
+            .field("len_chars", &self.rope.len_chars())
+            .field("range", &self.range)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'owner, V: RopeView> HasDependent<'owner> for RopeSnapshot<V> {
+    type Dependent = V::View<'owner>;
+}
+
+impl<V: RopeView> Owner for RopeSnapshot<V> {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(V::view(self.rope.slice(self.range.clone())))
+    }
+}
+
+/// An editable [`Rope`], with a view (defined by the [`RopeView`] `V`) of a
+/// covered range of it, such as the lines visible in an editor's viewport.
+///
+/// The view is stored in a [`RopePair`] of a snapshot of the rope. Edits are
+/// applied to the rope directly - the view is only rebuilt (from a new, cheap
+/// snapshot) when an edit overlaps its covered range. Edits before the covered
+/// range shift it, and edits after it leave it untouched, without rebuilding
+/// the view.
+///
+/// Only available with the `ropey` feature.
+///
+/// ```
+/// # use pair::{RopeBuffer, RopeView};
+/// # use ropey::{Rope, RopeSlice};
+/// // A view of the lines in the covered range
+/// struct Lines;
+///
+/// impl RopeView for Lines {
+///     type View<'rope> = Vec<RopeSlice<'rope>>;
+///
+///     fn view(slice: RopeSlice<'_>) -> Vec<RopeSlice<'_>> {
+///         slice.lines().collect()
+///     }
+/// }
+///
+/// let mut buffer = RopeBuffer::<Lines>::new(Rope::from_str("one\ntwo\nthree\n"), 4..8);
+/// assert_eq!(buffer.with_view(|lines| lines[0].to_string()), "two\n");
+///
+/// // Edits outside of the covered range don't rebuild the view...
+/// assert!(!buffer.insert(0, "zero\n"));
+/// assert!(!buffer.insert(buffer.rope().len_chars(), "four\n"));
+/// assert_eq!(buffer.coverage(), 9..13);
+///
+/// // ...but edits inside of it do
+/// assert!(buffer.insert(12, " (2)"));
+/// assert_eq!(buffer.with_view(|lines| lines[0].to_string()), "two (2)\n");
+/// ```
+pub struct RopeBuffer<V: RopeView> {
+    rope: Rope,
+    coverage: Range<usize>,
+    pair: RopePair<V>,
+}
+
+impl<V: RopeView> RopeBuffer<V> {
+    /// Constructs a new [`RopeBuffer`] of the given rope, with a view covering
+    /// the given range of chars.
+    ///
+    /// # Panics
+    /// If the range is decreasing, or extends past the end of the rope.
+    pub fn new(rope: Rope, coverage: Range<usize>) -> Self {
+        let pair = Pair::new(RopeSnapshot::new(rope.clone(), coverage.clone()));
+
+        Self {
+            rope,
+            coverage,
+            pair,
+        }
+    }
+
+    /// Returns the rope, including all edits.
+    pub fn rope(&self) -> &Rope {
+        &self.rope
+    }
+
+    /// Returns the range of chars of the rope covered by the view.
+    pub fn coverage(&self) -> Range<usize> {
+        self.coverage.clone()
+    }
+
+    /// Returns the [`RopePair`] storing the view. Its snapshot may be older
+    /// than the rope, but the text in its covered range is always the same as
+    /// the text in the rope's covered range.
+    pub fn pair(&self) -> &RopePair<V> {
+        &self.pair
+    }
+
+    /// Calls the given closure, providing shared access to the view, and
+    /// returns the value computed by the closure.
+    ///
+    /// See the documentation of [`Pair::with_dependent`] for more information.
+    pub fn with_view<'self_borrow, F, T>(&'self_borrow self, f: F) -> T
+    where
+        F: for<'any> FnOnce(&'self_borrow V::View<'_>) -> T,
+    {
+        self.pair.with_dependent(f)
+    }
+
+    /// Changes the range of chars covered by the view, and rebuilds it.
+    ///
+    /// # Panics
+    /// If the range is decreasing, or extends past the end of the rope.
+    pub fn set_coverage(&mut self, coverage: Range<usize>) {
+        assert_covers(&self.rope, &coverage);
+
+        self.coverage = coverage;
+        self.rebuild();
+    }
+
+    /// Inserts `text` at the given char index, and returns whether the view
+    /// was rebuilt.
+    ///
+    /// The view is only rebuilt if the text is inserted strictly inside of the
+    /// covered range. Text inserted at the start of the covered range is
+    /// treated as coming before it.
+    ///
+    /// # Panics
+    /// If the index is out of bounds.
+    pub fn insert(&mut self, char_idx: usize, text: &str) -> bool {
+        self.rope.insert(char_idx, text);
+        let inserted = text.chars().count();
+
+        if char_idx <= self.coverage.start {
+            self.coverage.start += inserted;
+            self.coverage.end += inserted;
+            false
+        } else if char_idx >= self.coverage.end {
+            false
+        } else {
+            self.coverage.end += inserted;
+            self.rebuild();
+            true
+        }
+    }
+
+    /// Removes the given range of chars, and returns whether the view was
+    /// rebuilt.
+    ///
+    /// The view is only rebuilt if the removed range overlaps the covered
+    /// range.
+    ///
+    /// # Panics
+    /// If the range is decreasing, or extends past the end of the rope.
+    pub fn remove(&mut self, char_range: Range<usize>) -> bool {
+        self.rope.remove(char_range.clone());
+
+        // Maps a char index from before the removal to after it
+        let map = |char_idx: usize| {
+            if char_idx <= char_range.start {
+                char_idx
+            } else if char_idx >= char_range.end {
+                char_idx - char_range.len()
+            } else {
+                char_range.start
+            }
+        };
+        let overlaps = !char_range.is_empty()
+            && char_range.start < self.coverage.end
+            && char_range.end > self.coverage.start;
+        self.coverage = map(self.coverage.start)..map(self.coverage.end);
+
+        if overlaps {
+            self.rebuild();
+        }
+        overlaps
+    }
+
+    /// Consumes the [`RopeBuffer`], returning the rope.
+    pub fn into_rope(self) -> Rope {
+        self.rope
+    }
+
+    /// Rebuilds the view from a new snapshot of the rope.
+    fn rebuild(&mut self) {
+        self.pair = Pair::new(RopeSnapshot::new(self.rope.clone(), self.coverage.clone()));
+    }
+}
+
+impl<V: RopeView> Debug for RopeBuffer<V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RopeBuffer")
+            .field("len_chars", &self.rope.len_chars())
+            .field("coverage", &self.coverage)
+            .finish_non_exhaustive()
+    }
+}
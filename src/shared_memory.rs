@@ -0,0 +1,371 @@
+//! Defines [`SharedSegment`], an owner of a read-only shared-memory segment
+//! whose dependent is parsed in place from its bytes. Only available with the
+//! `shared-memory` feature, on unix.
+
+use core::{
+    fmt::{Debug, Display},
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ptr::NonNull,
+};
+use std::{
+    io,
+    os::fd::{AsFd, AsRawFd, BorrowedFd, OwnedFd},
+    sync::OnceLock,
+};
+
+use crate::{Dependent, HasDependent, Owner, Pair};
+
+/// Defines how the bytes of a [`SharedSegment`] are parsed in place, for use as
+/// the dependent of a [`SharedSegmentPair`].
+///
+/// Implement this trait for a marker type (or the parsed view's "static" form)
+/// to define the layout of a segment. The view borrows directly from the mapped
+/// segment, without copying.
+///
+/// Only available with the `shared-memory` feature, on unix.
+pub trait SegmentLayout {
+    /// The parsed view, borrowing from the bytes of a segment with the lifetime
+    /// `'segment`.
+    type View<'segment>;
+
+    /// The error returned by [`parse`](SegmentLayout::parse) if the bytes of a
+    /// segment are malformed.
+    type Error;
+
+    /// Parses a view from the bytes of a segment, validating them (for example,
+    /// checking a header) along the way.
+    ///
+    /// # Errors
+    /// If the bytes of the segment are malformed.
+    fn parse(bytes: &[u8]) -> Result<Self::View<'_>, Self::Error>;
+}
+
+/// A handle to a read-only shared-memory segment, which can act as the owner of
+/// a [`Pair`] with a view parsed in place from its bytes as the dependent.
+///
+/// The segment may be a memfd, or an object opened with `shm_open`, and the
+/// view is defined by the [`SegmentLayout`] `L`. This is useful for zero-copy
+/// IPC: one process writes a segment and passes its file descriptor to another,
+/// which maps the segment and parses it without copying its bytes out. The
+/// segment is validated when the handle is constructed, and is only mapped into
+/// memory when the dependent is made - so a failure to map it is reported
+/// through the owner's [`Error`](Owner::Error) as a [`SegmentError::Map`], just
+/// like a malformed segment is reported as a [`SegmentError::Layout`].
+///
+/// Since the dependent borrows the segment's bytes directly, they must not
+/// change while the segment is mapped. [`SharedSegment::from_sealed_memfd`]
+/// checks this with file seals, while [`SharedSegment::from_fd`] leaves it to
+/// the caller.
+///
+/// Only available with the `shared-memory` feature, on unix. The memfd
+/// constructors are only available on Linux and Android.
+///
+/// ```
+/// # use pair::{Pair, SegmentLayout, SharedSegment, SharedSegmentPair};
+/// # use std::{convert::Infallible, os::fd::AsFd};
+/// // A segment of newline-separated records
+/// struct Lines;
+///
+/// impl SegmentLayout for Lines {
+///     type View<'segment> = Vec<&'segment [u8]>;
+///     type Error = Infallible;
+///
+///     fn parse(bytes: &[u8]) -> Result<Vec<&[u8]>, Self::Error> {
+///         Ok(bytes.split(|&byte| byte == b'\n').collect())
+///     }
+/// }
+///
+/// # // Miri can't create or map a memfd
+/// # #[cfg(any(miri, not(target_os = "linux")))] fn main() {}
+/// # #[cfg(all(not(miri), target_os = "linux"))] fn main() {
+/// let segment = SharedSegment::<Lines>::memfd(c"records", b"first\nsecond").unwrap();
+///
+/// // Another process would receive a copy of the file descriptor (for example,
+/// // over a unix socket), and validate it before mapping the segment
+/// let fd = segment.as_fd().try_clone_to_owned().unwrap();
+/// let received = SharedSegment::<Lines>::from_sealed_memfd(fd).unwrap();
+///
+/// let pair: SharedSegmentPair<Lines> = Pair::try_new(received).unwrap();
+/// assert_eq!(pair.with_dependent(|lines| lines[1]), b"second");
+/// # }
+/// ```
+pub struct SharedSegment<L> {
+    mapping: OnceLock<Mapping>,
+    fd: OwnedFd,
+    len: usize,
+    layout: PhantomData<fn() -> L>,
+}
+
+/// A [`Pair`] of a [`SharedSegment`] and the view parsed from it.
+///
+/// Only available with the `shared-memory` feature, on unix.
+pub type SharedSegmentPair<L> = Pair<SharedSegment<L>>;
+
+impl<L: SegmentLayout> SharedSegment<L> {
+    /// Constructs a handle to the shared-memory segment behind the given file
+    /// descriptor (such as one returned by `shm_open`). The length of the
+    /// segment is the size of the file when this is called.
+    ///
+    /// # Safety
+    /// Until the returned handle is dropped, nothing may write to or truncate
+    /// the segment - whether through this file descriptor, another file
+    /// descriptor or mapping of the same segment, or another process.
+    ///
+    /// # Errors
+    /// If the size of the file can't be determined.
+    pub unsafe fn from_fd(fd: OwnedFd) -> io::Result<Self> {
+        let len = segment_len(fd.as_fd())?;
+
+        Ok(Self {
+            mapping: OnceLock::new(),
+            fd,
+            len,
+            layout: PhantomData,
+        })
+    }
+
+    /// Constructs a handle to the memfd segment behind the given file
+    /// descriptor, after checking that it's sealed against writes and
+    /// shrinking. Seals can never be removed, so this is safe even for a
+    /// segment received from an untrusted process.
+    ///
+    /// Only available on Linux and Android.
+    ///
+    /// # Errors
+    /// If the seals of the file descriptor can't be read (for example, because
+    /// it isn't a memfd), if it's missing the `F_SEAL_WRITE` or `F_SEAL_SHRINK`
+    /// seal, or if the size of the file can't be determined.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn from_sealed_memfd(fd: OwnedFd) -> io::Result<Self> {
+        // SAFETY: `F_GET_SEALS` only reads the seals of the file descriptor,
+        // which is valid for the duration of this call.
+        let seals = unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_GET_SEALS) };
+        if seals < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let required = libc::F_SEAL_WRITE | libc::F_SEAL_SHRINK;
+        if seals & required != required {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the memfd is not sealed against writes and shrinking",
+            ));
+        }
+
+        // SAFETY: the memfd is sealed against writes and shrinking, and seals
+        // can never be removed, so nothing can write to or truncate it anymore.
+        unsafe { Self::from_fd(fd) }
+    }
+
+    /// Creates a new memfd segment holding a copy of the given bytes, and seals
+    /// it against writes and resizing, so it can be shared with other processes
+    /// through its file descriptor (see [`AsFd`]). The name is only used for
+    /// debugging, and need not be unique.
+    ///
+    /// Only available on Linux and Android.
+    ///
+    /// # Errors
+    /// If creating, writing to, or sealing the memfd fails.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn memfd(name: &core::ffi::CStr, bytes: &[u8]) -> io::Result<Self> {
+        use std::{io::Write, os::fd::FromRawFd};
+
+        // SAFETY: `name` is a valid nul-terminated string.
+        let fd = unsafe {
+            libc::memfd_create(name.as_ptr(), libc::MFD_CLOEXEC | libc::MFD_ALLOW_SEALING)
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        // SAFETY: `memfd_create` just returned this file descriptor, so it's
+        // open and nothing else owns it.
+        let mut file = std::fs::File::from(unsafe { OwnedFd::from_raw_fd(fd) });
+        file.write_all(bytes)?;
+
+        let seals = libc::F_SEAL_WRITE | libc::F_SEAL_SHRINK | libc::F_SEAL_GROW;
+        // SAFETY: `F_ADD_SEALS` only adds seals to the file descriptor, which is
+        // valid for the duration of this call.
+        if unsafe { libc::fcntl(file.as_raw_fd(), libc::F_ADD_SEALS, seals) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Self::from_sealed_memfd(file.into())
+    }
+
+    /// Returns the length of the segment, in bytes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the segment is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the bytes of the segment, mapping it into memory (read-only) if
+    /// it isn't already.
+    ///
+    /// # Errors
+    /// If mapping the segment fails.
+    pub fn bytes(&self) -> io::Result<&[u8]> {
+        if let Some(mapping) = self.mapping.get() {
+            return Ok(mapping.bytes());
+        }
+
+        // If another thread maps the segment first, our mapping is unmapped,
+        // and theirs is used instead
+        let mapping = Mapping::new(self.fd.as_fd(), self.len)?;
+        Ok(self.mapping.get_or_init(|| mapping).bytes())
+    }
+}
+
+impl<L> AsFd for SharedSegment<L> {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.fd.as_fd()
+    }
+}
+
+impl<L> Debug for SharedSegment<L> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SharedSegment")
+            .field("fd", &self.fd)
+            .field("len", &self.len)
+            .field("mapped", &self.mapping.get().is_some())
+            .finish()
+    }
+}
+
+impl<'owner, L: SegmentLayout> HasDependent<'owner> for SharedSegment<L> {
+    type Dependent = L::View<'owner>;
+}
+
+impl<L: SegmentLayout> Owner for SharedSegment<L> {
+    type Context<'a> = ();
+    type Error = SegmentError<L::Error>;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        let bytes = self.bytes().map_err(SegmentError::Map)?;
+        L::parse(bytes).map_err(SegmentError::Layout)
+    }
+}
+
+/// An error which occurred while making the dependent of a [`SharedSegment`].
+///
+/// Only available with the `shared-memory` feature, on unix.
+#[derive(Debug)]
+pub enum SegmentError<E> {
+    /// Mapping the segment into memory failed.
+    Map(io::Error),
+    /// [`SegmentLayout::parse`] rejected the bytes of the segment.
+    Layout(E),
+}
+
+/// The [`Display`] implementation for [`SegmentError`] does not include the
+/// underlying error, which is instead available through
+/// [`Error::source`](core::error::Error::source).
+impl<E> Display for SegmentError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::Map(_) => "failed to map a shared-memory segment",
+            Self::Layout(_) => "failed to parse a shared-memory segment",
+        })
+    }
+}
+
+impl<E: core::error::Error + 'static> core::error::Error for SegmentError<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Map(err) => Some(err),
+            Self::Layout(err) => Some(err),
+        }
+    }
+}
+
+/// A read-only, shared mapping of a segment. Empty segments aren't actually
+/// mapped, since `mmap` rejects empty mappings.
+struct Mapping {
+    ptr: NonNull<u8>,
+    len: usize,
+}
+
+// SAFETY: `Mapping` exclusively owns its mapping, which is read-only and only
+// unmapped when it's dropped, so it is safe to send and share between threads
+// like a `Box<[u8]>`.
+unsafe impl Send for Mapping {}
+// SAFETY: see above.
+unsafe impl Sync for Mapping {}
+
+impl Mapping {
+    /// Maps the first `len` bytes of the given segment into memory.
+    fn new(fd: BorrowedFd<'_>, len: usize) -> io::Result<Self> {
+        if len == 0 {
+            return Ok(Self {
+                ptr: NonNull::dangling(),
+                len,
+            });
+        }
+
+        // SAFETY: this creates a new read-only mapping, which can't alias any
+        // existing memory that Rust could write to.
+        let ptr = unsafe {
+            libc::mmap(
+                core::ptr::null_mut(),
+                len,
+                libc::PROT_READ,
+                libc::MAP_SHARED,
+                fd.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        let ptr = NonNull::new(ptr.cast::<u8>()).ok_or_else(io::Error::last_os_error)?;
+
+        Ok(Self { ptr, len })
+    }
+
+    /// Returns the mapped bytes.
+    fn bytes(&self) -> &[u8] {
+        // SAFETY: `ptr` is valid for reads of `len` bytes until the mapping is
+        // dropped (or is dangling, if `len` is 0). The constructors of
+        // `SharedSegment` ensure that nothing writes to or truncates the
+        // segment while it's mapped.
+        unsafe { core::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        if self.len == 0 {
+            return;
+        }
+
+        // SAFETY: `ptr` and `len` describe a mapping created by `mmap`, which
+        // nothing borrows anymore. Failing to unmap it only leaks the mapping.
+        unsafe {
+            libc::munmap(self.ptr.as_ptr().cast(), self.len);
+        }
+    }
+}
+
+/// Returns the size of the file behind the given file descriptor.
+fn segment_len(fd: BorrowedFd<'_>) -> io::Result<usize> {
+    let mut stat = MaybeUninit::<libc::stat>::uninit();
+    // SAFETY: `stat` is valid for writes of a `libc::stat`, and `fd` is valid
+    // for the duration of this call.
+    if unsafe { libc::fstat(fd.as_raw_fd(), stat.as_mut_ptr()) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: `fstat` succeeded, so it initialized `stat`.
+    let stat = unsafe { stat.assume_init() };
+
+    usize::try_from(stat.st_size).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the segment is too large to map",
+        )
+    })
+}
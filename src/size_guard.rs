@@ -0,0 +1,73 @@
+//! Defines the inline size of a [`Pair`], and
+//! [`static_assert_pair_size!`](crate::static_assert_pair_size!) for
+//! guarding against regressions in it.
+
+use crate::{Owner, Pair};
+
+impl<O: Owner + ?Sized> Pair<O> {
+    /// Returns the size of a `Pair<O>` itself, in bytes - not including the
+    /// heap allocations of its owner and dependent.
+    ///
+    /// This is currently two pointers for sized owners, and three for unsized
+    /// owners (whose pointer carries metadata, such as a length). With the
+    /// `leak-check` feature, every pair additionally stores a `u64` ID.
+    ///
+    /// Since this is a `const fn`, it can be used in constant assertions - see
+    /// [`static_assert_pair_size!`](crate::static_assert_pair_size!).
+    pub const fn inline_size() -> usize {
+        size_of::<Self>()
+    }
+
+    /// Returns the size of a `Pair<O>` itself, in pointers (rounded up). See
+    /// [`Pair::inline_size`] for more information.
+    pub const fn inline_size_in_pointers() -> usize {
+        Self::inline_size().div_ceil(size_of::<usize>())
+    }
+}
+
+/// Asserts at compile time that a `Pair<O>` is at most the given number of
+/// pointers in size (see [`Pair::inline_size`]).
+///
+/// Crates embedding pairs in hot, size-sensitive structs can use this to be
+/// notified (by a compilation error) if a pair ever grows, such as after
+/// upgrading this crate.
+///
+/// ```
+/// # use pair::{Dependent, HasDependent, Owner, static_assert_pair_size};
+/// # use std::convert::Infallible;
+/// struct Lines(String);
+///
+/// impl<'owner> HasDependent<'owner> for Lines {
+///     type Dependent = Vec<&'owner str>;
+/// }
+///
+/// impl Owner for Lines {
+///     type Context<'a> = ();
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         Ok(self.0.lines().collect())
+///     }
+/// }
+///
+/// # #[cfg(not(feature = "leak-check"))]
+/// static_assert_pair_size!(Lines, 2);
+/// ```
+///
+/// Note that features are unified across a dependency graph - if any crate
+/// enables the `leak-check` feature, every pair grows by a `u64`.
+#[macro_export]
+macro_rules! static_assert_pair_size {
+    ($owner:ty, $pointers:expr $(,)?) => {
+        const _: () = ::core::assert!(
+            $crate::Pair::<$owner>::inline_size_in_pointers() <= $pointers,
+            ::core::concat!(
+                "Pair<",
+                ::core::stringify!($owner),
+                "> is larger than ",
+                ::core::stringify!($pointers),
+                " pointers",
+            ),
+        );
+    };
+}
@@ -0,0 +1,280 @@
+//! Defines [`PairSlab`], a collection of [`Pair`]s identified by opaque `u32`
+//! handles, for exposing pairs across a WASM module boundary. Only available
+//! with the `wasm` feature.
+
+use core::fmt::Debug;
+
+use alloc::vec::Vec;
+
+use crate::{Dependent, Owner, Pair};
+
+/// The number of bits of a [`PairHandle`] used for the slot index. The
+/// remaining bits store the slot's generation.
+const INDEX_BITS: u32 = 24;
+
+/// A mask selecting the slot index bits of a [`PairHandle`].
+const INDEX_MASK: u32 = (1 << INDEX_BITS) - 1;
+
+/// An opaque handle to a [`Pair`] stored in a [`PairSlab`].
+///
+/// Handles can be converted to and from `u32`s with
+/// [`into_raw`](PairHandle::into_raw) and [`from_raw`](PairHandle::from_raw),
+/// to be passed across a WASM module boundary. Any `u32` may be converted into
+/// a handle - invalid or stale handles are simply not found in the slab.
+///
+/// Only available with the `wasm` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PairHandle(u32);
+
+impl PairHandle {
+    /// Converts a raw `u32` (previously returned by
+    /// [`into_raw`](PairHandle::into_raw)) into a [`PairHandle`].
+    pub fn from_raw(raw: u32) -> Self {
+        Self(raw)
+    }
+
+    /// Converts this [`PairHandle`] into a raw `u32`.
+    pub fn into_raw(self) -> u32 {
+        self.0
+    }
+
+    fn new(index: u32, generation: u8) -> Self {
+        Self(u32::from(generation) << INDEX_BITS | index)
+    }
+
+    fn index(self) -> usize {
+        (self.0 & INDEX_MASK) as usize
+    }
+
+    fn generation(self) -> u8 {
+        (self.0 >> INDEX_BITS) as u8
+    }
+}
+
+/// A slot in a [`PairSlab`].
+struct Slot<O: Owner + ?Sized> {
+    /// Incremented (wrapping) every time the slot is vacated, so that handles
+    /// to a removed pair don't refer to a pair later stored in the same slot.
+    generation: u8,
+    pair: Option<Pair<O>>,
+}
+
+/// A collection of [`Pair`]s, each identified by an opaque [`PairHandle`].
+///
+/// This is intended for exposing a pair-based API from a WASM module: pairs are
+/// registered in a slab (typically a `static` or thread-local), and only their
+/// handles - plain `u32`s - cross the module boundary. Exported functions then
+/// look the pairs up by handle, so neither side needs any `unsafe` code or raw
+/// pointers.
+///
+/// Handles of removed pairs are invalidated. Slots are reused, but each slot
+/// has a generation which is stored in its handles, so a stale handle is
+/// unlikely to refer to a different pair which later reuses its slot (the
+/// generation wraps after 256 reuses of the same slot). A slab can hold up to
+/// 2<sup>24</sup> pairs at once.
+///
+/// ```
+/// # use pair::{Dependent, HasDependent, Owner, Pair, PairHandle, PairSlab};
+/// # use std::{cell::RefCell, convert::Infallible};
+/// #[derive(Debug)]
+/// struct Document(String);
+///
+/// impl<'owner> HasDependent<'owner> for Document {
+///     type Dependent = Vec<&'owner str>;
+/// }
+///
+/// impl Owner for Document {
+///     type Context<'a> = ();
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         Ok(self.0.lines().collect())
+///     }
+/// }
+///
+/// thread_local! {
+///     static DOCUMENTS: RefCell<PairSlab<Document>> = const { RefCell::new(PairSlab::new()) };
+/// }
+///
+/// // These would be `#[unsafe(no_mangle)] pub extern "C" fn`s in a WASM module
+/// fn parse(text: String) -> u32 {
+///     let pair = Pair::new(Document(text));
+///     DOCUMENTS.with_borrow_mut(|documents| documents.insert(pair).unwrap().into_raw())
+/// }
+///
+/// fn line_count(handle: u32) -> Option<usize> {
+///     DOCUMENTS.with_borrow(|documents| {
+///         documents.with_dependent(PairHandle::from_raw(handle), |lines| lines.len())
+///     })
+/// }
+///
+/// fn free(handle: u32) {
+///     DOCUMENTS.with_borrow_mut(|documents| documents.remove(PairHandle::from_raw(handle)));
+/// }
+///
+/// let handle = parse(String::from("a\nb\nc"));
+/// assert_eq!(line_count(handle), Some(3));
+/// free(handle);
+/// assert_eq!(line_count(handle), None);
+/// ```
+///
+/// Only available with the `wasm` feature.
+pub struct PairSlab<O: Owner + ?Sized> {
+    slots: Vec<Slot<O>>,
+    vacant: Vec<u32>,
+    len: usize,
+}
+
+impl<O: Owner + ?Sized> PairSlab<O> {
+    /// Constructs a new, empty [`PairSlab`].
+    pub const fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            vacant: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Returns the number of pairs in the slab.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the slab contains no pairs.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts a pair into the slab, returning its handle.
+    ///
+    /// # Errors
+    /// If the slab is full (already holding 2<sup>24</sup> pairs), in which
+    /// case the pair is returned.
+    pub fn insert(&mut self, pair: Pair<O>) -> Result<PairHandle, Pair<O>> {
+        let index = if let Some(index) = self.vacant.pop() {
+            index
+        } else {
+            let index = u32::try_from(self.slots.len())
+                .ok()
+                .filter(|&index| index <= INDEX_MASK);
+            let Some(index) = index else {
+                return Err(pair);
+            };
+
+            self.slots.push(Slot {
+                generation: 0,
+                pair: None,
+            });
+            index
+        };
+
+        let slot = &mut self.slots[index as usize];
+        slot.pair = Some(pair);
+        self.len += 1;
+
+        Ok(PairHandle::new(index, slot.generation))
+    }
+
+    /// Removes the pair with the given handle from the slab, returning it, or
+    /// [`None`] if the handle is invalid.
+    pub fn remove(&mut self, handle: PairHandle) -> Option<Pair<O>> {
+        let slot = self.slots.get_mut(handle.index())?;
+        if slot.generation != handle.generation() {
+            return None;
+        }
+
+        let pair = slot.pair.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.vacant.push(handle.0 & INDEX_MASK);
+        self.len -= 1;
+
+        Some(pair)
+    }
+
+    /// Returns `true` if the slab contains a pair with the given handle.
+    pub fn contains(&self, handle: PairHandle) -> bool {
+        self.get(handle).is_some()
+    }
+
+    /// Returns a reference to the pair with the given handle, or [`None`] if
+    /// the handle is invalid.
+    pub fn get(&self, handle: PairHandle) -> Option<&Pair<O>> {
+        let slot = self.slots.get(handle.index())?;
+
+        (slot.generation == handle.generation())
+            .then_some(slot.pair.as_ref())
+            .flatten()
+    }
+
+    /// Returns a mutable reference to the pair with the given handle, or
+    /// [`None`] if the handle is invalid.
+    pub fn get_mut(&mut self, handle: PairHandle) -> Option<&mut Pair<O>> {
+        let slot = self.slots.get_mut(handle.index())?;
+
+        (slot.generation == handle.generation())
+            .then_some(slot.pair.as_mut())
+            .flatten()
+    }
+
+    /// Calls the given closure, providing shared access to the dependent of the
+    /// pair with the given handle, and returns the value computed by the
+    /// closure. Returns [`None`] (without calling the closure) if the handle is
+    /// invalid.
+    ///
+    /// See the documentation of [`Pair::with_dependent`] for more information
+    /// on the closure.
+    pub fn with_dependent<'self_borrow, F, T>(
+        &'self_borrow self,
+        handle: PairHandle,
+        f: F,
+    ) -> Option<T>
+    where
+        F: for<'any> FnOnce(&'self_borrow Dependent<'_, O>) -> T,
+    {
+        Some(self.get(handle)?.with_dependent(f))
+    }
+
+    /// Calls the given closure, providing exclusive access to the dependent of
+    /// the pair with the given handle, and returns the value computed by the
+    /// closure. Returns [`None`] (without calling the closure) if the handle is
+    /// invalid.
+    ///
+    /// See the documentation of [`Pair::with_dependent_mut`] for more
+    /// information on the closure.
+    pub fn with_dependent_mut<'self_borrow, F, T>(
+        &'self_borrow mut self,
+        handle: PairHandle,
+        f: F,
+    ) -> Option<T>
+    where
+        F: for<'any> FnOnce(&'self_borrow mut Dependent<'_, O>) -> T,
+    {
+        Some(self.get_mut(handle)?.with_dependent_mut(f))
+    }
+
+    /// Returns an iterator over the handles and pairs in the slab.
+    pub fn iter(&self) -> impl Iterator<Item = (PairHandle, &Pair<O>)> {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            let pair = slot.pair.as_ref()?;
+            // Slot indices are always within `INDEX_MASK`
+            let index = u32::try_from(index).ok()?;
+
+            Some((PairHandle::new(index, slot.generation), pair))
+        })
+    }
+}
+
+impl<O: Owner + ?Sized> Default for PairSlab<O> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<O: Owner + Debug + ?Sized> Debug for PairSlab<O>
+where
+    for<'any> Dependent<'any, O>: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,334 @@
+//! Defines [`SourceFile`], an owner of source text whose dependent is a
+//! [`LineIndex`], for reporting diagnostics. Only available with the
+//! `source-file` feature.
+//!
+//! With the `codespan-reporting` and `ariadne` features, [`SourceFilePair`]s
+//! can also be passed directly to those crates' diagnostic renderers.
+
+use core::{convert::Infallible, ops::Range};
+
+use alloc::{string::String, vec::Vec};
+
+use crate::{Dependent, HasDependent, Owner, Pair};
+
+/// A [`Pair`] of a [`SourceFile`] and its [`LineIndex`].
+///
+/// Only available with the `source-file` feature.
+pub type SourceFilePair = Pair<SourceFile>;
+
+/// The name and text of a source file, whose dependent is a [`LineIndex`] of
+/// its text.
+///
+/// This is intended as a building block for error reporting in compilers,
+/// linters, and similar tools - see [`SourceFilePair`]. Diagnostics typically
+/// store byte offsets into the source, which the line index converts into
+/// line and column numbers, and lines of source text to display.
+///
+/// ```
+/// # use pair::{LineCol, SourceFile, SourceFilePair};
+/// let source = SourceFilePair::new(SourceFile::new("main.rs", "fn main() {\n    let = 5;\n}\n"));
+///
+/// let offset = source.text().find('=').unwrap();
+/// let LineCol { line, column } = source.line_col(offset).unwrap();
+/// assert_eq!((line, column), (1, 8));
+///
+/// assert_eq!(
+///     format!("{}:{}:{}: {}", source.name(), line + 1, column + 1, source.line(line).unwrap()),
+///     "main.rs:2:9:     let = 5;"
+/// );
+/// ```
+///
+/// With the `codespan-reporting` feature, [`SourceFilePair`] implements
+/// [`codespan_reporting::files::Files`] (with a [`FileId`] of `()`), and with
+/// the `ariadne` feature, `&SourceFilePair` implements [`ariadne::Cache<()>`]
+/// (for spans of type [`Range<usize>`]). Either way, the source text is
+/// borrowed rather than copied.
+///
+/// Only available with the `source-file` feature.
+///
+/// [`FileId`]: codespan_reporting::files::Files::FileId
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourceFile {
+    name: String,
+    text: String,
+}
+
+impl SourceFile {
+    /// Constructs a new [`SourceFile`] with the given name (typically a path)
+    /// and text.
+    pub fn new(name: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            text: text.into(),
+        }
+    }
+
+    /// Returns the name of the source file.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the text of the source file.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Consumes the [`SourceFile`], returning its name and text.
+    pub fn into_parts(self) -> (String, String) {
+        (self.name, self.text)
+    }
+}
+
+impl<'owner> HasDependent<'owner> for SourceFile {
+    type Dependent = LineIndex<'owner>;
+}
+
+impl Owner for SourceFile {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(LineIndex::new(&self.text))
+    }
+}
+
+/// A zero-based line and column in a source file.
+///
+/// The column is measured in bytes from the start of the line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct LineCol {
+    /// The zero-based line number.
+    pub line: usize,
+    /// The zero-based column, in bytes from the start of the line.
+    pub column: usize,
+}
+
+/// An index of the lines in some text, for converting between byte offsets and
+/// [`LineCol`]s.
+///
+/// Lines are terminated by `\n` (a preceding `\r` is considered part of the
+/// line terminator). Text ending with a line terminator has a final, empty
+/// line after it.
+///
+/// With the `ariadne` feature, this also holds an [`ariadne::Source`] of the
+/// text, which ariadne requires a reference to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LineIndex<'text> {
+    text: &'text str,
+    // The byte offset of the start of each line - always starts with 0
+    line_starts: Vec<usize>,
+    #[cfg(feature = "ariadne")]
+    ariadne: ariadne::Source<&'text str>,
+}
+
+impl<'text> LineIndex<'text> {
+    /// Constructs a new [`LineIndex`] of the given text.
+    pub fn new(text: &'text str) -> Self {
+        let line_starts = core::iter::once(0)
+            .chain(text.match_indices('\n').map(|(offset, _)| offset + 1))
+            .collect();
+
+        Self {
+            text,
+            line_starts,
+            #[cfg(feature = "ariadne")]
+            ariadne: ariadne::Source::from(text),
+        }
+    }
+
+    /// Returns the indexed text.
+    pub fn text(&self) -> &'text str {
+        self.text
+    }
+
+    /// Returns the number of lines in the text. This is always at least 1.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Returns the span of the given zero-based line (excluding its line
+    /// terminator), or [`None`] if it is out of bounds.
+    pub fn line_span(&self, line: usize) -> Option<Range<usize>> {
+        let start = *self.line_starts.get(line)?;
+        let end = self
+            .line_starts
+            .get(line + 1)
+            .map_or(self.text.len(), |&next_start| next_start - 1);
+        let end = if self.text[start..end].ends_with('\r') {
+            end - 1
+        } else {
+            end
+        };
+
+        Some(start..end)
+    }
+
+    /// Returns the text of the given zero-based line (excluding its line
+    /// terminator), or [`None`] if it is out of bounds.
+    pub fn line(&self, line: usize) -> Option<&'text str> {
+        self.line_span(line).map(|span| &self.text[span])
+    }
+
+    /// Returns an iterator over the lines of the text (excluding their line
+    /// terminators).
+    pub fn lines(&self) -> impl Iterator<Item = &'text str> {
+        (0..self.line_count()).filter_map(|line| self.line(line))
+    }
+
+    /// Converts a byte offset into the text into a [`LineCol`], or returns
+    /// [`None`] if the offset is out of bounds.
+    ///
+    /// The offset may be equal to the length of the text (pointing just past
+    /// its end), which is useful for diagnostics at the end of a file.
+    pub fn line_col(&self, offset: usize) -> Option<LineCol> {
+        if offset > self.text.len() {
+            return None;
+        }
+
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+
+        Some(LineCol {
+            line,
+            column: offset - self.line_starts[line],
+        })
+    }
+
+    /// Converts a [`LineCol`] into a byte offset into the text, or returns
+    /// [`None`] if it is out of bounds.
+    ///
+    /// The column may point just past the end of the line's text (but not into
+    /// its line terminator).
+    pub fn offset(&self, line_col: LineCol) -> Option<usize> {
+        let span = self.line_span(line_col.line)?;
+        let offset = span.start.checked_add(line_col.column)?;
+
+        (offset <= span.end).then_some(offset)
+    }
+}
+
+impl Pair<SourceFile> {
+    /// Returns the name of the source file.
+    ///
+    /// Only available with the `source-file` feature.
+    pub fn name(&self) -> &str {
+        self.owner().name()
+    }
+
+    /// Returns the text of the source file.
+    ///
+    /// Only available with the `source-file` feature.
+    pub fn text(&self) -> &str {
+        self.owner().text()
+    }
+
+    /// Returns the number of lines in the source file. This is always at least
+    /// 1.
+    ///
+    /// Only available with the `source-file` feature.
+    pub fn line_count(&self) -> usize {
+        self.with_dependent(|index| index.line_starts.len())
+    }
+
+    /// Returns the text of the given zero-based line (excluding its line
+    /// terminator), or [`None`] if it is out of bounds.
+    ///
+    /// Only available with the `source-file` feature.
+    pub fn line(&self, line: usize) -> Option<&str> {
+        let span = self.line_span(line)?;
+
+        Some(&self.text()[span])
+    }
+
+    /// Returns the span of the given zero-based line (excluding its line
+    /// terminator), or [`None`] if it is out of bounds.
+    ///
+    /// Only available with the `source-file` feature.
+    pub fn line_span(&self, line: usize) -> Option<Range<usize>> {
+        self.with_dependent(|index| index.line_span(line))
+    }
+
+    /// Converts a byte offset into the source file into a [`LineCol`], or
+    /// returns [`None`] if the offset is out of bounds. See
+    /// [`LineIndex::line_col`] for more information.
+    ///
+    /// Only available with the `source-file` feature.
+    pub fn line_col(&self, offset: usize) -> Option<LineCol> {
+        self.with_dependent(|index| index.line_col(offset))
+    }
+
+    /// Converts a [`LineCol`] into a byte offset into the source file, or
+    /// returns [`None`] if it is out of bounds. See [`LineIndex::offset`] for
+    /// more information.
+    ///
+    /// Only available with the `source-file` feature.
+    pub fn offset(&self, line_col: LineCol) -> Option<usize> {
+        self.with_dependent(|index| index.offset(line_col))
+    }
+}
+
+#[cfg(feature = "codespan-reporting")]
+impl<'a> codespan_reporting::files::Files<'a> for Pair<SourceFile> {
+    type FileId = ();
+    type Name = &'a str;
+    type Source = &'a str;
+
+    fn name(&'a self, (): ()) -> Result<Self::Name, codespan_reporting::files::Error> {
+        Ok(self.name())
+    }
+
+    fn source(&'a self, (): ()) -> Result<Self::Source, codespan_reporting::files::Error> {
+        Ok(self.text())
+    }
+
+    fn line_index(
+        &'a self,
+        (): (),
+        byte_index: usize,
+    ) -> Result<usize, codespan_reporting::files::Error> {
+        self.line_col(byte_index)
+            .map(|line_col| line_col.line)
+            .ok_or(codespan_reporting::files::Error::IndexTooLarge {
+                given: byte_index,
+                max: self.text().len(),
+            })
+    }
+
+    fn line_range(
+        &'a self,
+        (): (),
+        line_index: usize,
+    ) -> Result<Range<usize>, codespan_reporting::files::Error> {
+        // Unlike `line_span`, codespan-reporting expects line ranges to
+        // include their line terminators
+        self.with_dependent(|index| {
+            let start = *index.line_starts.get(line_index).ok_or(
+                codespan_reporting::files::Error::LineTooLarge {
+                    given: line_index,
+                    max: index.line_starts.len() - 1,
+                },
+            )?;
+            let end = index
+                .line_starts
+                .get(line_index + 1)
+                .copied()
+                .unwrap_or(index.text.len());
+
+            Ok(start..end)
+        })
+    }
+}
+
+#[cfg(feature = "ariadne")]
+impl<'a> ariadne::Cache<()> for &'a Pair<SourceFile> {
+    type Storage = &'a str;
+
+    fn fetch(&mut self, (): &()) -> Result<&ariadne::Source<Self::Storage>, impl core::fmt::Debug> {
+        let source: &'a ariadne::Source<&'a str> = self.with_dependent(|index| &index.ariadne);
+
+        Ok::<_, Infallible>(source)
+    }
+
+    fn display<'b>(&self, (): &'b ()) -> Option<impl core::fmt::Display + 'b> {
+        Some(String::from(self.name()))
+    }
+}
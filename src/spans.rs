@@ -0,0 +1,247 @@
+//! Defines the [`ToSpans`] and [`FromSpans`] traits, for converting a borrowing
+//! dependent to and from an owner-independent form based on offsets, and
+//! [`SpanPair`], which stores such offsets alongside their owner.
+
+use core::ops::Range;
+
+use crate::{Dependent, Owner, Pair};
+
+/// An [`Owner`] whose dependent can be converted into "spans" - offsets into
+/// the owner which don't borrow from it.
+///
+/// Dependents usually store references into their owner (for example, `&str`
+/// slices of an owned `String`). Those references can't outlive the owner's
+/// borrow, so recovering the owner from a [`Pair`] normally means
+/// throwing the dependent away. Implementing this trait allows
+/// [`Pair::into_owner_with_spans`] to
+/// instead describe the dependent in terms of offsets (typically
+/// [`Range<usize>`]s), which survive the owner being returned by value.
+///
+/// See [`span_of`] for a helper to compute the span of a subslice.
+pub trait ToSpans: Owner {
+    /// The owner-independent representation of a dependent.
+    type Spans;
+
+    /// Converts a dependent borrowing from `self` into spans.
+    fn to_spans(&self, dependent: &Dependent<'_, Self>) -> Self::Spans;
+}
+
+/// An [`Owner`] whose dependent can be reconstructed from the spans produced by
+/// [`ToSpans::to_spans`], without calling [`Owner::make_dependent`].
+///
+/// Together with [`ToSpans`], this allows an expensive dependent to be
+/// persisted (for example, to a disk cache) as its owner plus the spans - see
+/// [`SpanPair`] - and later reconstructed with [`Pair::try_new_with_spans`] or
+/// [`SpanPair::try_into_pair`], skipping `make_dependent` entirely.
+///
+/// ```
+/// # use pair::{Dependent, FromSpans, HasDependent, Owner, Pair, SpanPair, ToSpans};
+/// # use std::{convert::Infallible, ops::Range};
+/// #[derive(Debug)]
+/// struct Text(String);
+///
+/// impl<'owner> HasDependent<'owner> for Text {
+///     type Dependent = Vec<&'owner str>;
+/// }
+///
+/// impl Owner for Text {
+///     type Context<'a> = ();
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         // Imagine this is very expensive
+///         Ok(self.0.split_whitespace().collect())
+///     }
+/// }
+///
+/// impl ToSpans for Text {
+///     type Spans = Vec<Range<usize>>;
+///
+///     fn to_spans(&self, words: &Vec<&str>) -> Self::Spans {
+///         words
+///             .iter()
+///             .map(|word| pair::span_of(self.0.as_bytes(), word.as_bytes()).unwrap())
+///             .collect()
+///     }
+/// }
+///
+/// impl FromSpans for Text {
+///     fn dependent_from_spans<'owner>(
+///         &'owner self,
+///         spans: &Self::Spans,
+///     ) -> Option<Vec<&'owner str>> {
+///         spans.iter().map(|span| self.0.get(span.clone())).collect()
+///     }
+/// }
+///
+/// // Save the owner and spans (for example, to a cache file)...
+/// let saved = SpanPair::from(Pair::new(Text(String::from("hello cached world"))));
+///
+/// // ...and later restore the pair, without calling `make_dependent`
+/// let pair = saved.try_into_pair().unwrap();
+/// assert_eq!(pair.with_dependent(|words| words[2]), "world");
+/// ```
+pub trait FromSpans: ToSpans {
+    /// Reconstructs a dependent borrowing from `self` from spans, or returns
+    /// [`None`] if the spans are not valid for `self` (for example, if they
+    /// are out of bounds).
+    ///
+    /// Spans produced by [`to_spans`](ToSpans::to_spans) for the same owner
+    /// should always be valid, and should reconstruct an equivalent dependent.
+    /// However, since spans may be loaded from an untrusted source (such as a
+    /// corrupted cache file), implementations should validate them rather than
+    /// panicking.
+    fn dependent_from_spans<'owner>(
+        &'owner self,
+        spans: &Self::Spans,
+    ) -> Option<Dependent<'owner, Self>>;
+}
+
+/// Returns the span of `part` within `whole`, or [`None`] if `part` is not a
+/// subslice of `whole`.
+///
+/// The span is measured in elements of `T` (so for a `&str` converted with
+/// [`str::as_bytes`], in bytes). This is intended to be used in
+/// implementations of [`ToSpans`].
+///
+/// ```
+/// let text = "hello world";
+/// let world = &text[6..];
+/// assert_eq!(pair::span_of(text.as_bytes(), world.as_bytes()), Some(6..11));
+/// assert_eq!(pair::span_of(text.as_bytes(), "world".as_bytes()), None);
+/// ```
+pub fn span_of<T>(whole: &[T], part: &[T]) -> Option<Range<usize>> {
+    let element_size = size_of::<T>();
+    if element_size == 0 {
+        // Every address is the same for zero-sized types - the best we can do
+        // is check that `part` could fit
+        return (part.len() <= whole.len()).then_some(0..part.len());
+    }
+
+    let whole_start = whole.as_ptr().addr();
+    let part_start = part.as_ptr().addr();
+
+    let offset_bytes = part_start.checked_sub(whole_start)?;
+    if offset_bytes % element_size != 0 {
+        return None;
+    }
+    let start = offset_bytes / element_size;
+    let end = start.checked_add(part.len())?;
+
+    (end <= whole.len()).then_some(start..end)
+}
+
+/// An owner stored alongside "spans" - offsets into the owner - rather than a
+/// borrowing dependent.
+///
+/// This is an alternative to [`Pair`] for dependents which can be
+/// expressed entirely in terms of offsets, such as the positions of tokens in
+/// a source string. Spans are resolved to `&str` or `&[u8]` on demand (see
+/// [`resolve_str`](SpanPair::resolve_str) and
+/// [`resolve_bytes`](SpanPair::resolve_bytes)), which costs a bounds check per
+/// access. In exchange, the owner can be freely mutated through
+/// [`owner_mut`](SpanPair::owner_mut), and the spans never borrow from the
+/// owner - they can be `Send + 'static` whenever the span type is.
+///
+/// Since nothing is borrowed, no `unsafe` code or heap allocation is involved -
+/// the owner and spans are stored inline.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct SpanPair<O, S> {
+    owner: O,
+    spans: S,
+}
+
+impl<O, S> SpanPair<O, S> {
+    /// Constructs a new [`SpanPair`] from an owner and spans into it.
+    pub fn new(owner: O, spans: S) -> Self {
+        Self { owner, spans }
+    }
+
+    /// Constructs a new [`SpanPair`] from an owner, computing the spans from a
+    /// reference to the owner with the given closure.
+    pub fn from_fn<F: FnOnce(&O) -> S>(owner: O, f: F) -> Self {
+        let spans = f(&owner);
+
+        Self { owner, spans }
+    }
+
+    /// Returns a reference to the owner.
+    pub fn owner(&self) -> &O {
+        &self.owner
+    }
+
+    /// Returns a mutable reference to the owner.
+    ///
+    /// Note that mutating the owner may cause the spans to no longer refer to
+    /// the data they were computed from. This is never unsound - spans which
+    /// fall out of bounds will simply fail to resolve.
+    pub fn owner_mut(&mut self) -> &mut O {
+        &mut self.owner
+    }
+
+    /// Returns a reference to the spans.
+    pub fn spans(&self) -> &S {
+        &self.spans
+    }
+
+    /// Returns a mutable reference to the spans.
+    pub fn spans_mut(&mut self) -> &mut S {
+        &mut self.spans
+    }
+
+    /// Returns mutable references to both the owner and the spans.
+    pub fn parts_mut(&mut self) -> (&mut O, &mut S) {
+        (&mut self.owner, &mut self.spans)
+    }
+
+    /// Consumes the [`SpanPair`], returning the owner and the spans.
+    pub fn into_parts(self) -> (O, S) {
+        (self.owner, self.spans)
+    }
+
+    /// Resolves a span to the bytes of the owner it refers to, or [`None`] if
+    /// the span is out of bounds.
+    pub fn resolve_bytes(&self, span: Range<usize>) -> Option<&[u8]>
+    where
+        O: AsRef<[u8]>,
+    {
+        self.owner.as_ref().get(span)
+    }
+
+    /// Resolves a span (measured in bytes) to the part of the owner's string
+    /// it refers to, or [`None`] if the span is out of bounds or does not lie
+    /// on [`char`] boundaries.
+    pub fn resolve_str(&self, span: Range<usize>) -> Option<&str>
+    where
+        O: AsRef<str>,
+    {
+        self.owner.as_ref().get(span)
+    }
+}
+
+impl<O: FromSpans<Spans = S>, S> SpanPair<O, S> {
+    /// Converts this [`SpanPair`] into a [`Pair`], reconstructing the dependent
+    /// from the spans with [`FromSpans::dependent_from_spans`] (rather than
+    /// calling [`Owner::make_dependent`]).
+    ///
+    /// # Errors
+    /// If `dependent_from_spans` returns [`None`], in which case the
+    /// [`SpanPair`] is returned unchanged.
+    #[track_caller]
+    pub fn try_into_pair(self) -> Result<Pair<O>, Self> {
+        let Self { owner, spans } = self;
+
+        match Pair::try_new_with_spans(owner, &spans) {
+            Ok(pair) => Ok(pair),
+            Err(owner) => Err(Self { owner, spans }),
+        }
+    }
+}
+
+impl<O: ToSpans> From<Pair<O>> for SpanPair<O, O::Spans> {
+    fn from(pair: Pair<O>) -> Self {
+        let (owner, spans) = pair.into_owner_with_spans();
+
+        Self { owner, spans }
+    }
+}
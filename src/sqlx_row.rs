@@ -0,0 +1,125 @@
+//! Defines [`DatabaseRow`], an owner of a database row whose dependent is the
+//! row's decoded columns. Only available with the `sqlx` feature.
+
+use core::{fmt::Debug, marker::PhantomData};
+
+use sqlx_core::{Error, row::Row};
+
+use crate::{Dependent, HasDependent, Owner, Pair};
+
+/// Decodes the columns of a database row of type `R`, for use with
+/// [`DatabaseRow`].
+///
+/// Implement this trait for a marker type (or the decoded struct's "static"
+/// form) to define which columns are decoded, and as which types. Columns
+/// decoded as `&str` or `&[u8]` borrow directly from the row's buffer, without
+/// copying.
+///
+/// Only available with the `sqlx` feature.
+pub trait RowDecoder<R: Row> {
+    /// The decoded columns, borrowing from a row with the lifetime `'row`.
+    type Decoded<'row>;
+
+    /// Decodes the columns of the given row.
+    ///
+    /// # Errors
+    /// If any column is missing, or can't be decoded as the expected type.
+    fn decode(row: &R) -> Result<Self::Decoded<'_>, Error>;
+}
+
+/// A database row (such as an `sqlx::postgres::PgRow`), which can act as the
+/// owner of a [`Pair`] with its columns decoded by the [`RowDecoder`] `D` as
+/// the dependent.
+///
+/// Rows returned by `sqlx` own their buffers, so text and binary columns can
+/// be decoded as borrowed `&str` and `&[u8]`s - but the decoded values can't
+/// outlive the row. A `DatabaseRow` pair keeps the row alive alongside its
+/// decoded columns, so a query layer can return decoded views without copying
+/// every text column into a `String`.
+///
+/// Only available with the `sqlx` feature.
+///
+/// ```
+/// # use pair::{DatabaseRow, Pair, RowDecoder, RowPair};
+/// # // `AnyRow` is only available with the `sqlx-any` feature
+/// # #[cfg(not(feature = "sqlx-any"))] fn main() {}
+/// # #[cfg(feature = "sqlx-any")] fn main() {
+/// # use sqlx_core::{Error, any::AnyRow, row::Row};
+/// struct User<'row> {
+///     id: i64,
+///     name: &'row str,
+/// }
+///
+/// impl RowDecoder<AnyRow> for User<'_> {
+///     type Decoded<'row> = User<'row>;
+///
+///     fn decode(row: &AnyRow) -> Result<User<'_>, Error> {
+///         Ok(User {
+///             id: row.try_get("id")?,
+///             name: row.try_get("name")?,
+///         })
+///     }
+/// }
+///
+/// // Decodes rows (for example, from `query.fetch_all(..)`) without copying
+/// // their names
+/// fn decode_users(rows: Vec<AnyRow>) -> Result<Vec<RowPair<AnyRow, User<'static>>>, Error> {
+///     rows.into_iter()
+///         .map(|row| Pair::try_new(DatabaseRow::new(row)).map_err(|(_, err)| err))
+///         .collect()
+/// }
+/// # }
+/// ```
+pub struct DatabaseRow<R, D> {
+    row: R,
+    decoder: PhantomData<fn() -> D>,
+}
+
+/// A [`Pair`] of a [`DatabaseRow`] and its decoded columns.
+///
+/// Only available with the `sqlx` feature.
+pub type RowPair<R, D> = Pair<DatabaseRow<R, D>>;
+
+impl<R: Row, D: RowDecoder<R>> DatabaseRow<R, D> {
+    /// Constructs a new [`DatabaseRow`] from a row.
+    ///
+    /// The row's columns are not decoded until a [`Pair`] is constructed from
+    /// it.
+    pub fn new(row: R) -> Self {
+        Self {
+            row,
+            decoder: PhantomData,
+        }
+    }
+
+    /// Returns the row.
+    pub fn row(&self) -> &R {
+        &self.row
+    }
+
+    /// Consumes the [`DatabaseRow`], returning the row.
+    pub fn into_row(self) -> R {
+        self.row
+    }
+}
+
+impl<R: Row, D> Debug for DatabaseRow<R, D> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DatabaseRow")
+            .field("columns", &self.row.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'owner, R: Row, D: RowDecoder<R>> HasDependent<'owner> for DatabaseRow<R, D> {
+    type Dependent = D::Decoded<'owner>;
+}
+
+impl<R: Row, D: RowDecoder<R>> Owner for DatabaseRow<R, D> {
+    type Context<'a> = ();
+    type Error = Error;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        D::decode(&self.row)
+    }
+}
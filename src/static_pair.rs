@@ -0,0 +1,153 @@
+//! Defines [`StaticPair`], a lazily-initialized [`Pair`] for use in `static`s,
+//! and the [`static_pair!`](crate::static_pair!) macro for declaring them. Only
+//! available with the `std` feature.
+
+use core::{fmt::Debug, ops::Deref};
+use std::sync::OnceLock;
+
+use crate::{Dependent, Owner, Pair};
+
+/// A [`Pair`] which is lazily constructed the first time it is accessed,
+/// suitable for storing in a `static`.
+///
+/// `StaticPair` dereferences to the inner [`Pair`], constructing it with the
+/// initializer function if it hasn't been already. Construction happens at
+/// most once, even if the `StaticPair` is accessed from multiple threads
+/// simultaneously.
+///
+/// Usually, `StaticPair`s are declared with the
+/// [`static_pair!`](crate::static_pair!) macro, but they can also be
+/// constructed directly for owners which require context, or whose
+/// [`make_dependent`](Owner::make_dependent) can fail:
+///
+/// ```
+/// # use pair::{Dependent, HasDependent, Owner, Pair, StaticPair};
+/// # use std::num::ParseIntError;
+/// struct Numbers(&'static str);
+///
+/// impl<'owner> HasDependent<'owner> for Numbers {
+///     type Dependent = Vec<u32>;
+/// }
+///
+/// impl Owner for Numbers {
+///     type Context<'a> = char;
+///     type Error = ParseIntError;
+///
+///     fn make_dependent(&self, delimiter: char) -> Result<Dependent<'_, Self>, Self::Error> {
+///         self.0.split(delimiter).map(str::parse).collect()
+///     }
+/// }
+///
+/// static NUMBERS: StaticPair<Numbers> = StaticPair::new(|| {
+///     Pair::try_new_with_context(Numbers("1;2;3"), ';')
+///         .unwrap_or_else(|(_, err)| panic!("invalid numbers: {err}"))
+/// });
+///
+/// assert_eq!(NUMBERS.with_dependent(|numbers| numbers.iter().sum::<u32>()), 6);
+/// ```
+///
+/// Only available with the `std` feature.
+pub struct StaticPair<O: Owner + ?Sized> {
+    pair: OnceLock<Pair<O>>,
+    init: fn() -> Pair<O>,
+}
+
+impl<O: Owner + ?Sized> StaticPair<O> {
+    /// Constructs a new, uninitialized [`StaticPair`]. The inner [`Pair`] will
+    /// be constructed by calling `init` the first time it is accessed.
+    pub const fn new(init: fn() -> Pair<O>) -> Self {
+        Self {
+            pair: OnceLock::new(),
+            init,
+        }
+    }
+
+    /// Returns a reference to the inner [`Pair`], constructing it if it hasn't
+    /// been already.
+    ///
+    /// # Panics
+    /// If the initializer function panics. In that case, the panic is
+    /// propagated to the caller, and the [`StaticPair`] remains uninitialized.
+    pub fn get(&self) -> &Pair<O> {
+        self.pair.get_or_init(self.init)
+    }
+
+    /// Returns a reference to the inner [`Pair`] if it has already been
+    /// constructed, without constructing it otherwise.
+    pub fn get_if_initialized(&self) -> Option<&Pair<O>> {
+        self.pair.get()
+    }
+}
+
+impl<O: Owner + ?Sized> Deref for StaticPair<O> {
+    type Target = Pair<O>;
+
+    fn deref(&self) -> &Self::Target {
+        self.get()
+    }
+}
+
+/// The [`Debug`] implementation for [`StaticPair`] does not construct the inner
+/// [`Pair`] if it hasn't been already.
+impl<O: Owner + Debug + ?Sized> Debug for StaticPair<O>
+where
+    for<'any> Dependent<'any, O>: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StaticPair")
+            .field("pair", &self.pair.get())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Declares one or more `static` [`StaticPair`]s, each of
+/// which is lazily constructed from the given owner with
+/// [`Pair::new`](crate::Pair::new) the first time it is accessed.
+///
+/// ```
+/// # use pair::{Dependent, HasDependent, Owner, static_pair};
+/// # use std::convert::Infallible;
+/// struct Index(&'static str);
+///
+/// impl<'owner> HasDependent<'owner> for Index {
+///     type Dependent = Vec<&'owner str>;
+/// }
+///
+/// impl Owner for Index {
+///     type Context<'a> = ();
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         Ok(self.0.lines().collect())
+///     }
+/// }
+///
+/// static_pair! {
+///     /// Every line of the embedded asset.
+///     static LINES: Index = Index("first\nsecond\nthird");
+/// }
+///
+/// assert_eq!(LINES.with_dependent(|lines| lines[1]), "second");
+/// ```
+///
+/// The owner expression is evaluated lazily, at the same time as the dependent
+/// is constructed. Owners which require context or whose
+/// [`make_dependent`](crate::Owner::make_dependent) can fail should construct a
+/// [`StaticPair`] directly instead.
+///
+/// Only available with the `std` feature.
+#[macro_export]
+macro_rules! static_pair {
+    () => {};
+    (
+        $(#[$attr:meta])*
+        $vis:vis static $name:ident: $owner:ty = $init:expr;
+        $($rest:tt)*
+    ) => {
+        $(#[$attr])*
+        $vis static $name: $crate::StaticPair<$owner> =
+            $crate::StaticPair::new(|| $crate::Pair::new($init));
+
+        $crate::static_pair!($($rest)*);
+    };
+}
@@ -0,0 +1,166 @@
+//! Defines [`SuspendedPair`], a guard providing exclusive access to the owner
+//! of a [`Pair`] while its dependent is suspended.
+
+use core::{
+    fmt::Debug,
+    mem::ManuallyDrop,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{Dependent, Owner, Pair, StoredContext};
+
+/// A guard providing exclusive access to the owner of a [`Pair`] whose
+/// dependent has been suspended, which rebuilds the dependent when it is
+/// dropped.
+///
+/// Obtained through [`Pair::suspend_dependent`] or
+/// [`ContextPair::suspend_dependent`](crate::ContextPair::suspend_dependent).
+/// While the guard exists, the dependent holds its [`Default`] value (which
+/// can't borrow the owner), so the owner can be freely mutated through
+/// [`DerefMut`]. When the guard is dropped, the dependent is rebuilt from the
+/// mutated owner with the stored context - or, with
+/// [`try_resume_with_context`](SuspendedPair::try_resume_with_context), with a
+/// context you provide.
+///
+/// ```
+/// # use pair::{Dependent, HasDependent, Owner, Pair};
+/// # use std::convert::Infallible;
+/// struct Text(String);
+///
+/// impl<'owner> HasDependent<'owner> for Text {
+///     type Dependent = Vec<&'owner str>;
+/// }
+///
+/// impl Owner for Text {
+///     type Context<'a> = ();
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         Ok(self.0.split_whitespace().collect())
+///     }
+/// }
+///
+/// let mut pair = Pair::new(Text(String::from("hello world")));
+///
+/// {
+///     let mut text = pair.suspend_dependent();
+///     text.0.push_str(" and");
+///     text.0.push_str(" goodbye");
+/// } // The dependent is rebuilt here
+///
+/// assert_eq!(pair.with_dependent(|words| words.len()), 4);
+/// ```
+///
+/// # Errors and leaks
+/// If rebuilding the dependent fails (by returning an error or panicking), the
+/// dependent is left with its [`Default`] value. Dropping the guard discards
+/// any error - use [`try_resume`](SuspendedPair::try_resume) to handle it.
+///
+/// Likewise, if the guard is leaked (for example, with [`core::mem::forget`]),
+/// the dependent is never rebuilt, and keeps its [`Default`] value.
+pub struct SuspendedPair<'pair, O, C = ()>
+where
+    O: Owner + ?Sized,
+    C: StoredContext<O> + ?Sized,
+{
+    pair: &'pair mut Pair<O>,
+    context: &'pair C,
+}
+
+impl<'pair, O, C> SuspendedPair<'pair, O, C>
+where
+    O: Owner + ?Sized,
+    for<'any> Dependent<'any, O>: Default,
+    C: StoredContext<O> + ?Sized,
+{
+    /// Suspends the dependent of the given pair, replacing it with its
+    /// [`Default`] value, and returns a guard which rebuilds it with `context`.
+    pub(crate) fn new(pair: &'pair mut Pair<O>, context: &'pair C) -> Self {
+        // The default dependent is constructed without access to the owner, so
+        // it can't borrow it
+        pair.with_dependent_mut(|dependent| drop(core::mem::take(dependent)));
+
+        Self { pair, context }
+    }
+}
+
+impl<O, C> SuspendedPair<'_, O, C>
+where
+    O: Owner + ?Sized,
+    C: StoredContext<O> + ?Sized,
+{
+    /// Consumes the guard, rebuilding the dependent with the stored context.
+    ///
+    /// # Errors
+    /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
+    /// error. In that case, the dependent is left with its [`Default`] value.
+    pub fn try_resume(self) -> Result<(), O::Error> {
+        let mut this = ManuallyDrop::new(self);
+        let context = this.context;
+
+        this.pair.try_replace_dependent(context.context())
+    }
+
+    /// Consumes the guard, rebuilding the dependent with the given context
+    /// (rather than the stored one).
+    ///
+    /// # Errors
+    /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
+    /// error. In that case, the dependent is left with its [`Default`] value.
+    pub fn try_resume_with_context(self, context: O::Context<'_>) -> Result<(), O::Error> {
+        let mut this = ManuallyDrop::new(self);
+
+        this.pair.try_replace_dependent(context)
+    }
+}
+
+impl<O, C> Deref for SuspendedPair<'_, O, C>
+where
+    O: Owner + ?Sized,
+    C: StoredContext<O> + ?Sized,
+{
+    type Target = O;
+
+    fn deref(&self) -> &O {
+        self.pair.owner()
+    }
+}
+
+impl<O, C> DerefMut for SuspendedPair<'_, O, C>
+where
+    O: Owner + ?Sized,
+    C: StoredContext<O> + ?Sized,
+{
+    fn deref_mut(&mut self) -> &mut O {
+        // SAFETY: the dependent was replaced with its default value when this
+        // guard was created, which was constructed without access to the
+        // owner, so it doesn't borrow it. The dependent can only be rebuilt
+        // (given new borrows of the owner) after this guard is consumed, which
+        // requires the returned borrow to have expired.
+        unsafe { self.pair.owner_mut_unchecked() }
+    }
+}
+
+impl<O, C> Drop for SuspendedPair<'_, O, C>
+where
+    O: Owner + ?Sized,
+    C: StoredContext<O> + ?Sized,
+{
+    fn drop(&mut self) {
+        // Errors are documented to be discarded - the default dependent
+        // remains in place
+        let _ = self.pair.try_replace_dependent(self.context.context());
+    }
+}
+
+impl<O, C> Debug for SuspendedPair<'_, O, C>
+where
+    O: Owner + Debug + ?Sized,
+    C: StoredContext<O> + ?Sized,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SuspendedPair")
+            .field("owner", &self.pair.owner())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,148 @@
+//! Property-test helpers for checking that [`Owner`] implementations uphold
+//! the invariants pairs rely on. Only available with the `proptest` feature.
+
+use core::fmt::Debug;
+
+use proptest::{
+    prop_assert, prop_assert_eq,
+    strategy::Strategy,
+    test_runner::{TestCaseError, TestRunner},
+};
+
+use crate::{Dependent, Owner, Pair};
+
+/// Checks that an [`Owner`] implementation (with a `()` context) upholds the
+/// laws every owner should, for owners generated by the given proptest
+/// [`Strategy`].
+///
+/// See [`check_owner_laws_with_context`] for the laws which are checked, and
+/// for owners which need a context.
+///
+/// Only available with the `proptest` feature.
+///
+/// ```
+/// # use pair::{Dependent, HasDependent, Owner, testing::check_owner_laws};
+/// # use proptest::prelude::*;
+/// # use std::num::ParseIntError;
+/// #[derive(Debug, Clone, PartialEq)]
+/// struct Numbers(String);
+///
+/// impl<'owner> HasDependent<'owner> for Numbers {
+///     type Dependent = Vec<&'owner str>;
+/// }
+///
+/// impl Owner for Numbers {
+///     type Context<'a> = ();
+///     type Error = ParseIntError;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         self.0
+///             .split(',')
+///             .map(|field| field.parse::<u32>().map(|_| field))
+///             .collect()
+///     }
+/// }
+///
+/// check_owner_laws("[0-9,]{0,20}".prop_map(Numbers));
+/// ```
+///
+/// # Panics
+/// If any law is violated, with a message describing the violation and the
+/// (shrunk) owner which violated it.
+#[track_caller]
+pub fn check_owner_laws<O, S>(strategy: S)
+where
+    O: for<'a> Owner<Context<'a> = ()> + Clone + PartialEq + Debug,
+    for<'any> Dependent<'any, O>: PartialEq + Debug,
+    S: Strategy<Value = O>,
+{
+    check_owner_laws_with_context(strategy, || ());
+}
+
+/// Checks that an [`Owner`] implementation upholds the laws every owner
+/// should, for owners generated by the given proptest [`Strategy`].
+///
+/// Each time a context is needed, it's produced by calling `context`.
+///
+/// The laws are:
+/// - [`make_dependent`](Owner::make_dependent) is deterministic - calling it
+///   twice with equal contexts either returns equal dependents both times, or
+///   returns an error both times
+/// - `make_dependent` never panics (returning an error is fine)
+/// - Constructing a [`Pair`] and converting it back with [`Pair::into_owner`]
+///   returns an owner equal to the original, and the constructor fails exactly
+///   when `make_dependent` does (returning an owner equal to the original)
+///
+/// Only available with the `proptest` feature.
+///
+/// # Panics
+/// If any law is violated, with a message describing the violation and the
+/// (shrunk) owner which violated it.
+#[track_caller]
+pub fn check_owner_laws_with_context<O, S, C>(strategy: S, context: C)
+where
+    O: Owner + Clone + PartialEq + Debug,
+    for<'any> Dependent<'any, O>: PartialEq + Debug,
+    S: Strategy<Value = O>,
+    C: Fn() -> O::Context<'static>,
+{
+    let mut runner = TestRunner::default();
+    if let Err(err) = runner.run(&strategy, |owner| check_laws(&owner, &context)) {
+        panic!("an Owner law was violated: {err}");
+    }
+}
+
+/// Checks the laws documented on [`check_owner_laws_with_context`] for a
+/// single owner.
+fn check_laws<O, C>(owner: &O, context: &C) -> Result<(), TestCaseError>
+where
+    O: Owner + Clone + PartialEq + Debug,
+    for<'any> Dependent<'any, O>: PartialEq + Debug,
+    C: Fn() -> O::Context<'static>,
+{
+    let first = owner.make_dependent(context());
+    let second = owner.make_dependent(context());
+    let succeeded = match (&first, &second) {
+        (Ok(first), Ok(second)) => {
+            prop_assert_eq!(
+                first,
+                second,
+                "make_dependent returned different dependents for equal contexts"
+            );
+            true
+        }
+        (Err(_), Err(_)) => false,
+        _ => {
+            return Err(TestCaseError::fail(
+                "make_dependent returned an error for only one of two equal contexts",
+            ));
+        }
+    };
+
+    match Pair::try_new_with_context(owner.clone(), context()) {
+        Ok(pair) => {
+            prop_assert!(
+                succeeded,
+                "constructing a pair succeeded, but make_dependent returned an error"
+            );
+            prop_assert_eq!(
+                &pair.into_owner(),
+                owner,
+                "into_owner returned a different owner"
+            );
+        }
+        Err((returned, _)) => {
+            prop_assert!(
+                !succeeded,
+                "constructing a pair failed, but make_dependent succeeded"
+            );
+            prop_assert_eq!(
+                &returned,
+                owner,
+                "a failed construction returned a different owner"
+            );
+        }
+    }
+
+    Ok(())
+}
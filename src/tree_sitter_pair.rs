@@ -0,0 +1,225 @@
+//! Defines [`SyntaxTree`], an owner of source text and its tree-sitter syntax
+//! tree, whose dependent is a view of the tree's nodes. Only available with the
+//! `tree-sitter` feature.
+
+use core::{convert::Infallible, fmt::Debug, marker::PhantomData, ops::Range};
+
+use alloc::string::String;
+
+use tree_sitter::{InputEdit, Language, LanguageError, Node, Parser, Point, Tree};
+
+use crate::{Dependent, HasDependent, Owner, Pair};
+
+/// Defines a view of the nodes of a [`SyntaxTree`], for use as the dependent of
+/// a [`SyntaxTreePair`].
+///
+/// Implement this trait for a marker type (or the view's "static" form) to
+/// define which nodes are collected, and how. Nodes (and the text they span)
+/// borrow directly from the syntax tree and its source text, without copying.
+///
+/// The view must implement [`Default`], which is used while the source text is
+/// being edited (see [`SyntaxTreePair::edit`](Pair::edit)).
+///
+/// Only available with the `tree-sitter` feature.
+pub trait SyntaxView {
+    /// The view, borrowing from a syntax tree and its source text with the
+    /// lifetime `'tree`.
+    type View<'tree>: Default;
+
+    /// Creates a view from the source text, and the root node of its syntax
+    /// tree.
+    fn view<'tree>(text: &'tree str, root: Node<'tree>) -> Self::View<'tree>;
+}
+
+/// Source text and its tree-sitter syntax [`Tree`], which can act as the owner
+/// of a [`Pair`] with a view of the tree's nodes (defined by the
+/// [`SyntaxView`] `V`) as the dependent.
+///
+/// Tree-sitter [`Node`]s borrow from their tree, so views built from them (such
+/// as an outline of the functions in a file) can't be stored alongside the tree
+/// on their own. A `SyntaxTree` pair keeps the text, the tree, and the view
+/// together as a single value - the canonical setup for an editor buffer.
+///
+/// Edits to the text are applied with [`edit`](SyntaxTree::edit), which
+/// reparses the text incrementally, reusing the unchanged parts of the old
+/// tree. [`SyntaxTreePair::edit`](Pair::edit) does the same for a pair,
+/// suspending the view while the text is edited and rebuilding it afterwards.
+///
+/// Only available with the `tree-sitter` feature.
+///
+/// ```
+/// # use pair::{SyntaxTree, SyntaxTreePair, SyntaxView};
+/// # use tree_sitter::Node;
+/// // A view of the keys of a JSON object
+/// struct Keys;
+///
+/// impl SyntaxView for Keys {
+///     type View<'tree> = Vec<&'tree str>;
+///
+///     fn view<'tree>(text: &'tree str, root: Node<'tree>) -> Vec<&'tree str> {
+///         let Some(object) = root.named_child(0) else {
+///             return Vec::new();
+///         };
+///
+///         let mut cursor = object.walk();
+///         object
+///             .named_children(&mut cursor)
+///             .filter_map(|pair| pair.child_by_field_name("key"))
+///             .map(|key| &text[key.byte_range()])
+///             .collect()
+///     }
+/// }
+///
+/// # // Miri can't call into tree-sitter's C library, and the JSON grammar is
+/// # // only available with the `tree-sitter-json` feature
+/// # #[cfg(any(miri, not(feature = "tree-sitter-json")))] fn main() {}
+/// # #[cfg(all(not(miri), feature = "tree-sitter-json"))] fn main() {
+/// let language = tree_sitter_json::LANGUAGE.into();
+/// let syntax = SyntaxTree::<Keys>::new(&language, r#"{"a": 1, "b": 2}"#).unwrap();
+/// let mut pair = SyntaxTreePair::new(syntax);
+/// assert_eq!(pair.with_dependent(|keys| keys.join(",")), r#""a","b""#);
+///
+/// // Rename "b" to "c", reparsing incrementally
+/// pair.edit(10..11, "c");
+/// assert_eq!(pair.owner().text(), r#"{"a": 1, "c": 2}"#);
+/// assert_eq!(pair.with_dependent(|keys| keys.join(",")), r#""a","c""#);
+/// # }
+/// ```
+pub struct SyntaxTree<V> {
+    text: String,
+    parser: Parser,
+    tree: Tree,
+    view: PhantomData<fn() -> V>,
+}
+
+/// A [`Pair`] of a [`SyntaxTree`] and a view of its nodes.
+///
+/// Only available with the `tree-sitter` feature.
+pub type SyntaxTreePair<V> = Pair<SyntaxTree<V>>;
+
+impl<V: SyntaxView> SyntaxTree<V> {
+    /// Parses the given source text with the given language, constructing a
+    /// new [`SyntaxTree`].
+    ///
+    /// The view is not created until a [`Pair`] is constructed from the syntax
+    /// tree.
+    ///
+    /// # Errors
+    /// If the language was generated by an incompatible version of
+    /// tree-sitter.
+    pub fn new(language: &Language, text: impl Into<String>) -> Result<Self, LanguageError> {
+        let text = text.into();
+
+        let mut parser = Parser::new();
+        parser.set_language(language)?;
+        let tree = parse(&mut parser, &text, None);
+
+        Ok(Self {
+            text,
+            parser,
+            tree,
+            view: PhantomData,
+        })
+    }
+
+    /// Returns the source text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the syntax tree of the source text.
+    pub fn tree(&self) -> &Tree {
+        &self.tree
+    }
+
+    /// Replaces the given byte range of the source text with `replacement`,
+    /// and incrementally reparses the text.
+    ///
+    /// # Panics
+    /// If the start or end of the range is out of bounds, or doesn't lie on a
+    /// [`char`] boundary.
+    pub fn edit(&mut self, range: Range<usize>, replacement: &str) {
+        let start_position = point_at(&self.text, range.start);
+        let old_end_position = point_at(&self.text, range.end);
+
+        self.text.replace_range(range.clone(), replacement);
+
+        let new_end_byte = range.start + replacement.len();
+        self.tree.edit(&InputEdit {
+            start_byte: range.start,
+            old_end_byte: range.end,
+            new_end_byte,
+            start_position,
+            old_end_position,
+            new_end_position: point_at(&self.text, new_end_byte),
+        });
+
+        self.tree = parse(&mut self.parser, &self.text, Some(&self.tree));
+    }
+
+    /// Consumes the [`SyntaxTree`], returning the source text.
+    pub fn into_text(self) -> String {
+        self.text
+    }
+}
+
+/// Parses `text` with `parser`, reusing the unchanged parts of `old_tree` (if
+/// given).
+fn parse(parser: &mut Parser, text: &str, old_tree: Option<&Tree>) -> Tree {
+    match parser.parse(text, old_tree) {
+        Some(tree) => tree,
+        // Parsing only fails if no language is set, or the parse was cancelled
+        // (which we never request)
+        None => unreachable!("parser has a language, and parsing is never cancelled"),
+    }
+}
+
+/// Returns the row and column (both in bytes) of the given byte offset in
+/// `text`.
+fn point_at(text: &str, offset: usize) -> Point {
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+
+    Point {
+        row: before.matches('\n').count(),
+        column: offset - line_start,
+    }
+}
+
+impl<V> Debug for SyntaxTree<V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SyntaxTree")
+            .field("len", &self.text.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'owner, V: SyntaxView> HasDependent<'owner> for SyntaxTree<V> {
+    type Dependent = V::View<'owner>;
+}
+
+impl<V: SyntaxView> Owner for SyntaxTree<V> {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(V::view(&self.text, self.tree.root_node()))
+    }
+}
+
+impl<V: SyntaxView> Pair<SyntaxTree<V>> {
+    /// Replaces the given byte range of the source text with `replacement`,
+    /// incrementally reparses the text, and rebuilds the view.
+    ///
+    /// The view is [suspended](Pair::suspend_dependent) while the text is
+    /// edited. See [`SyntaxTree::edit`] for more information.
+    ///
+    /// Only available with the `tree-sitter` feature.
+    ///
+    /// # Panics
+    /// If the start or end of the range is out of bounds, or doesn't lie on a
+    /// [`char`] boundary.
+    pub fn edit(&mut self, range: Range<usize>, replacement: &str) {
+        self.suspend_dependent().edit(range, replacement);
+    }
+}
@@ -0,0 +1,256 @@
+//! Defines [`UrlText`], an owner of the text of a URL whose dependent is its
+//! parsed [`UrlComponents`]. Only available with the `url` feature.
+
+use core::fmt::Display;
+
+use alloc::string::String;
+
+use crate::{Dependent, HasDependent, Owner, Pair};
+
+/// A [`Pair`] of a [`UrlText`] and its [`UrlComponents`].
+///
+/// Only available with the `url` feature.
+pub type UrlPair = Pair<UrlText>;
+
+/// The text of an absolute URL (or URI), whose dependent is its parsed
+/// [`UrlComponents`].
+///
+/// Storing a URL's components as owned strings duplicates its text, and
+/// re-parsing it whenever a component is needed is wasteful. A [`UrlPair`]
+/// parses the URL once, with each component borrowing from the owned text.
+///
+/// The URL is split into its components following the generic syntax of
+/// [RFC 3986](https://www.rfc-editor.org/rfc/rfc3986#section-3). Only the
+/// scheme and port are validated - components are not percent-decoded or
+/// normalized.
+///
+/// ```
+/// # use pair::{UrlPair, UrlText};
+/// let url = UrlPair::try_new(UrlText::new("https://user@example.com:8080/a/b?q=1#top")).unwrap();
+///
+/// assert_eq!(url.as_str(), "https://user@example.com:8080/a/b?q=1#top");
+/// url.with_dependent(|components| {
+///     assert_eq!(components.scheme(), "https");
+///     assert_eq!(components.userinfo(), Some("user"));
+///     assert_eq!(components.host(), Some("example.com"));
+///     assert_eq!(components.port(), Some(8080));
+///     assert_eq!(components.path(), "/a/b");
+///     assert_eq!(components.query(), Some("q=1"));
+///     assert_eq!(components.fragment(), Some("top"));
+/// });
+/// ```
+///
+/// Only available with the `url` feature.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UrlText(String);
+
+impl UrlText {
+    /// Constructs a new [`UrlText`] from the given text.
+    ///
+    /// The text is not parsed until a [`Pair`] is constructed from it.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self(text.into())
+    }
+
+    /// Returns the text of the URL.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Consumes the [`UrlText`], returning the text of the URL.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl<'owner> HasDependent<'owner> for UrlText {
+    type Dependent = UrlComponents<'owner>;
+}
+
+impl Owner for UrlText {
+    type Context<'a> = ();
+    type Error = UrlError;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        UrlComponents::parse(&self.0)
+    }
+}
+
+impl Pair<UrlText> {
+    /// Returns the text of the URL.
+    ///
+    /// Only available with the `url` feature.
+    pub fn as_str(&self) -> &str {
+        self.owner().as_str()
+    }
+}
+
+/// The components of an absolute URL, borrowing from its text.
+///
+/// Only available with the `url` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UrlComponents<'a> {
+    scheme: &'a str,
+    userinfo: Option<&'a str>,
+    host: Option<&'a str>,
+    port: Option<u16>,
+    path: &'a str,
+    query: Option<&'a str>,
+    fragment: Option<&'a str>,
+}
+
+impl<'a> UrlComponents<'a> {
+    /// Parses the components of the given absolute URL.
+    ///
+    /// # Errors
+    /// If the URL has no scheme, or its scheme or port is invalid.
+    pub fn parse(url: &'a str) -> Result<Self, UrlError> {
+        let (scheme, rest) = url.split_once(':').ok_or(UrlError::MissingScheme)?;
+        let mut scheme_chars = scheme.chars();
+        if !scheme_chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+            || !scheme_chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        {
+            // A colon in the path of a relative reference is also caught here
+            return Err(UrlError::InvalidScheme);
+        }
+
+        let (rest, fragment) = split_off(rest, '#');
+        let (rest, query) = split_off(rest, '?');
+
+        let (authority, path) = match rest.strip_prefix("//") {
+            Some(rest) => {
+                let path_start = rest.find('/').unwrap_or(rest.len());
+                (Some(&rest[..path_start]), &rest[path_start..])
+            }
+            None => (None, rest),
+        };
+
+        let (userinfo, host, port) = match authority {
+            Some(authority) => {
+                let (userinfo, host_port) = match authority.rsplit_once('@') {
+                    Some((userinfo, host_port)) => (Some(userinfo), host_port),
+                    None => (None, authority),
+                };
+
+                // The port follows the last colon, unless it's inside an IP
+                // literal (such as `[::1]`)
+                let (host, port) = match host_port.rfind(':') {
+                    Some(colon) if !host_port[colon..].contains(']') => {
+                        (&host_port[..colon], &host_port[colon + 1..])
+                    }
+                    _ => (host_port, ""),
+                };
+                let port = if port.is_empty() {
+                    None
+                } else if port.bytes().all(|byte| byte.is_ascii_digit()) {
+                    Some(port.parse().map_err(|_| UrlError::InvalidPort)?)
+                } else {
+                    return Err(UrlError::InvalidPort);
+                };
+
+                (userinfo, Some(host), port)
+            }
+            None => (None, None, None),
+        };
+
+        Ok(Self {
+            scheme,
+            userinfo,
+            host,
+            port,
+            path,
+            query,
+            fragment,
+        })
+    }
+
+    /// Returns the scheme of the URL (such as `"https"`).
+    pub fn scheme(&self) -> &'a str {
+        self.scheme
+    }
+
+    /// Returns the userinfo of the URL's authority (the part before the `@`),
+    /// if it has one.
+    pub fn userinfo(&self) -> Option<&'a str> {
+        self.userinfo
+    }
+
+    /// Returns the host of the URL, if it has an authority. The host may be
+    /// empty (as in `file:///etc/hosts`), and IP literals include their
+    /// brackets.
+    pub fn host(&self) -> Option<&'a str> {
+        self.host
+    }
+
+    /// Returns the port of the URL, if it has one.
+    pub fn port(&self) -> Option<u16> {
+        self.port
+    }
+
+    /// Returns the path of the URL, which may be empty.
+    pub fn path(&self) -> &'a str {
+        self.path
+    }
+
+    /// Returns an iterator over the segments of the URL's path (separated by
+    /// `/`, and ignoring a leading `/`).
+    pub fn path_segments(&self) -> impl Iterator<Item = &'a str> {
+        let path = self.path.strip_prefix('/').unwrap_or(self.path);
+
+        path.split('/').filter(move |_| !path.is_empty())
+    }
+
+    /// Returns the query of the URL (after the `?`), if it has one.
+    pub fn query(&self) -> Option<&'a str> {
+        self.query
+    }
+
+    /// Returns an iterator over the `key=value` pairs of the URL's query
+    /// (separated by `&`). A pair without an `=` has an empty value.
+    pub fn query_pairs(&self) -> impl Iterator<Item = (&'a str, &'a str)> {
+        self.query
+            .into_iter()
+            .flat_map(|query| query.split('&'))
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+    }
+
+    /// Returns the fragment of the URL (after the `#`), if it has one.
+    pub fn fragment(&self) -> Option<&'a str> {
+        self.fragment
+    }
+}
+
+/// Splits `text` at the first occurrence of `delimiter`, returning the text
+/// before it, and the text after it (if it occurred).
+fn split_off(text: &str, delimiter: char) -> (&str, Option<&str>) {
+    match text.split_once(delimiter) {
+        Some((before, after)) => (before, Some(after)),
+        None => (text, None),
+    }
+}
+
+/// An error which occurred while parsing the components of a URL.
+///
+/// Only available with the `url` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UrlError {
+    /// The URL has no scheme (it may be a relative reference).
+    MissingScheme,
+    /// The URL's scheme contains invalid characters.
+    InvalidScheme,
+    /// The URL's port is not a number from 0 to 65535.
+    InvalidPort,
+}
+
+impl Display for UrlError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::MissingScheme => "URL has no scheme",
+            Self::InvalidScheme => "invalid URL scheme",
+            Self::InvalidPort => "invalid URL port",
+        })
+    }
+}
+
+impl core::error::Error for UrlError {}
@@ -0,0 +1,35 @@
+//! Defines [`ValidationError`], the error returned by constructors which
+//! validate the dependent of a [`Pair`](crate::Pair) before accepting it.
+
+use core::fmt::Display;
+
+/// The error returned by validating constructors, such as
+/// [`Pair::try_new_validated`](crate::Pair::try_new_validated).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValidationError<E, V> {
+    /// [`make_dependent`](crate::Owner::make_dependent) returned an error, so
+    /// validation did not run.
+    Failed(E),
+    /// The dependent was constructed, but rejected by the validation closure.
+    Rejected(V),
+}
+
+impl<E, V> Display for ValidationError<E, V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Failed(_) => f.write_str("failed to construct the dependent of a pair"),
+            Self::Rejected(_) => f.write_str("the dependent of a pair was rejected by validation"),
+        }
+    }
+}
+
+impl<E: core::error::Error + 'static, V: core::error::Error + 'static> core::error::Error
+    for ValidationError<E, V>
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Failed(err) => Some(err),
+            Self::Rejected(err) => Some(err),
+        }
+    }
+}
@@ -0,0 +1,246 @@
+//! Defines [`ViewsPair`], a pair of one owner and a fixed number of
+//! independent dependents ("views") borrowing from it.
+
+use core::{
+    convert::Infallible,
+    fmt::Debug,
+    mem::{ManuallyDrop, MaybeUninit},
+};
+
+use crate::{Dependent, HasDependent, Owner, Pair};
+
+/// An owner along with `N` dependents borrowing from it, each constructed
+/// through [`Owner::make_dependent`] with its own context.
+///
+/// This is useful for homogeneous multi-view cases, such as `N` windows into
+/// one buffer. It's cheaper and simpler than `N` separate pairs sharing an
+/// owner through `Arc` clones - the owner is stored once, and the views are
+/// stored contiguously in a single allocation.
+///
+/// ```
+/// # use pair::{Dependent, HasDependent, Owner, ViewsPair};
+/// # use std::{convert::Infallible, ops::Range};
+/// struct Samples(Vec<f32>);
+///
+/// impl<'owner> HasDependent<'owner> for Samples {
+///     type Dependent = &'owner [f32];
+/// }
+///
+/// impl Owner for Samples {
+///     type Context<'a> = Range<usize>;
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, window: Range<usize>) -> Result<Dependent<'_, Self>, Self::Error> {
+///         Ok(&self.0[window])
+///     }
+/// }
+///
+/// let samples = Samples(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+/// let windows = ViewsPair::new_with_contexts(samples, [0..2, 2..4, 4..6]);
+///
+/// assert_eq!(windows.with_view(1, |window| window.iter().sum::<f32>()), 7.0);
+/// assert_eq!(windows.owner().0.len(), 6);
+/// ```
+pub struct ViewsPair<O: Owner, const N: usize> {
+    pair: Pair<Views<O, N>>,
+}
+
+impl<O: Owner, const N: usize> ViewsPair<O, N> {
+    /// Constructs a new [`ViewsPair`] with the given [`Owner`]. Each view is
+    /// computed through [`Owner::make_dependent`] with the corresponding
+    /// context during this construction.
+    ///
+    /// # Errors
+    /// If [`<O as Owner>::make_dependent`](Owner::make_dependent) returns an
+    /// error for any of the contexts. The owner is returned along with the
+    /// first error, and the views constructed before it are dropped.
+    #[track_caller]
+    pub fn try_new_with_contexts(
+        owner: O,
+        contexts: [O::Context<'_>; N],
+    ) -> Result<Self, (O, O::Error)> {
+        match Pair::try_new_with_context(Views(owner), contexts) {
+            Ok(pair) => Ok(Self { pair }),
+            Err((Views(owner), err)) => Err((owner, err)),
+        }
+    }
+
+    /// Returns a reference to the owner.
+    pub fn owner(&self) -> &O {
+        &self.pair.owner().0
+    }
+
+    /// Calls the given closure, providing shared access to the view with the
+    /// given index, and returns the value computed by the closure.
+    ///
+    /// The closure must be able to work with a [`Dependent`] with any
+    /// arbitrary lifetime - see the documentation of [`Pair::with_dependent`]
+    /// for more information.
+    ///
+    /// # Panics
+    /// If `index` is not less than `N`.
+    ///
+    /// [`Dependent`]: crate::HasDependent::Dependent
+    pub fn with_view<'self_borrow, F, T>(&'self_borrow self, index: usize, f: F) -> T
+    where
+        F: for<'any> FnOnce(&'self_borrow Dependent<'_, O>) -> T,
+    {
+        self.pair.with_dependent(|views| f(&views[index]))
+    }
+
+    /// Calls the given closure, providing exclusive access to the view with
+    /// the given index, and returns the value computed by the closure.
+    ///
+    /// The closure must be able to work with a [`Dependent`] with any
+    /// arbitrary lifetime - see the documentation of
+    /// [`Pair::with_dependent_mut`] for more information.
+    ///
+    /// # Panics
+    /// If `index` is not less than `N`.
+    ///
+    /// [`Dependent`]: crate::HasDependent::Dependent
+    pub fn with_view_mut<'self_borrow, F, T>(&'self_borrow mut self, index: usize, f: F) -> T
+    where
+        F: for<'any> FnOnce(&'self_borrow mut Dependent<'_, O>) -> T,
+    {
+        self.pair.with_dependent_mut(|views| f(&mut views[index]))
+    }
+
+    /// Calls the given closure, providing shared access to all views, and
+    /// returns the value computed by the closure.
+    ///
+    /// The closure must be able to work with [`Dependent`]s with any arbitrary
+    /// lifetime - see the documentation of [`Pair::with_dependent`] for more
+    /// information.
+    ///
+    /// [`Dependent`]: crate::HasDependent::Dependent
+    pub fn with_views<'self_borrow, F, T>(&'self_borrow self, f: F) -> T
+    where
+        F: for<'any> FnOnce(&'self_borrow [Dependent<'_, O>; N]) -> T,
+    {
+        self.pair.with_dependent(|views| f(views))
+    }
+
+    /// Calls the given closure, providing exclusive access to all views, and
+    /// returns the value computed by the closure.
+    ///
+    /// The closure must be able to work with [`Dependent`]s with any arbitrary
+    /// lifetime - see the documentation of [`Pair::with_dependent_mut`] for
+    /// more information.
+    ///
+    /// [`Dependent`]: crate::HasDependent::Dependent
+    pub fn with_views_mut<'self_borrow, F, T>(&'self_borrow mut self, f: F) -> T
+    where
+        F: for<'any> FnOnce(&'self_borrow mut [Dependent<'_, O>; N]) -> T,
+    {
+        self.pair.with_dependent_mut(|views| f(views))
+    }
+
+    /// Consumes the [`ViewsPair`], dropping the views and returning the owner.
+    pub fn into_owner(self) -> O {
+        self.pair.into_owner().0
+    }
+}
+
+impl<O: Owner<Error = Infallible>, const N: usize> ViewsPair<O, N> {
+    /// Constructs a new [`ViewsPair`] with the given [`Owner`]. Each view is
+    /// computed through [`Owner::make_dependent`] with the corresponding
+    /// context during this construction.
+    #[track_caller]
+    pub fn new_with_contexts(owner: O, contexts: [O::Context<'_>; N]) -> Self {
+        let Ok(pair) = Self::try_new_with_contexts(owner, contexts);
+        pair
+    }
+}
+
+// SAFETY: A `ViewsPair` is just a `Pair` of its owner and views, so it is
+// `Send` exactly when the owner and its dependents are. See the NOTE on
+// `Pair`'s `Send` impl for why this is written out explicitly.
+unsafe impl<O: Owner + Send, const N: usize> Send for ViewsPair<O, N> where
+    for<'any> Dependent<'any, O>: Send
+{
+}
+
+// SAFETY: A `ViewsPair` is just a `Pair` of its owner and views, so it is
+// `Sync` exactly when the owner and its dependents are. See the NOTE on
+// `Pair`'s `Send` impl for why this is written out explicitly.
+unsafe impl<O: Owner + Sync, const N: usize> Sync for ViewsPair<O, N> where
+    for<'any> Dependent<'any, O>: Sync
+{
+}
+
+impl<O: Owner + Debug, const N: usize> Debug for ViewsPair<O, N>
+where
+    for<'any> Dependent<'any, O>: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.with_views(|views| {
+            f.debug_struct("ViewsPair")
+                .field("owner", self.owner())
+                .field("views", views)
+                .finish()
+        })
+    }
+}
+
+/// The owner of a [`ViewsPair`], which acts as the owner of the single [`Pair`]
+/// it stores.
+struct Views<O, const N: usize>(O);
+
+impl<'owner, O: Owner, const N: usize> HasDependent<'owner> for Views<O, N> {
+    type Dependent = [Dependent<'owner, O>; N];
+}
+
+impl<O: Owner, const N: usize> Owner for Views<O, N> {
+    type Context<'a> = [O::Context<'a>; N];
+    type Error = O::Error;
+
+    fn make_dependent<'owner>(
+        &'owner self,
+        contexts: Self::Context<'_>,
+    ) -> Result<Dependent<'owner, Self>, Self::Error> {
+        let mut views = PartialViews::<Dependent<'owner, O>, N> {
+            views: [const { MaybeUninit::uninit() }; N],
+            len: 0,
+        };
+
+        // If `make_dependent(..)` fails (or panics), the views constructed so
+        // far are dropped by `PartialViews`
+        for (view, context) in views.views.iter_mut().zip(contexts) {
+            view.write(self.0.make_dependent(context)?);
+            views.len += 1;
+        }
+
+        let views = ManuallyDrop::new(views);
+
+        // SAFETY: There is exactly one context per view, and `make_dependent`
+        // returned successfully for each of them - so all `N` views are
+        // initialized. `MaybeUninit<T>` has the same layout as `T`, so an array
+        // of them has the same layout as an array of `T`. The views are read
+        // out exactly once, since `views` is never dropped.
+        Ok(unsafe {
+            (&raw const views.views)
+                .cast::<[Dependent<'owner, O>; N]>()
+                .read()
+        })
+    }
+}
+
+/// Views under construction, the first `len` of which are initialized.
+///
+/// Dropping this drops the initialized views, so they aren't leaked if
+/// constructing a later one fails.
+struct PartialViews<T, const N: usize> {
+    views: [MaybeUninit<T>; N],
+    len: usize,
+}
+
+impl<T, const N: usize> Drop for PartialViews<T, N> {
+    fn drop(&mut self) {
+        for view in self.views.iter_mut().take(self.len) {
+            // SAFETY: The first `len` views are initialized, and (since this
+            // is being dropped) are never used again.
+            unsafe { view.assume_init_drop() };
+        }
+    }
+}
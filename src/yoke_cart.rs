@@ -0,0 +1,102 @@
+//! Defines [`PairCart`], a wrapper which allows a [`Pair`] to act as the cart of
+//! a [`Yoke`](yoke::Yoke). Only available with the `yoke` feature.
+
+use core::{fmt::Debug, ops::Deref};
+
+use stable_deref_trait::StableDeref;
+
+use crate::{Dependent, Owner, Pair};
+
+/// A [`Pair`] which can act as the cart of a [`Yoke`](yoke::Yoke), such as the
+/// zero-copy data structs used by ICU4X data providers.
+///
+/// A `PairCart` dereferences to the pair's owner, which the yokeable may borrow
+/// from in addition to the pair's own dependent being available through
+/// [`PairCart::pair`]. This allows a pair (for example, a loaded data file and
+/// an index into it) to back any number of yoked data structs without copying
+/// the owner into an `Rc` or `Arc` first.
+///
+/// ICU4X providers generally require their carts to be type-erased, `Send`, and
+/// `Sync`. A `Yoke` with a `PairCart` can be converted into one with an
+/// [`ErasedArcCart`](yoke::erased::ErasedArcCart) using
+/// [`wrap_cart_in_arc`](yoke::Yoke::wrap_cart_in_arc) and
+/// [`erase_arc_cart`](yoke::Yoke::erase_arc_cart), as long as the pair is
+/// `Send` and `Sync`.
+///
+/// Only available with the `yoke` feature.
+///
+/// ```
+/// # use pair::{Dependent, HasDependent, Owner, Pair, PairCart};
+/// # use std::convert::Infallible;
+/// # use yoke::{Yoke, erased::ErasedArcCart};
+/// struct DataFile(String);
+///
+/// impl<'owner> HasDependent<'owner> for DataFile {
+///     type Dependent = Vec<&'owner str>;
+/// }
+///
+/// impl Owner for DataFile {
+///     type Context<'a> = ();
+///     type Error = Infallible;
+///
+///     fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+///         Ok(self.0.lines().collect())
+///     }
+/// }
+///
+/// let cart = PairCart::new(Pair::new(DataFile(String::from("en\nfr\nde"))));
+/// let yoke: Yoke<&'static str, PairCart<DataFile>> =
+///     Yoke::attach_to_cart(cart, |file| file.0.split_once('\n').unwrap().0);
+///
+/// // Erase the cart, as required by ICU4X data providers
+/// let erased: Yoke<&'static str, ErasedArcCart> = yoke.wrap_cart_in_arc().erase_arc_cart();
+/// assert_eq!(*erased.get(), "en");
+/// ```
+pub struct PairCart<O: Owner + ?Sized> {
+    pair: Pair<O>,
+}
+
+impl<O: Owner + ?Sized> PairCart<O> {
+    /// Wraps the given pair, so it can act as the cart of a
+    /// [`Yoke`](yoke::Yoke).
+    pub fn new(pair: Pair<O>) -> Self {
+        Self { pair }
+    }
+
+    /// Returns a reference to the inner [`Pair`].
+    pub fn pair(&self) -> &Pair<O> {
+        &self.pair
+    }
+
+    /// Consumes the [`PairCart`], returning the inner [`Pair`].
+    pub fn into_pair(self) -> Pair<O> {
+        self.pair
+    }
+}
+
+impl<O: Owner + ?Sized> Deref for PairCart<O> {
+    type Target = O;
+
+    fn deref(&self) -> &Self::Target {
+        self.pair.owner()
+    }
+}
+
+// SAFETY: The owner of a pair is stored in its own heap allocation, so moving
+// the pair (and so this wrapper) only moves the pointer to it, and the address
+// `deref` returns stays the same. The owner can only be mutated through a
+// `&mut Pair`, which `PairCart` never hands out - it doesn't implement
+// `DerefMut`, and `into_pair` consumes it - so the owner can't be mutated while
+// a yoke borrows from it.
+unsafe impl<O: Owner + ?Sized> StableDeref for PairCart<O> {}
+
+impl<O: Owner + Debug + ?Sized> Debug for PairCart<O>
+where
+    for<'any> Dependent<'any, O>: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PairCart")
+            .field("pair", &self.pair)
+            .finish()
+    }
+}
@@ -0,0 +1,97 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{cell::Cell, convert::Infallible};
+
+use pair::{Dependent, HasDependent, Owner, Pair};
+
+#[derive(Debug)]
+struct Buff(String);
+
+impl<'owner> HasDependent<'owner> for Buff {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Buff {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): Self::Context<'_>) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split_whitespace().collect())
+    }
+}
+
+#[test]
+fn access_guard_basic() {
+    let pair = Pair::new(Buff(String::from("this is a test")));
+    let guard = pair.access();
+
+    assert_eq!(guard.owner().0, "this is a test");
+    assert_eq!(
+        guard.with_dependent(|dep| dep.first().copied()),
+        Some("this")
+    );
+    assert_eq!(
+        guard.with_both(|owner, dep| (owner.0.len(), dep[3])),
+        (14, "test")
+    );
+
+    // Covariant dependents can be extracted from the closure, just like with
+    // `Pair::with_dependent`
+    let dep: &Vec<&str> = guard.with_dependent(|dep| dep);
+    assert_eq!(dep, &["this", "is", "a", "test"]);
+
+    // The guard is `Copy`, and can be passed around freely
+    let guard2 = guard;
+    let total_len: usize = (0..4)
+        .map(|i| guard2.with_dependent(|dep| dep[i].len()))
+        .sum();
+    assert_eq!(total_len, 11);
+
+    assert_eq!(
+        format!("{guard:?}"),
+        r#"AccessGuard { owner: Buff("this is a test"), dependent: ["this", "is", "a", "test"] }"#
+    );
+
+    let owner = pair.into_owner();
+    assert_eq!(owner.0, "this is a test");
+}
+
+struct InvariantOwner(String);
+
+impl<'owner> HasDependent<'owner> for InvariantOwner {
+    type Dependent = Cell<&'owner str>;
+}
+
+impl Owner for InvariantOwner {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): Self::Context<'_>) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(Cell::new(&self.0))
+    }
+}
+
+#[test]
+fn access_guard_invariant_dependent() {
+    let pair = Pair::new(InvariantOwner(String::from("hello")));
+    let guard = pair.access();
+
+    for _ in 0..10 {
+        assert_eq!(guard.with_dependent(|dep| dep.get().len()), 5);
+    }
+    assert!(guard.with_both(|owner, dep| std::ptr::eq(owner.0.as_str(), dep.get())));
+}
+
+fn first_word_len(owner: &Buff, words: pair::AccessGuard<'_, Buff>) -> (usize, usize) {
+    (owner.0.len(), words.with_dependent(|words| words[0].len()))
+}
+
+#[test]
+fn as_parts_ref() {
+    let pair = Pair::new(Buff(String::from("hello there world")));
+    let (owner, words) = pair.as_parts_ref();
+
+    assert_eq!(first_word_len(owner, words), (17, 5));
+    assert!(std::ptr::eq(owner, pair.owner()));
+    assert!(std::ptr::eq(words.owner(), owner));
+}
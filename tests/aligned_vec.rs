@@ -0,0 +1,143 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::convert::Infallible;
+
+use pair::{AlignedVec, Dependent, HasDependent, Owner, Pair};
+
+fn is_aligned<const ALIGN: usize>(vec: &AlignedVec<ALIGN>) -> bool {
+    vec.as_ptr().addr() % ALIGN == 0
+}
+
+#[test]
+fn alignment_is_kept_while_growing() {
+    let mut vec = AlignedVec::<64>::new();
+    assert!(vec.is_empty());
+    assert_eq!(vec.capacity(), 0);
+    assert!(is_aligned(&vec));
+
+    for byte in 0..=200 {
+        vec.push(byte);
+        assert!(is_aligned(&vec));
+    }
+    vec.extend_from_slice(&[1; 1000]);
+    assert!(is_aligned(&vec));
+
+    assert_eq!(vec.len(), 1201);
+    assert!(vec.capacity() >= 1201);
+    assert_eq!(vec[..201], (0..=200).collect::<Vec<u8>>());
+    assert!(vec[201..].iter().all(|&byte| byte == 1));
+
+    vec.clear();
+    assert!(vec.is_empty());
+    assert!(vec.capacity() >= 1201);
+}
+
+#[test]
+fn large_alignment() {
+    let vec = AlignedVec::<4096>::from(&[1, 2, 3][..]);
+    assert!(is_aligned(&vec));
+    assert_eq!(*vec, [1, 2, 3]);
+
+    let clone = vec.clone();
+    assert!(is_aligned(&clone));
+    assert_eq!(clone, vec);
+}
+
+#[test]
+fn mutation_and_traits() {
+    let mut vec = AlignedVec::<8>::with_capacity(16);
+    assert_eq!(vec.capacity(), 16);
+
+    vec.extend([1, 2, 3]);
+    vec.as_mut_slice()[0] = 10;
+    vec[1] = 20;
+    assert_eq!(vec.as_ref(), [10, 20, 3]);
+    assert_eq!(format!("{vec:?}"), "[10, 20, 3]");
+    assert_eq!(AlignedVec::<8>::default(), AlignedVec::new());
+}
+
+struct Words(AlignedVec<8>);
+
+impl From<AlignedVec<8>> for Words {
+    fn from(bytes: AlignedVec<8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl<'owner> HasDependent<'owner> for Words {
+    type Dependent = &'owner [u64];
+}
+
+impl Owner for Words {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        // SAFETY: any 8 initialized bytes are a valid `u64`
+        let (prefix, words, _) = unsafe { self.0.align_to::<u64>() };
+        assert!(prefix.is_empty());
+
+        Ok(words)
+    }
+}
+
+struct Header(AlignedVec<4>);
+
+impl From<AlignedVec<4>> for Header {
+    fn from(bytes: AlignedVec<4>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl<'owner> HasDependent<'owner> for Header {
+    type Dependent = &'owner [u32];
+}
+
+impl Owner for Header {
+    type Context<'a> = u32;
+    type Error = u32;
+
+    fn make_dependent(&self, magic: u32) -> Result<Dependent<'_, Self>, Self::Error> {
+        // SAFETY: any 4 initialized bytes are a valid `u32`
+        let (_, words, _) = unsafe { self.0.align_to::<u32>() };
+
+        match words.first() {
+            Some(&first) if first == magic => Ok(&words[1..]),
+            Some(&first) => Err(first),
+            None => Err(0),
+        }
+    }
+}
+
+#[test]
+fn pair_from_unaligned_bytes() {
+    let words = [7_u64, 8, 9];
+    let bytes: Vec<u8> = words.iter().flat_map(|word| word.to_ne_bytes()).collect();
+
+    // Deliberately misalign the source bytes
+    let mut unaligned = vec![0];
+    unaligned.extend_from_slice(&bytes);
+
+    let pair = Pair::<Words>::new_from_aligned_bytes(&unaligned[1..]);
+    assert!(is_aligned(&pair.owner().0));
+    assert_eq!(pair.with_dependent(|words| *words), words);
+}
+
+#[test]
+fn fallible_pair_from_aligned_bytes() {
+    let bytes: Vec<u8> = [0xCAFE_u32, 1, 2]
+        .iter()
+        .flat_map(|word| word.to_ne_bytes())
+        .collect();
+
+    let pair = Pair::<Header>::try_new_from_aligned_bytes_with_context(&bytes, 0xCAFE);
+    assert!(matches!(pair, Ok(pair) if pair.with_dependent(|words| *words) == [1, 2]));
+
+    let Err((owner, error)) =
+        Pair::<Header>::try_new_from_aligned_bytes_with_context(&bytes, 0xBEEF)
+    else {
+        panic!("construction should have failed");
+    };
+    assert_eq!(error, 0xCAFE);
+    assert_eq!(*owner.0, bytes);
+}
@@ -0,0 +1,125 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "alloc-budget")]
+
+use std::{alloc::System, convert::Infallible, num::ParseIntError};
+
+use pair::{
+    BudgetAllocator, BudgetError, BudgetExceeded, Dependent, HasDependent, Owner, Pair,
+    check_budget,
+};
+
+#[global_allocator]
+static ALLOCATOR: BudgetAllocator = BudgetAllocator::new(System);
+
+/// Parses a comma-separated list of numbers, polling the budget between each.
+#[derive(Debug)]
+struct Numbers(String);
+
+impl<'owner> HasDependent<'owner> for Numbers {
+    type Dependent = Vec<(&'owner str, u64)>;
+}
+
+impl Owner for Numbers {
+    type Context<'a> = &'a mut usize;
+    type Error = ParseIntError;
+
+    fn make_dependent(&self, parsed: &mut usize) -> Result<Dependent<'_, Self>, Self::Error> {
+        let mut numbers = Vec::new();
+        for number in self.0.split(',') {
+            if check_budget().is_err() {
+                break;
+            }
+            numbers.push((number, number.parse()?));
+            *parsed += 1;
+        }
+
+        Ok(numbers)
+    }
+}
+
+fn numbers(count: usize) -> Numbers {
+    Numbers(
+        (0..count)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(","),
+    )
+}
+
+#[test]
+fn within_budget() {
+    let mut parsed = 0;
+    let pair = Pair::try_new_with_budget(numbers(100), &mut parsed, 1 << 20).unwrap();
+
+    assert_eq!(parsed, 100);
+    assert_eq!(pair.dependent_len(), 100);
+}
+
+#[test]
+fn exceeding_budget_stops_early() {
+    let mut parsed = 0;
+    let (owner, err) = Pair::try_new_with_budget(numbers(100_000), &mut parsed, 4096).unwrap_err();
+
+    assert_eq!(err, BudgetError::Exceeded);
+    assert!(parsed < 100_000);
+    assert_eq!(owner.0, numbers(100_000).0);
+}
+
+#[test]
+fn failure_within_budget() {
+    let mut parsed = 0;
+    let (_, err) = Pair::try_new_from_box_with_budget(
+        Box::new(Numbers(String::from("1,x"))),
+        &mut parsed,
+        1 << 20,
+    )
+    .unwrap_err();
+
+    assert!(matches!(err, BudgetError::Failed(_)));
+    assert_eq!(parsed, 1);
+}
+
+#[test]
+fn budget_only_active_during_construction() {
+    assert_eq!(check_budget(), Ok(()));
+
+    let mut parsed = 0;
+    let _ = Pair::try_new_with_budget(numbers(100_000), &mut parsed, 0);
+
+    // Allocating outside of a budgeted construction is never limited
+    let big = vec![0_u8; 1 << 20];
+    assert_eq!(check_budget(), Ok(()));
+    drop(big);
+}
+
+#[derive(Debug)]
+struct Nested;
+
+impl HasDependent<'_> for Nested {
+    type Dependent = (bool, Result<(), BudgetExceeded>);
+}
+
+impl Owner for Nested {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        let mut parsed = 0;
+        let inner = Pair::try_new_with_budget(numbers(1000), &mut parsed, 64);
+        let inner_exceeded = matches!(inner, Err((_, BudgetError::Exceeded)));
+
+        // The inner budget being exceeded doesn't count against this one
+        Ok((inner_exceeded, check_budget()))
+    }
+}
+
+#[test]
+fn nested_budgets() {
+    let pair = Pair::try_new_with_budget(Nested, (), 1 << 20).unwrap();
+    assert_eq!(pair.with_dependent(|result| *result), (true, Ok(())));
+
+    assert_eq!(
+        BudgetError::<ParseIntError>::Exceeded.to_string(),
+        "allocation budget was exceeded"
+    );
+}
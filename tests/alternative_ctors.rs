@@ -146,3 +146,30 @@ fn fallible_with_context() {
         "Conversion of string 'This is a test of pair.' with context ', ' failed."
     );
 }
+
+#[test]
+fn discard_owner() {
+    let pair =
+        Pair::try_new_discard_owner(BuffFallible(String::from("This is a test of pair."))).unwrap();
+    assert_eq!(pair.owner().0, "This is a test of pair.");
+
+    let err = Pair::try_new_discard_owner(BuffFallible(String::from("     "))).unwrap_err();
+    assert_eq!(err, "Conversion failed");
+
+    let pair = Pair::try_new_discard_owner_with_context(
+        BuffFallibleWithContext(String::from("foo, bar, bat, baz")),
+        ", ",
+    )
+    .unwrap();
+    assert_eq!(pair.owner().0, "foo, bar, bat, baz");
+
+    let err = Pair::try_new_discard_owner_with_context(
+        BuffFallibleWithContext(String::from("This is a test of pair.")),
+        ", ",
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        "Conversion of string 'This is a test of pair.' with context ', ' failed."
+    );
+}
@@ -0,0 +1,87 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{any::Any, num::ParseIntError};
+
+use pair::{AnyContextError, Dependent, HasDependent, Owner, Pair};
+
+struct Separator(char);
+
+#[derive(Debug)]
+struct Words(String);
+
+impl<'owner> HasDependent<'owner> for Words {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Words {
+    type Context<'a> = &'a Separator;
+    type Error = std::convert::Infallible;
+
+    fn make_dependent(&self, separator: &Separator) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split(separator.0).collect())
+    }
+}
+
+struct Radix(u32);
+
+#[derive(Debug)]
+struct Number(String);
+
+impl HasDependent<'_> for Number {
+    type Dependent = u64;
+}
+
+impl Owner for Number {
+    type Context<'a> = &'a Radix;
+    type Error = ParseIntError;
+
+    fn make_dependent(&self, radix: &Radix) -> Result<Dependent<'_, Self>, Self::Error> {
+        u64::from_str_radix(&self.0, radix.0)
+    }
+}
+
+/// A "host" which only knows about contexts as `&dyn Any`.
+fn host_contexts() -> Vec<Box<dyn Any>> {
+    vec![Box::new(Separator('-')), Box::new(Radix(16))]
+}
+
+#[test]
+fn downcasts_context() {
+    let contexts = host_contexts();
+
+    let words = Pair::try_new_with_any_context(Words(String::from("a-b-c")), &*contexts[0])
+        .unwrap_or_else(|_| panic!("construction failed"));
+    assert_eq!(words.dependent_len(), 3);
+
+    let number = Pair::try_new_from_box_with_any_context(
+        Box::new(Number(String::from("ff"))),
+        &*contexts[1],
+    )
+    .unwrap();
+    assert_eq!(number.with_dependent(|n| *n), 255);
+}
+
+#[test]
+fn wrong_type() {
+    let contexts = host_contexts();
+
+    let (owner, err) =
+        Pair::try_new_with_any_context(Number(String::from("ff")), &*contexts[0]).unwrap_err();
+    assert_eq!(owner.0, "ff");
+    assert_eq!(err, AnyContextError::WrongType);
+    assert_eq!(
+        err.to_string(),
+        "context was not of the type expected by the owner"
+    );
+}
+
+#[test]
+fn make_dependent_fails() {
+    let (owner, err) =
+        Pair::try_new_with_any_context(Number(String::from("xyz")), &Radix(10)).unwrap_err();
+    assert_eq!(owner.0, "xyz");
+    assert!(matches!(err, AnyContextError::Failed(_)));
+
+    let err: &dyn std::error::Error = &err;
+    assert!(err.source().unwrap().is::<ParseIntError>());
+}
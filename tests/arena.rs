@@ -0,0 +1,159 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::convert::Infallible;
+
+use pair::{ArenaId, ArenaOwner, ArenaView, Dependent, HasDependent, PairArena};
+
+#[derive(Debug)]
+struct Module {
+    name: String,
+    imports: Vec<usize>,
+}
+
+impl Module {
+    fn new(name: &str, imports: &[usize]) -> Self {
+        Self {
+            name: String::from(name),
+            imports: imports.to_vec(),
+        }
+    }
+}
+
+impl<'arena> HasDependent<'arena> for Module {
+    type Dependent = (&'arena str, Vec<&'arena str>);
+}
+
+impl ArenaOwner for Module {
+    type Error = ArenaId;
+
+    fn make_dependent<'arena>(
+        &'arena self,
+        arena: ArenaView<'arena, Self>,
+    ) -> Result<Dependent<'arena, Self>, Self::Error> {
+        assert_eq!(
+            arena.get(arena.id()).map(|m| m.name.as_str()),
+            Some(&*self.name)
+        );
+        assert!(!arena.is_empty());
+
+        let imports = self
+            .imports
+            .iter()
+            .map(|&index| {
+                let id = ArenaId::new(index);
+                arena.get(id).map(|module| module.name.as_str()).ok_or(id)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok((&self.name, imports))
+    }
+}
+
+#[test]
+fn cross_references() {
+    let arena = PairArena::try_new(vec![
+        Module::new("a", &[1, 2]),
+        Module::new("b", &[2]),
+        Module::new("c", &[0]),
+    ])
+    .unwrap();
+
+    assert_eq!(arena.len(), 3);
+    assert!(!arena.is_empty());
+    assert_eq!(
+        arena.ids().map(ArenaId::index).collect::<Vec<_>>(),
+        [0, 1, 2]
+    );
+    assert_eq!(arena.owner(ArenaId::new(1)).unwrap().name, "b");
+    assert!(arena.owner(ArenaId::new(3)).is_none());
+    assert_eq!(arena.owners().len(), 3);
+
+    assert_eq!(
+        arena.with_dependent(ArenaId::new(0), |(name, imports)| (*name, imports.clone())),
+        Some(("a", vec!["b", "c"]))
+    );
+    assert_eq!(
+        arena.with_dependent(ArenaId::new(2), |(_, imports)| imports.clone()),
+        Some(vec!["a"])
+    );
+    assert_eq!(arena.with_dependent(ArenaId::new(3), |_| ()), None);
+
+    let owners = arena.into_owners();
+    assert_eq!(owners.len(), 3);
+    assert_eq!(owners[2].name, "c");
+}
+
+#[test]
+fn dependent_mut() {
+    let mut arena =
+        PairArena::try_new(vec![Module::new("a", &[0, 1]), Module::new("b", &[])]).unwrap();
+
+    arena.with_dependent_mut(ArenaId::new(0), |(_, imports)| {
+        imports.retain(|&name| name != "a");
+    });
+    assert_eq!(
+        arena.with_dependent(ArenaId::new(0), |(_, imports)| imports.clone()),
+        Some(vec!["b"])
+    );
+    assert_eq!(arena.with_dependent_mut(ArenaId::new(5), |_| ()), None);
+}
+
+#[test]
+fn error() {
+    let (owners, id, err) = PairArena::try_new(vec![
+        Module::new("a", &[1]),
+        Module::new("b", &[7]),
+        Module::new("c", &[]),
+    ])
+    .unwrap_err();
+
+    assert_eq!(owners.len(), 3);
+    assert_eq!(id, ArenaId::new(1));
+    assert_eq!(err, ArenaId::new(7));
+}
+
+#[derive(Debug)]
+struct Leaf(u32);
+
+impl<'arena> HasDependent<'arena> for Leaf {
+    type Dependent = Vec<&'arena u32>;
+}
+
+impl ArenaOwner for Leaf {
+    type Error = Infallible;
+
+    fn make_dependent<'arena>(
+        &'arena self,
+        arena: ArenaView<'arena, Self>,
+    ) -> Result<Dependent<'arena, Self>, Self::Error> {
+        Ok(arena
+            .iter()
+            .filter(|&(id, _)| id != arena.id())
+            .map(|(_, leaf)| &leaf.0)
+            .collect())
+    }
+}
+
+#[test]
+fn infallible_and_debug() {
+    let arena = PairArena::new(vec![Leaf(1), Leaf(2), Leaf(3)]);
+    assert_eq!(
+        arena.with_dependent(ArenaId::new(1), |others| others.clone()),
+        Some(vec![&1, &3])
+    );
+    assert_eq!(
+        format!("{arena:?}"),
+        "PairArena { owners: [Leaf(1), Leaf(2), Leaf(3)], dependents: [[2, 3], [1, 3], [1, 2]] }"
+    );
+
+    let empty = PairArena::<Leaf>::new(Vec::new());
+    assert!(empty.is_empty());
+    assert_eq!(empty.ids().count(), 0);
+}
+
+#[test]
+fn send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<PairArena<Leaf>>();
+    assert_send_sync::<PairArena<Module>>();
+}
@@ -0,0 +1,95 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "std")]
+
+use std::ffi::OsStr;
+
+use pair::{ArgsPair, ArgsSnapshot};
+
+#[test]
+fn parsed_args() {
+    let args = ArgsPair::new(ArgsSnapshot::new(
+        [
+            "prog",
+            "-",
+            "--out=a.txt",
+            "in.txt",
+            "-xé",
+            "--out=b.txt",
+            "--verbose",
+            "--",
+            "-y",
+        ],
+        [("LANG", "C"), ("LANG", "en_US.UTF-8")],
+    ));
+
+    args.with_dependent(|parsed| {
+        assert_eq!(parsed.program(), Some(OsStr::new("prog")));
+        assert_eq!(
+            parsed.options(),
+            [
+                ("out", Some("a.txt")),
+                ("x", None),
+                ("é", None),
+                ("out", Some("b.txt")),
+                ("verbose", None),
+            ]
+        );
+        assert_eq!(parsed.value("out"), Some("b.txt"));
+        assert_eq!(parsed.value("verbose"), None);
+        assert!(parsed.flag("verbose") && parsed.flag("é"));
+        assert!(!parsed.flag("y"));
+        assert_eq!(parsed.positionals(), ["-", "in.txt", "-y"]);
+
+        // The last value of a duplicated variable wins
+        assert_eq!(parsed.var("LANG"), Some(OsStr::new("en_US.UTF-8")));
+        assert_eq!(parsed.var("HOME"), None);
+    });
+
+    assert_eq!(args.owner().args().len(), 9);
+    assert_eq!(args.owner().vars().len(), 2);
+}
+
+#[test]
+fn empty_args() {
+    let args = ArgsPair::new(ArgsSnapshot::new(
+        Vec::<String>::new(),
+        Vec::<(String, String)>::new(),
+    ));
+
+    args.with_dependent(|parsed| {
+        assert_eq!(parsed.program(), None);
+        assert!(parsed.options().is_empty());
+        assert!(parsed.positionals().is_empty());
+    });
+}
+
+#[cfg(unix)]
+#[test]
+fn non_unicode_args() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let invalid = OsStr::from_bytes(b"--\xFF");
+    let args = ArgsPair::new(ArgsSnapshot::new(
+        ["prog".as_ref(), invalid],
+        [("KEY", invalid)],
+    ));
+
+    // Arguments which aren't valid Unicode are positional
+    args.with_dependent(|parsed| {
+        assert!(parsed.options().is_empty());
+        assert_eq!(parsed.positionals(), [invalid]);
+        assert_eq!(parsed.var("KEY"), Some(invalid));
+    });
+}
+
+#[test]
+fn capture() {
+    let args = ArgsPair::new(ArgsSnapshot::capture());
+
+    let program = std::env::args_os().next();
+    assert_eq!(
+        args.with_dependent(|parsed| parsed.program().map(OsStr::to_owned)),
+        program
+    );
+    assert_eq!(args.owner().vars().len(), std::env::vars_os().count());
+}
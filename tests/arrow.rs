@@ -0,0 +1,80 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "arrow")]
+
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, Int32Array, RecordBatch, StringArray};
+use arrow_schema::ArrowError;
+use pair::{ArrowBatch, ArrowBatchPair, BatchView, Pair, column_as};
+
+struct Scores;
+
+struct ScoresView<'batch> {
+    names: &'batch StringArray,
+    scores: &'batch Int32Array,
+}
+
+impl BatchView for Scores {
+    type View<'batch> = ScoresView<'batch>;
+
+    fn view(batch: &RecordBatch) -> Result<Self::View<'_>, ArrowError> {
+        Ok(ScoresView {
+            names: column_as(batch, "name")?,
+            scores: column_as(batch, "score")?,
+        })
+    }
+}
+
+fn batch(columns: Vec<(&str, ArrayRef)>) -> RecordBatch {
+    RecordBatch::try_from_iter(columns).unwrap()
+}
+
+#[test]
+fn typed_views() {
+    let pair: ArrowBatchPair<Scores> = Pair::try_new(ArrowBatch::new(batch(vec![
+        ("name", Arc::new(StringArray::from(vec!["ann", "bo", "cy"]))),
+        ("score", Arc::new(Int32Array::from(vec![3, 9, 4]))),
+    ])))
+    .unwrap();
+
+    let best = pair.with_dependent(|view| {
+        let (index, _) = view
+            .scores
+            .values()
+            .iter()
+            .enumerate()
+            .max_by_key(|&(_, score)| score)
+            .unwrap();
+        view.names.value(index).to_owned()
+    });
+    assert_eq!(best, "bo");
+
+    // The views borrow the batch's buffers
+    let values = pair.owner().batch().column(1).to_data().buffers()[0].as_ptr();
+    assert!(pair.with_dependent(|view| view.scores.values().as_ptr().cast() == values));
+
+    assert_eq!(pair.into_owner().into_batch().num_rows(), 3);
+}
+
+#[test]
+fn missing_column() {
+    let Err((_, err)) = Pair::try_new(ArrowBatch::<Scores>::new(batch(vec![(
+        "name",
+        Arc::new(StringArray::from(vec!["ann"])),
+    )]))) else {
+        panic!("the batch has no score column");
+    };
+    assert!(matches!(err, ArrowError::SchemaError(_)));
+}
+
+#[test]
+fn wrong_column_type() {
+    let Err((owner, err)) = Pair::try_new(ArrowBatch::<Scores>::new(batch(vec![
+        ("name", Arc::new(StringArray::from(vec!["ann"]))),
+        ("score", Arc::new(StringArray::from(vec!["high"]))),
+    ]))) else {
+        panic!("the score column has the wrong type");
+    };
+    assert!(matches!(err, ArrowError::CastError(_)));
+    assert_eq!(owner.batch().num_columns(), 2);
+}
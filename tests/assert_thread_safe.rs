@@ -0,0 +1,114 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{cell::Cell, convert::Infallible, marker::PhantomData, thread};
+
+use pair::{AssertSendPair, AssertSyncPair, Dependent, HasDependent, Owner, Pair};
+
+/// A buffer whose dependent stores a raw pointer into it. Raw pointers are
+/// never `Send` or `Sync`, but this one only ever points into the owner.
+#[derive(Debug)]
+struct Buffer(Vec<u8>);
+
+#[derive(Debug)]
+struct Cursor {
+    ptr: *const u8,
+    len: usize,
+}
+
+impl Cursor {
+    fn bytes(&self) -> &[u8] {
+        // SAFETY: `ptr` and `len` always describe the owner's buffer, which is
+        // never mutated while the dependent exists.
+        unsafe { std::slice::from_raw_parts(self.ptr, self.len) }
+    }
+}
+
+impl HasDependent<'_> for Buffer {
+    type Dependent = Cursor;
+}
+
+impl Owner for Buffer {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(Cursor {
+            ptr: self.0.as_ptr(),
+            len: self.0.len(),
+        })
+    }
+}
+
+#[test]
+fn send_across_threads() {
+    let pair = Pair::new(Buffer(vec![1, 2, 3]));
+    // SAFETY: The cursor only points into the owner, which moves with it.
+    let pair = unsafe { AssertSendPair::new(pair) };
+
+    let sum = thread::spawn(move || {
+        pair.with_dependent(|cursor| cursor.bytes().iter().map(|&b| u32::from(b)).sum::<u32>())
+    })
+    .join()
+    .unwrap();
+    assert_eq!(sum, 6);
+}
+
+#[test]
+fn sync_across_threads() {
+    let pair = Pair::new(Buffer(vec![4, 5]));
+    // SAFETY: The cursor only points into the owner, and is never mutated
+    // through shared references.
+    let pair = unsafe { AssertSyncPair::new(pair) };
+
+    thread::scope(|scope| {
+        for _ in 0..2 {
+            scope.spawn(|| assert_eq!(pair.with_dependent(|cursor| cursor.bytes().len()), 2));
+        }
+    });
+
+    let pair = pair.into_inner();
+    assert_eq!(pair.owner().0, [4, 5]);
+}
+
+#[test]
+fn accessors() {
+    // SAFETY: The cursor only points into the owner, which moves with it.
+    let pair = unsafe { AssertSendPair::new(Pair::new(Buffer(vec![7]))) };
+    assert_eq!(pair.get().owner().0, [7]);
+
+    let mut inner = pair.into_inner();
+    inner.with_dependent_mut(|cursor| cursor.len = 0);
+    // SAFETY: The cursor still only points into the owner.
+    let pair = unsafe { AssertSendPair::new(inner) };
+    assert!(pair.with_dependent(|cursor| cursor.bytes().is_empty()));
+    assert!(format!("{pair:?}").starts_with("AssertSendPair(Pair { owner: Buffer([7])"));
+}
+
+/// An owner which is `Send` but not `Sync`.
+struct Unsync(PhantomData<Cell<u8>>);
+
+impl HasDependent<'_> for Unsync {
+    type Dependent = ();
+}
+
+impl Owner for Unsync {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(())
+    }
+}
+
+#[test]
+fn auto_bounds() {
+    fn assert_send<T: Send>() {}
+    fn assert_sync<T: Sync>() {}
+
+    assert_send::<AssertSendPair<Buffer>>();
+    assert_sync::<AssertSyncPair<Buffer>>();
+
+    // The other trait is forwarded from the pair
+    assert_send::<AssertSyncPair<Unsync>>();
+    assert_sync::<AssertSyncPair<Unsync>>();
+}
@@ -58,6 +58,15 @@ fn basic_usage() {
     assert_eq!(owner.0, "This is a test of pair.");
 }
 
+#[test]
+fn with_owner() {
+    let pair = Pair::new(Buff(String::from("This is a test of pair.")));
+
+    assert_eq!(pair.with_owner(|owner| owner.0.len()), 23);
+    let owner: &Buff = pair.with_owner(|owner| owner);
+    assert!(std::ptr::eq(owner, pair.owner()));
+}
+
 #[test]
 fn basic_api_stress_test() {
     // Let's just do a bunch of the basic API functions interlaced together and
@@ -0,0 +1,156 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "std")]
+
+use std::{convert::Infallible, num::ParseIntError};
+
+use pair::{Dependent, HasDependent, Owner, Pair};
+
+#[derive(Debug)]
+struct Words(String);
+
+impl<'owner> HasDependent<'owner> for Words {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Words {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): Self::Context<'_>) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split_whitespace().collect())
+    }
+}
+
+#[derive(Debug)]
+struct Numbers(String);
+
+impl<'owner> HasDependent<'owner> for Numbers {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Numbers {
+    type Context<'a> = &'a str;
+    type Error = ParseIntError;
+
+    fn make_dependent(&self, delimiter: &str) -> Result<Dependent<'_, Self>, Self::Error> {
+        let parts: Vec<&str> = self.0.split(delimiter).collect();
+        for part in &parts {
+            part.parse::<u32>()?;
+        }
+
+        Ok(parts)
+    }
+}
+
+#[test]
+fn new_batch_preserves_order() {
+    let owners: Vec<Words> = (0..100)
+        .map(|i| Words(format!("word {i} {}", i * 2)))
+        .collect();
+    let pairs = Pair::new_batch(owners);
+
+    assert_eq!(pairs.len(), 100);
+    for (i, pair) in pairs.iter().enumerate() {
+        let expected = [String::from("word"), i.to_string(), (i * 2).to_string()];
+        assert_eq!(pair.with_dependent(|dep| dep), &expected);
+    }
+}
+
+#[test]
+fn empty_batch() {
+    assert!(Pair::new_batch(Vec::<Words>::new()).is_empty());
+    assert!(Pair::try_new_batch(Vec::<Words>::new()).is_empty());
+}
+
+#[test]
+fn single_owner() {
+    let pairs = Pair::new_batch(vec![Words(String::from("only one"))]);
+
+    assert_eq!(pairs.len(), 1);
+    assert_eq!(pairs[0].with_dependent(|dep| dep), &["only", "one"]);
+}
+
+#[test]
+fn try_new_batch_with_context_errors() {
+    let owners: Vec<Numbers> = (0..50)
+        .map(|i| {
+            if i % 7 == 0 {
+                Numbers(format!("{i};oops"))
+            } else {
+                Numbers(format!("{i};{}", i + 1))
+            }
+        })
+        .collect();
+    let results = Pair::try_new_batch_with_context(owners, ";");
+
+    assert_eq!(results.len(), 50);
+    for (i, result) in results.into_iter().enumerate() {
+        if i % 7 == 0 {
+            let (owner, _) = result.unwrap_err();
+            assert_eq!(owner.0, format!("{i};oops"));
+        } else {
+            let pair = result.unwrap();
+            assert_eq!(
+                pair.with_dependent(|dep| dep),
+                &[i.to_string(), (i + 1).to_string()]
+            );
+        }
+    }
+}
+
+#[test]
+fn panic_is_propagated() {
+    #[derive(Debug)]
+    struct PanicsOn(u32);
+
+    impl HasDependent<'_> for PanicsOn {
+        type Dependent = ();
+    }
+
+    impl Owner for PanicsOn {
+        type Context<'a> = ();
+        type Error = Infallible;
+
+        fn make_dependent(
+            &self,
+            (): Self::Context<'_>,
+        ) -> Result<Dependent<'_, Self>, Self::Error> {
+            assert_ne!(self.0, 13, "unlucky");
+            Ok(())
+        }
+    }
+
+    let owners: Vec<PanicsOn> = (0..20).map(PanicsOn).collect();
+    let payload = std::panic::catch_unwind(|| Pair::new_batch(owners)).unwrap_err();
+
+    assert!(
+        payload
+            .downcast_ref::<String>()
+            .unwrap()
+            .contains("unlucky")
+    );
+}
+
+#[test]
+fn partitioned_keeps_good_and_reports_bad() {
+    let owners: Vec<Numbers> = (0..50)
+        .map(|i| {
+            if i % 10 == 3 {
+                Numbers(format!("{i};bad"))
+            } else {
+                Numbers(format!("{i};{}", i + 1))
+            }
+        })
+        .collect();
+
+    let (pairs, failures) = Pair::new_batch_partitioned_with_context(owners, ";");
+    assert_eq!(pairs.len(), 45);
+    assert_eq!(pairs[3].with_dependent(|dep| dep.concat()), "45");
+
+    let bad: Vec<&str> = failures.iter().map(|(owner, _)| owner.0.as_str()).collect();
+    assert_eq!(bad, ["3;bad", "13;bad", "23;bad", "33;bad", "43;bad"]);
+
+    let (pairs, failures) = Pair::new_batch_partitioned(vec![Words(String::from("a b"))]);
+    assert_eq!(pairs.len(), 1);
+    assert!(failures.is_empty());
+}
@@ -0,0 +1,120 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::num::ParseIntError;
+
+use pair::{Blob, BlobError, BlobPair, BlobParser, BlobPool};
+
+// Parses a blob of comma-separated numbers, borrowing each one's text
+struct Numbers;
+
+impl BlobParser for Numbers {
+    type Parsed<'blob> = Vec<(&'blob [u8], u32)>;
+    type Error = ParseIntError;
+
+    fn parse(bytes: &[u8]) -> Result<Self::Parsed<'_>, Self::Error> {
+        bytes
+            .split(|&byte| byte == b',')
+            .map(|text| Ok((text, String::from_utf8_lossy(text).parse()?)))
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Missing(&'static str);
+
+#[test]
+fn load() {
+    let pair: BlobPair<Vec<u8>, Numbers> =
+        Blob::load(|| Ok::<_, Missing>(b"1,22,333".to_vec())).unwrap();
+    assert_eq!(
+        pair.with_dependent(|numbers| numbers[2]),
+        (b"333".as_slice(), 333)
+    );
+    assert_eq!(pair.owner().bytes(), b"1,22,333");
+    assert_eq!(format!("{:?}", pair.owner()), "Blob { len: 8, .. }");
+
+    let Err(BlobError::Load(Missing("x"))) = Blob::<Vec<u8>, Numbers>::load(|| Err(Missing("x")))
+    else {
+        panic!("the loader failed");
+    };
+
+    let Err(err) = Blob::<&[u8], Numbers>::load(|| Ok::<_, Missing>(b"1,two".as_slice())) else {
+        panic!("the blob is invalid");
+    };
+    assert_eq!(err.to_string(), "failed to parse a blob");
+    let BlobError::Parse(bytes, _) = err else {
+        panic!("the blob is invalid");
+    };
+    assert_eq!(bytes, b"1,two");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn load_batch() {
+    use std::collections::HashMap;
+
+    let store = HashMap::from([("a", "1,2"), ("b", "three"), ("c", "4"), ("e", "5,6,7")]);
+
+    let results = Blob::<&[u8], Numbers>::load_batch(["a", "b", "c", "d", "e"], |id| {
+        store.get(id).map(|blob| blob.as_bytes()).ok_or(Missing(id))
+    });
+
+    let summary: Vec<_> = results
+        .iter()
+        .map(|result| match result {
+            Ok(pair) => Ok(pair.with_dependent(|numbers| numbers.iter().map(|&(_, n)| n).sum())),
+            Err(BlobError::Load(missing)) => Err(missing.0),
+            Err(BlobError::Parse(bytes, _)) => Err(std::str::from_utf8(bytes).unwrap()),
+        })
+        .collect();
+    assert_eq!(summary, [Ok(3), Err("three"), Ok(4), Err("d"), Ok(18)]);
+}
+
+#[test]
+fn pool() {
+    let mut pool = BlobPool::<Numbers>::default();
+    assert_eq!(pool.idle(), 0);
+
+    let pair = pool
+        .load(|buffer| {
+            assert!(buffer.is_empty());
+            buffer.extend_from_slice(b"10,20");
+            Ok::<_, Missing>(())
+        })
+        .unwrap();
+    let allocation = pair.owner().bytes().as_ptr();
+    pool.recycle(pair);
+    assert_eq!(pool.idle(), 1);
+
+    // The recycled buffer is cleared and reused
+    let pair = pool
+        .load(|buffer| {
+            assert!(buffer.is_empty());
+            buffer.extend_from_slice(b"30");
+            Ok::<_, Missing>(())
+        })
+        .unwrap();
+    assert_eq!(pair.owner().bytes().as_ptr(), allocation);
+    assert_eq!(pool.idle(), 0);
+
+    // A failed load returns its buffer to the pool
+    let Err(BlobError::Load(Missing("gone"))) = pool.load(|buffer| {
+        buffer.push(b'!');
+        Err(Missing("gone"))
+    }) else {
+        panic!("the loader failed");
+    };
+    assert_eq!(pool.idle(), 1);
+
+    // A failed parse returns the buffer, which can be recycled by hand
+    let Err(BlobError::Parse(buffer, _)) = pool.load(|buffer| {
+        buffer.extend_from_slice(b"?");
+        Ok::<_, Missing>(())
+    }) else {
+        panic!("the blob is invalid");
+    };
+    pool.recycle_buffer(buffer);
+    pool.recycle(pair);
+    assert_eq!(pool.idle(), 2);
+    assert_eq!(format!("{pool:?}"), "BlobPool { idle: 2 }");
+}
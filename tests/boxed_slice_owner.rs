@@ -0,0 +1,109 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{convert::Infallible, str::Utf8Error};
+
+use pair::{Dependent, HasDependent, Owner, Pair};
+
+#[derive(Debug)]
+#[repr(transparent)]
+struct Bytes([u8]);
+
+impl From<Box<[u8]>> for Box<Bytes> {
+    fn from(bytes: Box<[u8]>) -> Self {
+        // SAFETY: `Bytes` is a `#[repr(transparent)]` wrapper of `[u8]`
+        unsafe { Self::from_raw(Box::into_raw(bytes) as *mut Bytes) }
+    }
+}
+
+impl<'owner> HasDependent<'owner> for Bytes {
+    type Dependent = &'owner str;
+}
+
+impl Owner for Bytes {
+    type Context<'a> = ();
+    type Error = Utf8Error;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        std::str::from_utf8(&self.0)
+    }
+}
+
+#[derive(Debug)]
+#[repr(transparent)]
+struct Text(str);
+
+impl From<Box<str>> for Box<Text> {
+    fn from(text: Box<str>) -> Self {
+        // SAFETY: `Text` is a `#[repr(transparent)]` wrapper of `str`
+        unsafe { Self::from_raw(Box::into_raw(text) as *mut Text) }
+    }
+}
+
+impl<'owner> HasDependent<'owner> for Text {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Text {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.lines().collect())
+    }
+}
+
+#[test]
+fn from_vec() {
+    let mut bytes = Vec::with_capacity(64);
+    bytes.extend_from_slice(b"hello");
+
+    let pair = Pair::<Bytes>::try_new_from_vec_with_context(bytes, ()).unwrap();
+    assert_eq!(pair.owner().0.len(), 5);
+    assert_eq!(pair.with_dependent(|text| *text), "hello");
+
+    let (owner, _) = Pair::<Bytes>::try_new_from_vec_with_context(vec![0xff], ()).unwrap_err();
+    assert_eq!(owner.0, [0xff]);
+}
+
+#[test]
+fn from_string() {
+    let mut text = String::with_capacity(64);
+    text.push_str("a\nb");
+
+    let pair = Pair::<Text>::new_from_string(text);
+    assert_eq!(&pair.owner().0, "a\nb");
+    assert_eq!(pair.dependent_len(), 2);
+
+    let Ok(pair) = Pair::<Text>::try_new_from_string_with_context(String::from("c"), ());
+    assert_eq!(pair.with_dependent(|lines| lines[0]), "c");
+}
+
+#[test]
+fn infallible_from_vec() {
+    #[derive(Debug)]
+    #[repr(transparent)]
+    struct Numbers([u32]);
+
+    impl From<Box<[u32]>> for Box<Numbers> {
+        fn from(numbers: Box<[u32]>) -> Self {
+            // SAFETY: `Numbers` is a `#[repr(transparent)]` wrapper of `[u32]`
+            unsafe { Self::from_raw(Box::into_raw(numbers) as *mut Numbers) }
+        }
+    }
+
+    impl<'owner> HasDependent<'owner> for Numbers {
+        type Dependent = Option<&'owner u32>;
+    }
+
+    impl Owner for Numbers {
+        type Context<'a> = ();
+        type Error = Infallible;
+
+        fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+            Ok(self.0.iter().max())
+        }
+    }
+
+    let pair = Pair::<Numbers>::new_from_vec(vec![3, 9, 4]);
+    assert_eq!(pair.with_dependent(|max| max.copied()), Some(9));
+}
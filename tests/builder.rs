@@ -0,0 +1,76 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{convert::Infallible, num::ParseIntError};
+
+use pair::{Dependent, HasDependent, Owner, Pair};
+
+#[derive(Debug)]
+struct Words(String);
+
+impl<'owner> HasDependent<'owner> for Words {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Words {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split_whitespace().collect())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Numbers(String);
+
+impl HasDependent<'_> for Numbers {
+    type Dependent = Vec<u32>;
+}
+
+impl Owner for Numbers {
+    type Context<'a> = &'a str;
+    type Error = ParseIntError;
+
+    fn make_dependent(&self, separator: &str) -> Result<Dependent<'_, Self>, Self::Error> {
+        self.0.split(separator).map(str::parse).collect()
+    }
+}
+
+#[test]
+fn build_without_context() {
+    let pair = Pair::builder(Words(String::from("a b"))).build();
+    assert_eq!(pair.with_dependent(|words| words[1]), "b");
+
+    let pair = Pair::builder_from_box(Box::new(Words(String::from("c"))))
+        .try_build()
+        .unwrap();
+    assert_eq!(pair.with_dependent(|words| words[0]), "c");
+
+    let pair = Pair::builder(Words(String::from("d e")))
+        .context(())
+        .build();
+    assert_eq!(pair.owner().0, "d e");
+}
+
+#[test]
+fn build_with_context() {
+    let builder = Pair::builder(Numbers(String::from("1;2")));
+    assert_eq!(
+        format!("{builder:?}"),
+        r#"NeedsContext { owner: Numbers("1;2") }"#
+    );
+
+    let builder = builder.context(";");
+    assert_eq!(
+        format!("{builder:?}"),
+        r#"Ready { owner: Numbers("1;2"), context: ";" }"#
+    );
+    let pair = builder.try_build().unwrap();
+    assert_eq!(pair.with_dependent(Clone::clone), [1, 2]);
+
+    let (owner, _) = Pair::builder(Numbers(String::from("1;2")))
+        .context(",")
+        .try_build()
+        .unwrap_err();
+    assert_eq!(*owner, Numbers(String::from("1;2")));
+}
@@ -0,0 +1,210 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{
+    cell::Cell,
+    convert::Infallible,
+    num::ParseIntError,
+    panic::{AssertUnwindSafe, catch_unwind},
+};
+
+use pair::{CachedPair, Dependent, HasDependent, Owner, Pair, RebuildStats};
+
+#[derive(Debug)]
+struct Words(String);
+
+impl<'owner> HasDependent<'owner> for Words {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Words {
+    type Context<'a> = &'a Cell<usize>;
+    type Error = Infallible;
+
+    fn make_dependent(
+        &self,
+        builds: Self::Context<'_>,
+    ) -> Result<Dependent<'_, Self>, Self::Error> {
+        builds.set(builds.get() + 1);
+        Ok(self.0.split_whitespace().collect())
+    }
+}
+
+#[test]
+fn rebuilds_on_demand() {
+    let builds = Cell::new(0);
+    let mut pair = CachedPair::new(Words(String::from("a b")));
+    assert!(!pair.is_fresh());
+    assert!(pair.pair().is_none());
+    assert_eq!(builds.get(), 0);
+
+    assert_eq!(
+        pair.with_dependent_or_rebuild(&builds, |dep| dep),
+        &["a", "b"]
+    );
+    assert!(pair.is_fresh());
+    assert_eq!(builds.get(), 1);
+
+    // A fresh dependent isn't rebuilt
+    assert_eq!(
+        pair.with_dependent_or_rebuild(&builds, |dep| dep),
+        &["a", "b"]
+    );
+    assert_eq!(pair.pair().unwrap().with_dependent(|dep| dep), &["a", "b"]);
+    assert_eq!(builds.get(), 1);
+
+    pair.owner_mut().0.push_str(" c");
+    assert!(!pair.is_fresh());
+    assert_eq!(pair.owner().0, "a b c");
+    assert_eq!(
+        pair.with_dependent_or_rebuild(&builds, |dep| dep),
+        &["a", "b", "c"]
+    );
+    assert_eq!(builds.get(), 2);
+
+    pair.invalidate();
+    pair.invalidate();
+    assert!(!pair.is_fresh());
+    assert_eq!(
+        pair.refresh(&builds).with_dependent(|dep| dep),
+        &["a", "b", "c"]
+    );
+    assert_eq!(builds.get(), 3);
+
+    assert_eq!(pair.into_owner().0, "a b c");
+}
+
+#[test]
+fn from_pair() {
+    let builds = Cell::new(0);
+    let mut pair = CachedPair::from(Pair::new_with_context(Words(String::from("x")), &builds));
+    assert!(pair.is_fresh());
+    assert_eq!(pair.with_dependent_or_rebuild(&builds, |dep| dep), &["x"]);
+    assert_eq!(builds.get(), 1);
+
+    assert_eq!(pair.into_boxed_owner().0, "x");
+}
+
+#[test]
+fn try_into_owner_requires_fresh() {
+    let builds = Cell::new(0);
+    let pair = CachedPair::new(Words(String::from("a b")));
+    assert!(!pair.is_poisoned());
+
+    // A rebuild is pending, so the pair is returned intact
+    let mut pair = pair.try_into_owner().unwrap_err();
+    assert!(!pair.is_fresh());
+
+    pair.refresh(&builds);
+    assert_eq!(pair.try_into_boxed_owner().unwrap().0, "a b");
+}
+
+#[test]
+fn debug() {
+    let builds = Cell::new(0);
+    let mut pair = CachedPair::new(Words(String::from("a b")));
+    assert_eq!(
+        format!("{pair:?}"),
+        r#"CachedPair { owner: Words("a b"), dependent: None }"#
+    );
+
+    pair.refresh(&builds);
+    assert_eq!(
+        format!("{pair:?}"),
+        r#"CachedPair { owner: Words("a b"), dependent: Some(["a", "b"]) }"#
+    );
+}
+
+#[derive(Debug)]
+struct Number(String);
+
+impl HasDependent<'_> for Number {
+    type Dependent = u32;
+}
+
+impl Owner for Number {
+    type Context<'a> = ();
+    type Error = ParseIntError;
+
+    fn make_dependent(&self, (): Self::Context<'_>) -> Result<Dependent<'_, Self>, Self::Error> {
+        self.0.parse()
+    }
+}
+
+#[test]
+fn failed_rebuild_stays_stale() {
+    let mut pair = CachedPair::new(Number(String::from("abc")));
+    assert!(pair.try_with_dependent_or_rebuild((), |n| *n).is_err());
+    assert!(!pair.is_fresh());
+
+    pair.owner_mut().0 = String::from("123");
+    assert_eq!(pair.try_with_dependent_or_rebuild((), |n| *n), Ok(123));
+    assert!(pair.is_fresh());
+    assert_eq!(pair.try_refresh(()).unwrap().owner().0, "123");
+}
+
+#[test]
+fn panic_while_rebuilding_poisons() {
+    #[derive(Debug)]
+    struct Panics(bool);
+
+    impl HasDependent<'_> for Panics {
+        type Dependent = ();
+    }
+
+    impl Owner for Panics {
+        type Context<'a> = ();
+        type Error = Infallible;
+
+        fn make_dependent(
+            &self,
+            (): Self::Context<'_>,
+        ) -> Result<Dependent<'_, Self>, Self::Error> {
+            assert!(!self.0, "oh no");
+            Ok(())
+        }
+    }
+
+    let mut pair = CachedPair::new(Panics(true));
+    let result = catch_unwind(AssertUnwindSafe(|| pair.refresh(()).owner().0));
+    assert!(result.is_err());
+
+    assert_eq!(format!("{pair:?}"), "CachedPair(<poisoned>)");
+    assert!(catch_unwind(AssertUnwindSafe(|| pair.is_fresh())).is_err());
+    assert!(catch_unwind(AssertUnwindSafe(|| pair.owner().0)).is_err());
+    assert!(catch_unwind(AssertUnwindSafe(|| pair.invalidate())).is_err());
+    assert!(pair.is_poisoned());
+
+    let pair = pair.try_into_owner().unwrap_err();
+    assert!(catch_unwind(|| pair.into_owner()).is_err());
+}
+
+#[test]
+fn rebuild_stats() {
+    let mut pair = CachedPair::new(Number(String::from("abc")));
+    assert_eq!(pair.stats(), RebuildStats::default());
+    #[cfg(feature = "std")]
+    assert_eq!(pair.stats().last_rebuild_duration(), None);
+
+    assert!(pair.try_refresh(()).is_err());
+    pair.owner_mut().0 = String::from("1");
+    assert_eq!(pair.try_with_dependent_or_rebuild((), |n| *n), Ok(1));
+    assert_eq!(pair.try_with_dependent_or_rebuild((), |n| *n), Ok(1));
+    pair.owner_mut().0 = String::from("2");
+    pair.invalidate();
+    assert_eq!(pair.try_with_dependent_or_rebuild((), |n| *n), Ok(2));
+
+    let stats = pair.stats();
+    assert_eq!(stats.rebuilds(), 2);
+    assert_eq!(stats.failed_rebuilds(), 1);
+    assert_eq!(stats.invalidations(), 1);
+    #[cfg(feature = "std")]
+    assert!(stats.last_rebuild_duration().is_some());
+
+    // Stats are kept when failing to take the owner
+    pair.invalidate();
+    let mut pair = pair.try_into_owner().unwrap_err();
+    assert_eq!(pair.stats().invalidations(), 2);
+
+    pair.reset_stats();
+    assert_eq!(pair.stats(), RebuildStats::default());
+}
@@ -0,0 +1,182 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{cell::Cell, error::Error};
+
+use pair::{CancellableError, CancellationToken, Cancelled, Dependent, HasDependent, Owner, Pair};
+
+#[derive(Debug)]
+struct Words(String);
+
+impl<'owner> HasDependent<'owner> for Words {
+    type Dependent = Vec<&'owner str>;
+}
+
+struct WordsContext<'a> {
+    token: &'a CancellationToken,
+    cancel_after: Option<usize>,
+    calls: &'a Cell<usize>,
+}
+
+impl Owner for Words {
+    type Context<'a> = WordsContext<'a>;
+    type Error = Cancelled;
+
+    fn make_dependent(
+        &self,
+        context: Self::Context<'_>,
+    ) -> Result<Dependent<'_, Self>, Self::Error> {
+        context.calls.set(context.calls.get() + 1);
+
+        let mut words = Vec::new();
+        for (i, word) in self.0.split_whitespace().enumerate() {
+            if context.cancel_after == Some(i) {
+                context.token.cancel();
+            }
+            context.token.check()?;
+            words.push(word);
+        }
+
+        Ok(words)
+    }
+}
+
+fn context<'a>(
+    token: &'a CancellationToken,
+    cancel_after: Option<usize>,
+    calls: &'a Cell<usize>,
+) -> WordsContext<'a> {
+    WordsContext {
+        token,
+        cancel_after,
+        calls,
+    }
+}
+
+#[test]
+fn token_basics() {
+    let token = CancellationToken::new();
+    assert!(!token.is_cancelled());
+    assert_eq!(token.check(), Ok(()));
+
+    token.cancel();
+    assert!(token.is_cancelled());
+    assert_eq!(token.check(), Err(Cancelled));
+
+    token.cancel();
+    assert!(token.is_cancelled());
+
+    assert!(!CancellationToken::default().is_cancelled());
+}
+
+#[test]
+fn not_cancelled() {
+    let token = CancellationToken::new();
+    let calls = Cell::new(0);
+    let pair = Pair::try_new_cancellable(
+        Words(String::from("a b c")),
+        context(&token, None, &calls),
+        &token,
+    )
+    .unwrap();
+
+    assert_eq!(pair.with_dependent(|dep| dep), &["a", "b", "c"]);
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn cancelled_before_construction() {
+    let token = CancellationToken::new();
+    token.cancel();
+    let calls = Cell::new(0);
+    let Err((owner, err)) = Pair::try_new_cancellable(
+        Words(String::from("a b c")),
+        context(&token, None, &calls),
+        &token,
+    ) else {
+        panic!("construction should have been cancelled");
+    };
+
+    assert_eq!(owner.0, "a b c");
+    assert!(matches!(err, CancellableError::Cancelled));
+    assert_eq!(calls.get(), 0);
+}
+
+#[test]
+fn cancelled_during_construction() {
+    let token = CancellationToken::new();
+    let calls = Cell::new(0);
+    let Err((owner, err)) = Pair::try_new_from_box_cancellable(
+        Box::new(Words(String::from("a b c"))),
+        context(&token, Some(1), &calls),
+        &token,
+    ) else {
+        panic!("construction should have been cancelled");
+    };
+
+    assert_eq!(owner.0, "a b c");
+    assert!(matches!(err, CancellableError::Cancelled));
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn cancelled_without_polling() {
+    // An owner which ignores the token still has its result discarded if the
+    // token was cancelled by the time it finishes
+    #[derive(Debug)]
+    struct Ignorant(String);
+
+    impl<'owner> HasDependent<'owner> for Ignorant {
+        type Dependent = &'owner str;
+    }
+
+    impl Owner for Ignorant {
+        type Context<'a> = &'a CancellationToken;
+        type Error = std::convert::Infallible;
+
+        fn make_dependent(
+            &self,
+            token: Self::Context<'_>,
+        ) -> Result<Dependent<'_, Self>, Self::Error> {
+            token.cancel();
+            Ok(&self.0)
+        }
+    }
+
+    let token = CancellationToken::new();
+    let Err((owner, err)) = Pair::try_new_cancellable(Ignorant(String::from("hi")), &token, &token)
+    else {
+        panic!("construction should have been cancelled");
+    };
+
+    assert_eq!(owner.0, "hi");
+    assert!(matches!(err, CancellableError::Cancelled));
+}
+
+#[test]
+fn failed_without_cancellation() {
+    // The owner's own token is cancelled, but the token passed to the
+    // constructor is not
+    let owner_token = CancellationToken::new();
+    let constructor_token = CancellationToken::new();
+    let calls = Cell::new(0);
+    let Err((owner, err)) = Pair::try_new_cancellable(
+        Words(String::from("a b c")),
+        context(&owner_token, Some(2), &calls),
+        &constructor_token,
+    ) else {
+        panic!("construction should have failed");
+    };
+
+    assert_eq!(owner.0, "a b c");
+    assert!(matches!(err, CancellableError::Failed(Cancelled)));
+}
+
+#[test]
+fn error_impls() {
+    let cancelled = CancellableError::<Cancelled>::Cancelled;
+    assert_eq!(cancelled.to_string(), "operation was cancelled");
+    assert!(cancelled.source().is_none());
+
+    let failed = CancellableError::Failed(Cancelled);
+    assert!(failed.source().unwrap().is::<Cancelled>());
+}
@@ -0,0 +1,63 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "itertools")]
+
+use std::num::ParseIntError;
+
+use pair::{Dependent, HasDependent, Owner, PairIteratorExt};
+
+#[derive(Debug)]
+struct Numbers(&'static str);
+
+impl HasDependent<'_> for Numbers {
+    type Dependent = Vec<u32>;
+}
+
+impl Owner for Numbers {
+    type Context<'a> = ();
+    type Error = ParseIntError;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        self.0.split(',').map(str::parse).collect()
+    }
+}
+
+fn numbers(lines: &[&'static str]) -> impl Iterator<Item = Numbers> {
+    lines.iter().map(|&line| Numbers(line))
+}
+
+#[test]
+fn try_collect_pairs_succeeds() {
+    let Ok(pairs) = numbers(&["1,2", "3"]).try_collect_pairs() else {
+        panic!("every line is valid");
+    };
+    let sums: Vec<u32> = pairs
+        .iter()
+        .map(|pair| pair.with_dependent(|numbers| numbers.iter().sum()))
+        .collect();
+    assert_eq!(sums, [3, 3]);
+
+    let Ok(pairs) = numbers(&[]).try_collect_pairs() else {
+        panic!("there are no lines");
+    };
+    assert!(pairs.is_empty());
+}
+
+#[test]
+fn try_collect_pairs_aggregates_failures() {
+    let Err(failures) = numbers(&["x", "1", "2,y", ""]).try_collect_pairs() else {
+        panic!("some lines are invalid");
+    };
+    let owners: Vec<&str> = failures.iter().map(|(owner, _)| owner.0).collect();
+    assert_eq!(owners, ["x", "2,y", ""]);
+}
+
+#[test]
+fn partition_pairs() {
+    let (pairs, failures) = numbers(&["x", "1", "2,y", "3,4"]).partition_pairs();
+
+    let owners: Vec<&str> = pairs.iter().map(|pair| pair.owner().0).collect();
+    assert_eq!(owners, ["1", "3,4"]);
+
+    let owners: Vec<&str> = failures.iter().map(|(owner, _)| owner.0).collect();
+    assert_eq!(owners, ["x", "2,y"]);
+}
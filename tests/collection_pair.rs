@@ -0,0 +1,123 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{collections::BTreeMap, convert::Infallible};
+
+use pair::{CollectionPair, Dependent, HasDependent, Owner, Pair};
+
+#[derive(Debug)]
+struct Words(String);
+
+impl<'owner> HasDependent<'owner> for Words {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Words {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split_whitespace().collect())
+    }
+}
+
+#[test]
+fn vec_dependent() {
+    let mut words = CollectionPair::new(Words(String::from("a bb ccc dd e")));
+
+    words.retain(|word| word.len() > 1);
+    assert_eq!(words.with_dependent(|words| words.join(" ")), "bb ccc dd");
+
+    words.extend_from_owner(|owner| {
+        owner
+            .0
+            .split_whitespace()
+            .filter(|w| w.len() == 1)
+            .collect()
+    });
+    assert_eq!(
+        words.with_dependent(|words| words.join(" ")),
+        "bb ccc dd a e"
+    );
+
+    // Derefs to the inner pair
+    words.with_dependent_mut(|words| words.truncate(1));
+    assert_eq!(words.dependent_len(), 1);
+
+    words.clear();
+    assert!(words.dependent_is_empty());
+
+    let pair = words.into_pair();
+    assert_eq!(pair.owner().0, "a bb ccc dd e");
+}
+
+#[derive(Debug)]
+struct Config(String);
+
+impl<'owner> HasDependent<'owner> for Config {
+    type Dependent = BTreeMap<&'owner str, &'owner str>;
+}
+
+impl Owner for Config {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self
+            .0
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .collect())
+    }
+}
+
+#[test]
+fn map_dependent() {
+    let mut config = CollectionPair::from(Pair::new(Config(String::from("a=1\nb=2\nc=3"))));
+
+    config.retain(|&(key, _)| key != "b");
+    assert_eq!(
+        config.with_dependent(|map| map.keys().copied().collect::<Vec<_>>()),
+        ["a", "c"]
+    );
+
+    config.extend_from_owner(|owner| vec![("b", &owner.0[2..3])]);
+    assert_eq!(
+        config.with_dependent(|map| map.get("b").copied()),
+        Some("1")
+    );
+
+    assert_eq!(
+        format!("{config:?}"),
+        r#"CollectionPair(Pair { owner: Config("a=1\nb=2\nc=3"), dependent: {"a": "1", "b": "1", "c": "3"} })"#
+    );
+}
+
+#[cfg(feature = "std")]
+#[derive(Debug)]
+struct Index(String);
+
+#[cfg(feature = "std")]
+impl<'owner> HasDependent<'owner> for Index {
+    type Dependent = std::collections::HashMap<&'owner str, usize>;
+}
+
+#[cfg(feature = "std")]
+impl Owner for Index {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split(',').enumerate().map(|(i, s)| (s, i)).collect())
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn hash_map_dependent() {
+    let mut index = CollectionPair::new(Index(String::from("x,y,z")));
+
+    index.retain(|&(_, position)| position != 1);
+    assert_eq!(index.dependent_len(), 2);
+    assert_eq!(index.with_dependent(|map| map.get("y").copied()), None);
+    assert_eq!(index.with_dependent(|map| map.get("z").copied()), Some(2));
+}
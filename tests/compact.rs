@@ -0,0 +1,164 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "compact")]
+
+use std::{cell::Cell, ops::Range};
+
+use pair::{
+    CompactError, CompactOwner, Dependent, FromSpans, HasDependent, Owner, Pair, ToSpans, span_of,
+};
+
+#[derive(Debug)]
+struct Words(String);
+
+impl<'owner> HasDependent<'owner> for Words {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Words {
+    type Context<'a> = &'a Cell<usize>;
+    type Error = usize;
+
+    fn make_dependent(&self, calls: Self::Context<'_>) -> Result<Dependent<'_, Self>, Self::Error> {
+        calls.set(calls.get() + 1);
+        let words: Vec<&str> = self.0.split_whitespace().collect();
+        if words.len() > 5 {
+            return Err(words.len());
+        }
+
+        Ok(words)
+    }
+}
+
+impl ToSpans for Words {
+    type Spans = Vec<Range<usize>>;
+
+    fn to_spans(&self, dependent: &Dependent<'_, Self>) -> Self::Spans {
+        dependent
+            .iter()
+            .map(|word| span_of(self.0.as_bytes(), word.as_bytes()).unwrap())
+            .collect()
+    }
+}
+
+impl FromSpans for Words {
+    fn dependent_from_spans<'owner>(
+        &'owner self,
+        spans: &Self::Spans,
+    ) -> Option<Dependent<'owner, Self>> {
+        spans.iter().map(|span| self.0.get(span.clone())).collect()
+    }
+}
+
+impl CompactOwner for Words {
+    fn as_compact_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+
+    fn from_compact_bytes(bytes: &[u8]) -> Option<Self> {
+        Some(Self(String::from_utf8(bytes.to_vec()).ok()?))
+    }
+}
+
+#[test]
+fn round_trip_without_spans() {
+    let calls = Cell::new(0);
+    let pair = Pair::try_new_with_context(Words(String::from("a bb ccc")), &calls).unwrap();
+    let bytes = pair.encode_compact();
+    assert_eq!(bytes.len(), 1 + 8 + 8 + 1);
+
+    let decoded = Pair::<Words>::try_decode_compact(&bytes, &calls).unwrap();
+    assert_eq!(decoded.with_dependent(|dep| dep), &["a", "bb", "ccc"]);
+    assert_eq!(calls.get(), 2);
+
+    // Without a sidecar, the dependent must be recomputed
+    let decoded = Pair::<Words>::try_decode_compact_with_spans(&bytes, &calls).unwrap();
+    assert_eq!(decoded.with_dependent(|dep| dep), &["a", "bb", "ccc"]);
+    assert_eq!(calls.get(), 3);
+}
+
+#[test]
+fn round_trip_with_spans() {
+    let calls = Cell::new(0);
+    let pair = Pair::try_new_with_context(Words(String::from("a bb ccc")), &calls).unwrap();
+    let bytes = pair.encode_compact_with_spans();
+
+    let decoded = Pair::<Words>::try_decode_compact_with_spans(&bytes, &calls).unwrap();
+    assert_eq!(decoded.with_dependent(|dep| dep), &["a", "bb", "ccc"]);
+    assert_eq!(calls.get(), 1);
+
+    // The sidecar is ignored by `try_decode_compact`
+    let decoded = Pair::<Words>::try_decode_compact(&bytes, &calls).unwrap();
+    assert_eq!(decoded.with_dependent(|dep| dep), &["a", "bb", "ccc"]);
+    assert_eq!(calls.get(), 2);
+}
+
+#[test]
+fn invalid_spans_fall_back() {
+    let calls = Cell::new(0);
+    let pair = Pair::try_new_with_context(Words(String::from("a bb ccc")), &calls).unwrap();
+    let mut bytes = pair.encode_compact_with_spans();
+
+    // Corrupt the end of the last span to be out of bounds
+    let len = bytes.len();
+    bytes[len - 8] = 200;
+
+    let decoded = Pair::<Words>::try_decode_compact_with_spans(&bytes, &calls).unwrap();
+    assert_eq!(decoded.with_dependent(|dep| dep), &["a", "bb", "ccc"]);
+    assert_eq!(calls.get(), 2);
+}
+
+#[test]
+fn malformed() {
+    let calls = Cell::new(0);
+    let pair = Pair::try_new_with_context(Words(String::from("a bb ccc")), &calls).unwrap();
+    let bytes = pair.encode_compact_with_spans();
+
+    for len in 0..bytes.len() {
+        assert!(matches!(
+            Pair::<Words>::try_decode_compact_with_spans(&bytes[..len], &calls),
+            Err(CompactError::Malformed)
+        ));
+    }
+
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert!(matches!(
+        Pair::<Words>::try_decode_compact(&trailing, &calls),
+        Err(CompactError::Malformed)
+    ));
+
+    let mut bad_version = bytes.clone();
+    bad_version[0] = 99;
+    assert!(matches!(
+        Pair::<Words>::try_decode_compact(&bad_version, &calls),
+        Err(CompactError::Malformed)
+    ));
+
+    // A huge span count shouldn't cause a huge allocation
+    let mut huge_count = pair.encode_compact();
+    *huge_count.last_mut().unwrap() = 1;
+    huge_count.extend_from_slice(&u64::MAX.to_le_bytes());
+    assert!(matches!(
+        Pair::<Words>::try_decode_compact(&huge_count, &calls),
+        Err(CompactError::Malformed)
+    ));
+}
+
+#[test]
+fn invalid_owner_and_failure() {
+    let calls = Cell::new(0);
+
+    let mut bytes = vec![1];
+    bytes.extend_from_slice(&2_u64.to_le_bytes());
+    bytes.extend_from_slice(&[0xff, 0xfe, 0]);
+    let err = Pair::<Words>::try_decode_compact(&bytes, &calls).unwrap_err();
+    assert_eq!(err, CompactError::InvalidOwner);
+    assert_eq!(err.to_string(), "invalid owner in compact pair encoding");
+
+    let mut bytes = vec![1];
+    bytes.extend_from_slice(&11_u64.to_le_bytes());
+    bytes.extend_from_slice(b"a b c d e f");
+    bytes.push(0);
+    let err = Pair::<Words>::try_decode_compact(&bytes, &calls).unwrap_err();
+    assert_eq!(err, CompactError::Failed(6));
+}
@@ -0,0 +1,32 @@
+extern crate pair;
+
+use std::{convert::Infallible, rc::Rc};
+
+use pair::{AssertSendPair, Dependent, HasDependent, Owner, Pair};
+
+struct RcOwner(Rc<u32>);
+
+impl HasDependent<'_> for RcOwner {
+    type Dependent = ();
+}
+
+impl Owner for RcOwner {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): Self::Context<'_>) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(())
+    }
+}
+
+fn main() {
+    let shared = Rc::new(0);
+
+    // SAFETY: this Rc is uniquely owned by the pair, so sending it is fine.
+    let mut wrapped = unsafe { AssertSendPair::new(Pair::new(RcOwner(Rc::new(1)))) };
+
+    // Replacing the pair would smuggle an unchecked `Rc` to another thread.
+    *wrapped = Pair::new(RcOwner(Rc::clone(&shared)));
+
+    std::thread::spawn(move || drop(wrapped));
+}
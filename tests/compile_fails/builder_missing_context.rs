@@ -0,0 +1,27 @@
+extern crate pair;
+
+use std::num::ParseIntError;
+
+use pair::{Dependent, HasDependent, Owner, Pair};
+
+struct Numbers(String);
+
+impl HasDependent<'_> for Numbers {
+    type Dependent = Vec<u32>;
+}
+
+impl Owner for Numbers {
+    type Context<'a> = &'a str;
+    type Error = ParseIntError;
+
+    fn make_dependent(
+        &self,
+        separator: Self::Context<'_>,
+    ) -> Result<Dependent<'_, Self>, Self::Error> {
+        self.0.split(separator).map(str::parse).collect()
+    }
+}
+
+fn main() {
+    let _ = Pair::builder(Numbers(String::from("1,2"))).try_build();
+}
@@ -0,0 +1,27 @@
+extern crate pair;
+
+use std::{cell::Cell, convert::Infallible};
+
+use pair::{Dependent, HasDependent, Owner};
+
+struct Buff(String);
+
+impl<'owner> HasDependent<'owner> for Buff {
+    type Dependent = Cell<&'owner str>;
+}
+
+impl Owner for Buff {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(
+        &self,
+        (): Self::Context<'_>,
+    ) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(Cell::new(&self.0))
+    }
+}
+
+pair::covariant!(Buff);
+
+fn main() {}
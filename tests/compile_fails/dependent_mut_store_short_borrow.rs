@@ -0,0 +1,35 @@
+extern crate pair;
+
+use std::convert::Infallible;
+
+use pair::{CovariantDependent, Dependent, HasDependent, Owner, Pair};
+
+struct Buff(String);
+
+impl<'owner> HasDependent<'owner> for Buff {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Buff {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(
+        &self,
+        (): Self::Context<'_>,
+    ) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split_whitespace().collect())
+    }
+}
+
+// SAFETY: `Vec<&'owner str>` is covariant over `'owner`.
+unsafe impl CovariantDependent for Buff {}
+
+fn main() {
+    let mut pair = Pair::new(Buff(String::from("This is a test of pair.")));
+    {
+        let short_lived = String::from("short-lived");
+        pair.dependent_mut().push(&short_lived);
+    }
+    pair.with_dependent(|words| println!("{words:?}"));
+}
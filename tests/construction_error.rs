@@ -0,0 +1,109 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{error::Error, fmt::Display};
+
+use pair::{ConstructionError, Dependent, HasDependent, Owner, Pair};
+
+#[derive(Debug)]
+struct Root;
+
+impl Display for Root {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("root cause")
+    }
+}
+
+impl Error for Root {}
+
+#[derive(Debug)]
+struct ParseError(Root);
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("parse error")
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+// Intentionally doesn't implement Debug
+struct Buff(String);
+
+impl<'owner> HasDependent<'owner> for Buff {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Buff {
+    type Context<'a> = ();
+    type Error = ParseError;
+
+    fn make_dependent(&self, (): Self::Context<'_>) -> Result<Dependent<'_, Self>, Self::Error> {
+        let words: Vec<_> = self.0.split_whitespace().collect();
+
+        if words.is_empty() {
+            Err(ParseError(Root))
+        } else {
+            Ok(words)
+        }
+    }
+}
+
+fn build(text: &str) -> Result<Pair<Buff>, Box<dyn Error>> {
+    Ok(Pair::try_new(Buff(String::from(text))).map_err(ConstructionError::from)?)
+}
+
+#[test]
+fn construction_error_source_chain() {
+    assert!(build("hello").is_ok());
+
+    let err = build("   ").err().unwrap();
+    let mut chain = vec![err.to_string()];
+    let mut source = err.source();
+    while let Some(err) = source {
+        chain.push(err.to_string());
+        source = err.source();
+    }
+    assert_eq!(
+        chain,
+        [
+            "failed to construct the dependent of a pair",
+            "parse error",
+            "root cause"
+        ]
+    );
+}
+
+#[test]
+fn construction_error_parts() {
+    let err = ConstructionError::from(Pair::try_new(Buff(String::from(" "))).err().unwrap());
+    assert_eq!(err.owner().0, " ");
+    assert!(matches!(err.error(), ParseError(Root)));
+    assert!(
+        format!("{err:?}").starts_with("ConstructionError { error: ParseError(Root), location: ")
+    );
+
+    let (owner, ParseError(Root)) = err.into_parts();
+    assert_eq!(owner.0, " ");
+}
+
+#[test]
+fn construction_error_location() {
+    fn build_located(text: &str) -> (u32, Result<Pair<Buff>, ConstructionError<Buff, ParseError>>) {
+        let line = line!() + 1;
+        let result = (|| Ok(Pair::try_new(Buff(String::from(text)))?))();
+        (line, result)
+    }
+
+    let (line, result) = build_located("");
+    let err = result.err().unwrap();
+    assert_eq!(err.location().file(), file!());
+    assert_eq!(err.location().line(), line);
+
+    let line = line!() + 1;
+    let err = ConstructionError::new(Buff(String::new()), ParseError(Root));
+    assert_eq!(err.location().line(), line);
+}
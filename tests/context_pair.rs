@@ -0,0 +1,113 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{
+    cell::Cell,
+    convert::Infallible,
+    panic::{AssertUnwindSafe, catch_unwind},
+};
+
+use pair::{ContextPair, Dependent, HasDependent, Owner, StoredContext};
+
+#[derive(Debug)]
+struct Csv(String);
+
+impl<'owner> HasDependent<'owner> for Csv {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Csv {
+    type Context<'a> = &'a Cell<&'static str>;
+    type Error = Infallible;
+
+    fn make_dependent(
+        &self,
+        context: Self::Context<'_>,
+    ) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split(context.get()).collect())
+    }
+}
+
+#[derive(Debug)]
+struct Delimiter(Cell<&'static str>);
+
+impl StoredContext<Csv> for Delimiter {
+    fn context(&self) -> &Cell<&'static str> {
+        &self.0
+    }
+}
+
+#[test]
+fn context_pair_rebuild() {
+    let mut pair = ContextPair::new(Csv(String::from("a,b;c,d")), Delimiter(Cell::new(",")));
+    assert_eq!(pair.context().0.get(), ",");
+    assert_eq!(pair.with_dependent(|dep| dep), &["a", "b;c", "d"]);
+
+    pair.pair_mut().with_dependent_mut(|dep| dep.truncate(0));
+    assert_eq!(pair.with_dependent(|dep| dep), &[] as &[&str]);
+
+    pair.rebuild_dependent();
+    assert_eq!(pair.with_dependent(|dep| dep), &["a", "b;c", "d"]);
+
+    pair.context().0.set(";");
+    pair.rebuild_dependent();
+    assert_eq!(pair.with_dependent(|dep| dep), &["a,b", "c,d"]);
+
+    assert_eq!(
+        format!("{pair:?}"),
+        r#"ContextPair { owner: Csv("a,b;c,d"), dependent: ["a,b", "c,d"], context: Delimiter(Cell { value: ";" }) }"#
+    );
+
+    let (owner, context) = pair.into_owner();
+    assert_eq!(owner.0, "a,b;c,d");
+    assert_eq!(context.0.get(), ";");
+}
+
+#[derive(Debug)]
+struct Numbers(String);
+
+impl HasDependent<'_> for Numbers {
+    type Dependent = Vec<u32>;
+}
+
+impl Owner for Numbers {
+    type Context<'a> = u32;
+    type Error = String;
+
+    fn make_dependent(
+        &self,
+        context: Self::Context<'_>,
+    ) -> Result<Dependent<'_, Self>, Self::Error> {
+        self.0
+            .split(',')
+            .map(|n| u32::from_str_radix(n, context).map_err(|err| err.to_string()))
+            .collect()
+    }
+}
+
+impl StoredContext<Numbers> for Cell<u32> {
+    fn context(&self) -> u32 {
+        self.get()
+    }
+}
+
+#[test]
+fn context_pair_failed_rebuild_keeps_dependent() {
+    let mut pair = ContextPair::try_new(Numbers(String::from("10,1f")), Cell::new(16)).unwrap();
+    assert_eq!(pair.with_dependent(|dep| dep), &[16, 31]);
+
+    pair.context().set(10);
+    assert!(pair.try_rebuild_dependent().is_err());
+    assert_eq!(pair.with_dependent(|dep| dep), &[16, 31]);
+
+    // `from_str_radix` panics for a radix above 36
+    pair.context().set(40);
+    let result = catch_unwind(AssertUnwindSafe(|| pair.try_rebuild_dependent()));
+    assert!(result.is_err());
+    assert_eq!(pair.with_dependent(|dep| dep), &[16, 31]);
+
+    let (_, context, err) = ContextPair::try_new(Numbers(String::from("12")), Cell::new(2))
+        .err()
+        .unwrap();
+    assert_eq!(context.get(), 2);
+    assert_eq!(err, "invalid digit found in string");
+}
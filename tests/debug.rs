@@ -178,3 +178,42 @@ fn debug_impls_match_derive_nomiri() {
         O7::Bat(String::from("testing")), O7::Baz { name: String::from("Hermes"), age: u8::MAX },
     }
 }
+
+#[derive(Debug)]
+struct Tokens(String);
+
+struct PanickingDebug<'a>(&'a str);
+
+impl Debug for PanickingDebug<'_> {
+    fn fmt(&self, _: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        panic!("the dependent was formatted: {}", self.0);
+    }
+}
+
+impl<'owner> HasDependent<'owner> for Tokens {
+    type Dependent = PanickingDebug<'owner>;
+}
+
+impl Owner for Tokens {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(PanickingDebug(&self.0))
+    }
+}
+
+#[test]
+fn debug_owner_only_skips_dependent() {
+    let pair = real::Pair::new(Tokens(String::from("a b")));
+    let debug = pair.debug_owner_only();
+
+    assert_eq!(
+        format!("{debug:?}"),
+        r#"Pair { owner: Tokens("a b"), dependent: <skipped> }"#
+    );
+    assert_eq!(
+        format!("{debug:#?}"),
+        "Pair {\n    owner: Tokens(\n        \"a b\",\n    ),\n    dependent: <skipped>,\n}"
+    );
+}
@@ -0,0 +1,88 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::num::ParseIntError;
+
+use pair::{Dependent, HasDependent, Owner, Pair};
+
+#[derive(Debug)]
+struct Numbers(String);
+
+impl HasDependent<'_> for Numbers {
+    type Dependent = Vec<u32>;
+}
+
+impl Owner for Numbers {
+    type Context<'a> = char;
+    type Error = ParseIntError;
+
+    fn make_dependent(&self, separator: char) -> Result<Dependent<'_, Self>, Self::Error> {
+        self.0.split(separator).map(str::parse).collect()
+    }
+}
+
+#[derive(Debug)]
+struct Csv(String);
+
+impl<'owner> HasDependent<'owner> for Csv {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Csv {
+    type Context<'a> = ();
+    type Error = &'static str;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        if self.0.is_empty() {
+            return Err("empty");
+        }
+        Ok(self.0.split(',').collect())
+    }
+}
+
+#[test]
+fn success_records_no_error() {
+    let pair = Pair::try_new_or_default_dependent_with_context(Numbers(String::from("1;2")), ';');
+    assert!(!pair.is_defaulted());
+    assert!(pair.last_error().is_none());
+    assert_eq!(pair.with_dependent(Clone::clone), [1, 2]);
+
+    let pair = Pair::try_new_or_default_dependent(Csv(String::from("a,b")));
+    assert!(!pair.is_defaulted());
+    assert_eq!(pair.pair().with_dependent(|fields| fields[1]), "b");
+}
+
+#[test]
+fn failure_falls_back_to_default() {
+    let mut pair = Pair::try_new_from_box_or_default_dependent_with_context(
+        Box::new(Numbers(String::from("1,x"))),
+        ',',
+    );
+    assert!(pair.is_defaulted());
+    assert!(pair.with_dependent(Vec::is_empty));
+    assert_eq!(pair.owner().0, "1,x");
+
+    let err = pair.take_last_error().unwrap();
+    assert_eq!(err.to_string(), "invalid digit found in string");
+    assert!(!pair.is_defaulted());
+
+    let pair = Pair::try_new_from_box_or_default_dependent(Box::new(Csv(String::new())));
+    assert_eq!(pair.last_error(), Some(&"empty"));
+    assert_eq!(
+        format!("{pair:?}"),
+        r#"DefaultedPair { pair: Pair { owner: Csv(""), dependent: [] }, last_error: Some("empty") }"#
+    );
+}
+
+#[test]
+fn recover_with_pair_mut() {
+    let mut defaulted =
+        Pair::try_new_or_default_dependent_with_context(Numbers(String::from("1;2")), ',');
+    defaulted.pair_mut().try_remake_dependent(';').unwrap();
+
+    let (pair, err) = defaulted.into_parts();
+    assert!(err.is_some());
+    assert_eq!(
+        pair.with_dependent(|numbers| numbers.iter().sum::<u32>()),
+        3
+    );
+}
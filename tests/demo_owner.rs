@@ -0,0 +1,33 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use pair::{Owner, Pair, demo_owner};
+
+demo_owner! {
+    /// A demo owner.
+    struct Words;
+}
+
+mod public {
+    pair::demo_owner!(
+        pub struct Sentence;
+    );
+}
+
+#[test]
+fn demo_owner_works() {
+    let pair = Words::new_pair("the quick brown fox");
+    assert_eq!(pair.owner().0, "the quick brown fox");
+    assert_eq!(pair.with_dependent(|words| words[2]), "brown");
+
+    let mut pair = Pair::new(public::Sentence::new("a b"));
+    pair.with_dependent_mut(|words| words.reverse());
+    assert_eq!(pair.with_dependent(|words| words.concat()), "ba");
+    assert_eq!(pair.into_owner(), public::Sentence::new("a b"));
+}
+
+#[test]
+fn make_dependent_directly() {
+    let words = Words::new("  spaced\tout\n");
+    let Ok(dependent) = words.make_dependent(());
+    assert_eq!(dependent, ["spaced", "out"]);
+}
@@ -0,0 +1,120 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{cell::Cell, convert::Infallible};
+
+use pair::{CloneDetached, Dependent, HasDependent, Owner, Pair};
+
+#[derive(Debug)]
+struct Buff(String);
+
+impl<'owner> HasDependent<'owner> for Buff {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Buff {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): Self::Context<'_>) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split_whitespace().collect())
+    }
+}
+
+#[test]
+fn dependent_cloned_builtin() {
+    let pair = Pair::new(Buff(String::from("this is a test")));
+    let words: Vec<String> = pair.dependent_cloned();
+    drop(pair);
+
+    assert_eq!(words, ["this", "is", "a", "test"]);
+}
+
+struct Keyed(String);
+
+// An invariant dependent, which can't simply be returned from `with_dependent`
+struct KeyedDep<'a> {
+    key: Cell<&'a str>,
+    values: Vec<&'a str>,
+}
+
+#[derive(Debug, PartialEq)]
+struct KeyedDetached {
+    key: String,
+    values: Vec<String>,
+}
+
+impl CloneDetached for KeyedDep<'_> {
+    type Detached = KeyedDetached;
+
+    fn clone_detached(&self) -> Self::Detached {
+        KeyedDetached {
+            key: self.key.get().to_owned(),
+            values: self.values.clone_detached(),
+        }
+    }
+}
+
+impl<'owner> HasDependent<'owner> for Keyed {
+    type Dependent = KeyedDep<'owner>;
+}
+
+impl Owner for Keyed {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): Self::Context<'_>) -> Result<Dependent<'_, Self>, Self::Error> {
+        let (key, values) = self.0.split_once('=').unwrap();
+
+        Ok(KeyedDep {
+            key: Cell::new(key),
+            values: values.split(',').collect(),
+        })
+    }
+}
+
+#[test]
+fn dependent_cloned_custom() {
+    let pair = Pair::new(Keyed(String::from("numbers=one,two,three")));
+    let detached = pair.dependent_cloned();
+    drop(pair);
+
+    assert_eq!(
+        detached,
+        KeyedDetached {
+            key: String::from("numbers"),
+            values: vec![
+                String::from("one"),
+                String::from("two"),
+                String::from("three")
+            ],
+        }
+    );
+}
+
+impl From<&KeyedDep<'_>> for KeyedDetached {
+    fn from(dependent: &KeyedDep<'_>) -> Self {
+        dependent.clone_detached()
+    }
+}
+
+/// A second detached form of `KeyedDep`, keeping only the number of values
+#[derive(Debug, PartialEq)]
+struct KeyedCount(String, usize);
+
+impl From<&KeyedDep<'_>> for KeyedCount {
+    fn from(dependent: &KeyedDep<'_>) -> Self {
+        Self(dependent.key.get().to_owned(), dependent.values.len())
+    }
+}
+
+#[test]
+fn to_detached() {
+    let pair = Pair::new(Keyed(String::from("letters=a,b")));
+    let detached: KeyedDetached = pair.to_detached();
+    let count: KeyedCount = pair.to_detached();
+    drop(pair);
+
+    assert_eq!(detached.key, "letters");
+    assert_eq!(detached.values, ["a", "b"]);
+    assert_eq!(count, KeyedCount(String::from("letters"), 2));
+}
@@ -0,0 +1,118 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{
+    cell::{Cell, RefCell},
+    panic::{AssertUnwindSafe, catch_unwind},
+};
+
+use pair::{ContextPair, Dependent, DependentDiff, HasDependent, Owner, StoredContext};
+
+struct Words(RefCell<String>);
+
+impl HasDependent<'_> for Words {
+    type Dependent = Vec<String>;
+}
+
+impl Owner for Words {
+    type Context<'a> = &'a Cell<bool>;
+    type Error = String;
+
+    fn make_dependent(&self, fail: &Cell<bool>) -> Result<Dependent<'_, Self>, Self::Error> {
+        if fail.get() {
+            return Err(String::from("failed"));
+        }
+
+        Ok(self
+            .0
+            .borrow()
+            .split_whitespace()
+            .map(String::from)
+            .collect())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct WordsDiff {
+    added: Vec<String>,
+    removed: Vec<String>,
+}
+
+impl DependentDiff for Words {
+    type Diff = WordsDiff;
+
+    fn diff(old: &Vec<String>, new: &Vec<String>) -> WordsDiff {
+        assert!(!new.iter().any(|word| word == "panic"), "diff panicked");
+
+        WordsDiff {
+            added: new
+                .iter()
+                .filter(|word| !old.contains(word))
+                .cloned()
+                .collect(),
+            removed: old
+                .iter()
+                .filter(|word| !new.contains(word))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+struct Fail(Cell<bool>);
+
+impl StoredContext<Words> for Fail {
+    fn context(&self) -> &Cell<bool> {
+        &self.0
+    }
+}
+
+#[test]
+fn rebuild_reports_diff() {
+    let mut pair = ContextPair::try_new(
+        Words(RefCell::new(String::from("a b c"))),
+        Fail(Cell::new(false)),
+    )
+    .unwrap_or_else(|_| panic!());
+
+    *pair.owner().0.borrow_mut() = String::from("b c d e");
+    assert_eq!(
+        pair.try_rebuild_dependent_diff(),
+        Ok(WordsDiff {
+            added: vec![String::from("d"), String::from("e")],
+            removed: vec![String::from("a")],
+        })
+    );
+    assert_eq!(pair.with_dependent(|words| words.join(" ")), "b c d e");
+
+    assert_eq!(
+        pair.try_rebuild_dependent_diff(),
+        Ok(WordsDiff {
+            added: vec![],
+            removed: vec![],
+        })
+    );
+}
+
+#[test]
+fn failed_rebuild_keeps_old_dependent() {
+    let mut pair = ContextPair::try_new(
+        Words(RefCell::new(String::from("a b"))),
+        Fail(Cell::new(false)),
+    )
+    .unwrap_or_else(|_| panic!());
+
+    *pair.owner().0.borrow_mut() = String::from("c");
+    pair.context().0.set(true);
+    assert_eq!(
+        pair.try_rebuild_dependent_diff(),
+        Err(String::from("failed"))
+    );
+    assert_eq!(pair.with_dependent(|words| words.join(" ")), "a b");
+
+    // A panicking diff also leaves the old dependent in place
+    pair.context().0.set(false);
+    *pair.owner().0.borrow_mut() = String::from("panic");
+    let result = catch_unwind(AssertUnwindSafe(|| pair.try_rebuild_dependent_diff()));
+    assert!(result.is_err());
+    assert_eq!(pair.with_dependent(|words| words.join(" ")), "a b");
+}
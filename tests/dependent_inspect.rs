@@ -0,0 +1,117 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    convert::Infallible,
+};
+
+use pair::{Dependent, DependentInspect, HasDependent, Owner, Pair};
+
+struct Words(String);
+
+impl<'owner> HasDependent<'owner> for Words {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Words {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): Self::Context<'_>) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split_whitespace().collect())
+    }
+}
+
+#[test]
+fn vec_dependent() {
+    let pair = Pair::new(Words(String::from("a b c")));
+    assert_eq!(pair.dependent_len(), 3);
+    assert!(!pair.dependent_is_empty());
+    assert!(pair.dependent_is_valid());
+
+    let pair = Pair::new(Words(String::from("   ")));
+    assert_eq!(pair.dependent_len(), 0);
+    assert!(pair.dependent_is_empty());
+}
+
+struct Trimmed(String);
+
+impl<'owner> HasDependent<'owner> for Trimmed {
+    type Dependent = &'owner str;
+}
+
+impl Owner for Trimmed {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): Self::Context<'_>) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.trim())
+    }
+}
+
+#[test]
+fn str_dependent() {
+    let pair = Pair::new(Trimmed(String::from("  hi  ")));
+    assert_eq!(pair.dependent_len(), 2);
+    assert!(!pair.dependent_is_empty());
+}
+
+struct Parsed(String);
+
+struct Parse<'a> {
+    numbers: Vec<&'a str>,
+    errors: usize,
+}
+
+impl DependentInspect for Parse<'_> {
+    fn len(&self) -> usize {
+        self.numbers.len()
+    }
+
+    fn is_valid(&self) -> bool {
+        self.errors == 0
+    }
+}
+
+impl<'owner> HasDependent<'owner> for Parsed {
+    type Dependent = Parse<'owner>;
+}
+
+impl Owner for Parsed {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): Self::Context<'_>) -> Result<Dependent<'_, Self>, Self::Error> {
+        let (numbers, errors): (Vec<&str>, Vec<&str>) = self
+            .0
+            .split(',')
+            .partition(|part| part.parse::<u32>().is_ok());
+
+        Ok(Parse {
+            numbers,
+            errors: errors.len(),
+        })
+    }
+}
+
+#[test]
+fn custom_dependent() {
+    let pair = Pair::new(Parsed(String::from("1,2,x,4")));
+    assert_eq!(pair.dependent_len(), 3);
+    assert!(!pair.dependent_is_empty());
+    assert!(!pair.dependent_is_valid());
+
+    let pair = Pair::new(Parsed(String::from("1,2")));
+    assert!(pair.dependent_is_valid());
+}
+
+#[test]
+fn provided_impls() {
+    assert_eq!(DependentInspect::len("abc"), 3);
+    assert_eq!(DependentInspect::len(&[1, 2][..]), 2);
+    assert_eq!(DependentInspect::len(&String::from("ab")), 2);
+    assert!(DependentInspect::is_empty(&VecDeque::<u8>::new()));
+    assert_eq!(DependentInspect::len(&BTreeMap::from([(1, 2)])), 1);
+    assert_eq!(DependentInspect::len(&Box::new(vec![1, 2, 3])), 3);
+    assert!(DependentInspect::is_valid(&&mut vec![0]));
+}
@@ -0,0 +1,80 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{collections::HashMap, convert::Infallible, ffi::c_void};
+
+use pair::{Dependent, HasDependent, Owner, Pair};
+
+#[derive(Debug)]
+struct Record(String);
+
+impl<'owner> HasDependent<'owner> for Record {
+    type Dependent = (&'owner str, usize);
+}
+
+impl Owner for Record {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok((self.0.split(':').next().unwrap_or_default(), self.0.len()))
+    }
+}
+
+#[test]
+fn index_by_address_across_moves() {
+    let mut pairs: Vec<Pair<Record>> = ["a:1", "bb:22", "ccc:333"]
+        .into_iter()
+        .map(|record| Pair::new(Record(String::from(record))))
+        .collect();
+
+    let index: HashMap<*const (), usize> = pairs
+        .iter()
+        .enumerate()
+        .map(|(i, pair)| (pair.dependent_raw_for().cast::<()>(), i))
+        .collect();
+
+    // Moving every pair (by reallocating and reordering the Vec) doesn't move
+    // any dependents
+    pairs.reserve(100);
+    pairs.reverse();
+
+    for pair in &pairs {
+        let address = pair.dependent_raw_for();
+        let original = index[&address.cast::<()>()];
+
+        // SAFETY: the pair is alive, and not exclusively borrowed.
+        let (key, len) = unsafe { *address };
+        assert_eq!(key.len(), original + 1);
+        assert_eq!(len, key.len() * 2 + 1);
+    }
+}
+
+#[test]
+fn reads_see_mutations() {
+    let mut pair = Pair::new(Record(String::from("key:value")));
+
+    pair.with_dependent_mut(|(_, len)| *len = 0);
+    let address = pair.dependent_raw_for();
+
+    // SAFETY: the pair is alive, and hasn't been exclusively borrowed since
+    // the pointer was created.
+    let (key, len) = unsafe { *address };
+    assert_eq!(key, "key");
+    assert_eq!(len, 0);
+}
+
+// The pointer keeps its provenance through a round trip as `void *` user data,
+// so this passes under `-Zmiri-strict-provenance` without exposing anything.
+#[test]
+fn round_trips_through_user_data() {
+    let pair = Pair::new(Record(String::from("user:data")));
+    let user_data: *const c_void = pair.dependent_raw_for().cast();
+
+    let address = user_data.cast::<Dependent<'_, Record>>();
+    assert_eq!(address.addr(), pair.dependent_raw_for().addr());
+
+    // SAFETY: the pair is alive, and not exclusively borrowed.
+    let (key, len) = unsafe { *address };
+    assert_eq!(key, "user");
+    assert_eq!(len, 9);
+}
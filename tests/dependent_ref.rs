@@ -0,0 +1,183 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{cell::Cell, convert::Infallible, num::ParseIntError};
+
+use pair::{
+    CovariantDependent, Dependent, DependentRef, DependentRefMut, HasDependent, Owner, Pair,
+};
+
+#[derive(Debug)]
+struct Csv(String);
+
+impl<'owner> HasDependent<'owner> for Csv {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Csv {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split(',').collect())
+    }
+}
+
+// SAFETY: `Vec<&'owner str>` is covariant over `'owner`.
+unsafe impl CovariantDependent for Csv {}
+
+fn sum(pair: &Pair<Csv>) -> Result<u32, ParseIntError> {
+    pair.dependent_ref()
+        .iter()
+        .map(|field| field.parse::<u32>())
+        .sum()
+}
+
+#[test]
+fn deref_to_dependent() {
+    let pair = Pair::new(Csv(String::from("1,2,3")));
+
+    let fields = pair.dependent_ref();
+    assert_eq!(fields.len(), 3);
+    assert_eq!(fields[1], "2");
+    assert_eq!(format!("{fields:?}"), r#"["1", "2", "3"]"#);
+
+    let copy = fields;
+    assert_eq!(*copy, *fields);
+
+    assert_eq!(sum(&pair), Ok(6));
+    assert!(sum(&Pair::new(Csv(String::from("1,x")))).is_err());
+}
+
+#[test]
+fn into_ref_outlives_guard() {
+    let pair = Pair::new(Csv(String::from("a,b")));
+
+    let first: &str = {
+        let fields = DependentRef::into_ref(pair.dependent_ref());
+        fields[0]
+    };
+    assert_eq!(first, "a");
+    assert_eq!(pair.owner().0, "a,b");
+}
+
+fn retain_numbers(fields: &mut DependentRefMut<'_, Csv>) {
+    fields.with_mut(|fields| fields.retain(|field| field.parse::<u32>().is_ok()));
+}
+
+#[test]
+fn mutable_session() {
+    let mut pair = Pair::new(Csv(String::from("1,x,2,y")));
+
+    let mut fields = pair.dependent_mut();
+    assert_eq!(fields.len(), 4);
+    retain_numbers(&mut fields);
+    assert_eq!(*fields, ["1", "2"]);
+    fields.with_mut(|fields| fields.push("3"));
+    assert_eq!(format!("{fields:?}"), r#"["1", "2", "3"]"#);
+
+    assert_eq!(sum(&pair), Ok(6));
+}
+
+/// An owner whose dependent doesn't borrow from it.
+struct Counted(String);
+
+impl HasDependent<'_> for Counted {
+    type Dependent = usize;
+}
+
+impl Owner for Counted {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.len())
+    }
+}
+
+// SAFETY: `usize` doesn't depend on `'owner`, so is trivially covariant.
+unsafe impl CovariantDependent for Counted {}
+
+#[test]
+fn deref_mut_without_borrows() {
+    let mut pair = Pair::new(Counted(String::from("four")));
+
+    let mut count = pair.dependent_mut();
+    *count += 1;
+    *count *= 2;
+    assert_eq!(*count, 10);
+
+    assert_eq!(*pair.dependent_ref(), 10);
+}
+
+/// An owner whose dependent is invariant, so doesn't implement
+/// `CovariantDependent`.
+struct Cursor(String);
+
+impl<'owner> HasDependent<'owner> for Cursor {
+    type Dependent = Cell<&'owner str>;
+}
+
+impl Owner for Cursor {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(Cell::new(&self.0))
+    }
+}
+
+#[test]
+fn with_mut_for_invariant_dependents() {
+    let mut pair = Pair::new(Cursor(String::from("abc")));
+
+    let mut cursor = pair.dependent_mut();
+    cursor.with_mut(|cursor| cursor.set(&cursor.get()[1..]));
+    cursor.with_mut(|cursor| cursor.set(&cursor.get()[1..]));
+
+    assert!(pair.with_dependent(|cursor| cursor.get() == "c"));
+}
+
+fn longest(pair: &Pair<Csv>) -> Option<&str> {
+    pair.dependent()
+        .iter()
+        .copied()
+        .max_by_key(|field| field.len())
+}
+
+#[test]
+fn direct_getter() {
+    let pair = Pair::new(Csv(String::from("a,bbb,cc")));
+    assert_eq!(longest(&pair), Some("bbb"));
+    assert_eq!(pair.dependent(), &["a", "bbb", "cc"]);
+
+    let mut pair = Pair::new(Counted(String::from("abc")));
+    *pair.dependent_mut() = 5;
+    assert_eq!(*pair.dependent(), 5);
+}
+
+struct Fields(String);
+
+impl<'owner> HasDependent<'owner> for Fields {
+    type Dependent = (Option<&'owner str>, Box<[&'owner str]>);
+}
+
+impl Owner for Fields {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        let mut fields = self.0.split(',');
+        Ok((fields.next(), fields.collect()))
+    }
+}
+
+pair::covariant!(Fields);
+
+#[test]
+fn covariant_macro() {
+    let pair = Pair::new(Fields(String::from("head,a,b")));
+
+    let (head, rest) = pair.dependent();
+    assert_eq!(*head, Some("head"));
+    assert_eq!(**rest, ["a", "b"]);
+}
@@ -0,0 +1,76 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(any(feature = "codespan-reporting", feature = "ariadne"))]
+
+use pair::{SourceFile, SourceFilePair};
+
+fn source() -> SourceFilePair {
+    SourceFilePair::new(SourceFile::new("main.rs", "fn main() {\n    let = 5;\n}\n"))
+}
+
+#[cfg(feature = "codespan-reporting")]
+#[test]
+fn codespan_files() {
+    use codespan_reporting::files::{Error, Files};
+
+    let source = source();
+
+    assert_eq!(Files::name(&source, ()).unwrap(), "main.rs");
+    assert_eq!(Files::source(&source, ()).unwrap(), source.text());
+    assert_eq!(source.line_index((), 16).unwrap(), 1);
+    assert_eq!(Files::line_range(&source, (), 1).unwrap(), 12..25);
+    assert_eq!(Files::line_range(&source, (), 3).unwrap(), 27..27);
+    assert!(matches!(
+        source.line_index((), 100),
+        Err(Error::IndexTooLarge {
+            given: 100,
+            max: 27
+        })
+    ));
+    assert!(matches!(
+        Files::line_range(&source, (), 4),
+        Err(Error::LineTooLarge { given: 4, max: 3 })
+    ));
+
+    let location = source.location((), 20).unwrap();
+    assert_eq!((location.line_number, location.column_number), (2, 9));
+}
+
+#[cfg(feature = "codespan-reporting")]
+#[test]
+fn codespan_render() {
+    use codespan_reporting::{
+        diagnostic::{Diagnostic, Label},
+        term::{Config, emit_into_string},
+    };
+
+    let source = source();
+    let diagnostic = Diagnostic::error()
+        .with_message("expected identifier")
+        .with_label(Label::primary((), 20..21).with_message("found `=`"));
+
+    let rendered = emit_into_string(&Config::default(), &source, &diagnostic).unwrap();
+    assert!(rendered.contains("main.rs:2:9"));
+    assert!(rendered.contains("let = 5;"));
+    assert!(rendered.contains("found `=`"));
+}
+
+#[cfg(feature = "ariadne")]
+#[test]
+fn ariadne_render() {
+    use ariadne::{Config, Label, Report, ReportKind};
+
+    let source = source();
+    let report = Report::build(ReportKind::Error, 20..21)
+        .with_config(Config::default().with_color(false))
+        .with_message("expected identifier")
+        .with_label(Label::new(20..21).with_message("found `=`"))
+        .finish();
+
+    let mut rendered = Vec::new();
+    report.write(&source, &mut rendered).unwrap();
+    let rendered = String::from_utf8(rendered).unwrap();
+
+    assert!(rendered.contains("main.rs:2:9"));
+    assert!(rendered.contains("let = 5;"));
+    assert!(rendered.contains("found `=`"));
+}
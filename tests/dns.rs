@@ -0,0 +1,73 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "dns")]
+
+use pair::{DnsPacket, DnsPacketPair, Pair};
+use simple_dns::{
+    CLASS, Name, Packet, QCLASS, QTYPE, Question, ResourceRecord, TYPE,
+    rdata::{A, RData, TXT},
+};
+
+fn reply() -> Vec<u8> {
+    let mut reply = Packet::new_reply(7);
+    reply.questions.push(Question::new(
+        Name::new("example.com").unwrap(),
+        QTYPE::TYPE(TYPE::TXT),
+        QCLASS::CLASS(CLASS::IN),
+        false,
+    ));
+    reply.answers.push(ResourceRecord::new(
+        Name::new("example.com").unwrap(),
+        CLASS::IN,
+        300,
+        RData::TXT(TXT::new().with_string("v=spf1 -all").unwrap()),
+    ));
+    reply.answers.push(ResourceRecord::new(
+        Name::new("example.com").unwrap(),
+        CLASS::IN,
+        60,
+        RData::A(A {
+            address: 0x7F00_0001,
+        }),
+    ));
+    reply.build_bytes_vec_compressed().unwrap()
+}
+
+#[test]
+fn parsed_packet() {
+    let pair: DnsPacketPair = Pair::try_new(DnsPacket::new(reply())).unwrap();
+
+    pair.with_dependent(|packet| {
+        assert_eq!(packet.id(), 7);
+        assert_eq!(packet.questions.len(), 1);
+        assert_eq!(packet.answers.len(), 2);
+
+        let RData::TXT(txt) = &packet.answers[0].rdata else {
+            panic!("the first answer should be a TXT record");
+        };
+        assert_eq!(String::try_from(txt.clone()).unwrap(), "v=spf1 -all");
+        assert!(matches!(
+            packet.answers[1].rdata,
+            RData::A(A {
+                address: 0x7F00_0001
+            })
+        ));
+        assert_eq!(packet.answers[1].name.to_string(), "example.com");
+    });
+
+    assert_eq!(
+        format!("{:?}", pair.owner()),
+        format!("DnsPacket {{ len: {}, .. }}", reply().len())
+    );
+    assert_eq!(*pair.into_owner().into_bytes(), *reply());
+}
+
+#[test]
+fn truncated_packet() {
+    let mut bytes = reply();
+    bytes.truncate(bytes.len() - 3);
+
+    let Err((owner, _)) = Pair::try_new(DnsPacket::new(bytes)) else {
+        panic!("the truncated packet should be invalid");
+    };
+    assert_eq!(owner.bytes().len(), reply().len() - 3);
+}
@@ -0,0 +1,80 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "either")]
+
+use either::Either;
+use pair::{Dependent, HasDependent, Owner, Pair};
+
+struct Words(String);
+
+impl<'owner> HasDependent<'owner> for Words {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Words {
+    type Context<'a> = char;
+    type Error = String;
+
+    fn make_dependent(&self, separator: char) -> Result<Dependent<'_, Self>, Self::Error> {
+        if self.0.is_empty() {
+            return Err(String::from("no words"));
+        }
+
+        Ok(self.0.split(separator).collect())
+    }
+}
+
+struct Bytes(Vec<u8>);
+
+impl<'owner> HasDependent<'owner> for Bytes {
+    type Dependent = Vec<&'owner [u8]>;
+}
+
+impl Owner for Bytes {
+    type Context<'a> = char;
+    type Error = String;
+
+    fn make_dependent(&self, separator: char) -> Result<Dependent<'_, Self>, Self::Error> {
+        let separator = u8::try_from(separator).map_err(|err| err.to_string())?;
+        Ok(self.0.split(|&byte| byte == separator).collect())
+    }
+}
+
+fn count(pair: &Pair<Either<Words, Bytes>>) -> usize {
+    pair.with_dependent(|dependent| dependent.as_ref().either(Vec::len, Vec::len))
+}
+
+#[test]
+fn either_owner() {
+    let left =
+        Pair::try_new_with_context(Either::<_, Bytes>::Left(Words(String::from("a b c"))), ' ');
+    let Ok(left) = left else {
+        panic!("construction should succeed");
+    };
+    assert_eq!(count(&left), 3);
+    assert!(left.owner().is_left());
+
+    let right = Pair::try_new_with_context(Either::<Words, _>::Right(Bytes(b"a,b".to_vec())), ',');
+    let Ok(right) = right else {
+        panic!("construction should succeed");
+    };
+    assert_eq!(count(&right), 2);
+    assert!(right.with_dependent(|dependent| matches!(dependent, Either::Right(_))));
+}
+
+#[test]
+fn either_owner_errors() {
+    let Err((owner, err)) =
+        Pair::<Either<Words, Bytes>>::try_new_with_context(Either::Left(Words(String::new())), ' ')
+    else {
+        panic!("construction should fail");
+    };
+    assert!(owner.is_left());
+    assert_eq!(err, "no words");
+
+    let Err((owner, _)) =
+        Pair::<Either<Words, Bytes>>::try_new_with_context(Either::Right(Bytes(Vec::new())), '€')
+    else {
+        panic!("construction should fail");
+    };
+    assert!(owner.is_right());
+}
@@ -0,0 +1,132 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{convert::Infallible, error::Error, fmt};
+
+use pair::{Dependent, ErasedSendPair, HasDependent, Owner, Pair};
+
+#[derive(Debug)]
+struct Source(String);
+
+impl<'owner> HasDependent<'owner> for Source {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Source {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split_whitespace().collect())
+    }
+}
+
+#[derive(Debug)]
+struct Other;
+
+impl HasDependent<'_> for Other {
+    type Dependent = ();
+}
+
+impl Owner for Other {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct DiagnosticError {
+    token: usize,
+    source: ErasedSendPair,
+}
+
+impl fmt::Display for DiagnosticError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let token = self
+            .source
+            .downcast_ref::<Source>()
+            .and_then(|source| source.with_dependent(|tokens| tokens.get(self.token).copied()))
+            .unwrap_or("?");
+
+        write!(f, "unexpected token `{token}`")
+    }
+}
+
+impl Error for DiagnosticError {}
+
+#[test]
+fn static_send_sync() {
+    fn assert_bounds<T: Send + Sync + 'static>() {}
+    assert_bounds::<Pair<Source>>();
+    assert_bounds::<ErasedSendPair>();
+    assert_bounds::<DiagnosticError>();
+}
+
+#[test]
+fn embedded_in_boxed_error() {
+    let pair = Pair::new(Source(String::from("let x = = 1")));
+    let err: Box<dyn Error + Send + Sync> = Box::new(DiagnosticError {
+        token: 3,
+        source: pair.into(),
+    });
+    assert_eq!(err.to_string(), "unexpected token `=`");
+
+    let err = err.downcast::<DiagnosticError>().unwrap();
+    let pair = err.source.downcast::<Source>().unwrap();
+    assert_eq!(pair.owner().0, "let x = = 1");
+}
+
+#[test]
+fn downcasting() {
+    let mut erased = ErasedSendPair::new(Pair::new(Source(String::from("a b"))));
+
+    assert!(erased.is::<Source>());
+    assert!(!erased.is::<Other>());
+    assert!(erased.downcast_ref::<Other>().is_none());
+    assert!(erased.downcast_mut::<Other>().is_none());
+    assert!(erased.owner_type_name().ends_with("Source"));
+    assert!(erased.dependent_type_name().contains("Vec<&str>"));
+
+    erased
+        .downcast_mut::<Source>()
+        .unwrap()
+        .with_dependent_mut(|tokens| tokens.push("c"));
+    assert_eq!(erased.downcast_ref::<Source>().unwrap().dependent_len(), 3);
+
+    let erased = erased.downcast::<Other>().unwrap_err();
+    assert!(erased.downcast::<Source>().is_ok());
+}
+
+#[test]
+fn debug() {
+    struct NotDebug;
+
+    impl<'owner> HasDependent<'owner> for NotDebug {
+        type Dependent = &'owner Self;
+    }
+
+    impl Owner for NotDebug {
+        type Context<'a> = ();
+        type Error = Infallible;
+
+        fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+            Ok(self)
+        }
+    }
+
+    let erased = ErasedSendPair::new(Pair::new(Source(String::from("a b"))));
+    assert_eq!(
+        format!("{erased:?}"),
+        format!(
+            "ErasedSendPair {{ owner_type: {:?}, dependent_type: {:?} }}",
+            erased.owner_type_name(),
+            erased.dependent_type_name(),
+        )
+    );
+
+    // Neither the owner nor the dependent need implement `Debug`
+    let erased = ErasedSendPair::from(Pair::new(NotDebug));
+    assert!(format!("{erased:?}").contains("NotDebug"));
+}
@@ -0,0 +1,179 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "failpoints")]
+
+use std::{
+    cell::RefCell,
+    panic::{AssertUnwindSafe, catch_unwind},
+    rc::Rc,
+};
+
+use pair::{
+    ContextPair, Dependent, FailPoint, FailPointOwner, FailPoints, HasDependent, Owner, Pair,
+    StoredContext,
+};
+
+// Records the order in which owners and dependents are dropped
+type Log = Rc<RefCell<Vec<&'static str>>>;
+
+struct Tracked(Log);
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.0.borrow_mut().push("owner");
+    }
+}
+
+struct TrackedDependent<'owner>(&'owner Tracked);
+
+impl Drop for TrackedDependent<'_> {
+    fn drop(&mut self) {
+        (self.0).0.borrow_mut().push("dependent");
+    }
+}
+
+impl<'owner> HasDependent<'owner> for Tracked {
+    type Dependent = TrackedDependent<'owner>;
+}
+
+impl Owner for Tracked {
+    type Context<'a> = ();
+    type Error = String;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        self.check_fail_point()?;
+        Ok(TrackedDependent(self))
+    }
+}
+
+impl FailPointOwner for Tracked {
+    fn injected_error(&self) -> Self::Error {
+        String::from("injected")
+    }
+}
+
+struct NoContext;
+
+impl StoredContext<Tracked> for NoContext {
+    fn context(&self) {}
+}
+
+/// An owner which doesn't check for injected failures.
+struct Unchecked;
+
+impl HasDependent<'_> for Unchecked {
+    type Dependent = ();
+}
+
+impl Owner for Unchecked {
+    type Context<'a> = ();
+    type Error = ();
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(())
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("")
+}
+
+#[test]
+fn make_dependent_failure() {
+    let log = Log::default();
+    let failpoints = FailPoints::new();
+    failpoints
+        .fail_at(FailPoint::MakeDependent, 2)
+        .fail_at(FailPoint::MakeDependent, 4);
+    assert_eq!(failpoints.pending(), 2);
+
+    let first = Pair::try_new(Tracked(Rc::clone(&log)));
+    assert!(first.is_ok());
+    drop(first);
+    log.borrow_mut().clear();
+
+    let Err((owner, err)) = Pair::try_new(Tracked(Rc::clone(&log))) else {
+        panic!("the second construction should fail");
+    };
+    assert_eq!(err, "injected");
+    drop(owner);
+    assert_eq!(*log.borrow(), ["owner"]);
+
+    // Rebuilding a dependent counts as a call to make_dependent too
+    let Ok(mut pair) = ContextPair::try_new(Tracked(Rc::clone(&log)), NoContext) else {
+        panic!("the third construction should succeed");
+    };
+    assert_eq!(pair.try_rebuild_dependent(), Err(String::from("injected")));
+    assert_eq!(failpoints.calls(FailPoint::MakeDependent), 4);
+    assert_eq!(failpoints.pending(), 0);
+
+    assert!(pair.try_rebuild_dependent().is_ok());
+}
+
+#[test]
+fn unchecked_owner_is_unaffected() {
+    let failpoints = FailPoints::new();
+    failpoints.fail_at(FailPoint::MakeDependent, 1);
+
+    assert!(Pair::try_new(Unchecked).is_ok());
+    assert_eq!(failpoints.calls(FailPoint::MakeDependent), 0);
+    assert_eq!(failpoints.pending(), 1);
+}
+
+#[test]
+fn allocation_failure() {
+    let log = Log::default();
+    let failpoints = FailPoints::new();
+    failpoints.fail_at(FailPoint::Allocation, 1);
+
+    let payload = catch_unwind(AssertUnwindSafe(|| {
+        Pair::try_new(Tracked(Rc::clone(&log))).is_ok()
+    }))
+    .unwrap_err();
+    assert_eq!(panic_message(&*payload), "injected an allocation failure");
+
+    // The dependent is dropped before the owner it borrows from
+    assert_eq!(*log.borrow(), ["dependent", "owner"]);
+    assert_eq!(failpoints.calls(FailPoint::Allocation), 1);
+}
+
+#[test]
+fn drop_dependent_panic() {
+    let log = Log::default();
+    let failpoints = FailPoints::new();
+    failpoints.fail_at(FailPoint::DropDependent, 2);
+
+    let Ok(first) = Pair::try_new(Tracked(Rc::clone(&log))) else {
+        panic!("construction should succeed");
+    };
+    let Ok(second) = Pair::try_new(Tracked(Rc::clone(&log))) else {
+        panic!("construction should succeed");
+    };
+
+    drop(first);
+    assert_eq!(*log.borrow(), ["dependent", "owner"]);
+
+    let payload = catch_unwind(AssertUnwindSafe(|| drop(second))).unwrap_err();
+    assert_eq!(
+        panic_message(&*payload),
+        "injected a panic while dropping a dependent"
+    );
+    assert_eq!(*log.borrow(), ["dependent", "owner", "dependent", "owner"]);
+}
+
+#[test]
+fn dropping_failpoints_cancels_failures() {
+    let log = Log::default();
+    let failpoints = FailPoints::new();
+    failpoints.fail_at(FailPoint::MakeDependent, 1);
+    drop(failpoints);
+
+    assert!(Pair::try_new(Tracked(Rc::clone(&log))).is_ok());
+
+    // Counting starts over with a new `FailPoints`
+    let failpoints = FailPoints::new();
+    assert_eq!(failpoints.calls(FailPoint::MakeDependent), 0);
+}
@@ -0,0 +1,53 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "flatbuffers")]
+
+use flatbuffers::{FlatBufferBuilder, Vector};
+use pair::{FlatbufferMessage, FlatbufferPair, FlatbufferRoot, Pair};
+
+struct Numbers;
+
+impl FlatbufferRoot for Numbers {
+    type Root<'buf> = Vector<'buf, u32>;
+}
+
+fn numbers_message(numbers: &[u32]) -> FlatbufferMessage<Numbers> {
+    let mut builder = FlatBufferBuilder::new();
+    let root = builder.create_vector(numbers);
+    builder.finish_minimal(root);
+
+    FlatbufferMessage::new(builder.finished_data())
+}
+
+#[test]
+fn vector_root() {
+    let Ok(pair): Result<FlatbufferPair<Numbers>, _> = Pair::try_new(numbers_message(&[1, 2, 3]))
+    else {
+        panic!("the message should be valid");
+    };
+
+    assert_eq!(
+        pair.with_dependent(|numbers| numbers.iter().sum::<u32>()),
+        6
+    );
+    assert_eq!(pair.with_dependent(|numbers| numbers.get(1)), 2);
+    assert!(format!("{:?}", pair.owner()).starts_with("FlatbufferMessage"));
+
+    // The root borrows the message's buffer
+    let bytes = pair.owner().bytes().as_ptr_range();
+    assert!(pair.with_dependent(|numbers| bytes.contains(&numbers.bytes().as_ptr())));
+
+    let bytes = pair.into_owner().into_bytes();
+    assert!(!bytes.is_empty());
+}
+
+#[test]
+fn invalid_message() {
+    let mut message = numbers_message(&[1, 2, 3]).into_bytes().into_vec();
+    let len = message.len();
+    message.truncate(len - 4);
+
+    let Err((owner, _)) = Pair::try_new(FlatbufferMessage::<Numbers>::new(message)) else {
+        panic!("the truncated message should be invalid");
+    };
+    assert_eq!(owner.bytes().len(), len - 4);
+}
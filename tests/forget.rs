@@ -0,0 +1,120 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{
+    cell::Cell,
+    convert::Infallible,
+    ffi::c_void,
+    marker::PhantomData,
+    ptr,
+    sync::atomic::{AtomicPtr, AtomicU32, Ordering},
+};
+
+use pair::{Dependent, HasDependent, Owner, Pair};
+
+static DROPS: AtomicU32 = AtomicU32::new(0);
+
+// Keeps the leaked owner reachable, so the leak sanitizer and Miri don't report
+// the intentional leak. The dependent is zero-sized, so it doesn't allocate.
+static LEAKED: AtomicPtr<Tracked> = AtomicPtr::new(ptr::null_mut());
+static LEAKED_USER_DATA: AtomicPtr<c_void> = AtomicPtr::new(ptr::null_mut());
+
+struct Tracked(String);
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+struct Marker<'owner>(PhantomData<&'owner Tracked>);
+
+impl Drop for Marker<'_> {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl<'owner> HasDependent<'owner> for Tracked {
+    type Dependent = Marker<'owner>;
+}
+
+impl Owner for Tracked {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(Marker(PhantomData))
+    }
+}
+
+#[test]
+fn forget_leaks_without_dropping() {
+    let pair = Pair::new(Tracked(String::from("leaked")));
+    let owner_address = ptr::from_ref(pair.owner());
+
+    let owner = pair.forget();
+    LEAKED.store(owner.as_ptr(), Ordering::Relaxed);
+    assert_eq!(owner.as_ptr().cast_const(), owner_address);
+    assert_eq!(DROPS.load(Ordering::Relaxed), 0);
+
+    // SAFETY: the owner is leaked, so it's valid (and never mutated) forever
+    let owner: &'static Tracked = unsafe { owner.as_ref() };
+    assert_eq!(owner.0, "leaked");
+    assert_eq!(DROPS.load(Ordering::Relaxed), 0);
+}
+
+// FFI libraries usually hold on to Rust objects as `void *` user data. The
+// pointer keeps its provenance through that round trip, so this passes under
+// `-Zmiri-strict-provenance` without exposing anything.
+#[test]
+fn forget_round_trips_through_user_data() {
+    let user_data: *mut c_void = Pair::new(Tracked(String::from("user data")))
+        .forget()
+        .as_ptr()
+        .cast();
+    LEAKED_USER_DATA.store(user_data, Ordering::Relaxed);
+
+    let user_data = LEAKED_USER_DATA.load(Ordering::Relaxed);
+    // SAFETY: the owner is leaked, so it's valid (and never mutated) forever
+    let owner: &'static Tracked = unsafe { &*user_data.cast::<Tracked>() };
+    assert_eq!(owner.0, "user data");
+}
+
+#[derive(Debug)]
+struct Settings(String);
+
+impl<'owner> HasDependent<'owner> for Settings {
+    type Dependent = Cell<&'owner str>;
+}
+
+impl Owner for Settings {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(Cell::new(self.0.lines().next().unwrap_or_default()))
+    }
+}
+
+thread_local! {
+    static CURRENT: (&'static Settings, &'static Cell<&'static str>) =
+        Pair::new(Settings(String::from("first\nsecond"))).leak();
+}
+
+// NOTE: Miri reports the intentional leaks (it only ignores memory reachable
+// from a static), so this is named `*_nomiri`
+#[test]
+fn leak_returns_static_references_nomiri() {
+    let (settings, line) = Pair::new(Settings(String::from("a\nb"))).leak();
+    assert_eq!(line.get(), "a");
+
+    // Even for an invariant dependent, `'static` is its real lifetime
+    line.set(settings.0.lines().nth(1).unwrap());
+    line.set("static str");
+    assert_eq!(line.get(), "static str");
+
+    let (_, current) = CURRENT.with(|&leaked| leaked);
+    assert_eq!(current.get(), "first");
+    current.set("replaced");
+    assert_eq!(CURRENT.with(|(_, line)| line.get()), "replaced");
+}
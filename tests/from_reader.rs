@@ -0,0 +1,105 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "std")]
+
+use std::{
+    error::Error,
+    io::{self, Read},
+    str::Utf8Error,
+};
+
+use pair::{Dependent, HasDependent, Owner, Pair, ReadError};
+
+#[repr(transparent)]
+struct Text([u8]);
+
+impl From<Box<[u8]>> for Box<Text> {
+    fn from(bytes: Box<[u8]>) -> Self {
+        // SAFETY: `Text` is a `#[repr(transparent)]` wrapper of `[u8]`
+        unsafe { Self::from_raw(Box::into_raw(bytes) as *mut Text) }
+    }
+}
+
+impl<'owner> HasDependent<'owner> for Text {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Text {
+    type Context<'a> = ();
+    type Error = Utf8Error;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(std::str::from_utf8(&self.0)?.lines().collect())
+    }
+}
+
+// Yields its bytes a few at a time, then fails (if `fail` is set)
+struct Chunked {
+    bytes: &'static [u8],
+    fail: bool,
+}
+
+impl Read for Chunked {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.bytes.is_empty() && self.fail {
+            return Err(io::Error::other("connection reset"));
+        }
+
+        let len = buf.len().min(self.bytes.len()).min(3);
+        buf[..len].copy_from_slice(&self.bytes[..len]);
+        self.bytes = &self.bytes[len..];
+        Ok(len)
+    }
+}
+
+#[test]
+fn from_reader() {
+    let reader = Chunked {
+        bytes: b"first\nsecond\nthird",
+        fail: false,
+    };
+    let Ok(pair) = Pair::<Text>::from_reader(reader) else {
+        panic!("reading should succeed");
+    };
+
+    assert_eq!(pair.owner().0.len(), 18);
+    assert_eq!(
+        pair.with_dependent(|lines| lines.join(",")),
+        "first,second,third"
+    );
+
+    // The dependent borrows from the owner's buffer, rather than copying it
+    let owner_range = pair.owner().0.as_ptr_range();
+    assert!(pair.with_dependent(|lines| owner_range.contains(&lines[1].as_ptr())));
+}
+
+#[test]
+fn io_error() {
+    let reader = Chunked {
+        bytes: b"partial",
+        fail: true,
+    };
+    let Err(err) = Pair::<Text>::from_reader(reader) else {
+        panic!("reading should fail");
+    };
+
+    assert!(matches!(&err, ReadError::Io(err) if err.to_string() == "connection reset"));
+    assert_eq!(err.to_string(), "failed to read the owner of a pair");
+    assert!(err.source().unwrap().is::<io::Error>());
+    assert!(format!("{err:?}").starts_with("Io("));
+}
+
+#[test]
+fn dependent_error() {
+    let reader: &[u8] = b"valid\n\xFF";
+    let Err(err) = Pair::<Text>::from_reader(reader) else {
+        panic!("constructing the dependent should fail");
+    };
+
+    assert!(matches!(&err, ReadError::Dependent(owner, _) if owner.0.len() == 7));
+    assert_eq!(
+        err.to_string(),
+        "failed to construct the dependent of a pair"
+    );
+    assert!(err.source().unwrap().is::<Utf8Error>());
+    assert!(format!("{err:?}").starts_with("Dependent("));
+}
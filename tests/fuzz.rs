@@ -0,0 +1,108 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "fuzz")]
+
+use std::{
+    cell::Cell,
+    convert::Infallible,
+    str::{self, Utf8Error},
+};
+
+use arbitrary::{Arbitrary, Unstructured};
+use pair::{
+    Dependent, HasDependent, Owner,
+    fuzz::{fuzz_owner, fuzz_owner_with, fuzz_owner_with_context},
+};
+
+#[derive(Debug)]
+struct Text(Vec<u8>);
+
+impl<'a> Arbitrary<'a> for Text {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary().map(Self)
+    }
+}
+
+impl<'owner> HasDependent<'owner> for Text {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Text {
+    type Context<'a> = ();
+    type Error = Utf8Error;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(str::from_utf8(&self.0)?.split_whitespace().collect())
+    }
+}
+
+const INPUTS: [&[u8]; 5] = [b"", b"hello world", b"\xff\xfe", b"\x00a b\x00", b" \n\t "];
+
+#[test]
+fn well_behaved_owner() {
+    for input in INPUTS {
+        fuzz_owner::<Text>(input);
+        fuzz_owner_with(input, |data| Some(Text(data.to_vec())));
+        fuzz_owner_with(input, |data| {
+            (!data.is_empty()).then(|| Text(data.to_vec()))
+        });
+    }
+}
+
+#[derive(Debug)]
+struct Limited(String);
+
+impl<'owner> HasDependent<'owner> for Limited {
+    type Dependent = &'owner str;
+}
+
+impl Owner for Limited {
+    type Context<'a> = usize;
+    type Error = Infallible;
+
+    fn make_dependent(&self, limit: usize) -> Result<Dependent<'_, Self>, Self::Error> {
+        let end = self
+            .0
+            .char_indices()
+            .nth(limit)
+            .map_or(self.0.len(), |(i, _)| i);
+        Ok(&self.0[..end])
+    }
+}
+
+#[test]
+fn owner_with_context() {
+    for input in INPUTS {
+        fuzz_owner_with_context(
+            input,
+            |data| Some(Limited(String::from_utf8_lossy(data).into_owned())),
+            || 3,
+        );
+    }
+}
+
+/// An owner whose `make_dependent` only succeeds the first time it's called.
+#[derive(Debug)]
+struct Flaky(Cell<bool>);
+
+impl HasDependent<'_> for Flaky {
+    type Dependent = ();
+}
+
+impl Owner for Flaky {
+    type Context<'a> = ();
+    type Error = ();
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        if self.0.replace(true) {
+            Err(())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[test]
+#[should_panic = "make_dependent returned an error when rebuilding"]
+fn nondeterministic_owner() {
+    fuzz_owner_with(b"", |_| Some(Flaky(Cell::new(false))));
+}
@@ -0,0 +1,78 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(all(feature = "guard-pages", unix))]
+
+use std::convert::Infallible;
+
+use pair::{Dependent, GuardedBytes, HasDependent, Owner, Pair};
+
+// NOTE: MIRI can't map guard pages with `mmap` and `mprotect`, so every test
+// here is named `*_nomiri`
+
+// Divides the page size of any common platform
+const MIN_PAGE_SIZE: usize = 4096;
+
+#[derive(Debug)]
+struct Message(GuardedBytes);
+
+impl<'owner> HasDependent<'owner> for Message {
+    type Dependent = Vec<&'owner [u8]>;
+}
+
+impl Owner for Message {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.chunks(1000).collect())
+    }
+}
+
+fn bytes(len: usize) -> Vec<u8> {
+    (0..=u8::MAX).cycle().take(len).collect()
+}
+
+#[test]
+fn contents_end_at_page_boundary_nomiri() {
+    for len in [
+        1,
+        10,
+        MIN_PAGE_SIZE - 1,
+        MIN_PAGE_SIZE,
+        3 * MIN_PAGE_SIZE + 5,
+    ] {
+        let expected = bytes(len);
+        let guarded = GuardedBytes::new(&expected).unwrap();
+
+        assert_eq!(guarded.len(), len);
+        assert!(!guarded.is_empty());
+        assert_eq!(&*guarded, &expected[..]);
+        assert_eq!(guarded.as_ptr_range().end.addr() % MIN_PAGE_SIZE, 0);
+    }
+}
+
+#[test]
+fn empty_nomiri() {
+    let guarded = GuardedBytes::new(&[]).unwrap();
+
+    assert!(guarded.is_empty());
+    assert_eq!(guarded.as_slice(), b"");
+    assert_eq!(format!("{guarded:?}"), "GuardedBytes { len: 0, .. }");
+}
+
+#[test]
+fn pair_with_guarded_owner_nomiri() {
+    let pair = Pair::new(Message(GuardedBytes::new(&bytes(2500)).unwrap()));
+
+    assert_eq!(pair.dependent_len(), 3);
+    assert_eq!(pair.with_dependent(|chunks| chunks[2].len()), 500);
+    assert_eq!(
+        format!("{:?}", pair.owner()),
+        "Message(GuardedBytes { len: 2500, .. })"
+    );
+
+    // Owners can be moved between threads
+    let owner = std::thread::spawn(move || pair.into_owner())
+        .join()
+        .unwrap();
+    assert_eq!(owner.0.as_ref(), &bytes(2500)[..]);
+}
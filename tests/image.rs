@@ -0,0 +1,71 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "image-pnm")]
+
+use std::io::Cursor;
+
+use image::{ColorType, GrayImage, ImageError, ImageFormat, Luma, RgbaImage};
+use pair::{EncodedImage, EncodedImagePair, Pair};
+
+fn encode_pnm(image: &RgbaImage) -> Vec<u8> {
+    let mut encoded = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut encoded), ImageFormat::Pnm)
+        .unwrap();
+    encoded
+}
+
+#[test]
+fn decodes_pixels() {
+    let image = RgbaImage::from_fn(3, 5, |x, y| {
+        [x.try_into().unwrap(), y.try_into().unwrap(), 7, 255].into()
+    });
+    let pair: EncodedImagePair = Pair::try_new(EncodedImage::new(encode_pnm(&image))).unwrap();
+
+    pair.with_dependent(|decoded| {
+        assert_eq!(decoded.format(), ImageFormat::Pnm);
+        assert_eq!(decoded.dimensions(), (3, 5));
+        assert_eq!(decoded.color_type(), ColorType::Rgba8);
+        assert_eq!(decoded.pixels(), image.as_raw().as_slice());
+
+        assert_eq!(decoded.rows().len(), 5);
+        assert_eq!(
+            decoded.row(4),
+            Some([0, 4, 7, 255, 1, 4, 7, 255, 2, 4, 7, 255].as_slice())
+        );
+        assert_eq!(decoded.row(5), None);
+    });
+
+    // The original bytes are kept, and borrowed by the dependent
+    let encoded = pair.owner().bytes().as_ptr();
+    assert!(pair.with_dependent(|decoded| decoded.encoded().as_ptr() == encoded));
+}
+
+#[test]
+fn grayscale() {
+    let mut encoded = Vec::new();
+    GrayImage::from_pixel(2, 3, Luma([9]))
+        .write_to(&mut Cursor::new(&mut encoded), ImageFormat::Pnm)
+        .unwrap();
+    let pair: EncodedImagePair = Pair::try_new(EncodedImage::new(encoded)).unwrap();
+
+    // The pixels are decoded in the image's own color type
+    pair.with_dependent(|decoded| {
+        assert_eq!(decoded.color_type(), ColorType::L8);
+        assert_eq!(decoded.pixels(), [9; 6]);
+        assert!(decoded.rows().all(|row| row == [9, 9]));
+    });
+}
+
+#[test]
+fn invalid_image() {
+    let Err((owner, err)) = Pair::try_new(EncodedImage::new(b"not an image".as_slice())) else {
+        panic!("the bytes aren't an image");
+    };
+    assert!(matches!(err, ImageError::Unsupported(_)));
+    assert_eq!(&*owner.into_bytes(), b"not an image");
+
+    // A PNM header, followed by garbage
+    let mut encoded = encode_pnm(&RgbaImage::new(1, 1));
+    encoded.truncate(20);
+    assert!(Pair::try_new(EncodedImage::new(encoded)).is_err());
+}
@@ -0,0 +1,118 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    rc::Rc,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use pair::{Dependent, HasDependent, Owner, Pair};
+
+/// A word-frequency summary of a text, which doesn't borrow from it.
+#[derive(Debug)]
+struct Text(String);
+
+impl HasDependent<'_> for Text {
+    type Dependent = HashMap<String, usize>;
+}
+
+impl Owner for Text {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        let mut counts = HashMap::new();
+        for word in self.0.split_whitespace() {
+            *counts.entry(word.to_lowercase()).or_insert(0) += 1;
+        }
+        Ok(counts)
+    }
+}
+
+#[test]
+fn recover_both_halves() {
+    let pair = Pair::new(Text(String::from("The cat and the hat")));
+    let (text, counts) = pair.into_parts();
+
+    assert_eq!(text.0, "The cat and the hat");
+    assert_eq!(counts["the"], 2);
+    assert_eq!(counts.len(), 4);
+
+    let pair = Pair::new_from_box(Box::new(text));
+    let (text, counts) = pair.into_boxed_parts();
+    assert_eq!(text.0, "The cat and the hat");
+    assert_eq!(counts["hat"], 1);
+}
+
+static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+struct CountDrops;
+
+impl Drop for CountDrops {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// An owner whose dependent shares (rather than borrows) its data.
+struct Shared(Rc<str>, CountDrops);
+
+impl HasDependent<'_> for Shared {
+    type Dependent = (Rc<str>, CountDrops);
+}
+
+impl Owner for Shared {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok((Rc::clone(&self.0), CountDrops))
+    }
+}
+
+#[test]
+fn nothing_dropped_or_leaked() {
+    let shared: Rc<str> = Rc::from("shared");
+    let pair = Pair::new(Shared(Rc::clone(&shared), CountDrops));
+    assert_eq!(Rc::strong_count(&shared), 3);
+
+    let (owner, dependent) = pair.into_parts();
+    assert_eq!(DROPS.load(Ordering::Relaxed), 0);
+    assert_eq!(&*dependent.0, "shared");
+
+    drop(owner);
+    assert_eq!(DROPS.load(Ordering::Relaxed), 1);
+    drop(dependent);
+    assert_eq!(DROPS.load(Ordering::Relaxed), 2);
+    assert_eq!(Rc::strong_count(&shared), 1);
+}
+
+/// An owner which shares its data, and whose dependent is only its length.
+struct Source(Rc<str>);
+
+impl HasDependent<'_> for Source {
+    type Dependent = usize;
+}
+
+impl Owner for Source {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.len())
+    }
+}
+
+#[test]
+fn into_dependent_drops_owner() {
+    let shared: Rc<str> = Rc::from("shared");
+    let pair = Pair::new(Source(Rc::clone(&shared)));
+    assert_eq!(Rc::strong_count(&shared), 2);
+
+    assert_eq!(pair.into_dependent(), 6);
+    assert_eq!(Rc::strong_count(&shared), 1);
+
+    let counts = Pair::new(Text(String::from("a b a"))).into_dependent();
+    assert_eq!(counts["a"], 2);
+}
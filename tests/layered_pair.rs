@@ -0,0 +1,139 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{convert::Infallible, sync::Arc};
+
+use pair::{Dependent, HasDependent, LayeredPair, Overlay, Owner, Pair};
+
+#[derive(Debug)]
+struct Base(String);
+
+impl<'owner> HasDependent<'owner> for Base {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Base {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split_whitespace().collect())
+    }
+}
+
+// Words from the base, filtered by a prefix stored in the overlay, followed by
+// the overlay's own words
+#[derive(Debug)]
+struct Filter {
+    prefix: String,
+    extra: String,
+}
+
+impl<'owner> HasDependent<'owner> for Filter {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Overlay<Base> for Filter {
+    type Context<'a> = usize;
+    type Error = String;
+
+    fn make_dependent<'owner>(
+        &'owner self,
+        base: &'owner Base,
+        limit: usize,
+    ) -> Result<Dependent<'owner, Self>, Self::Error> {
+        let words: Vec<&str> = base
+            .0
+            .split_whitespace()
+            .filter(|word| word.starts_with(&self.prefix))
+            .chain(self.extra.split_whitespace())
+            .collect();
+
+        if words.len() > limit {
+            return Err(format!("{} words is more than {limit}", words.len()));
+        }
+
+        Ok(words)
+    }
+}
+
+fn filter(prefix: &str, extra: &str) -> Filter {
+    Filter {
+        prefix: String::from(prefix),
+        extra: String::from(extra),
+    }
+}
+
+#[test]
+fn layered_over_base() {
+    let base = Arc::new(Pair::new(Base(String::from(
+        "apple avocado banana apricot",
+    ))));
+
+    let mut layered =
+        LayeredPair::try_new_with_context(Arc::clone(&base), filter("a", "cherry"), 10).unwrap();
+    assert_eq!(
+        layered.with_dependent(|words| words.join(" ")),
+        "apple avocado apricot cherry"
+    );
+    assert_eq!(layered.overlay().prefix, "a");
+    assert!(Arc::ptr_eq(layered.base(), &base));
+
+    layered.with_dependent_mut(|words| words.retain(|word| word.len() == 5));
+    assert_eq!(layered.with_dependent(|words| words.join(" ")), "apple");
+
+    // The layered pair keeps the base alive
+    assert_eq!(Arc::strong_count(&base), 2);
+    drop(base);
+    assert_eq!(layered.with_dependent(|words| words[0].len()), 5);
+
+    let (base, overlay) = layered.into_parts();
+    assert_eq!(Arc::strong_count(&base), 1);
+    assert_eq!(overlay.extra, "cherry");
+    assert_eq!(
+        base.with_dependent(|words| words.join(" ")),
+        "apple avocado banana apricot"
+    );
+}
+
+#[test]
+fn many_overlays_share_a_base() {
+    let base = Arc::new(Pair::new(Base(String::from("one two three"))));
+
+    let overlays: Vec<_> = ["o", "t"]
+        .into_iter()
+        .map(|prefix| {
+            LayeredPair::try_new_with_context(Arc::clone(&base), filter(prefix, ""), 10).unwrap()
+        })
+        .collect();
+    assert_eq!(Arc::strong_count(&base), 3);
+
+    assert_eq!(overlays[0].with_dependent(|words| words.join(" ")), "one");
+    assert_eq!(
+        overlays[1].with_dependent(|words| words.join(" ")),
+        "two three"
+    );
+}
+
+#[test]
+fn failure_returns_parts() {
+    let base = Arc::new(Pair::new(Base(String::from("a b c"))));
+
+    let Err((returned, overlay, err)) =
+        LayeredPair::try_new_with_context(Arc::clone(&base), filter("", "d"), 3)
+    else {
+        panic!("construction should fail");
+    };
+    assert!(Arc::ptr_eq(&returned, &base));
+    assert_eq!(overlay.extra, "d");
+    assert_eq!(err, "4 words is more than 3");
+}
+
+#[test]
+fn debug() {
+    let base = Arc::new(Pair::new(Base(String::from("x"))));
+    let layered = LayeredPair::try_new_with_context(base, filter("", ""), 1).unwrap();
+
+    let debug = format!("{layered:?}");
+    assert!(debug.starts_with("LayeredPair"));
+    assert!(debug.contains("dependent: [\"x\"]"));
+}
@@ -0,0 +1,58 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "leak-check")]
+
+use std::{convert::Infallible, mem::ManuallyDrop};
+
+use pair::{Dependent, HasDependent, Owner, Pair, dump_live_pairs};
+
+#[derive(Debug)]
+struct LeakCheckOwner(String);
+
+impl<'owner> HasDependent<'owner> for LeakCheckOwner {
+    type Dependent = &'owner str;
+}
+
+impl Owner for LeakCheckOwner {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): Self::Context<'_>) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(&self.0)
+    }
+}
+
+fn live_lines() -> Vec<u32> {
+    dump_live_pairs()
+        .into_iter()
+        .filter(|live| live.owner_type_name().ends_with("LeakCheckOwner"))
+        .map(|live| {
+            assert!(live.location().file().ends_with("leak_check.rs"));
+            live.location().line()
+        })
+        .collect()
+}
+
+#[test]
+fn live_pairs_tracked() {
+    assert_eq!(live_lines(), [] as [u32; 0]);
+
+    let line = line!() + 1;
+    let pair1 = Pair::new(LeakCheckOwner(String::from("one")));
+    let pair2 = Pair::new_from_box(Box::new(LeakCheckOwner(String::from("two"))));
+    let pair3 = Pair::try_new(LeakCheckOwner(String::from("three"))).unwrap();
+    assert_eq!(live_lines(), [line, line + 1, line + 2]);
+
+    drop(pair2);
+    assert_eq!(live_lines(), [line, line + 2]);
+
+    let _owner = pair1.into_owner();
+    assert_eq!(live_lines(), [line + 2]);
+
+    // Intentionally leaked pairs are still reported
+    let pair3 = ManuallyDrop::new(pair3);
+    assert_eq!(live_lines(), [line + 2]);
+
+    // (but we'll clean it up anyways, to keep MIRI happy)
+    drop(ManuallyDrop::into_inner(pair3));
+    assert_eq!(live_lines(), [] as [u32; 0]);
+}
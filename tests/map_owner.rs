@@ -0,0 +1,81 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{convert::Infallible, num::ParseIntError};
+
+use pair::{Dependent, HasDependent, Owner, Pair};
+
+#[derive(Debug)]
+struct Buffer(Vec<u8>);
+
+impl<'owner> HasDependent<'owner> for Buffer {
+    type Dependent = &'owner [u8];
+}
+
+impl Owner for Buffer {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.trim_ascii())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Numbers(String);
+
+impl<'owner> HasDependent<'owner> for Numbers {
+    type Dependent = Vec<(&'owner str, u32)>;
+}
+
+impl Owner for Numbers {
+    type Context<'a> = char;
+    type Error = ParseIntError;
+
+    fn make_dependent(&self, separator: char) -> Result<Dependent<'_, Self>, Self::Error> {
+        self.0
+            .split(separator)
+            .map(|number| Ok((number, number.parse()?)))
+            .collect()
+    }
+}
+
+fn normalize(Buffer(bytes): Buffer) -> Numbers {
+    Numbers(String::from_utf8(bytes).unwrap().trim().to_owned())
+}
+
+#[test]
+fn map_to_different_owner() {
+    let pair = Pair::new(Buffer(b"  1;2;3 \n".to_vec()));
+    assert_eq!(pair.with_dependent(|bytes| *bytes), b"1;2;3");
+
+    let pair = pair.try_map_owner_with_context(';', normalize).unwrap();
+    assert_eq!(pair.owner(), &Numbers(String::from("1;2;3")));
+    assert_eq!(
+        pair.with_dependent(|numbers| numbers.iter().map(|&(_, n)| n).sum::<u32>()),
+        6
+    );
+}
+
+#[test]
+fn map_to_failing_owner() {
+    let pair = Pair::new(Buffer(b"1,x".to_vec()));
+
+    let (owner, _) = pair.try_map_owner_with_context(',', normalize).unwrap_err();
+    assert_eq!(owner, Numbers(String::from("1,x")));
+}
+
+#[test]
+fn map_to_same_owner_type() {
+    let pair = Pair::new(Buffer(b" a ".to_vec()));
+
+    let pair = pair.map_owner(|Buffer(mut bytes)| {
+        bytes.extend_from_slice(b"b ");
+        Buffer(bytes)
+    });
+    assert_eq!(pair.with_dependent(|bytes| *bytes), b"a b");
+
+    let pair = pair
+        .try_map_owner(|Buffer(bytes)| Buffer(bytes.to_ascii_uppercase()))
+        .unwrap();
+    assert_eq!(pair.with_dependent(|bytes| *bytes), b"A B");
+}
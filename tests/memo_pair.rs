@@ -0,0 +1,96 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{
+    cell::Cell,
+    collections::HashSet,
+    hash::{BuildHasher, RandomState},
+};
+
+use pair::{Dependent, HasDependent, MemoPair, Owner, Pair};
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct Csv(String);
+
+impl<'owner> HasDependent<'owner> for Csv {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Csv {
+    type Context<'a> = &'a Cell<usize>;
+    type Error = String;
+
+    fn make_dependent(&self, builds: &Cell<usize>) -> Result<Dependent<'_, Self>, Self::Error> {
+        builds.set(builds.get() + 1);
+        if self.0.is_empty() {
+            return Err(String::from("empty"));
+        }
+        Ok(self.0.split(',').collect())
+    }
+}
+
+fn csv(s: &str) -> Csv {
+    Csv(String::from(s))
+}
+
+#[test]
+fn reuse_when_owner_unchanged() {
+    let builds = Cell::new(0);
+
+    let first = MemoPair::try_reuse_or_new_with_context(None, csv("a,b"), &builds).unwrap();
+    assert_eq!(builds.get(), 1);
+
+    let second =
+        MemoPair::try_reuse_or_new_with_context(Some(&first), csv("a,b"), &builds).unwrap();
+    assert_eq!(builds.get(), 1);
+    assert!(MemoPair::ptr_eq(&first, &second));
+
+    let third =
+        MemoPair::try_reuse_or_new_with_context(Some(&second), csv("a,b,c"), &builds).unwrap();
+    assert_eq!(builds.get(), 2);
+    assert!(!MemoPair::ptr_eq(&second, &third));
+    assert_eq!(third.dependent_len(), 3);
+    assert_ne!(second, third);
+}
+
+#[test]
+fn make_dependent_error() {
+    let builds = Cell::new(0);
+    let first = MemoPair::try_reuse_or_new_with_context(None, csv("a"), &builds).unwrap();
+
+    let (owner, err) =
+        MemoPair::try_reuse_or_new_with_context(Some(&first), csv(""), &builds).unwrap_err();
+    assert_eq!(owner, csv(""));
+    assert_eq!(err, "empty");
+}
+
+#[test]
+fn equality_and_hash_use_owner() {
+    let builds = Cell::new(0);
+    let a = MemoPair::new(Pair::try_new_with_context(csv("x,y"), &builds).unwrap());
+    let b = MemoPair::new(Pair::try_new_with_context(csv("x,y"), &builds).unwrap());
+    let c = MemoPair::from(Pair::try_new_with_context(csv("z"), &builds).unwrap());
+
+    assert!(!MemoPair::ptr_eq(&a, &b));
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+
+    let state = RandomState::new();
+    assert_eq!(state.hash_one(&a), state.hash_one(&b));
+    assert_eq!(state.hash_one(&a), state.hash_one(csv("x,y")));
+
+    let set: HashSet<_> = [a.clone(), b, c].into_iter().collect();
+    assert_eq!(set.len(), 2);
+    assert_eq!(format!("{a:?}"), r#"MemoPair(Csv("x,y"))"#);
+}
+
+#[test]
+fn try_unwrap() {
+    let builds = Cell::new(0);
+    let memo = MemoPair::new(Pair::try_new_with_context(csv("a"), &builds).unwrap());
+    let clone = memo.clone();
+
+    let memo = MemoPair::try_unwrap(memo).unwrap_err();
+    drop(clone);
+    let pair = MemoPair::try_unwrap(memo).unwrap();
+    assert_eq!(pair.into_owner(), csv("a"));
+}
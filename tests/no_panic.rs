@@ -0,0 +1,37 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "panic-free")]
+
+use std::convert::Infallible;
+
+use pair::{Dependent, HasDependent, Owner, Pair};
+
+#[derive(Debug)]
+struct Words(String);
+
+impl<'owner> HasDependent<'owner> for Words {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Words {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split_whitespace().collect())
+    }
+}
+
+// In optimized builds, this test failing to link means an accessor may panic.
+#[test]
+fn accessors_link() {
+    let mut pair = Pair::new(Words(String::from("one two three")));
+
+    assert_eq!(pair.owner().0, "one two three");
+    assert!(pair.with_dependent(|words| words == &["one", "two", "three"]));
+
+    pair.with_dependent_mut(|words| words.retain(|word| word.starts_with('t')));
+    assert_eq!(pair.with_dependent(|words| words.join(" ")), "two three");
+
+    let total = pair.with_both_mut(|owner, words| owner.0.len() + words.len());
+    assert_eq!(total, 15);
+}
@@ -0,0 +1,113 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{
+    cell::RefCell,
+    convert::Infallible,
+    panic::{AssertUnwindSafe, catch_unwind},
+    rc::Rc,
+};
+
+use pair::{Dependent, HasDependent, Owner, Pair};
+
+#[derive(Debug)]
+struct Tracked {
+    name: &'static str,
+    log: Rc<RefCell<Vec<String>>>,
+    panic_in_dependent_drop: bool,
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.log
+            .borrow_mut()
+            .push(format!("drop owner {}", self.name));
+    }
+}
+
+#[derive(Debug)]
+struct TrackedDependent<'owner>(&'owner Tracked);
+
+impl Drop for TrackedDependent<'_> {
+    fn drop(&mut self) {
+        self.0.log.borrow_mut().push(String::from("drop dependent"));
+        assert!(!self.0.panic_in_dependent_drop, "dependent drop panicked");
+    }
+}
+
+impl<'owner> HasDependent<'owner> for Tracked {
+    type Dependent = TrackedDependent<'owner>;
+}
+
+impl Owner for Tracked {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(TrackedDependent(self))
+    }
+}
+
+fn tracked(name: &'static str, log: &Rc<RefCell<Vec<String>>>) -> Tracked {
+    Tracked {
+        name,
+        log: Rc::clone(log),
+        panic_in_dependent_drop: false,
+    }
+}
+
+#[test]
+fn hook_receives_owner_after_dependent() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let handed_off = Rc::new(RefCell::new(Vec::new()));
+
+    let pair = Pair::new(tracked("a", &log)).on_drop({
+        let log = Rc::clone(&log);
+        let handed_off = Rc::clone(&handed_off);
+        move |owner| {
+            log.borrow_mut().push(format!("hook {}", owner.name));
+            handed_off.borrow_mut().push(owner);
+        }
+    });
+    assert_eq!(pair.with_dependent(|dependent| dependent.0.name), "a");
+    assert_eq!(pair.owner().name, "a");
+
+    drop(pair);
+    assert_eq!(*log.borrow(), ["drop dependent", "hook a"]);
+
+    // The owner is only dropped once the hook's recipient drops it
+    handed_off.borrow_mut().clear();
+    assert_eq!(*log.borrow(), ["drop dependent", "hook a", "drop owner a"]);
+}
+
+#[test]
+fn deref_mut() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut pair = Pair::new(tracked("a", &log)).on_drop(|owner| assert_eq!(owner.name, "a"));
+
+    pair.with_dependent_mut(|dependent| assert_eq!(dependent.0.name, "a"));
+    assert!(format!("{pair:?}").starts_with("OnDropPair"));
+}
+
+#[test]
+fn into_pair_disarms_hook() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    let pair = Pair::new(tracked("a", &log))
+        .on_drop(|_| panic!("the hook should not be called"))
+        .into_pair();
+    drop(pair);
+
+    assert_eq!(*log.borrow(), ["drop dependent", "drop owner a"]);
+}
+
+#[test]
+fn dependent_drop_panic_skips_hook() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut owner = tracked("a", &log);
+    owner.panic_in_dependent_drop = true;
+
+    let pair = Pair::new(owner).on_drop(|_| panic!("the hook should not be called"));
+    catch_unwind(AssertUnwindSafe(|| drop(pair))).unwrap_err();
+
+    assert_eq!(*log.borrow(), ["drop dependent", "drop owner a"]);
+}
@@ -0,0 +1,70 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{cell::Cell, convert::Infallible};
+
+use pair::{Dependent, HasDependent, OptionPairExt, Owner, Pair};
+
+#[derive(Debug)]
+struct Counted {
+    text: String,
+    builds: Cell<u32>,
+}
+
+impl<'owner> HasDependent<'owner> for Counted {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Counted {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        self.builds.set(self.builds.get() + 1);
+        Ok(self.text.split_whitespace().collect())
+    }
+}
+
+fn counted(text: &str) -> Counted {
+    Counted {
+        text: String::from(text),
+        builds: Cell::new(0),
+    }
+}
+
+#[test]
+fn get_or_create() {
+    let mut slot: Option<Pair<Counted>> = None;
+
+    let pair = slot.get_or_create(|| counted("a b"));
+    assert_eq!(pair.with_dependent(|words| words.join(",")), "a,b");
+
+    // An existing pair is reused, and the closure isn't called
+    let pair = slot.get_or_create(|| panic!("the pair should already exist"));
+    assert_eq!(pair.owner().builds.get(), 1);
+}
+
+#[test]
+fn rebuild_if() {
+    let mut slot: Option<Pair<Counted>> = None;
+    assert!(!slot.rebuild_if(|_| panic!("there is no pair to check")));
+
+    slot.get_or_create(|| counted("a b"))
+        .with_dependent_mut(|words| words.truncate(0));
+
+    assert!(!slot.rebuild_if(|pair| !pair.dependent_is_empty()));
+    assert!(slot.rebuild_if(Pair::dependent_is_empty));
+
+    let pair = slot.as_ref().unwrap();
+    assert_eq!(pair.with_dependent(|words| words.join(",")), "a,b");
+    assert_eq!(pair.owner().builds.get(), 2);
+}
+
+#[test]
+fn take_owner() {
+    let mut slot: Option<Pair<Counted>> = None;
+    assert!(slot.take_owner().is_none());
+
+    slot.get_or_create(|| counted("a b"));
+    assert_eq!(slot.take_owner().unwrap().text, "a b");
+    assert!(slot.is_none());
+}
@@ -0,0 +1,97 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "proptest")]
+
+use std::{cell::Cell, convert::Infallible, num::ParseIntError};
+
+use pair::{
+    Dependent, HasDependent, Owner,
+    testing::{check_owner_laws, check_owner_laws_with_context},
+};
+use proptest::prelude::*;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Numbers(String);
+
+impl HasDependent<'_> for Numbers {
+    type Dependent = Vec<u32>;
+}
+
+impl Owner for Numbers {
+    type Context<'a> = ();
+    type Error = ParseIntError;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        self.0.split(',').map(str::parse).collect()
+    }
+}
+
+#[test]
+fn lawful_owner() {
+    check_owner_laws("[0-9,]{0,20}".prop_map(Numbers));
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Words(String);
+
+impl<'owner> HasDependent<'owner> for Words {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Words {
+    type Context<'a> = char;
+    type Error = Infallible;
+
+    fn make_dependent(&self, separator: char) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split(separator).collect())
+    }
+}
+
+#[test]
+fn lawful_owner_with_context() {
+    check_owner_laws_with_context("[a-z ]{0,20}".prop_map(Words), || ' ');
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Counter(Cell<u32>);
+
+impl HasDependent<'_> for Counter {
+    type Dependent = u32;
+}
+
+impl Owner for Counter {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        self.0.set(self.0.get() + 1);
+        Ok(self.0.get())
+    }
+}
+
+#[test]
+#[should_panic = "make_dependent returned different dependents for equal contexts"]
+fn nondeterministic_owner() {
+    check_owner_laws(any::<u16>().prop_map(|n| Counter(Cell::new(u32::from(n)))));
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Panicky(u8);
+
+impl HasDependent<'_> for Panicky {
+    type Dependent = u8;
+}
+
+impl Owner for Panicky {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.checked_mul(2).expect("overflow"))
+    }
+}
+
+#[test]
+#[should_panic = "an Owner law was violated"]
+fn panicking_owner() {
+    check_owner_laws(any::<u8>().prop_map(Panicky));
+}
@@ -0,0 +1,97 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "std")]
+
+use std::{convert::Infallible, thread::ThreadId};
+
+use pair::{Dependent, HasDependent, Owner, Pair};
+
+struct Numbers(String);
+
+impl<'owner> HasDependent<'owner> for Numbers {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Numbers {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split(',').collect())
+    }
+}
+
+fn numbers(count: usize) -> Pair<Numbers> {
+    let text: Vec<String> = (0..count).map(|n| n.to_string()).collect();
+    Pair::new(Numbers(text.join(",")))
+}
+
+#[test]
+fn chunks_are_ordered() {
+    let pair = numbers(10);
+
+    let chunks = pair.par_with_dependent_chunks(3, |chunk| chunk.join(","));
+    assert_eq!(chunks, ["0,1,2,3", "4,5,6,7", "8,9"]);
+
+    let chunks = pair.par_with_dependent_chunks(100, |chunk| chunk.join(","));
+    assert_eq!(chunks, ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"]);
+}
+
+#[test]
+fn chunks_run_on_threads() {
+    let pair = numbers(4);
+
+    let threads: Vec<ThreadId> = pair.par_with_dependent_chunks(2, |_| std::thread::current().id());
+    assert_eq!(threads.len(), 2);
+    assert_ne!(threads[0], threads[1]);
+
+    // A single chunk runs on the current thread
+    let threads = pair.par_with_dependent_chunks(1, |_| std::thread::current().id());
+    assert_eq!(threads, [std::thread::current().id()]);
+}
+
+#[test]
+fn chunks_mut() {
+    let mut pair = numbers(9);
+
+    let lens = pair.par_with_dependent_chunks_mut(3, |chunk| {
+        chunk.reverse();
+        chunk.len()
+    });
+    assert_eq!(lens, [3, 3, 3]);
+    assert_eq!(
+        pair.with_dependent(|numbers| numbers.join(",")),
+        "2,1,0,5,4,3,8,7,6"
+    );
+}
+
+#[test]
+fn empty_dependent() {
+    let pair = Pair::new(Numbers(String::new()));
+    assert_eq!(
+        pair.par_with_dependent_chunks(4, |chunk| chunk.join(",")),
+        [""]
+    );
+
+    let mut pair = pair;
+    pair.with_dependent_mut(|numbers| numbers.truncate(0));
+    assert!(
+        pair.par_with_dependent_chunks(4, |chunk| chunk.join(","))
+            .is_empty()
+    );
+}
+
+#[test]
+#[should_panic = "boom"]
+fn panics_are_propagated() {
+    let pair = numbers(8);
+
+    pair.par_with_dependent_chunks(4, |chunk| assert!(!chunk.contains(&"5"), "boom"));
+}
+
+#[test]
+#[should_panic = "the number of chunks must be non-zero"]
+fn zero_chunks() {
+    let pair = numbers(8);
+
+    pair.par_with_dependent_chunks(0, |chunk| chunk.join(","));
+}
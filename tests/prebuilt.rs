@@ -0,0 +1,91 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{cell::Cell, convert::Infallible, ops::Range};
+
+use pair::{Dependent, HasDependent, Owner, Pair};
+
+#[derive(Debug, PartialEq)]
+struct Lines(String);
+
+impl<'owner> HasDependent<'owner> for Lines {
+    type Dependent = Vec<&'owner str>;
+}
+
+thread_local! {
+    static MAKE_DEPENDENT_CALLS: Cell<usize> = const { Cell::new(0) };
+}
+
+impl Owner for Lines {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        MAKE_DEPENDENT_CALLS.with(|calls| calls.set(calls.get() + 1));
+        Ok(self.0.lines().collect())
+    }
+}
+
+/// Computes the range of every line, without borrowing from `text`.
+fn line_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut start = 0;
+    text.lines()
+        .map(|line| {
+            let range = start..start + line.len();
+            start = range.end + 1;
+            range
+        })
+        .collect()
+}
+
+#[test]
+fn adopts_without_make_dependent() {
+    let text = String::from("first\nsecond\nthird");
+    let ranges = line_ranges(&text);
+
+    let pair = Pair::new_with_prebuilt(Lines(text), ranges, |lines, ranges| {
+        ranges.into_iter().map(|range| &lines.0[range]).collect()
+    });
+    assert_eq!(
+        pair.with_dependent(|lines| lines.join(" ")),
+        "first second third"
+    );
+    assert_eq!(MAKE_DEPENDENT_CALLS.with(Cell::get), 0);
+
+    let pair = Pair::new_from_box_with_prebuilt(Box::new(Lines(String::from("x"))), (), |_, ()| {
+        vec!["adopted"]
+    });
+    assert_eq!(pair.with_dependent(|lines| lines[0]), "adopted");
+    assert_eq!(MAKE_DEPENDENT_CALLS.with(Cell::get), 0);
+}
+
+#[test]
+fn adopt_error_returns_owner() {
+    let stale_ranges = vec![0..1, 2..100];
+
+    let (owner, err) = Pair::try_new_with_prebuilt(
+        Lines(String::from("short")),
+        stale_ranges,
+        |lines, ranges| {
+            ranges
+                .into_iter()
+                .map(|range| lines.0.get(range).ok_or("stale range"))
+                .collect()
+        },
+    )
+    .unwrap_err();
+    assert_eq!(owner, Lines(String::from("short")));
+    assert_eq!(err, "stale range");
+
+    let pair = Pair::try_new_from_box_with_prebuilt(
+        Box::new(owner),
+        line_ranges("short"),
+        |lines, ranges| {
+            ranges
+                .into_iter()
+                .map(|range| lines.0.get(range).ok_or("stale range"))
+                .collect()
+        },
+    )
+    .unwrap();
+    assert_eq!(pair.with_dependent(|lines| lines[0]), "short");
+}
@@ -0,0 +1,97 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::convert::Infallible;
+
+use pair::{Dependent, HasDependent, Owner, Pair, Progress};
+
+struct Lines(String);
+
+impl<'owner> HasDependent<'owner> for Lines {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Lines {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): Self::Context<'_>) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.lines().collect())
+    }
+
+    fn make_dependent_with_progress<'owner>(
+        &'owner self,
+        (): Self::Context<'_>,
+        progress: &mut dyn FnMut(Progress),
+    ) -> Result<Dependent<'owner, Self>, Self::Error> {
+        let total = Some(self.0.len() as u64);
+        let mut lines = Vec::new();
+        let mut completed = 0;
+        progress(Progress::new(0, total));
+        for line in self.0.split_inclusive('\n') {
+            completed += line.len() as u64;
+            lines.push(line.trim_end_matches('\n'));
+            progress(Progress::new(completed, total));
+        }
+
+        Ok(lines)
+    }
+}
+
+#[test]
+fn progress_reported() {
+    let mut reports = Vec::new();
+    let pair = Pair::new_with_progress(Lines(String::from("ab\ncd\nefgh\n")), (), |progress| {
+        reports.push(progress);
+    });
+
+    assert_eq!(pair.with_dependent(|dep| dep), &["ab", "cd", "efgh"]);
+    let completed: Vec<u64> = reports.iter().map(Progress::completed).collect();
+    assert_eq!(completed, [0, 3, 6, 11]);
+    assert!(reports.iter().all(|progress| progress.total() == Some(11)));
+    assert_eq!(reports.last().unwrap().fraction(), Some(1.0));
+}
+
+#[derive(Debug)]
+struct NoProgress(String);
+
+impl<'owner> HasDependent<'owner> for NoProgress {
+    type Dependent = &'owner str;
+}
+
+impl Owner for NoProgress {
+    type Context<'a> = ();
+    type Error = String;
+
+    fn make_dependent(&self, (): Self::Context<'_>) -> Result<Dependent<'_, Self>, Self::Error> {
+        self.0
+            .strip_prefix('#')
+            .ok_or_else(|| String::from("no prefix"))
+    }
+}
+
+#[test]
+fn progress_default_impl() {
+    let mut reports = 0;
+    let pair =
+        Pair::try_new_from_box_with_progress(Box::new(NoProgress(String::from("#hi"))), (), |_| {
+            reports += 1;
+        })
+        .unwrap();
+    assert_eq!(pair.with_dependent(|dep| *dep), "hi");
+
+    let (owner, err) =
+        Pair::try_new_with_progress(NoProgress(String::from("hi")), (), |_| reports += 1)
+            .err()
+            .unwrap();
+    assert_eq!(owner.0, "hi");
+    assert_eq!(err, "no prefix");
+
+    assert_eq!(reports, 0);
+}
+
+#[test]
+fn progress_fraction() {
+    assert_eq!(Progress::new(1, Some(4)).fraction(), Some(0.25));
+    assert_eq!(Progress::new(1, Some(0)).fraction(), None);
+    assert_eq!(Progress::new(1, None).fraction(), None);
+}
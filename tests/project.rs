@@ -0,0 +1,58 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use pair::{Pair, Project, Projected};
+
+#[derive(Debug)]
+struct Document {
+    title: String,
+    tags: Vec<String>,
+}
+
+impl Project<str> for Document {
+    fn project(&self) -> &str {
+        &self.title
+    }
+}
+
+impl Project<[String]> for Document {
+    fn project(&self) -> &[String] {
+        &self.tags
+    }
+}
+
+fn document() -> Document {
+    Document {
+        title: String::from("Notes"),
+        tags: vec![String::from("a"), String::from("b")],
+    }
+}
+
+#[test]
+fn project_user_type() {
+    let pair = Pair::new(Projected::<_, str>::new(document()));
+    assert_eq!(pair.with_dependent(|title| title.len()), 5);
+    assert_eq!(&**pair.dependent_ref(), "Notes");
+
+    let pair = Pair::new(Projected::<_, [String]>::new(document()));
+    assert_eq!(pair.dependent_ref().len(), 2);
+    assert_eq!(
+        format!("{pair:?}"),
+        r#"Pair { owner: Projected(Document { title: "Notes", tags: ["a", "b"] }), dependent: ["a", "b"] }"#
+    );
+
+    let document = pair.into_owner().into_inner();
+    assert_eq!(document.title, "Notes");
+}
+
+#[test]
+fn project_std_types() {
+    let pair = Pair::new(Projected::<_, str>::new(String::from("text")));
+    assert_eq!(*pair.dependent_ref(), "text");
+    assert_eq!(pair.owner().get(), "text");
+
+    let pair = Pair::new(Projected::<_, [u8]>::new(vec![1, 2, 3]));
+    assert_eq!(*pair.dependent_ref(), [1, 2, 3]);
+
+    let pair = Pair::new(Projected::<Box<u32>, u32>::new(Box::new(7)));
+    assert_eq!(**pair.dependent_ref(), 7);
+}
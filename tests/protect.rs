@@ -0,0 +1,118 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(all(feature = "mprotect", unix))]
+
+use std::convert::Infallible;
+
+use pair::{AlignedVec, Dependent, HasDependent, Owner, Pair};
+
+// NOTE: MIRI can't call `mprotect`, so every test here is named `*_nomiri`
+
+// Larger than the page size of any common platform
+const PAGE_ALIGN: usize = 1 << 16;
+
+#[derive(Debug)]
+struct Buffer(AlignedVec<PAGE_ALIGN>);
+
+impl AsRef<[u8]> for Buffer {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<'owner> HasDependent<'owner> for Buffer {
+    type Dependent = &'owner [u8];
+}
+
+impl Owner for Buffer {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(&self.0[1..])
+    }
+}
+
+fn buffer(len: usize) -> Buffer {
+    let mut bytes = AlignedVec::with_capacity(len);
+    bytes.extend((0..=u8::MAX).cycle().take(len));
+    Buffer(bytes)
+}
+
+#[test]
+fn protect_and_unprotect_nomiri() {
+    let pair = Pair::new(buffer(PAGE_ALIGN * 2 + 10));
+
+    // SAFETY: the bytes are exclusively owned by the `AlignedVec`
+    let protected = unsafe { pair.protect_owner_readonly() }.unwrap();
+
+    // The trailing partial page isn't protected
+    assert_eq!(protected.protected_len() % PAGE_ALIGN, 0);
+    assert!(protected.protected_len() <= PAGE_ALIGN * 2);
+    assert!(protected.protected_len() > PAGE_ALIGN);
+
+    // Reads still work
+    assert_eq!(protected.with_dependent(|bytes| bytes[0]), 1);
+    assert_eq!(protected.owner().0.len(), PAGE_ALIGN * 2 + 10);
+
+    // The owner is writable again (and can be deallocated) once unprotected
+    let mut owner = protected.into_pair().into_owner();
+    owner.0[0] = 42;
+    owner.0.push(0);
+}
+
+#[test]
+fn drop_unprotects_nomiri() {
+    let pair = Pair::new(buffer(PAGE_ALIGN));
+
+    // SAFETY: the bytes are exclusively owned by the `AlignedVec`
+    let protected = unsafe { pair.protect_owner_readonly() }.unwrap();
+    assert!(protected.protected_len() > 0);
+
+    // Dropping deallocates the bytes, which would fault if they were still
+    // protected (the allocator may write to freed memory)
+    drop(protected);
+}
+
+#[test]
+fn small_owner_protects_nothing_nomiri() {
+    let pair = Pair::new(buffer(10));
+
+    // SAFETY: the bytes are exclusively owned by the `AlignedVec`
+    let protected = unsafe { pair.protect_owner_readonly() }.unwrap();
+    assert_eq!(protected.protected_len(), 0);
+    assert!(format!("{protected:?}").starts_with("ReadOnlyPair"));
+}
+
+#[derive(Debug)]
+struct Unaligned(AlignedVec<PAGE_ALIGN>);
+
+impl AsRef<[u8]> for Unaligned {
+    fn as_ref(&self) -> &[u8] {
+        &self.0[1..]
+    }
+}
+
+impl HasDependent<'_> for Unaligned {
+    type Dependent = ();
+}
+
+impl Owner for Unaligned {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(())
+    }
+}
+
+#[test]
+fn unaligned_owner_is_rejected_nomiri() {
+    let pair = Pair::new(Unaligned(buffer(PAGE_ALIGN * 2).0));
+
+    // SAFETY: the bytes are exclusively owned by the `AlignedVec`
+    let Err((pair, err)) = (unsafe { pair.protect_owner_readonly() }) else {
+        panic!("protecting an unaligned owner should fail");
+    };
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(pair.owner().0.len(), PAGE_ALIGN * 2);
+}
@@ -0,0 +1,123 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "prost")]
+
+use pair::{FieldValue, Pair, ProtobufFields, ProtobufMessage, ProtobufPair};
+use prost::encoding::{
+    WireType, bytes, encode_key, encode_varint, fixed32, fixed64, message, string, uint64,
+};
+
+fn encoded_message() -> Vec<u8> {
+    let mut encoded = Vec::new();
+    string::encode(1, &String::from("first"), &mut encoded);
+    uint64::encode(2, &u64::MAX, &mut encoded);
+    fixed32::encode(3, &0xDEAD_BEEF, &mut encoded);
+    fixed64::encode(4, &7, &mut encoded);
+    bytes::encode(5, &vec![0xFF, 0x00], &mut encoded);
+    // prost encodes a `String` message as a `google.protobuf.StringValue`
+    message::encode(6, &String::from("inner"), &mut encoded);
+    string::encode(1, &String::from("last"), &mut encoded);
+
+    // A group, with a nested field
+    encode_key(7, WireType::StartGroup, &mut encoded);
+    uint64::encode(1, &5, &mut encoded);
+    encode_key(7, WireType::EndGroup, &mut encoded);
+
+    encoded
+}
+
+#[test]
+fn fields() {
+    let Ok(pair): Result<ProtobufPair, _> = Pair::try_new(ProtobufMessage::new(encoded_message()))
+    else {
+        panic!("the message should be valid");
+    };
+
+    pair.with_dependent(|fields| {
+        // The last occurrence of a field takes precedence
+        assert_eq!(fields.string(1), Some(Ok("last")));
+        let all: Vec<_> = fields.get_all(1).collect();
+        assert_eq!(
+            all,
+            [
+                FieldValue::LengthDelimited(b"first"),
+                FieldValue::LengthDelimited(b"last"),
+            ]
+        );
+
+        assert_eq!(fields.varint(2), Some(u64::MAX));
+        assert_eq!(fields.get(3), Some(FieldValue::Fixed32(0xDEAD_BEEF)));
+        assert_eq!(fields.get(4), Some(FieldValue::Fixed64(7)));
+        assert_eq!(fields.bytes(5), Some([0xFF, 0x00].as_slice()));
+        assert!(matches!(fields.string(5), Some(Err(_))));
+
+        // Wrong wire types and missing fields
+        assert_eq!(fields.varint(1), None);
+        assert_eq!(fields.bytes(2), None);
+        assert_eq!(fields.get(8), None);
+
+        let Some(Ok(inner)) = fields.message(6) else {
+            panic!("the embedded message should be valid");
+        };
+        assert_eq!(inner.string(1), Some(Ok("inner")));
+
+        let Some(FieldValue::Group(group)) = fields.get(7) else {
+            panic!("the group should be present");
+        };
+        let Ok(group) = ProtobufFields::parse(group) else {
+            panic!("the group's fields should be valid");
+        };
+        assert_eq!(group.varint(1), Some(5));
+
+        let tags: Vec<_> = fields.iter().map(|(tag, _)| tag).collect();
+        assert_eq!(tags, [1, 2, 3, 4, 5, 6, 1, 7]);
+    });
+}
+
+#[test]
+fn borrows_from_owner() {
+    let pair: ProtobufPair = Pair::try_new(ProtobufMessage::new(encoded_message())).unwrap();
+
+    let bytes = pair.owner().bytes().as_ptr_range();
+    let Some(Ok(last)) = pair.with_dependent(|fields| fields.string(1)) else {
+        panic!("the string field should be valid");
+    };
+    assert!(bytes.contains(&last.as_ptr()));
+}
+
+#[test]
+fn full_decode() {
+    let mut encoded = Vec::new();
+    string::encode(1, &String::from("whole"), &mut encoded);
+
+    let pair: ProtobufPair = Pair::try_new(ProtobufMessage::new(encoded)).unwrap();
+    let decoded: String = pair.owner().decode().unwrap();
+    assert_eq!(decoded, "whole");
+    assert_eq!(
+        pair.with_dependent(|fields| fields.string(1)),
+        Some(Ok("whole"))
+    );
+}
+
+#[test]
+fn invalid_framing() {
+    // A length-delimited field which claims more bytes than remain
+    let mut encoded = Vec::new();
+    encode_key(1, WireType::LengthDelimited, &mut encoded);
+    encode_varint(10, &mut encoded);
+    encoded.extend_from_slice(b"short");
+    assert!(Pair::try_new(ProtobufMessage::new(encoded)).is_err());
+
+    // An unmatched end of a group
+    let mut encoded = Vec::new();
+    encode_key(1, WireType::EndGroup, &mut encoded);
+    assert!(Pair::try_new(ProtobufMessage::new(encoded)).is_err());
+
+    // A group which is never ended
+    let mut encoded = Vec::new();
+    encode_key(1, WireType::StartGroup, &mut encoded);
+    uint64::encode(2, &3, &mut encoded);
+    let Err((owner, _)) = Pair::try_new(ProtobufMessage::new(encoded)) else {
+        panic!("the unterminated group should be invalid");
+    };
+    assert_eq!(owner.into_bytes().len(), 3);
+}
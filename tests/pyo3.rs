@@ -0,0 +1,105 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "pyo3")]
+
+use std::thread;
+
+use pair::{Dependent, HasDependent, Owner, Pair, PyPair};
+use pyo3::{IntoPyObjectExt, exceptions::PyValueError, prelude::*};
+
+// NOTE: MIRI can't run the Python interpreter, so every test here is named
+// `*_nomiri`
+
+struct Document(String);
+
+impl<'owner> HasDependent<'owner> for Document {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Document {
+    type Context<'a> = ();
+    type Error = PyErr;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        if self.0.is_empty() {
+            return Err(PyValueError::new_err("empty document"));
+        }
+
+        Ok(self.0.split_whitespace().collect())
+    }
+}
+
+#[pyclass(frozen)]
+struct PyDocument {
+    document: PyPair<Document>,
+}
+
+#[pymethods]
+impl PyDocument {
+    #[new]
+    fn new(py: Python<'_>, text: String) -> PyResult<Self> {
+        Ok(Self {
+            document: PyPair::try_new(py, Document(text))?,
+        })
+    }
+
+    fn words<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        self.document
+            .dependent_to_py(py, |py, words| words.into_bound_py_any(py))
+    }
+
+    fn count(&self, py: Python<'_>, word: &str) -> usize {
+        self.document.with_dependent_detached(py, |words| {
+            words.iter().filter(|&&other| other == word).count()
+        })
+    }
+}
+
+#[test]
+fn pyclass_nomiri() {
+    Python::initialize();
+    Python::attach(|py| {
+        let class = py.get_type::<PyDocument>();
+        let document = class.call1(("to be or not to be",)).unwrap();
+
+        let words: Vec<String> = document.call_method0("words").unwrap().extract().unwrap();
+        assert_eq!(words, ["to", "be", "or", "not", "to", "be"]);
+
+        let count: usize = document
+            .call_method1("count", ("be",))
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(count, 2);
+
+        // The owner's error is raised as a Python exception
+        let err = class.call1(("",)).unwrap_err();
+        assert!(err.is_instance_of::<PyValueError>(py));
+        assert_eq!(err.value(py).to_string(), "empty document");
+    });
+}
+
+#[test]
+fn detached_nomiri() {
+    Python::initialize();
+    let document = Python::attach(|py| {
+        PyPair::try_new(py, Document(String::from("released while parsing"))).unwrap()
+    });
+
+    // Other threads can attach to the interpreter while the GIL is released
+    let words = Python::attach(|py| {
+        document.with_dependent_detached(py, |words| {
+            thread::scope(|scope| {
+                scope
+                    .spawn(|| Python::attach(|py| py.version().len()))
+                    .join()
+                    .unwrap()
+            });
+            words.len()
+        })
+    });
+    assert_eq!(words, 3);
+
+    let pair: Pair<Document> = document.into_pair();
+    let document = PyPair::from(pair);
+    assert_eq!(document.owner().0, "released while parsing");
+}
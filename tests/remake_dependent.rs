@@ -0,0 +1,137 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{cell::Cell, convert::Infallible};
+
+use pair::{Dependent, HasDependent, Owner, Pair};
+
+struct Settings {
+    delimiter: char,
+    skip_empty: bool,
+}
+
+struct Line(String);
+
+impl<'owner> HasDependent<'owner> for Line {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Line {
+    type Context<'a> = &'a Settings;
+    type Error = Infallible;
+
+    fn make_dependent(&self, settings: &Settings) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self
+            .0
+            .split(settings.delimiter)
+            .filter(|field| !(settings.skip_empty && field.is_empty()))
+            .collect())
+    }
+}
+
+#[test]
+fn remake_with_changed_context() {
+    let mut settings = Settings {
+        delimiter: ',',
+        skip_empty: false,
+    };
+    let mut pair = Pair::new_with_context(Line(String::from("a,,b c")), &settings);
+    let owner_ptr: *const Line = pair.owner();
+    assert_eq!(
+        pair.with_dependent(|fields| fields.clone()),
+        ["a", "", "b c"]
+    );
+
+    settings.skip_empty = true;
+    pair.remake_dependent(&settings);
+    assert_eq!(pair.with_dependent(|fields| fields.clone()), ["a", "b c"]);
+
+    settings.delimiter = ' ';
+    pair.remake_dependent(&settings);
+    assert_eq!(pair.with_dependent(|fields| fields.clone()), ["a,,b", "c"]);
+
+    // The owner was never moved
+    assert!(std::ptr::eq(owner_ptr, pair.owner()));
+}
+
+struct Counted<'a>(&'a Cell<usize>);
+
+impl Drop for Counted<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+struct Tracked(Cell<usize>);
+
+impl<'owner> HasDependent<'owner> for Tracked {
+    type Dependent = Counted<'owner>;
+}
+
+impl Owner for Tracked {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(Counted(&self.0))
+    }
+}
+
+#[test]
+fn remake_drops_old_dependent() {
+    let mut pair = Pair::new(Tracked(Cell::new(0)));
+
+    pair.remake_dependent(());
+    assert_eq!(pair.owner().0.get(), 1);
+    pair.remake_dependent(());
+    assert_eq!(pair.owner().0.get(), 2);
+
+    assert_eq!(pair.into_owner().0.get(), 3);
+}
+
+#[derive(Debug)]
+struct Numbers(String);
+
+impl<'owner> HasDependent<'owner> for Numbers {
+    type Dependent = Vec<(&'owner str, u8)>;
+}
+
+impl Owner for Numbers {
+    type Context<'a> = u32;
+    type Error = std::num::ParseIntError;
+
+    fn make_dependent(&self, radix: u32) -> Result<Dependent<'_, Self>, Self::Error> {
+        assert_ne!(radix, 0, "radix must be nonzero");
+        self.0
+            .split(',')
+            .map(|number| Ok((number, u8::from_str_radix(number, radix)?)))
+            .collect()
+    }
+}
+
+fn values(pair: &Pair<Numbers>) -> Vec<u8> {
+    pair.with_dependent(|numbers| numbers.iter().map(|&(_, value)| value).collect())
+}
+
+#[test]
+fn try_remake_keeps_dependent_on_failure() {
+    let mut pair = Pair::try_new_with_context(Numbers(String::from("10,f")), 16).unwrap();
+    assert_eq!(values(&pair), [16, 15]);
+
+    assert!(pair.try_remake_dependent(8).is_err());
+    assert_eq!(values(&pair), [16, 15]);
+
+    // Retrying with a different context succeeds
+    assert_eq!(pair.try_remake_dependent(20), Ok(()));
+    assert_eq!(values(&pair), [20, 15]);
+}
+
+#[test]
+fn try_remake_keeps_dependent_on_panic() {
+    let mut pair = Pair::try_new_with_context(Numbers(String::from("1,2")), 10).unwrap();
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let _ = pair.try_remake_dependent(0);
+    }));
+    assert!(result.is_err());
+    assert_eq!(values(&pair), [1, 2]);
+}
@@ -0,0 +1,114 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "ropey")]
+
+use pair::{RopeBuffer, RopePair, RopeSnapshot, RopeView};
+use ropey::{Rope, RopeSlice};
+
+/// A view of the covered range, as its chunks.
+struct Chunks;
+
+impl RopeView for Chunks {
+    type View<'rope> = Vec<&'rope str>;
+
+    fn view(slice: RopeSlice<'_>) -> Vec<&str> {
+        slice.chunks().collect()
+    }
+}
+
+fn covered(buffer: &RopeBuffer<Chunks>) -> String {
+    buffer.with_view(|chunks| chunks.concat())
+}
+
+/// Checks that the view matches the covered range of the rope.
+fn assert_consistent(buffer: &RopeBuffer<Chunks>) {
+    assert_eq!(
+        covered(buffer),
+        buffer.rope().slice(buffer.coverage()).to_string()
+    );
+}
+
+#[test]
+fn snapshot_borrows_chunks() {
+    let text = "abc".repeat(10_000);
+    let pair = RopePair::<Chunks>::new(RopeSnapshot::new(Rope::from_str(&text), 10..20_000));
+
+    assert_eq!(pair.owner().range(), 10..20_000);
+    assert!(pair.with_dependent(|chunks| chunks.len() > 1));
+    assert_eq!(
+        pair.with_dependent(|chunks| chunks.concat()),
+        text[10..20_000]
+    );
+    assert_eq!(
+        format!("{:?}", pair.owner()),
+        "RopeSnapshot { len_chars: 30000, range: 10..20000, .. }"
+    );
+}
+
+#[test]
+#[should_panic = "range 2..5 is out of bounds for a rope of 3 chars"]
+fn snapshot_out_of_bounds() {
+    let _ = RopeSnapshot::<Chunks>::new(Rope::from_str("abc"), 2..5);
+}
+
+#[test]
+fn insert_rebuilds_only_when_overlapping() {
+    let mut buffer = RopeBuffer::<Chunks>::new(Rope::from_str("0123456789"), 3..6);
+    assert_eq!(covered(&buffer), "345");
+
+    assert!(!buffer.insert(1, "ab"));
+    assert_eq!(buffer.coverage(), 5..8);
+    assert!(!buffer.insert(5, "é"));
+    assert_eq!(buffer.coverage(), 6..9);
+    assert!(!buffer.insert(9, "cd"));
+    assert_eq!(buffer.coverage(), 6..9);
+    assert_consistent(&buffer);
+
+    assert!(buffer.insert(7, "xy"));
+    assert_eq!(buffer.coverage(), 6..11);
+    assert_eq!(covered(&buffer), "3xy45");
+    assert_consistent(&buffer);
+
+    assert_eq!(buffer.into_rope().to_string(), "0ab12é3xy45cd6789");
+}
+
+#[test]
+fn remove_rebuilds_only_when_overlapping() {
+    let mut buffer = RopeBuffer::<Chunks>::new(Rope::from_str("0123456789"), 3..6);
+
+    assert!(!buffer.remove(0..2));
+    assert_eq!(buffer.coverage(), 1..4);
+    assert!(!buffer.remove(5..7));
+    assert!(!buffer.remove(2..2));
+    assert_eq!(covered(&buffer), "345");
+    assert_consistent(&buffer);
+
+    // Overlapping the start of the covered range
+    assert!(buffer.remove(0..2));
+    assert_eq!(buffer.coverage(), 0..2);
+    assert_eq!(covered(&buffer), "45");
+
+    // Containing the entire covered range
+    assert!(buffer.remove(0..3));
+    assert_eq!(buffer.coverage(), 0..0);
+    assert_eq!(covered(&buffer), "");
+    assert_consistent(&buffer);
+    assert_eq!(buffer.rope().to_string(), "9");
+}
+
+#[test]
+fn set_coverage() {
+    let mut buffer = RopeBuffer::<Chunks>::new(Rope::from_str("hello world"), 0..5);
+    let snapshot = buffer.pair().owner().rope().clone();
+
+    buffer.insert(11, "!");
+    // The snapshot is only replaced when the view is rebuilt
+    assert_eq!(buffer.pair().owner().rope(), &snapshot);
+
+    buffer.set_coverage(6..12);
+    assert_eq!(covered(&buffer), "world!");
+    assert_eq!(buffer.pair().owner().rope(), buffer.rope());
+    assert_eq!(
+        format!("{buffer:?}"),
+        "RopeBuffer { len_chars: 12, coverage: 6..12, .. }"
+    );
+}
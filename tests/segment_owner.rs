@@ -0,0 +1,110 @@
+#![allow(missing_docs, reason = "integration test")]
+
+// Owners which are handles to externally-managed memory, such as a
+// shared-memory segment. The "segment" here is simulated with a heap
+// allocation, but the pattern is the same for a real mapping (see
+// tests/shared_memory.rs for `SharedSegment`, which maps real segments).
+
+use pair::{Dependent, HasDependent, Owner, Pair};
+
+const MAGIC: &[u8; 4] = b"SEG1";
+
+/// A stand-in for a shared-memory segment handle (such as a wrapper around
+/// `shared_memory::Shmem` or a memfd mapping).
+#[derive(Debug)]
+struct Segment {
+    mapping: Option<Box<[u8]>>,
+}
+
+impl Segment {
+    fn bytes(&self) -> Result<&[u8], SegmentError> {
+        self.mapping.as_deref().ok_or(SegmentError::NotMapped)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum SegmentError {
+    NotMapped,
+    BadHeader,
+    Truncated { expected: usize, actual: usize },
+}
+
+/// Records of fixed-size entries, parsed in place from the segment
+#[derive(Debug)]
+struct Records<'a> {
+    entries: Vec<&'a [u8]>,
+}
+
+impl<'owner> HasDependent<'owner> for Segment {
+    type Dependent = Records<'owner>;
+}
+
+impl Owner for Segment {
+    type Context<'a> = usize;
+    type Error = SegmentError;
+
+    fn make_dependent(
+        &self,
+        record_len: Self::Context<'_>,
+    ) -> Result<Dependent<'_, Self>, Self::Error> {
+        // Validate the segment before parsing anything
+        let bytes = self.bytes()?;
+        let body = bytes.strip_prefix(MAGIC).ok_or(SegmentError::BadHeader)?;
+        let (count, records) = body.split_first().ok_or(SegmentError::BadHeader)?;
+        let expected = usize::from(*count) * record_len;
+        if records.len() != expected {
+            return Err(SegmentError::Truncated {
+                expected,
+                actual: records.len(),
+            });
+        }
+
+        Ok(Records {
+            entries: records.chunks_exact(record_len).collect(),
+        })
+    }
+}
+
+fn segment(bytes: &[u8]) -> Segment {
+    Segment {
+        mapping: Some(bytes.into()),
+    }
+}
+
+#[test]
+fn parses_in_place() {
+    let pair = Pair::try_new_with_context(segment(b"SEG1\x02abcdef"), 3).unwrap();
+
+    pair.with_both(|segment, records| {
+        assert_eq!(records.entries, [b"abc", b"def"]);
+
+        // The records borrow directly from the segment's memory
+        let mapping = segment.bytes().unwrap();
+        assert!(
+            mapping
+                .as_ptr_range()
+                .contains(&records.entries[1].as_ptr())
+        );
+    });
+}
+
+#[test]
+fn validation_errors() {
+    let unmapped = Segment { mapping: None };
+    let (unmapped, err) = Pair::try_new_with_context(unmapped, 3).unwrap_err();
+    assert_eq!(err, SegmentError::NotMapped);
+    assert!(unmapped.mapping.is_none());
+
+    let (_, err) = Pair::try_new_with_context(segment(b"XXXX\x01abc"), 3).unwrap_err();
+    assert_eq!(err, SegmentError::BadHeader);
+
+    let (segment, err) = Pair::try_new_with_context(segment(b"SEG1\x02abcde"), 3).unwrap_err();
+    assert_eq!(
+        err,
+        SegmentError::Truncated {
+            expected: 6,
+            actual: 5
+        }
+    );
+    assert_eq!(segment.bytes().unwrap().len(), 10);
+}
@@ -0,0 +1,140 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{cell::Cell, convert::Infallible, marker::PhantomData, sync::MutexGuard};
+
+use pair::{
+    ArenaOwner, ArenaView, AssertSendPair, AssertSyncPair, Dependent, HasDependent, LayeredPair,
+    Overlay, Owner, Pair, PairArena, ViewsPair,
+};
+
+// Markers with each combination of `Send` and `Sync`
+type SendSync = ();
+type SendOnly = Cell<()>;
+type SyncOnly = MutexGuard<'static, ()>;
+type Neither = *const ();
+
+/// An owner which is `Send`/`Sync` exactly when `O` is, with a dependent which
+/// is `Send`/`Sync` exactly when `D` is.
+struct Marked<O, D>(PhantomData<O>, PhantomData<fn() -> D>);
+
+impl<'owner, O, D> HasDependent<'owner> for Marked<O, D> {
+    type Dependent = PhantomData<(&'owner (), D)>;
+}
+
+impl<O, D> Owner for Marked<O, D> {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(PhantomData)
+    }
+}
+
+impl<O, D> ArenaOwner for Marked<O, D> {
+    type Error = Infallible;
+
+    fn make_dependent<'arena>(
+        &'arena self,
+        _: ArenaView<'arena, Self>,
+    ) -> Result<Dependent<'arena, Self>, Self::Error> {
+        Ok(PhantomData)
+    }
+}
+
+impl<O, D> Overlay<Marked<SendSync, SendSync>> for Marked<O, D> {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent<'owner>(
+        &'owner self,
+        _: &'owner Marked<SendSync, SendSync>,
+        (): (),
+    ) -> Result<Dependent<'owner, Self>, Self::Error> {
+        Ok(PhantomData)
+    }
+}
+
+/// The fallback for [`implements!`], which is shadowed by the inherent
+/// associated constant on its `Check` type when the trait is implemented.
+trait DoesNotImplement {
+    const IMPLEMENTS: bool = false;
+}
+
+impl<T: ?Sized> DoesNotImplement for T {}
+
+/// Evaluates to whether the given (concrete) type implements the given trait.
+macro_rules! implements {
+    ($ty:ty: $trait:path) => {{
+        struct Check<T: ?Sized>(PhantomData<T>);
+        impl<T: ?Sized + $trait> Check<T> {
+            #![allow(dead_code, reason = "only used when the trait is implemented")]
+            const IMPLEMENTS: bool = true;
+        }
+
+        <Check<$ty>>::IMPLEMENTS
+    }};
+}
+
+/// Defines a test asserting that every wrapper around a `Pair` of
+/// `Marked<$o, $d>` is `Send`/`Sync` exactly when the `Pair` itself is.
+macro_rules! wrappers_match_pair {
+    ($($name:ident: $o:ty, $d:ty;)*) => {$(
+        #[test]
+        fn $name() {
+            type P = Pair<Marked<$o, $d>>;
+            // A layered pair's base pair is always `Send` and `Sync` here, so
+            // only the overlay and its dependent matter
+            type L = LayeredPair<Marked<SendSync, SendSync>, Marked<$o, $d>>;
+
+            let pair_send = implements!(P: Send);
+            let pair_sync = implements!(P: Sync);
+
+            assert_eq!(implements!(PairArena<Marked<$o, $d>>: Send), pair_send);
+            assert_eq!(implements!(PairArena<Marked<$o, $d>>: Sync), pair_sync);
+
+            assert_eq!(implements!(ViewsPair<Marked<$o, $d>, 2>: Send), pair_send);
+            assert_eq!(implements!(ViewsPair<Marked<$o, $d>, 2>: Sync), pair_sync);
+
+            assert_eq!(implements!(L: Send), pair_send);
+            assert_eq!(implements!(L: Sync), pair_sync);
+
+            assert!(implements!(AssertSendPair<Marked<$o, $d>>: Send));
+            assert_eq!(implements!(AssertSendPair<Marked<$o, $d>>: Sync), pair_sync);
+
+            assert_eq!(implements!(AssertSyncPair<Marked<$o, $d>>: Send), pair_send);
+            assert!(implements!(AssertSyncPair<Marked<$o, $d>>: Sync));
+        }
+    )*};
+}
+
+#[test]
+fn pair_markers() {
+    assert!(implements!(Pair<Marked<SendSync, SendSync>>: Send));
+    assert!(implements!(Pair<Marked<SendSync, SendSync>>: Sync));
+    assert!(!implements!(Pair<Marked<SendOnly, SendSync>>: Sync));
+    assert!(!implements!(Pair<Marked<SyncOnly, SendSync>>: Send));
+    assert!(!implements!(Pair<Marked<SendSync, SendOnly>>: Sync));
+    assert!(!implements!(Pair<Marked<SendSync, SyncOnly>>: Send));
+}
+
+wrappers_match_pair! {
+    send_sync_owner_send_sync_dependent: SendSync, SendSync;
+    send_sync_owner_send_only_dependent: SendSync, SendOnly;
+    send_sync_owner_sync_only_dependent: SendSync, SyncOnly;
+    send_sync_owner_neither_dependent: SendSync, Neither;
+
+    send_only_owner_send_sync_dependent: SendOnly, SendSync;
+    send_only_owner_send_only_dependent: SendOnly, SendOnly;
+    send_only_owner_sync_only_dependent: SendOnly, SyncOnly;
+    send_only_owner_neither_dependent: SendOnly, Neither;
+
+    sync_only_owner_send_sync_dependent: SyncOnly, SendSync;
+    sync_only_owner_send_only_dependent: SyncOnly, SendOnly;
+    sync_only_owner_sync_only_dependent: SyncOnly, SyncOnly;
+    sync_only_owner_neither_dependent: SyncOnly, Neither;
+
+    neither_owner_send_sync_dependent: Neither, SendSync;
+    neither_owner_send_only_dependent: Neither, SendOnly;
+    neither_owner_sync_only_dependent: Neither, SyncOnly;
+    neither_owner_neither_dependent: Neither, Neither;
+}
@@ -0,0 +1,133 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(all(feature = "shared-memory", target_os = "linux"))]
+
+use std::{
+    fs::{self, File},
+    io,
+    os::fd::{AsFd, OwnedFd},
+    path::PathBuf,
+};
+
+use pair::{Pair, SegmentError, SegmentLayout, SharedSegment, SharedSegmentPair};
+
+// NOTE: MIRI can't create or map memfds, so every test here is named `*_nomiri`
+
+const MAGIC: &[u8; 4] = b"SEG1";
+
+/// Fixed-size records after a header of the magic bytes and a record count
+struct Records;
+
+#[derive(Debug, PartialEq, Eq)]
+enum LayoutError {
+    BadHeader,
+    Truncated { expected: usize, actual: usize },
+}
+
+impl SegmentLayout for Records {
+    type View<'segment> = Vec<&'segment [u8]>;
+    type Error = LayoutError;
+
+    fn parse(bytes: &[u8]) -> Result<Vec<&[u8]>, Self::Error> {
+        let body = bytes.strip_prefix(MAGIC).ok_or(LayoutError::BadHeader)?;
+        let (&count, records) = body.split_first().ok_or(LayoutError::BadHeader)?;
+        let expected = usize::from(count) * 3;
+        if records.len() != expected {
+            return Err(LayoutError::Truncated {
+                expected,
+                actual: records.len(),
+            });
+        }
+
+        Ok(records.chunks_exact(3).collect())
+    }
+}
+
+/// Returns a path in the temporary directory which is unique to this test.
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("pair-shared-memory-{}-{name}", std::process::id()))
+}
+
+#[test]
+fn parses_in_place_nomiri() {
+    let segment = SharedSegment::<Records>::memfd(c"records", b"SEG1\x02abcdef").unwrap();
+    let fd = segment.as_fd().try_clone_to_owned().unwrap();
+
+    // Both handles map the same segment
+    let received = SharedSegment::<Records>::from_sealed_memfd(fd).unwrap();
+    assert_eq!(received.len(), 11);
+
+    let pair: SharedSegmentPair<Records> = Pair::try_new(received).unwrap();
+    pair.with_both(|segment, records| {
+        assert_eq!(*records, [b"abc", b"def"]);
+
+        // The records borrow directly from the mapped segment
+        let mapping = segment.bytes().unwrap();
+        assert!(mapping.as_ptr_range().contains(&records[1].as_ptr()));
+    });
+
+    assert_eq!(segment.bytes().unwrap(), b"SEG1\x02abcdef");
+}
+
+#[test]
+fn layout_errors_nomiri() {
+    let segment = SharedSegment::<Records>::memfd(c"bad header", b"XXXX\x01abc").unwrap();
+    let (_, err) = Pair::try_new(segment).unwrap_err();
+    assert!(matches!(err, SegmentError::Layout(LayoutError::BadHeader)));
+
+    let segment = SharedSegment::<Records>::memfd(c"truncated", b"SEG1\x02abcde").unwrap();
+    let (segment, err) = Pair::try_new(segment).unwrap_err();
+    assert!(matches!(
+        err,
+        SegmentError::Layout(LayoutError::Truncated {
+            expected: 6,
+            actual: 5
+        })
+    ));
+    assert_eq!(segment.bytes().unwrap().len(), 10);
+
+    let segment = SharedSegment::<Records>::memfd(c"empty", b"").unwrap();
+    assert!(segment.is_empty());
+    let (_, err) = Pair::try_new(segment).unwrap_err();
+    assert!(matches!(err, SegmentError::Layout(LayoutError::BadHeader)));
+}
+
+#[test]
+fn rejects_unsealed_files_nomiri() {
+    let path = temp_path("unsealed");
+    fs::write(&path, b"SEG1\x01abc").unwrap();
+
+    let err =
+        SharedSegment::<Records>::from_sealed_memfd(File::open(&path).unwrap().into()).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    // A regular file can still be mapped, as long as nothing writes to it
+    let fd: OwnedFd = File::open(&path).unwrap().into();
+    // SAFETY: nothing writes to or truncates the file until it's removed, after
+    // the pair is dropped.
+    let segment = unsafe { SharedSegment::<Records>::from_fd(fd) }.unwrap();
+    let pair = Pair::try_new(segment).unwrap();
+    assert_eq!(pair.with_dependent(|records| records[0]), b"abc");
+
+    drop(pair);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn map_errors_nomiri() {
+    // Directories can't be mapped. The directory isn't empty, so that its size
+    // isn't 0 on any file system (which would skip mapping it).
+    let path = temp_path("directory");
+    fs::create_dir(&path).unwrap();
+    fs::write(path.join("file"), b"").unwrap();
+
+    let fd: OwnedFd = File::open(&path).unwrap().into();
+    // SAFETY: nothing writes to the directory until it's removed, after the
+    // segment is dropped.
+    let segment = unsafe { SharedSegment::<Records>::from_fd(fd) }.unwrap();
+    let (segment, err) = Pair::try_new(segment).unwrap_err();
+    assert!(matches!(err, SegmentError::Map(_)));
+    assert!(format!("{segment:?}").contains("mapped: false"));
+
+    drop(segment);
+    fs::remove_dir_all(&path).unwrap();
+}
@@ -0,0 +1,65 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::convert::Infallible;
+
+use pair::{Dependent, HasDependent, Owner, Pair, static_assert_pair_size};
+
+struct Lines(String);
+
+impl<'owner> HasDependent<'owner> for Lines {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Lines {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.lines().collect())
+    }
+}
+
+struct Bytes([u8]);
+
+impl HasDependent<'_> for Bytes {
+    type Dependent = usize;
+}
+
+impl Owner for Bytes {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.len())
+    }
+}
+
+#[cfg(not(feature = "leak-check"))]
+static_assert_pair_size!(Lines, 2);
+#[cfg(not(feature = "leak-check"))]
+static_assert_pair_size!(Bytes, 3);
+static_assert_pair_size!(Lines, 4);
+
+#[test]
+fn inline_size() {
+    assert_eq!(Pair::<Lines>::inline_size(), size_of::<Pair<Lines>>());
+    assert_eq!(Pair::<Bytes>::inline_size(), size_of::<Pair<Bytes>>());
+
+    let pointer = size_of::<usize>();
+    let extra = if cfg!(feature = "leak-check") { 8 } else { 0 };
+    assert_eq!(
+        Pair::<Lines>::inline_size_in_pointers(),
+        (2 * pointer + extra).div_ceil(pointer)
+    );
+    assert_eq!(
+        Pair::<Bytes>::inline_size_in_pointers(),
+        (3 * pointer + extra).div_ceil(pointer)
+    );
+}
+
+#[test]
+fn usable_in_const_contexts() {
+    const LINES_SIZE: usize = Pair::<Lines>::inline_size();
+
+    assert!(LINES_SIZE >= 2 * size_of::<usize>());
+}
@@ -0,0 +1,97 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "wasm")]
+
+use std::convert::Infallible;
+
+use pair::{Dependent, HasDependent, Owner, Pair, PairHandle, PairSlab};
+
+#[derive(Debug)]
+struct Words(String);
+
+impl<'owner> HasDependent<'owner> for Words {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Words {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): Self::Context<'_>) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split_whitespace().collect())
+    }
+}
+
+fn words(text: &str) -> Pair<Words> {
+    Pair::new(Words(String::from(text)))
+}
+
+#[test]
+fn insert_get_remove() {
+    let mut slab = PairSlab::new();
+    assert!(slab.is_empty());
+
+    let a = slab.insert(words("a b")).unwrap();
+    let b = slab.insert(words("c")).unwrap();
+    assert_ne!(a, b);
+    assert_eq!(slab.len(), 2);
+    assert!(slab.contains(a));
+
+    assert_eq!(
+        slab.with_dependent(a, |dep| dep.clone()),
+        Some(vec!["a", "b"])
+    );
+    assert_eq!(slab.get(b).unwrap().owner().0, "c");
+    slab.with_dependent_mut(a, |dep| dep.truncate(1));
+    assert_eq!(slab.with_dependent(a, |dep| dep.clone()), Some(vec!["a"]));
+
+    let removed = slab.remove(a).unwrap();
+    assert_eq!(removed.into_owner().0, "a b");
+    assert_eq!(slab.len(), 1);
+    assert!(!slab.contains(a));
+    assert!(slab.remove(a).is_none());
+    assert!(slab.with_dependent(a, |_| ()).is_none());
+    assert!(slab.get_mut(a).is_none());
+}
+
+#[test]
+fn stale_handles() {
+    let mut slab = PairSlab::new();
+    let old = slab.insert(words("old")).unwrap();
+    slab.remove(old).unwrap();
+
+    // The slot is reused, but the old handle doesn't refer to the new pair
+    let new = slab.insert(words("new")).unwrap();
+    assert_ne!(old, new);
+    assert!(slab.get(old).is_none());
+    assert_eq!(slab.get(new).unwrap().owner().0, "new");
+}
+
+#[test]
+fn raw_handles() {
+    let mut slab = PairSlab::default();
+    let handle = slab.insert(words("raw")).unwrap();
+
+    let raw: u32 = handle.into_raw();
+    assert_eq!(PairHandle::from_raw(raw), handle);
+    assert!(slab.contains(PairHandle::from_raw(raw)));
+
+    // Arbitrary handles are simply not found
+    assert!(!slab.contains(PairHandle::from_raw(12345)));
+    assert!(!slab.contains(PairHandle::from_raw(u32::MAX)));
+}
+
+#[test]
+fn iter_and_debug() {
+    let mut slab = PairSlab::new();
+    let a = slab.insert(words("a")).unwrap();
+    let b = slab.insert(words("b")).unwrap();
+    let c = slab.insert(words("c")).unwrap();
+    slab.remove(b);
+
+    let handles: Vec<PairHandle> = slab.iter().map(|(handle, _)| handle).collect();
+    assert_eq!(handles, [a, c]);
+
+    let debug = format!("{slab:?}");
+    assert!(debug.contains(r#"Pair { owner: Words("a"), dependent: ["a"] }"#));
+    assert!(!debug.contains(r#"Words("b")"#));
+}
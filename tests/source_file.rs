@@ -0,0 +1,60 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "source-file")]
+
+use pair::{LineCol, LineIndex, SourceFile, SourceFilePair};
+
+#[test]
+fn line_index() {
+    let index = LineIndex::new("ab\r\ncd\n\nef");
+
+    assert_eq!(index.line_count(), 4);
+    assert_eq!(index.lines().collect::<Vec<_>>(), ["ab", "cd", "", "ef"]);
+    assert_eq!(index.line_span(0), Some(0..2));
+    assert_eq!(index.line_span(1), Some(4..6));
+    assert_eq!(index.line(3), Some("ef"));
+    assert_eq!(index.line(4), None);
+
+    assert_eq!(index.line_col(0), Some(LineCol { line: 0, column: 0 }));
+    assert_eq!(index.line_col(5), Some(LineCol { line: 1, column: 1 }));
+    assert_eq!(index.line_col(7), Some(LineCol { line: 2, column: 0 }));
+    assert_eq!(index.line_col(10), Some(LineCol { line: 3, column: 2 }));
+    assert_eq!(index.line_col(11), None);
+
+    assert_eq!(index.offset(LineCol { line: 1, column: 1 }), Some(5));
+    assert_eq!(index.offset(LineCol { line: 0, column: 2 }), Some(2));
+    assert_eq!(index.offset(LineCol { line: 0, column: 3 }), None);
+    assert_eq!(index.offset(LineCol { line: 9, column: 0 }), None);
+}
+
+#[test]
+fn trailing_newline_and_empty() {
+    let index = LineIndex::new("a\n");
+    assert_eq!(index.line_count(), 2);
+    assert_eq!(index.line(1), Some(""));
+    assert_eq!(index.line_col(2), Some(LineCol { line: 1, column: 0 }));
+
+    let index = LineIndex::new("");
+    assert_eq!(index.line_count(), 1);
+    assert_eq!(index.line(0), Some(""));
+    assert_eq!(index.line_col(0), Some(LineCol::default()));
+}
+
+#[test]
+fn source_file_pair() {
+    let source = SourceFilePair::new(SourceFile::new("lib.rs", "mod a;\nmod b;\n"));
+
+    assert_eq!(source.name(), "lib.rs");
+    assert_eq!(source.text(), "mod a;\nmod b;\n");
+    assert_eq!(source.line_count(), 3);
+    assert_eq!(source.line(1), Some("mod b;"));
+    assert_eq!(source.line_span(1), Some(7..13));
+    assert_eq!(source.line_col(11), Some(LineCol { line: 1, column: 4 }));
+    assert_eq!(source.offset(LineCol { line: 1, column: 4 }), Some(11));
+    assert_eq!(
+        source.with_dependent(|index| index.text().len()),
+        source.text().len()
+    );
+
+    let (name, text) = source.into_owner().into_parts();
+    assert_eq!((&*name, &*text), ("lib.rs", "mod a;\nmod b;\n"));
+}
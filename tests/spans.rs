@@ -0,0 +1,171 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{convert::Infallible, ops::Range};
+
+use pair::{Dependent, FromSpans, HasDependent, Owner, Pair, SpanPair, ToSpans, span_of};
+
+#[derive(Debug)]
+struct Buff(String);
+
+impl<'owner> HasDependent<'owner> for Buff {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Buff {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): Self::Context<'_>) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split_whitespace().collect())
+    }
+}
+
+impl ToSpans for Buff {
+    type Spans = Vec<Range<usize>>;
+
+    fn to_spans(&self, dependent: &Dependent<'_, Self>) -> Self::Spans {
+        dependent
+            .iter()
+            .map(|word| span_of(self.0.as_bytes(), word.as_bytes()).unwrap())
+            .collect()
+    }
+}
+
+impl FromSpans for Buff {
+    fn dependent_from_spans<'owner>(
+        &'owner self,
+        spans: &Self::Spans,
+    ) -> Option<Dependent<'owner, Self>> {
+        spans.iter().map(|span| self.0.get(span.clone())).collect()
+    }
+}
+
+#[test]
+fn into_owner_with_spans() {
+    let mut pair = Pair::new(Buff(String::from("  this is\ta test ")));
+    pair.with_dependent_mut(|dep| dep.remove(1));
+
+    let (owner, spans) = pair.into_owner_with_spans();
+    assert_eq!(spans, [2..6, 10..11, 12..16]);
+
+    let words: Vec<&str> = spans.into_iter().map(|span| &owner.0[span]).collect();
+    assert_eq!(words, ["this", "a", "test"]);
+}
+
+#[test]
+fn into_boxed_owner_with_spans() {
+    let pair = Pair::new(Buff(String::from("hello world")));
+
+    let (owner, spans) = pair.into_boxed_owner_with_spans();
+    assert_eq!(owner.0, "hello world");
+    assert_eq!(spans, [0..5, 6..11]);
+}
+
+#[test]
+fn span_of_subslices() {
+    let numbers = [1_u32, 2, 3, 4, 5];
+    assert_eq!(span_of(&numbers, &numbers), Some(0..5));
+    assert_eq!(span_of(&numbers, &numbers[1..3]), Some(1..3));
+    assert_eq!(span_of(&numbers, &numbers[5..]), Some(5..5));
+    assert_eq!(span_of(&numbers[1..], &numbers[..2]), None);
+    assert_eq!(span_of(&numbers[..2], &numbers[1..]), None);
+    assert_eq!(span_of(&numbers, &[2, 3]), None);
+
+    let units = [(); 4];
+    assert_eq!(span_of(&units, &units[1..]), Some(0..3));
+    assert_eq!(span_of(&units[1..], &units), None);
+}
+
+impl AsRef<str> for Buff {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[test]
+fn span_pair_resolve() {
+    let mut span_pair = SpanPair::from_fn(String::from("héllo world"), |text| {
+        assert_eq!(text.len(), 12);
+        vec![0..6, 7..12, 1..2, 20..25]
+    });
+
+    assert_eq!(
+        span_pair.resolve_str(span_pair.spans()[0].clone()),
+        Some("héllo")
+    );
+    assert_eq!(
+        span_pair.resolve_str(span_pair.spans()[1].clone()),
+        Some("world")
+    );
+    // Not on a char boundary
+    assert_eq!(span_pair.resolve_str(span_pair.spans()[2].clone()), None);
+    assert_eq!(span_pair.resolve_bytes(1..2), Some(&[0xC3][..]));
+    // Out of bounds
+    assert_eq!(span_pair.resolve_str(span_pair.spans()[3].clone()), None);
+
+    // The owner can be freely mutated
+    span_pair.owner_mut().push_str(", and then some");
+    assert_eq!(span_pair.resolve_str(18..22), Some("then"));
+
+    let (owner, spans) = span_pair.into_parts();
+    assert_eq!(owner, "héllo world, and then some");
+    assert_eq!(spans.len(), 4);
+}
+
+#[test]
+fn span_pair_from_pair() {
+    let pair = Pair::new(Buff(String::from("one two three")));
+    let span_pair = SpanPair::from(pair);
+
+    let words: Vec<&str> = span_pair
+        .spans()
+        .iter()
+        .map(|span| span_pair.resolve_str(span.clone()).unwrap())
+        .collect();
+    assert_eq!(words, ["one", "two", "three"]);
+
+    // Spans don't borrow from the owner, so they can be sent to other threads
+    // independently of it
+    let spans = span_pair.into_parts().1;
+    std::thread::spawn(move || assert_eq!(spans, [0..3, 4..7, 8..13]))
+        .join()
+        .unwrap();
+}
+
+#[test]
+fn round_trip_through_spans() {
+    let pair = Pair::new(Buff(String::from("saved to a cache")));
+    let (owner, spans) = pair.into_owner_with_spans();
+
+    let pair = Pair::try_new_with_spans(owner, &spans).unwrap();
+    assert_eq!(
+        pair.with_dependent(|dep| dep),
+        &["saved", "to", "a", "cache"]
+    );
+
+    let (owner, spans) = pair.into_boxed_owner_with_spans();
+    let pair = Pair::try_new_from_box_with_spans(owner, &spans).unwrap();
+    assert_eq!(
+        pair.with_dependent(|dep| dep),
+        &["saved", "to", "a", "cache"]
+    );
+
+    let span_pair = SpanPair::from(pair);
+    let pair = span_pair.try_into_pair().unwrap();
+    assert_eq!(
+        pair.with_dependent(|dep| dep),
+        &["saved", "to", "a", "cache"]
+    );
+}
+
+#[test]
+fn invalid_spans() {
+    let owner = Buff(String::from("short"));
+    let owner = Pair::try_new_with_spans(owner, &vec![0..3, 10..20]).unwrap_err();
+    assert_eq!(owner.0, "short");
+
+    let span_pair = SpanPair::new(Buff(String::from("héllo")), vec![0..1, 0..2]);
+    let span_pair = span_pair.try_into_pair().unwrap_err();
+    assert_eq!(span_pair.spans(), &[0..1, 0..2]);
+    assert_eq!(span_pair.owner().0, "héllo");
+}
@@ -0,0 +1,97 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::convert::Infallible;
+
+use pair::{Dependent, HasDependent, Owner, Pair};
+
+struct Words(String);
+
+impl<'owner> HasDependent<'owner> for Words {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Words {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split_whitespace().collect())
+    }
+}
+
+struct Numbers;
+
+impl HasDependent<'_> for Numbers {
+    type Dependent = [u64; 8];
+}
+
+impl Owner for Numbers {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok([1, 2, 3, 4, 5, 6, 7, 8])
+    }
+}
+
+#[test]
+fn split_parts() {
+    let mut pair = Pair::new(Words(String::from("a b c d e f g")));
+
+    let lens = pair.with_dependent_split_mut(
+        |_| vec![2, 2, 5],
+        |mut parts| {
+            parts[0].reverse();
+            parts[2].reverse();
+            parts.iter().map(|part| part.len()).collect::<Vec<_>>()
+        },
+    );
+
+    assert_eq!(lens, [2, 0, 3, 2]);
+    assert_eq!(pair.with_dependent(|words| words.join("")), "baedcfg");
+}
+
+#[test]
+fn no_split_points() {
+    let mut pair = Pair::new(Words(String::from("x y")));
+
+    let count = pair.with_dependent_split_mut(|_| Vec::new(), |parts| parts.len());
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn parallel_mutation() {
+    let mut pair = Pair::new(Numbers);
+
+    pair.with_dependent_split_mut(
+        |numbers| (1..4).map(|i| i * numbers.len() / 4).collect(),
+        |parts| {
+            std::thread::scope(|scope| {
+                for part in parts {
+                    scope.spawn(move || part.iter_mut().for_each(|n| *n *= 10));
+                }
+            });
+        },
+    );
+
+    assert_eq!(
+        pair.with_dependent(|numbers| *numbers),
+        [10, 20, 30, 40, 50, 60, 70, 80]
+    );
+}
+
+#[test]
+#[should_panic = "split points must be ascending and in bounds"]
+fn descending_split_points() {
+    let mut pair = Pair::new(Words(String::from("a b c d")));
+
+    pair.with_dependent_split_mut(|_| vec![3, 1], |parts| parts.len());
+}
+
+#[test]
+#[should_panic = "split points must be ascending and in bounds"]
+fn out_of_bounds_split_point() {
+    let mut pair = Pair::new(Words(String::from("a b c d")));
+
+    pair.with_dependent_split_mut(|_| vec![5], |parts| parts.len());
+}
@@ -0,0 +1,115 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "sqlx-any")]
+
+use std::{borrow::Cow, collections::HashMap, sync::Arc};
+
+use pair::{DatabaseRow, Pair, RowDecoder, RowPair};
+use sqlx_core::{
+    Error,
+    any::{AnyColumn, AnyRow, AnyTypeInfo, AnyTypeInfoKind, AnyValue, AnyValueKind},
+    row::Row,
+};
+
+// Builds a row by hand, as a driver would after reading it from the database
+fn row(values: Vec<(&'static str, AnyValueKind<'static>)>) -> AnyRow {
+    let mut column_names = HashMap::new();
+    let mut columns = Vec::new();
+    let mut row_values = Vec::new();
+    for (ordinal, (name, kind)) in values.into_iter().enumerate() {
+        let type_info = AnyTypeInfo {
+            kind: match kind {
+                AnyValueKind::BigInt(_) => AnyTypeInfoKind::BigInt,
+                AnyValueKind::Text(_) => AnyTypeInfoKind::Text,
+                AnyValueKind::Blob(_) => AnyTypeInfoKind::Blob,
+                _ => unimplemented!(),
+            },
+        };
+        column_names.insert(name.into(), ordinal);
+        columns.push(AnyColumn {
+            ordinal,
+            name: name.into(),
+            type_info,
+        });
+        row_values.push(AnyValue { kind });
+    }
+
+    AnyRow {
+        column_names: Arc::new(column_names.into_iter().collect()),
+        columns,
+        values: row_values,
+    }
+}
+
+struct Document<'row> {
+    id: i64,
+    title: &'row str,
+    body: &'row [u8],
+}
+
+impl RowDecoder<AnyRow> for Document<'_> {
+    type Decoded<'row> = Document<'row>;
+
+    fn decode(row: &AnyRow) -> Result<Document<'_>, Error> {
+        Ok(Document {
+            id: row.try_get("id")?,
+            title: row.try_get("title")?,
+            body: row.try_get("body")?,
+        })
+    }
+}
+
+fn document_row(title: &'static str) -> AnyRow {
+    row(vec![
+        ("id", AnyValueKind::BigInt(7)),
+        ("title", AnyValueKind::Text(Cow::Owned(title.to_owned()))),
+        ("body", AnyValueKind::Blob(Cow::Owned(vec![1, 2, 3]))),
+    ])
+}
+
+#[test]
+fn decoded_columns_borrow_row() {
+    let pair: RowPair<AnyRow, Document<'static>> =
+        Pair::try_new(DatabaseRow::new(document_row("Hello"))).unwrap();
+
+    pair.with_dependent(|document| {
+        assert_eq!(document.id, 7);
+        assert_eq!(document.title, "Hello");
+        assert_eq!(document.body, [1, 2, 3]);
+    });
+
+    // The title wasn't copied out of the row
+    let AnyValueKind::Text(Cow::Owned(title)) = &pair.owner().row().values[1].kind else {
+        panic!("the title column should be owned text");
+    };
+    let title = title.as_ptr();
+    assert!(pair.with_dependent(|document| document.title.as_ptr() == title));
+
+    assert_eq!(
+        format!("{:?}", pair.owner()),
+        "DatabaseRow { columns: 3, .. }"
+    );
+    assert_eq!(pair.into_owner().into_row().len(), 3);
+}
+
+#[test]
+fn decode_errors() {
+    // A missing column
+    let Err((owner, err)) = Pair::try_new(DatabaseRow::<_, Document<'static>>::new(row(vec![(
+        "id",
+        AnyValueKind::BigInt(1),
+    )]))) else {
+        panic!("the row has no title column");
+    };
+    assert!(matches!(err, Error::ColumnNotFound(_)));
+    assert_eq!(owner.row().len(), 1);
+
+    // A column of the wrong type
+    let Err((_, err)) = Pair::try_new(DatabaseRow::<_, Document<'static>>::new(row(vec![
+        ("id", AnyValueKind::Text(Cow::Borrowed("seven"))),
+        ("title", AnyValueKind::Text(Cow::Borrowed("Hello"))),
+        ("body", AnyValueKind::Blob(Cow::Borrowed(&[]))),
+    ]))) else {
+        panic!("the id column has the wrong type");
+    };
+    assert!(matches!(err, Error::ColumnDecode { .. }));
+}
@@ -0,0 +1,99 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "std")]
+
+use std::{
+    convert::Infallible,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use pair::{Dependent, HasDependent, Owner, Pair, StaticPair, static_pair};
+
+#[derive(Debug)]
+struct Lines(&'static str, &'static AtomicUsize);
+
+impl<'owner> HasDependent<'owner> for Lines {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Lines {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): Self::Context<'_>) -> Result<Dependent<'_, Self>, Self::Error> {
+        self.1.fetch_add(1, Ordering::SeqCst);
+        Ok(self.0.lines().collect())
+    }
+}
+
+static FIRST_CALLS: AtomicUsize = AtomicUsize::new(0);
+static SECOND_CALLS: AtomicUsize = AtomicUsize::new(0);
+static CONCURRENT_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+static_pair! {
+    /// The first pair.
+    static FIRST: Lines = Lines("a\nb", &FIRST_CALLS);
+
+    pub(crate) static SECOND: Lines = Lines("c\nd\ne", &SECOND_CALLS);
+}
+
+static_pair! {
+    static CONCURRENT: Lines = Lines("x\ny", &CONCURRENT_CALLS);
+}
+
+#[test]
+fn lazy_initialization() {
+    assert_eq!(FIRST_CALLS.load(Ordering::SeqCst), 0);
+    assert!(FIRST.get_if_initialized().is_none());
+    assert_eq!(format!("{FIRST:?}"), "StaticPair { pair: None, .. }");
+
+    assert_eq!(FIRST.with_dependent(|lines| lines), &["a", "b"]);
+    assert_eq!(FIRST.get().owner().0, "a\nb");
+    assert_eq!(FIRST_CALLS.load(Ordering::SeqCst), 1);
+    assert!(FIRST.get_if_initialized().is_some());
+    assert!(format!("{FIRST:?}").contains(r#"dependent: ["a", "b"]"#));
+
+    // Other statics in the same invocation are independent
+    assert_eq!(SECOND_CALLS.load(Ordering::SeqCst), 0);
+    assert_eq!(SECOND.with_dependent(|lines| lines), &["c", "d", "e"]);
+    assert_eq!(SECOND_CALLS.load(Ordering::SeqCst), 1);
+    assert_eq!(FIRST_CALLS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn initialized_once_across_threads() {
+    std::thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|| {
+                assert_eq!(CONCURRENT.with_dependent(|lines| lines), &["x", "y"]);
+            });
+        }
+    });
+
+    assert_eq!(CONCURRENT_CALLS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn manual_static_pair() {
+    static MANUAL: StaticPair<Lines> = StaticPair::new(|| {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        Pair::new(Lines("manual", &CALLS))
+    });
+
+    assert_eq!(MANUAL.with_dependent(|lines| lines), &["manual"]);
+}
+
+#[test]
+fn panicking_initializer_stays_uninitialized() {
+    static ATTEMPTS: AtomicUsize = AtomicUsize::new(0);
+    static FLAKY: StaticPair<Lines> = StaticPair::new(|| {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        assert_ne!(ATTEMPTS.fetch_add(1, Ordering::SeqCst), 0, "first attempt");
+        Pair::new(Lines("ok", &CALLS))
+    });
+
+    assert!(std::panic::catch_unwind(|| FLAKY.get()).is_err());
+    assert!(FLAKY.get_if_initialized().is_none());
+
+    assert_eq!(FLAKY.with_dependent(|lines| lines), &["ok"]);
+    assert_eq!(ATTEMPTS.load(Ordering::SeqCst), 2);
+}
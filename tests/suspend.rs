@@ -0,0 +1,123 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{
+    convert::Infallible,
+    panic::{AssertUnwindSafe, catch_unwind},
+};
+
+use pair::{ContextPair, Dependent, HasDependent, Owner, Pair, StoredContext};
+
+#[derive(Debug)]
+struct Text(String);
+
+impl<'owner> HasDependent<'owner> for Text {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Text {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split_whitespace().collect())
+    }
+}
+
+#[test]
+fn suspend_and_rebuild_on_drop() {
+    let mut pair = Pair::new(Text(String::from("a b")));
+
+    let mut text = pair.suspend_dependent();
+    assert_eq!(text.0, "a b");
+    assert_eq!(
+        format!("{text:?}"),
+        r#"SuspendedPair { owner: Text("a b"), .. }"#
+    );
+
+    // Force a reallocation, which would invalidate any borrows of the old text
+    text.0.push_str(&" c".repeat(1000));
+    drop(text);
+
+    assert_eq!(pair.dependent_len(), 1002);
+    assert_eq!(pair.with_dependent(|words| words[..3].join(" ")), "a b c");
+}
+
+#[test]
+fn leaked_guard_leaves_default_dependent() {
+    let mut pair = Pair::new(Text(String::from("a b")));
+
+    std::mem::forget(pair.suspend_dependent());
+    assert!(pair.dependent_is_empty());
+    assert_eq!(pair.into_owner().0, "a b");
+}
+
+#[derive(Debug)]
+struct Csv(String);
+
+impl<'owner> HasDependent<'owner> for Csv {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Csv {
+    type Context<'a> = &'a str;
+    type Error = String;
+
+    fn make_dependent(&self, delimiter: &str) -> Result<Dependent<'_, Self>, Self::Error> {
+        if self.0.contains('!') {
+            return Err(format!("invalid csv: {}", self.0));
+        }
+        assert!(!self.0.contains('?'), "make_dependent panicked");
+
+        Ok(self.0.split(delimiter).collect())
+    }
+}
+
+struct Delimiter(String);
+
+impl StoredContext<Csv> for Delimiter {
+    fn context(&self) -> &str {
+        &self.0
+    }
+}
+
+#[test]
+fn context_pair_uses_stored_context() {
+    let mut pair = ContextPair::try_new(Csv(String::from("a,b")), Delimiter(String::from(",")))
+        .unwrap_or_else(|_| panic!());
+
+    pair.suspend_dependent().0.push_str(",c");
+    assert_eq!(pair.with_dependent(|dep| dep), &["a", "b", "c"]);
+
+    let mut csv = pair.suspend_dependent();
+    csv.0 = String::from("x;y,z");
+    csv.try_resume_with_context(";").unwrap();
+    assert_eq!(pair.with_dependent(|dep| dep), &["x", "y,z"]);
+
+    pair.suspend_dependent().try_resume().unwrap();
+    assert_eq!(pair.with_dependent(|dep| dep), &["x;y", "z"]);
+}
+
+#[test]
+fn failed_rebuild_leaves_default_dependent() {
+    let mut pair = ContextPair::try_new(Csv(String::from("a,b")), Delimiter(String::from(",")))
+        .unwrap_or_else(|_| panic!());
+
+    let mut csv = pair.suspend_dependent();
+    csv.0.push('!');
+    assert_eq!(csv.try_resume().unwrap_err(), "invalid csv: a,b!");
+    assert!(pair.dependent_is_empty());
+
+    // Errors are discarded when the guard is dropped
+    pair.suspend_dependent();
+    assert!(pair.dependent_is_empty());
+
+    pair.suspend_dependent().0 = String::from("c,d");
+    assert_eq!(pair.with_dependent(|dep| dep), &["c", "d"]);
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        pair.suspend_dependent().0.push('?');
+    }));
+    assert!(result.is_err());
+    assert!(pair.dependent_is_empty());
+    assert_eq!(pair.owner().0, "c,d?");
+}
@@ -0,0 +1,122 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "std")]
+
+use std::time::{Duration, Instant};
+
+use pair::{CancellableError, CancellationToken, Cancelled, Dependent, HasDependent, Owner, Pair};
+
+/// An owner whose `make_dependent` spins (polling the token) until the given
+/// amount of time has elapsed.
+#[derive(Debug)]
+struct Slow {
+    text: String,
+    work: Duration,
+}
+
+impl<'owner> HasDependent<'owner> for Slow {
+    type Dependent = &'owner str;
+}
+
+impl Owner for Slow {
+    type Context<'a> = &'a CancellationToken;
+    type Error = Cancelled;
+
+    fn make_dependent(&self, token: Self::Context<'_>) -> Result<Dependent<'_, Self>, Self::Error> {
+        let start = Instant::now();
+        while start.elapsed() < self.work {
+            token.check()?;
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        Ok(&self.text)
+    }
+}
+
+#[test]
+fn completes_within_timeout() {
+    let token = CancellationToken::new();
+    let start = Instant::now();
+    let pair = Pair::try_new_timeout(
+        Slow {
+            text: String::from("done"),
+            work: Duration::ZERO,
+        },
+        &token,
+        &token,
+        Duration::from_secs(60),
+    )
+    .unwrap();
+
+    assert_eq!(*pair.with_dependent(|dep| dep), "done");
+    assert!(!token.is_cancelled());
+    // The watchdog is woken as soon as construction finishes, rather than
+    // waiting for the full timeout
+    assert!(start.elapsed() < Duration::from_secs(30));
+}
+
+#[test]
+fn unrepresentable_timeout() {
+    let token = CancellationToken::new();
+    let pair = Pair::try_new_from_box_timeout(
+        Box::new(Slow {
+            text: String::from("done"),
+            work: Duration::ZERO,
+        }),
+        &token,
+        &token,
+        Duration::MAX,
+    )
+    .unwrap();
+
+    assert_eq!(*pair.with_dependent(|dep| dep), "done");
+}
+
+#[test]
+fn times_out() {
+    let token = CancellationToken::new();
+    let Err((owner, err)) = Pair::try_new_timeout(
+        Slow {
+            text: String::from("never"),
+            work: Duration::from_secs(60),
+        },
+        &token,
+        &token,
+        Duration::from_millis(10),
+    ) else {
+        panic!("construction should have timed out");
+    };
+
+    assert_eq!(owner.text, "never");
+    assert!(matches!(err, CancellableError::Cancelled));
+    assert!(token.is_cancelled());
+}
+
+#[test]
+fn panic_in_make_dependent() {
+    #[derive(Debug)]
+    struct Panics;
+
+    impl HasDependent<'_> for Panics {
+        type Dependent = ();
+    }
+
+    impl Owner for Panics {
+        type Context<'a> = ();
+        type Error = Cancelled;
+
+        fn make_dependent(
+            &self,
+            (): Self::Context<'_>,
+        ) -> Result<Dependent<'_, Self>, Self::Error> {
+            panic!("oh no");
+        }
+    }
+
+    let token = CancellationToken::new();
+    let result = std::panic::catch_unwind(|| {
+        Pair::try_new_timeout(Panics, (), &token, Duration::from_secs(60))
+    });
+
+    assert!(result.is_err());
+    assert!(!token.is_cancelled());
+}
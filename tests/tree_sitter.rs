@@ -0,0 +1,106 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "tree-sitter-json")]
+
+use pair::{SyntaxTree, SyntaxTreePair, SyntaxView};
+use tree_sitter::{Language, Node};
+
+// NOTE: MIRI can't call into tree-sitter's C library, so every test here is
+// named `*_nomiri`
+
+fn json() -> Language {
+    tree_sitter_json::LANGUAGE.into()
+}
+
+/// A view of the string nodes in a JSON document, in document order.
+struct Strings;
+
+impl SyntaxView for Strings {
+    type View<'tree> = Vec<Node<'tree>>;
+
+    fn view<'tree>(_: &'tree str, root: Node<'tree>) -> Vec<Node<'tree>> {
+        let mut strings = Vec::new();
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            if node.kind() == "string" {
+                strings.push(node);
+            }
+
+            let mut cursor = node.walk();
+            let children: Vec<_> = node.named_children(&mut cursor).collect();
+            stack.extend(children.into_iter().rev());
+        }
+
+        strings
+    }
+}
+
+fn strings(pair: &SyntaxTreePair<Strings>) -> Vec<String> {
+    let text = pair.owner().text();
+    pair.with_dependent(|strings| {
+        strings
+            .iter()
+            .map(|node| String::from(&text[node.byte_range()]))
+            .collect()
+    })
+}
+
+#[test]
+fn view_borrows_nodes_nomiri() {
+    let syntax = SyntaxTree::<Strings>::new(&json(), r#"["a", {"b": "c"}, 1]"#).unwrap();
+    let pair = SyntaxTreePair::new(syntax);
+
+    assert_eq!(strings(&pair), [r#""a""#, r#""b""#, r#""c""#]);
+    assert!(!pair.owner().tree().root_node().has_error());
+    assert_eq!(format!("{:?}", pair.owner()), "SyntaxTree { len: 20, .. }");
+}
+
+#[test]
+fn edits_match_a_fresh_parse_nomiri() {
+    let text = "{\n  \"first\": [1, 2],\n  \"second\": \"x\"\n}\n";
+    let mut pair = SyntaxTreePair::new(SyntaxTree::<Strings>::new(&json(), text).unwrap());
+
+    // Insertion, spanning multiple lines
+    let offset = text.find('2').unwrap() + 1;
+    pair.edit(offset..offset, ",\n    \"third\"");
+    // Replacement
+    let start = pair.owner().text().find("\"x\"").unwrap();
+    pair.edit(start..start + 3, "\"yz\"");
+    // Deletion
+    let start = pair.owner().text().find("1, ").unwrap();
+    pair.edit(start..start + 3, "");
+
+    let expected = "{\n  \"first\": [2,\n    \"third\"],\n  \"second\": \"yz\"\n}\n";
+    assert_eq!(pair.owner().text(), expected);
+    assert_eq!(
+        strings(&pair),
+        [r#""first""#, r#""third""#, r#""second""#, r#""yz""#]
+    );
+
+    // The incrementally-updated tree matches a tree parsed from scratch
+    let fresh = SyntaxTree::<Strings>::new(&json(), expected).unwrap();
+    let root = pair.owner().tree().root_node();
+    assert_eq!(root.to_sexp(), fresh.tree().root_node().to_sexp());
+    assert_eq!(root.end_position(), fresh.tree().root_node().end_position());
+    assert!(!root.has_error());
+}
+
+#[test]
+fn edit_owner_directly_nomiri() {
+    let mut syntax = SyntaxTree::<Strings>::new(&json(), r#"["a"]"#).unwrap();
+    syntax.edit(4..4, r#", "b""#);
+    assert_eq!(syntax.text(), r#"["a", "b"]"#);
+    assert_eq!(
+        syntax
+            .tree()
+            .root_node()
+            .named_child(0)
+            .unwrap()
+            .named_child_count(),
+        2
+    );
+
+    // Errors are reported in the tree, rather than failing the edit
+    syntax.edit(0..1, "");
+    assert!(syntax.tree().root_node().has_error());
+    assert_eq!(syntax.into_text(), r#""a", "b"]"#);
+}
@@ -0,0 +1,147 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{convert::Infallible, num::ParseIntError};
+
+use pair::{Dependent, HasDependent, Owner, Pair};
+
+struct Csv(String);
+
+impl<'owner> HasDependent<'owner> for Csv {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Csv {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split(',').collect())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Error {
+    Parse,
+    Empty,
+}
+
+impl From<ParseIntError> for Error {
+    fn from(_: ParseIntError) -> Self {
+        Self::Parse
+    }
+}
+
+fn first(pair: &Pair<Csv>) -> Result<u32, Error> {
+    let first = pair.try_with_dependent(|fields| fields[0].parse::<u32>())?;
+    Ok(first)
+}
+
+fn sum_checked(pair: &Pair<Csv>) -> Result<u32, Error> {
+    pair.try_with_both(|owner, fields| {
+        if owner.0.is_empty() {
+            return Err(Error::Empty);
+        }
+
+        let mut sum = 0;
+        for field in fields {
+            sum += field.parse::<u32>()?;
+        }
+        Ok(sum)
+    })
+}
+
+#[test]
+fn try_with_dependent() {
+    assert_eq!(first(&Pair::new(Csv(String::from("4,5")))), Ok(4));
+    assert_eq!(
+        first(&Pair::new(Csv(String::from("x,5")))),
+        Err(Error::Parse)
+    );
+}
+
+#[test]
+fn try_with_both() {
+    assert_eq!(sum_checked(&Pair::new(Csv(String::from("4,5")))), Ok(9));
+    assert_eq!(
+        sum_checked(&Pair::new(Csv(String::from("4,x")))),
+        Err(Error::Parse)
+    );
+    assert_eq!(
+        sum_checked(&Pair::new(Csv(String::new()))),
+        Err(Error::Empty)
+    );
+}
+
+#[test]
+fn borrows_outlive_closure() {
+    let pair = Pair::new(Csv(String::from("a,b")));
+
+    let fields: Result<&Vec<&str>, Infallible> = pair.try_with_dependent(|fields| Ok(fields));
+    assert_eq!(fields, Ok(&vec!["a", "b"]));
+
+    let (owner, fields) = pair
+        .try_with_both(|owner, fields| Ok::<_, Infallible>((owner, fields)))
+        .unwrap();
+    assert_eq!(owner.0, "a,b");
+    assert_eq!(fields.len(), 2);
+}
+
+fn parse_in_place(pair: &mut Pair<Csv>) -> Result<usize, Error> {
+    pair.try_with_both_mut(|owner, fields| {
+        if owner.0.is_empty() {
+            return Err(Error::Empty);
+        }
+
+        fields.retain(|field| !field.is_empty());
+        for field in fields.iter() {
+            field.parse::<u32>()?;
+        }
+        Ok(fields.len())
+    })
+}
+
+#[test]
+fn try_with_both_mut() {
+    let mut pair = Pair::new(Csv(String::from("1,,2")));
+    assert_eq!(parse_in_place(&mut pair), Ok(2));
+    assert_eq!(pair.with_dependent(|fields| fields.concat()), "12");
+
+    let mut pair = Pair::new(Csv(String::from(",x")));
+    assert_eq!(parse_in_place(&mut pair), Err(Error::Parse));
+    assert_eq!(pair.with_dependent(|fields| fields.concat()), "x");
+
+    let mut pair = Pair::new(Csv(String::new()));
+    assert_eq!(parse_in_place(&mut pair), Err(Error::Empty));
+}
+
+fn push_validated<'a>(fields: &mut Vec<&'a str>, field: &'a str) -> Result<(), Error> {
+    if field.is_empty() {
+        return Err(Error::Empty);
+    }
+    field.parse::<u32>()?;
+    fields.push(field);
+    Ok(())
+}
+
+#[test]
+fn try_with_dependent_mut() {
+    let mut pair = Pair::new(Csv(String::from("1")));
+
+    assert_eq!(
+        pair.try_with_dependent_mut(|fields| {
+            fields.clear();
+            push_validated(fields, "7")?;
+            Ok::<_, Error>(fields.len())
+        }),
+        Ok(1)
+    );
+    assert_eq!(
+        pair.try_with_dependent_mut(|fields| push_validated(fields, "x")),
+        Err(Error::Parse)
+    );
+    assert_eq!(
+        pair.try_with_dependent_mut(|fields| push_validated(fields, "")),
+        Err(Error::Empty)
+    );
+    assert_eq!(pair.with_dependent(|fields| fields.concat()), "7");
+}
@@ -0,0 +1,93 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::convert::Infallible;
+
+use pair::{Dependent, HasDependent, Owner, Pair};
+
+struct SplitByStr(String);
+
+impl<'owner> HasDependent<'owner> for SplitByStr {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for SplitByStr {
+    type Context<'a> = &'a str;
+    type Error = Infallible;
+
+    fn make_dependent(
+        &self,
+        context: Self::Context<'_>,
+    ) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split(context).collect())
+    }
+}
+
+struct SplitByBytes(Vec<u8>);
+
+impl<'owner> HasDependent<'owner> for SplitByBytes {
+    type Dependent = Vec<&'owner [u8]>;
+}
+
+impl Owner for SplitByBytes {
+    type Context<'a> = &'a [u8];
+    type Error = Infallible;
+
+    fn make_dependent(
+        &self,
+        context: Self::Context<'_>,
+    ) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split(|byte| context.contains(byte)).collect())
+    }
+}
+
+struct FilterWords(String);
+
+impl<'owner> HasDependent<'owner> for FilterWords {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for FilterWords {
+    type Context<'a> = &'a dyn Fn(&str) -> bool;
+    type Error = Infallible;
+
+    fn make_dependent(
+        &self,
+        context: Self::Context<'_>,
+    ) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self
+            .0
+            .split_whitespace()
+            .filter(|word| context(word))
+            .collect())
+    }
+}
+
+#[test]
+fn str_context() {
+    let delimiter = String::from(", ");
+    let pair = Pair::new_with_context(SplitByStr(String::from("a, b, c")), &delimiter);
+    drop(delimiter);
+
+    assert_eq!(pair.with_dependent(|dep| dep), &["a", "b", "c"]);
+}
+
+#[test]
+fn slice_context() {
+    let pair = Pair::new_with_context(SplitByBytes(b"a,b;c".to_vec()), b",;");
+
+    assert_eq!(
+        pair.with_dependent(|dep| dep),
+        &[&b"a"[..], &b"b"[..], &b"c"[..]]
+    );
+}
+
+#[test]
+fn dyn_fn_context() {
+    let min_len = 3;
+    let pair = Pair::new_with_context(
+        FilterWords(String::from("this is a test of pair")),
+        &|word: &str| word.len() >= min_len,
+    );
+
+    assert_eq!(pair.with_dependent(|dep| dep), &["this", "test", "pair"]);
+}
@@ -0,0 +1,156 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{
+    cell::Cell,
+    convert::Infallible,
+    panic::{AssertUnwindSafe, catch_unwind},
+};
+
+use pair::{ContextPair, Dependent, HasDependent, OptionPairExt, Owner, Pair, StoredContext};
+
+/// A log whose dependent indexes (up to) the first `limit` lines.
+struct Log {
+    text: String,
+    full_builds: Cell<usize>,
+}
+
+impl Log {
+    fn new(text: &str) -> Self {
+        Self {
+            text: String::from(text),
+            full_builds: Cell::new(0),
+        }
+    }
+}
+
+impl<'owner> HasDependent<'owner> for Log {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Log {
+    type Context<'a> = usize;
+    type Error = String;
+
+    fn make_dependent(&self, limit: usize) -> Result<Dependent<'_, Self>, Self::Error> {
+        self.full_builds.set(self.full_builds.get() + 1);
+
+        Ok(self.text.lines().take(limit).collect())
+    }
+
+    fn update_dependent<'owner>(
+        &'owner self,
+        lines: &mut Dependent<'owner, Self>,
+        limit: usize,
+    ) -> Result<(), Self::Error> {
+        assert!(limit < 100, "limit is too large");
+        if limit == 0 {
+            return Err(String::from("limit must be nonzero"));
+        }
+
+        // Only the lines which weren't indexed yet are visited
+        let indexed = lines.len();
+        lines.truncate(limit);
+        lines.extend(
+            self.text
+                .lines()
+                .skip(indexed)
+                .take(limit.saturating_sub(indexed)),
+        );
+
+        Ok(())
+    }
+}
+
+struct Limit(Cell<usize>);
+
+impl StoredContext<Log> for Limit {
+    fn context(&self) -> usize {
+        self.0.get()
+    }
+}
+
+#[test]
+fn rebuild_updates_in_place() {
+    let mut pair = ContextPair::try_new(Log::new("a\nb\nc\nd"), Limit(Cell::new(1)))
+        .unwrap_or_else(|_| panic!());
+    assert_eq!(pair.with_dependent(|lines| lines.join(",")), "a");
+
+    pair.context().0.set(3);
+    pair.try_rebuild_dependent().unwrap();
+    assert_eq!(pair.with_dependent(|lines| lines.join(",")), "a,b,c");
+
+    pair.context().0.set(2);
+    pair.try_rebuild_dependent().unwrap();
+    assert_eq!(pair.with_dependent(|lines| lines.join(",")), "a,b");
+
+    pair.context().0.set(10);
+    pair.try_rebuild_dependent().unwrap();
+    assert_eq!(pair.with_dependent(|lines| lines.join(",")), "a,b,c,d");
+
+    // Only the initial construction built the dependent from scratch
+    assert_eq!(pair.owner().full_builds.get(), 1);
+}
+
+#[test]
+fn failed_update_keeps_dependent() {
+    let mut pair =
+        ContextPair::try_new(Log::new("a\nb"), Limit(Cell::new(1))).unwrap_or_else(|_| panic!());
+
+    pair.context().0.set(0);
+    assert_eq!(
+        pair.try_rebuild_dependent(),
+        Err(String::from("limit must be nonzero"))
+    );
+    assert_eq!(pair.with_dependent(|lines| lines.join(",")), "a");
+
+    pair.context().0.set(100);
+    let result = catch_unwind(AssertUnwindSafe(|| pair.try_rebuild_dependent()));
+    assert!(result.is_err());
+    assert_eq!(pair.with_dependent(|lines| lines.join(",")), "a");
+}
+
+struct Words(String);
+
+impl<'owner> HasDependent<'owner> for Words {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Words {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split_whitespace().collect())
+    }
+
+    fn update_dependent<'owner>(
+        &'owner self,
+        words: &mut Dependent<'owner, Self>,
+        (): (),
+    ) -> Result<(), Self::Error> {
+        // Restores the original order, without reallocating
+        words.sort_unstable_by_key(|word| word.as_ptr());
+
+        Ok(())
+    }
+}
+
+#[test]
+fn rebuild_if_updates_in_place() {
+    let mut slot = Some(Pair::new(Words(String::from("b c a"))));
+    slot.as_mut()
+        .unwrap()
+        .with_dependent_mut(|words| words.sort_unstable());
+    let before = slot
+        .as_ref()
+        .unwrap()
+        .with_dependent(|words| (words.len(), words.capacity()));
+
+    assert!(slot.rebuild_if(|_| true));
+    let pair = slot.as_ref().unwrap();
+    assert_eq!(pair.with_dependent(|words| words.join(" ")), "b c a");
+    assert_eq!(
+        pair.with_dependent(|words| (words.len(), words.capacity())),
+        before
+    );
+}
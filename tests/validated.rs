@@ -0,0 +1,120 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{cell::Cell, convert::Infallible, error::Error, fmt, num::ParseIntError};
+
+use pair::{Dependent, HasDependent, Owner, Pair, ValidationError};
+
+#[derive(Debug)]
+struct Numbers(String);
+
+impl HasDependent<'_> for Numbers {
+    type Dependent = Vec<u32>;
+}
+
+impl Owner for Numbers {
+    type Context<'a> = ();
+    type Error = ParseIntError;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        self.0.split(',').map(str::parse).collect()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Unsorted;
+
+impl fmt::Display for Unsorted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("numbers are not sorted")
+    }
+}
+
+impl Error for Unsorted {}
+
+#[expect(
+    clippy::ptr_arg,
+    reason = "validators receive a reference to the dependent"
+)]
+fn sorted(_: &Numbers, numbers: &Vec<u32>) -> Result<(), Unsorted> {
+    if numbers.is_sorted() {
+        Ok(())
+    } else {
+        Err(Unsorted)
+    }
+}
+
+#[test]
+fn accepted() {
+    let pair = Pair::try_new_validated(Numbers(String::from("1,2,3")), (), sorted).unwrap();
+    assert_eq!(pair.with_dependent(Clone::clone), [1, 2, 3]);
+
+    let pair =
+        Pair::try_new_from_box_validated(Box::new(Numbers(String::from("4"))), (), sorted).unwrap();
+    assert_eq!(pair.owner().0, "4");
+}
+
+#[test]
+fn rejected() {
+    let (owner, err) =
+        Pair::try_new_validated(Numbers(String::from("3,1")), (), sorted).unwrap_err();
+    assert_eq!(owner.0, "3,1");
+    assert_eq!(err, ValidationError::Rejected(Unsorted));
+    assert_eq!(
+        err.to_string(),
+        "the dependent of a pair was rejected by validation"
+    );
+    assert!(err.source().unwrap().is::<Unsorted>());
+}
+
+#[test]
+fn failed_skips_validation() {
+    let validated = Cell::new(false);
+    let (owner, err) = Pair::try_new_from_box_validated(
+        Box::new(Numbers(String::from("1,x"))),
+        (),
+        |_, _| -> Result<(), Unsorted> {
+            validated.set(true);
+            Ok(())
+        },
+    )
+    .unwrap_err();
+
+    assert_eq!(owner.0, "1,x");
+    assert!(matches!(err, ValidationError::Failed(_)));
+    assert!(err.source().unwrap().is::<ParseIntError>());
+    assert!(!validated.get());
+}
+
+#[derive(Debug)]
+struct Text(String);
+
+impl<'owner> HasDependent<'owner> for Text {
+    type Dependent = &'owner str;
+}
+
+impl Owner for Text {
+    type Context<'a> = usize;
+    type Error = Infallible;
+
+    fn make_dependent(&self, len: usize) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(&self.0[..len.min(self.0.len())])
+    }
+}
+
+#[test]
+fn infallible() {
+    let validate = |text: &Text, prefix: &&str| {
+        if prefix.len() < text.0.len() {
+            Ok(())
+        } else {
+            Err("prefix is the whole text")
+        }
+    };
+
+    let pair = Pair::new_validated(Text(String::from("hello")), 2, validate).unwrap();
+    assert_eq!(pair.with_dependent(|prefix| *prefix), "he");
+
+    let (owner, err) = Pair::new_validated(Text(String::from("hi")), 5, validate).unwrap_err();
+    assert_eq!(owner.0, "hi");
+    assert_eq!(err, "prefix is the whole text");
+}
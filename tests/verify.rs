@@ -0,0 +1,84 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::convert::Infallible;
+
+use pair::{Dependent, HasDependent, Owner, Pair};
+
+#[derive(Debug)]
+struct Document(String);
+
+impl<'owner> HasDependent<'owner> for Document {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Document {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.lines().collect())
+    }
+
+    fn verify_dependent(&self, lines: &Vec<&str>) -> bool {
+        lines.len() == self.0.lines().count()
+    }
+}
+
+#[test]
+fn verify_closure() {
+    let mut pair = Pair::new(Document(String::from("a\nb\nc")));
+
+    assert!(pair.verify(|document, lines| lines.len() == document.0.lines().count()));
+    assert!(!pair.verify(|_, lines| lines.is_empty()));
+
+    pair.with_dependent_mut(|lines| lines.truncate(0));
+    assert!(pair.verify(|_, lines| lines.is_empty()));
+}
+
+#[test]
+fn debug_assert_verified_passes() {
+    let pair = Pair::new(Document(String::from("a\nb\nc")));
+    pair.debug_assert_verified();
+}
+
+#[test]
+#[cfg_attr(
+    debug_assertions,
+    should_panic(expected = "the dependent of a pair is inconsistent with its owner")
+)]
+fn debug_assert_verified_fails() {
+    let mut pair = Pair::new(Document(String::from("a\nb\nc")));
+    pair.with_dependent_mut(|lines| {
+        lines.pop();
+    });
+    pair.debug_assert_verified();
+}
+
+struct Inconsistent(String);
+
+impl<'owner> HasDependent<'owner> for Inconsistent {
+    type Dependent = &'owner str;
+}
+
+impl Owner for Inconsistent {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(&self.0[1..])
+    }
+
+    fn verify_dependent(&self, dependent: &&str) -> bool {
+        *dependent == self.0
+    }
+}
+
+#[test]
+#[cfg_attr(
+    all(feature = "debug-verify", debug_assertions),
+    should_panic(expected = "the dependent of a pair is inconsistent with its owner")
+)]
+fn checked_on_construction() {
+    let pair = Pair::new(Inconsistent(String::from("hello")));
+    assert_eq!(pair.with_dependent(|dependent| *dependent), "ello");
+}
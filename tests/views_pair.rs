@@ -0,0 +1,131 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{
+    ops::Range,
+    panic::{AssertUnwindSafe, catch_unwind},
+    rc::Rc,
+};
+
+use pair::{Dependent, HasDependent, Owner, ViewsPair};
+
+#[derive(Debug)]
+struct Text(String);
+
+impl<'owner> HasDependent<'owner> for Text {
+    type Dependent = Vec<&'owner str>;
+}
+
+impl Owner for Text {
+    type Context<'a> = Range<usize>;
+    type Error = Range<usize>;
+
+    fn make_dependent(&self, range: Range<usize>) -> Result<Dependent<'_, Self>, Self::Error> {
+        match self.0.get(range.clone()) {
+            Some(text) => Ok(text.split_whitespace().collect()),
+            None => Err(range),
+        }
+    }
+}
+
+#[test]
+fn views() {
+    let text = Text(String::from("one two three four five six"));
+    let Ok(mut views) = ViewsPair::try_new_with_contexts(text, [0..7, 8..18, 19..27]) else {
+        panic!("construction should succeed");
+    };
+
+    assert_eq!(views.with_view(0, |words| words.join(" ")), "one two");
+    assert_eq!(views.with_view(1, |words| words.join(" ")), "three four");
+    assert_eq!(views.with_view(2, |words| words[0].len()), 4);
+
+    views.with_view_mut(1, |words| words.reverse());
+    assert_eq!(views.with_view(1, |words| words.join(" ")), "four three");
+
+    views.with_views_mut(|views| views.swap(0, 2));
+    assert_eq!(
+        views.with_views(|views| views.iter().map(Vec::len).sum::<usize>()),
+        6
+    );
+    assert_eq!(views.with_view(0, |words| words[0]), "five");
+
+    assert_eq!(
+        format!("{views:?}"),
+        r#"ViewsPair { owner: Text("one two three four five six"), views: [["five", "six"], ["four", "three"], ["one", "two"]] }"#
+    );
+
+    assert_eq!(views.owner().0.len(), 27);
+    assert_eq!(views.into_owner().0, "one two three four five six");
+}
+
+#[test]
+fn failed_view_returns_owner() {
+    let text = Text(String::from("short"));
+    let Err((text, range)) = ViewsPair::try_new_with_contexts(text, [0..2, 3..100, 200..300])
+    else {
+        panic!("construction should fail");
+    };
+
+    assert_eq!(text.0, "short");
+    assert_eq!(range, 3..100);
+}
+
+#[test]
+#[should_panic = "index out of bounds"]
+fn out_of_bounds_view() {
+    let text = Text(String::from("a b"));
+    let Ok(views) = ViewsPair::try_new_with_contexts(text, [0..1, 2..3]) else {
+        panic!("construction should succeed");
+    };
+
+    views.with_view(2, |words| words[0].len());
+}
+
+/// An owner whose views each hold a clone of its `Rc`, so leaked views can be
+/// detected.
+struct Shared(Rc<()>);
+
+impl HasDependent<'_> for Shared {
+    type Dependent = Rc<()>;
+}
+
+impl Owner for Shared {
+    type Context<'a> = Option<bool>;
+    type Error = ();
+
+    fn make_dependent(&self, context: Option<bool>) -> Result<Dependent<'_, Self>, Self::Error> {
+        match context {
+            Some(true) => Ok(Rc::clone(&self.0)),
+            Some(false) => Err(()),
+            None => panic!("make_dependent panicked"),
+        }
+    }
+}
+
+#[test]
+fn partial_views_are_dropped() {
+    let shared = Rc::new(());
+
+    let Ok(views) =
+        ViewsPair::try_new_with_contexts(Shared(Rc::clone(&shared)), [Some(true), Some(true)])
+    else {
+        panic!("construction should succeed");
+    };
+    assert_eq!(Rc::strong_count(&shared), 4);
+    drop(views);
+    assert_eq!(Rc::strong_count(&shared), 1);
+
+    let Err((owner, ())) = ViewsPair::try_new_with_contexts(
+        Shared(Rc::clone(&shared)),
+        [Some(true), Some(true), Some(false), Some(true)],
+    ) else {
+        panic!("construction should fail");
+    };
+    drop(owner);
+    assert_eq!(Rc::strong_count(&shared), 1);
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        ViewsPair::try_new_with_contexts(Shared(Rc::clone(&shared)), [Some(true), None])
+    }));
+    assert!(result.is_err());
+    assert_eq!(Rc::strong_count(&shared), 1);
+}
@@ -0,0 +1,123 @@
+#![allow(missing_docs, reason = "integration test")]
+
+use std::{
+    convert::Infallible,
+    panic::{AssertUnwindSafe, catch_unwind},
+};
+
+use pair::{Dependent, HasDependent, Owner, Pair};
+
+struct Buffer(Vec<u8>);
+
+impl<'owner> HasDependent<'owner> for Buffer {
+    type Dependent = Vec<&'owner [u8]>;
+}
+
+impl Owner for Buffer {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self.0.split(|&byte| byte == b'\n').collect())
+    }
+}
+
+#[test]
+fn mutate_owner_and_rebuild() {
+    let mut pair = Pair::new(Buffer(b"a\nb".to_vec()));
+
+    let len = pair.with_owner_mut(|buffer| {
+        buffer.0.extend_from_slice(b"\nc");
+        buffer.0.len()
+    });
+    assert_eq!(len, 5);
+    assert_eq!(pair.with_dependent(|lines| lines.concat()), b"abc");
+    assert_eq!(pair.dependent_len(), 3);
+}
+
+#[test]
+fn panicking_closure_still_rebuilds() {
+    let mut pair = Pair::new(Buffer(b"a".to_vec()));
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        pair.with_owner_mut(|buffer| {
+            buffer.0.extend_from_slice(b"\nb");
+            panic!("closure panicked");
+        });
+    }));
+    assert!(result.is_err());
+    assert_eq!(pair.with_dependent(|lines| lines.concat()), b"ab");
+}
+
+#[derive(Debug)]
+struct Number(String);
+
+impl HasDependent<'_> for Number {
+    type Dependent = u32;
+}
+
+impl Owner for Number {
+    type Context<'a> = ();
+    type Error = std::num::ParseIntError;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        self.0.parse()
+    }
+}
+
+#[test]
+fn failed_rebuild_returns_owner() {
+    let pair = Pair::try_new(Number(String::from("12"))).unwrap();
+
+    let (pair, ()) = pair
+        .try_with_owner_mut(|number| number.0.push('3'))
+        .unwrap();
+    assert_eq!(pair.with_dependent(|n| *n), 123);
+
+    let (number, err) = pair
+        .try_with_owner_mut(|number| number.0.push('x'))
+        .unwrap_err();
+    assert_eq!(number.0, "123x");
+    assert_eq!(err, "123x".parse::<u32>().unwrap_err());
+}
+
+/// A `key=value` entry, whose dependent borrows both halves (and has no
+/// sensible default).
+struct Entry(String);
+
+struct Fields<'owner> {
+    key: &'owner str,
+    value: &'owner str,
+}
+
+impl<'owner> HasDependent<'owner> for Entry {
+    type Dependent = Fields<'owner>;
+}
+
+impl Owner for Entry {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        let (key, value) = self.0.split_once('=').unwrap_or((&self.0, ""));
+        Ok(Fields { key, value })
+    }
+}
+
+#[test]
+fn dependent_without_default() {
+    let mut pair = Pair::new(Entry(String::from("name=pair")));
+
+    pair.with_owner_mut(|entry| entry.0.push_str("-rs"));
+    assert_eq!(pair.with_dependent(|fields| fields.key), "name");
+    assert_eq!(pair.with_dependent(|fields| fields.value), "pair-rs");
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        pair.with_owner_mut(|entry| {
+            entry.0 = String::from("version=1");
+            panic!("closure panicked");
+        });
+    }));
+    assert!(result.is_err());
+    assert_eq!(pair.with_dependent(|fields| fields.value), "1");
+}
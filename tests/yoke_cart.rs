@@ -0,0 +1,79 @@
+#![allow(missing_docs, reason = "integration test")]
+#![cfg(feature = "yoke")]
+
+use std::{borrow::Cow, convert::Infallible, sync::Arc};
+
+use pair::{Dependent, HasDependent, Owner, Pair, PairCart};
+use yoke::{Yoke, erased::ErasedArcCart};
+
+#[derive(Debug)]
+struct Table(String);
+
+impl<'owner> HasDependent<'owner> for Table {
+    type Dependent = Vec<(&'owner str, &'owner str)>;
+}
+
+impl Owner for Table {
+    type Context<'a> = ();
+    type Error = Infallible;
+
+    fn make_dependent(&self, (): ()) -> Result<Dependent<'_, Self>, Self::Error> {
+        Ok(self
+            .0
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .collect())
+    }
+}
+
+fn cart() -> PairCart<Table> {
+    PairCart::new(Pair::new(Table(String::from("hello=hallo\nbye=tschüss"))))
+}
+
+#[test]
+fn yoke_borrows_owner() {
+    let yoke: Yoke<Cow<'static, str>, PairCart<Table>> =
+        Yoke::attach_to_cart(cart(), |table| Cow::Borrowed(&table.0[..5]));
+
+    // Moving the yoke moves the cart, but not the owner it borrows from
+    let yoke = Box::new(yoke);
+    assert_eq!(yoke.get(), "hello");
+    assert!(matches!(yoke.get(), Cow::Borrowed(_)));
+
+    // The pair's own dependent is still available
+    let cart = yoke.backing_cart();
+    assert_eq!(
+        cart.pair().with_dependent(|entries| entries[1]),
+        ("bye", "tschüss")
+    );
+    assert_eq!(
+        format!("{cart:?}"),
+        r#"PairCart { pair: Pair { owner: Table("hello=hallo\nbye=tschüss"), dependent: [("hello", "hallo"), ("bye", "tschüss")] } }"#
+    );
+
+    let pair = yoke.into_backing_cart().into_pair();
+    assert_eq!(pair.into_owner().0.len(), 24);
+}
+
+#[test]
+fn erased_cart() {
+    let yokes: Vec<Yoke<&'static str, ErasedArcCart>> = ["hello", "bye"]
+        .into_iter()
+        .map(|key| {
+            Yoke::<&'static str, _>::attach_to_cart(cart(), |table| {
+                let start = table.0.find(key).unwrap();
+                &table.0[start..start + key.len()]
+            })
+            .wrap_cart_in_arc()
+            .erase_arc_cart()
+        })
+        .collect();
+
+    // Erased carts can be shared across threads
+    let yokes = Arc::new(yokes);
+    let handle = std::thread::spawn({
+        let yokes = Arc::clone(&yokes);
+        move || yokes.iter().map(|yoke| yoke.get().len()).sum::<usize>()
+    });
+    assert_eq!(handle.join().unwrap(), 8);
+}